@@ -6,6 +6,7 @@ use std::{rc::Rc, time::Instant};
 use gleam_core::{
     Result,
     build::{Built, Codegen, NullTelemetry, Options, ProjectCompiler, Telemetry},
+    config::PackageConfig,
     manifest::Manifest,
     paths::ProjectPaths,
     warning::WarningEmitterIO,
@@ -40,8 +41,35 @@ pub(crate) fn main_with_warnings(
     manifest: Manifest,
     warnings: Rc<dyn WarningEmitterIO>,
 ) -> Result<Built> {
-    let perform_codegen = options.codegen;
     let root_config = crate::config::root_config(paths)?;
+    compile(paths, root_config, options, manifest, warnings)
+}
+
+/// Builds the project using the given configuration for the root package,
+/// instead of the one read from its `gleam.toml`.
+pub(crate) fn main_with_config(
+    paths: &ProjectPaths,
+    root_config: PackageConfig,
+    options: Options,
+    manifest: Manifest,
+) -> Result<Built> {
+    compile(
+        paths,
+        root_config,
+        options,
+        manifest,
+        Rc::new(ConsoleWarningEmitter),
+    )
+}
+
+fn compile(
+    paths: &ProjectPaths,
+    root_config: PackageConfig,
+    options: Options,
+    manifest: Manifest,
+    warnings: Rc<dyn WarningEmitterIO>,
+) -> Result<Built> {
+    let perform_codegen = options.codegen;
     let telemetry: &'static dyn Telemetry = if options.no_print_progress {
         &NullTelemetry
     } else {
@@ -41,10 +41,12 @@ pub fn command(options: CompilePackage) -> Result<()> {
         Target::JavaScript => TargetCodegenConfiguration::JavaScript {
             emit_typescript_definitions: false,
             emit_source_maps: false,
+            module_format: config.javascript.module_format,
             prelude_location: options
                 .javascript_prelude
                 .ok_or_else(|| Error::JavaScriptPreludeRequired)?,
         },
+        Target::Wasm => TargetCodegenConfiguration::Wasm,
     };
 
     tracing::info!("Compiling package");
@@ -45,7 +45,7 @@ pub fn find_package_config_for_module(
     Ok((root_config(project_paths)?, PackageKind::Root))
 }
 
-fn package_root(package: &ManifestPackage, project_paths: &ProjectPaths) -> Utf8PathBuf {
+pub(crate) fn package_root(package: &ManifestPackage, project_paths: &ProjectPaths) -> Utf8PathBuf {
     match &package.source {
         ManifestPackageSource::Local { path } => project_paths.root().join(path),
 
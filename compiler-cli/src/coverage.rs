@@ -0,0 +1,209 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Collecting the code coverage of `gleam test --coverage`. The runtime
+//! writes the coverage it collects to the build directory, and once the
+//! tests are over it's mapped back to the Gleam code, see the
+//! `gleam_core::coverage` module.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+use gleam_core::{
+    Result,
+    build::{Built, ModuleFormat, Target},
+    config::PackageConfig,
+    coverage::{Coverage, ModuleCoverage, V8Coverage},
+    paths::ProjectPaths,
+};
+use itertools::Itertools;
+
+use crate::text_layout::space_table;
+
+/// The modules whose coverage is collected while running the tests.
+///
+#[derive(Debug)]
+pub struct CoverageRun {
+    target: Target,
+    package: EcoString,
+    module_format: ModuleFormat,
+    directory: Utf8PathBuf,
+    /// The `src` modules of the root package, along with the path of their
+    /// source file. The test modules, and the modules of the dependencies, are
+    /// not included in the report.
+    modules: Vec<(EcoString, Utf8PathBuf)>,
+}
+
+impl CoverageRun {
+    /// Prepares the coverage directory, removing the coverage collected by a
+    /// previous run.
+    ///
+    pub fn new(
+        paths: &ProjectPaths,
+        config: &PackageConfig,
+        target: Target,
+        built: &Built,
+    ) -> Result<Self> {
+        let directory = paths.build_coverage_directory();
+        crate::fs::delete_directory(&directory)?;
+        crate::fs::mkdir(&directory)?;
+
+        let modules = built
+            .module_interfaces
+            .values()
+            .filter(|module| module.package == config.name && module.origin.is_src())
+            .map(|module| (module.name.clone(), module.src_path.clone()))
+            .sorted()
+            .collect();
+
+        Ok(Self {
+            target,
+            package: config.name.clone(),
+            module_format: config.javascript.module_format,
+            directory,
+            modules,
+        })
+    }
+
+    /// The names of the Erlang modules to instrument.
+    ///
+    pub fn erlang_modules(&self) -> impl Iterator<Item = String> {
+        self.modules
+            .iter()
+            .map(|(name, _)| name.as_str().replace('/', "@"))
+    }
+
+    /// The file the Erlang `cover` analysis is written to.
+    ///
+    pub fn erlang_analysis_path(&self) -> Utf8PathBuf {
+        self.directory.join("erlang.cover")
+    }
+
+    /// The directory Node.js writes the V8 coverage to.
+    ///
+    pub fn v8_directory(&self) -> Utf8PathBuf {
+        self.directory.join("v8")
+    }
+
+    /// Maps the collected coverage back to the Gleam code, writing an lcov
+    /// report and printing a summary of it.
+    ///
+    pub fn report(&self, paths: &ProjectPaths) -> Result<()> {
+        let mut coverage = Coverage::new();
+        for (name, path) in &self.modules {
+            let src = crate::fs::read(path)?;
+            let path = path.strip_prefix(paths.root()).unwrap_or(path);
+            coverage.add_module(name.clone(), path.to_path_buf(), &src);
+        }
+
+        match self.target {
+            Target::Erlang => {
+                let analysis = self.erlang_analysis_path();
+                // If the program halted before the analysis could be written
+                // there's no coverage to report.
+                if analysis.is_file() {
+                    coverage.add_erlang_analysis(&crate::fs::read(analysis)?);
+                }
+            }
+            Target::JavaScript => self.add_javascript_coverage(paths, &mut coverage)?,
+            // Coverage is never collected for WebAssembly, `gleam run` refuses
+            // to start the program instead.
+            Target::Wasm => (),
+        }
+
+        let lcov = self.directory.join("lcov.info");
+        crate::fs::write(&lcov, &coverage.to_lcov())?;
+
+        println!("\n{}", summary(&coverage));
+        let lcov = lcov.strip_prefix(paths.root()).unwrap_or(&lcov);
+        println!("Coverage report written to {lcov}");
+        Ok(())
+    }
+
+    fn add_javascript_coverage(&self, paths: &ProjectPaths, coverage: &mut Coverage) -> Result<()> {
+        let mut scripts = vec![];
+        let v8_directory = self.v8_directory();
+        if v8_directory.is_dir() {
+            for entry in crate::fs::read_dir(&v8_directory)?.filter_map(Result::ok) {
+                let path = entry.into_path();
+                let Ok(file) = serde_json::from_str::<V8Coverage>(&crate::fs::read(&path)?) else {
+                    continue;
+                };
+                scripts.extend(file.result);
+            }
+        }
+
+        let extension = self.module_format.extension();
+        let package_directory = paths.build_directory_for_package(
+            gleam_core::build::Mode::Dev,
+            Target::JavaScript,
+            &self.package,
+        );
+        for (name, _) in &self.modules {
+            let javascript_path = package_directory.join(format!("{name}.{extension}"));
+            let source_map_path = package_directory.join(format!("{name}.{extension}.map"));
+            if !javascript_path.is_file() || !source_map_path.is_file() {
+                continue;
+            }
+
+            // Each run of the program has its own coverage file, so the same
+            // module can show up more than once.
+            let suffix = format!("/{}/{name}.{extension}", self.package);
+            let module_scripts = scripts
+                .iter()
+                .filter(|script| script.url.ends_with(&suffix))
+                .collect_vec();
+            let javascript = crate::fs::read(&javascript_path)?;
+            let source_map = crate::fs::read_bytes(&source_map_path)?;
+            if module_scripts.is_empty() {
+                coverage.add_javascript_coverage(name, &javascript, &source_map, None);
+            }
+            for script in module_scripts {
+                coverage.add_javascript_coverage(name, &javascript, &source_map, Some(script));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn summary(coverage: &Coverage) -> EcoString {
+    let mut lines = (0, 0);
+    let mut branches = (0, 0);
+    let mut rows = coverage
+        .modules()
+        .map(|(name, module)| {
+            lines.0 += module.lines_hit();
+            lines.1 += module.lines_found();
+            branches.0 += module.branches_hit();
+            branches.1 += module.branches_found();
+            module_row(name, module)
+        })
+        .collect_vec();
+    rows.push(vec![
+        "Total".into(),
+        percentage(lines.0, lines.1),
+        percentage(branches.0, branches.1),
+    ]);
+    space_table(&["Module", "Lines", "Branches"], rows)
+}
+
+fn module_row(name: &EcoString, module: &ModuleCoverage) -> Vec<String> {
+    vec![
+        name.to_string(),
+        percentage(module.lines_hit(), module.lines_found()),
+        percentage(module.branches_hit(), module.branches_found()),
+    ]
+}
+
+fn percentage(hit: usize, found: usize) -> String {
+    if found == 0 {
+        return "-".into();
+    }
+    let percentage = hit as f64 * 100.0 / found as f64;
+    format!("{percentage:.1}% ({hit}/{found})")
+}
+
+/// Escapes a path so it can be used in an Erlang string literal.
+///
+pub fn erlang_string(path: &Utf8Path) -> String {
+    path.as_str().replace('\\', "\\\\").replace('"', "\\\"")
+}
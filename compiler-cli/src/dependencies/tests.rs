@@ -10,12 +10,13 @@ use pretty_assertions::assert_eq;
 
 use gleam_core::{
     Error,
-    build::Runtime,
+    build::{ModuleFormat, Runtime},
     config::{DenoConfig, DenoFlag, Docs, ErlangConfig, JavaScriptConfig},
     dependency::{PackageFetchError, PackageFetcher},
     manifest::{Base16Checksum, Manifest, ManifestPackage, ManifestPackageSource},
     paths::ProjectPaths,
     requirement::Requirement,
+    warning::WarningLevels,
 };
 
 use crate::dependencies::*;
@@ -1663,9 +1664,11 @@ fn package_config(
                 unstable: true,
                 location: None,
             },
+            module_format: ModuleFormat::Esm,
         },
         target: Target::Erlang,
         internal_modules: None,
+        warnings: WarningLevels::default(),
     }
 }
 
@@ -5,18 +5,22 @@ use std::{collections::HashMap, time::SystemTime};
 
 use camino::{Utf8Path, Utf8PathBuf};
 use ecow::EcoString;
+use itertools::Itertools;
 
 use crate::{cli, fs::ProjectIO, http::HttpClient};
 use gleam_core::{
     Result,
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Package, Target},
+    build::{Built, Codegen, Compile, Mode, Module, Options, Package, Target},
     config::{DocsPage, PackageConfig},
-    docs::{Dependency, DependencyKind, DocContext},
+    docs::{
+        Dependency, DependencyKind, DependencyLinks, DocContext, DocsFormat, DocumentationConfig,
+        PublishedVersion,
+    },
     error::Error,
-    hex,
-    io::HttpClient as _,
-    manifest::ManifestPackageSource,
+    hex::{self, HEXPM_PUBLIC_KEY},
+    io::{HttpClient as _, OutputFile},
+    manifest::{Manifest, ManifestPackage, ManifestPackageSource},
     paths::ProjectPaths,
     type_,
 };
@@ -49,10 +53,16 @@ pub struct BuildOptions {
     /// Whether to open the docs after building.
     pub open: bool,
     pub target: Option<Target>,
+    /// Whether to also render the documentation of all the dependencies.
+    pub with_deps: bool,
+    pub format: DocsFormat,
+    /// Whether to look up the versions of the package published to Hex.
+    pub versions: bool,
 }
 
 pub fn build(paths: &ProjectPaths, options: BuildOptions) -> Result<()> {
     let config = crate::config::root_config(paths)?;
+    let target = options.target.unwrap_or(config.target);
 
     // Reset the build directory for the root package so that's recompiled and
     // the docs can be up to date for all modules.
@@ -61,13 +71,25 @@ pub fn build(paths: &ProjectPaths, options: BuildOptions) -> Result<()> {
     // documentation for our package.
     crate::fs::delete_directory(&paths.build_directory_for_package(
         Mode::Prod,
-        options.target.unwrap_or(config.target),
+        target,
         &config.name,
     ))?;
 
-    let out = paths.build_documentation_directory(&config.name);
-
     let manifest = crate::build::download_dependencies(paths, cli::Reporter::new())?;
+
+    // Modules loaded from the cache don't have the information needed to
+    // render their documentation, so if we're documenting the dependencies
+    // too they all have to be recompiled.
+    if options.with_deps {
+        for package in gleam_packages(&manifest) {
+            crate::fs::delete_directory(&paths.build_directory_for_package(
+                Mode::Prod,
+                target,
+                &package.name,
+            ))?;
+        }
+    }
+
     let dependencies = manifest
         .packages
         .iter()
@@ -99,38 +121,119 @@ pub fn build(paths: &ProjectPaths, options: BuildOptions) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
-        manifest,
-    )?;
-    let outputs = build_documentation(
-        paths,
-        &config,
-        dependencies,
-        &mut built.root_package,
-        DocContext::Build,
-        &built.module_interfaces,
+        manifest.clone(),
     )?;
 
-    // Write
-    crate::fs::delete_directory(&out)?;
-    crate::fs::write_outputs_under(&outputs, &out)?;
+    let published_versions = if options.versions {
+        published_versions(&config)?
+    } else {
+        vec![]
+    };
 
-    let index_html = out.join("index.html");
+    let out = paths.build_documentation_directory(&config.name);
+
+    if options.with_deps {
+        let outputs = build_documentation_with_dependencies(
+            paths,
+            &config,
+            dependencies,
+            &manifest,
+            &mut built,
+            options.format,
+            &published_versions,
+        )?;
+
+        // Write
+        let root = paths.build_documentation_root_directory();
+        let packages = outputs
+            .iter()
+            .filter_map(|file| file.path.components().next())
+            .unique();
+        for package in packages {
+            crate::fs::delete_directory(&root.join(package))?;
+        }
+        crate::fs::write_outputs_under(&outputs, &root)?;
+    } else {
+        let outputs = build_documentation(
+            paths,
+            &config,
+            dependencies,
+            &mut built.root_package,
+            DocContext::Build,
+            &built.module_interfaces,
+            options.format,
+            &published_versions,
+        )?;
+
+        // Write
+        crate::fs::delete_directory(&out)?;
+        crate::fs::write_outputs_under(&outputs, &out)?;
+    }
+
+    let index = out.join(match options.format {
+        DocsFormat::Html => "index.html",
+        DocsFormat::Markdown => "index.md",
+    });
 
     println!(
-        "\nThe documentation for {package} has been rendered to \n{index_html}",
+        "\nThe documentation for {package} has been rendered to \n{index}",
         package = config.name,
-        index_html = index_html
     );
 
     if options.open {
-        open_docs(&index_html)?;
+        open_docs(&index)?;
     }
 
     // We're done!
     Ok(())
 }
 
+/// Looks up the versions of the package published to Hex, along with the
+/// interface of each version as published to HexDocs. Documentation published
+/// by older Gleam versions doesn't include the package interface, so those
+/// versions will only show up in the version selector.
+fn published_versions(config: &PackageConfig) -> Result<Vec<PublishedVersion>> {
+    let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
+    let http = HttpClient::new();
+    let request =
+        hexpm::repository_v2_get_package_request(&config.name, None, &hexpm::Config::new());
+    let response = runtime.block_on(http.send(request))?;
+    let package = match hexpm::repository_v2_get_package_response(response, HEXPM_PUBLIC_KEY) {
+        Ok(package) => package,
+        // The package has not been published yet
+        Err(hexpm::ApiError::NotFound) => return Ok(vec![]),
+        Err(error) => return Err(Error::hex(error)),
+    };
+
+    let package_name = config.name.replace("_", "-");
+    let versions = package.releases.into_iter().map(|release| {
+        let url = format!(
+            "https://{package_name}.hexdocs.pm/{}/package-interface.json",
+            release.version
+        );
+        let http = &http;
+        async move {
+            let request = http::Request::get(url)
+                .body(vec![])
+                .expect("Package interface request");
+            let interface = match http.send(request).await {
+                Ok(response) if response.status().is_success() => {
+                    serde_json::from_slice(response.body()).ok()
+                }
+                Ok(_) | Err(_) => None,
+            };
+            PublishedVersion {
+                version: release.version,
+                interface,
+            }
+        }
+    });
+    Ok(runtime.block_on(futures::future::join_all(versions)))
+}
+
 /// Opens the indicated path in the default program configured by the system.
 ///
 /// For the docs this will generally be a browser (unless some other program is
@@ -144,6 +247,7 @@ fn open_docs(path: &Utf8Path) -> Result<()> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn build_documentation(
     paths: &ProjectPaths,
     config: &PackageConfig,
@@ -151,27 +255,30 @@ pub(crate) fn build_documentation(
     compiled: &mut Package,
     is_hex_publish: DocContext,
     cached_modules: &im::HashMap<EcoString, type_::ModuleInterface>,
-) -> Result<Vec<gleam_core::io::OutputFile>, Error> {
+    format: DocsFormat,
+    published_versions: &[PublishedVersion],
+) -> Result<Vec<OutputFile>, Error> {
     compiled.attach_doc_and_module_comments();
     cli::print_generating_documentation();
-    let mut pages = vec![DocsPage {
-        title: "README".into(),
-        path: "index.html".into(),
-        source: paths.readme(), // TODO: support non markdown READMEs. Or a default if there is none.
-    }];
-    pages.extend(config.documentation.pages.iter().cloned());
-    let mut outputs = gleam_core::docs::generate_html(
-        paths,
-        gleam_core::docs::DocumentationConfig {
-            package_config: config,
-            dependencies,
-            analysed: compiled.modules.as_slice(),
-            docs_pages: &pages,
-            rendering_timestamp: SystemTime::now(),
-            context: is_hex_publish,
-        },
-        ProjectIO::new(),
-    );
+    let pages = documentation_pages(paths, config);
+    let documentation_config = DocumentationConfig {
+        package_config: config,
+        dependencies,
+        dependency_links: DependencyLinks::HexDocs,
+        analysed: compiled.modules.as_slice(),
+        docs_pages: &pages,
+        rendering_timestamp: SystemTime::now(),
+        context: is_hex_publish,
+        published_versions,
+    };
+    let mut outputs = match format {
+        DocsFormat::Html => {
+            gleam_core::docs::generate_html(paths, documentation_config, ProjectIO::new())
+        }
+        DocsFormat::Markdown => {
+            gleam_core::docs::generate_markdown(paths, documentation_config, ProjectIO::new())
+        }
+    };
 
     outputs.push(gleam_core::docs::generate_json_package_interface(
         Utf8PathBuf::from("package-interface.json"),
@@ -181,6 +288,107 @@ pub(crate) fn build_documentation(
     Ok(outputs)
 }
 
+/// Renders the documentation of the root package along with the documentation
+/// of all its Gleam dependencies, each one in its own directory, so that they
+/// can link to each other.
+fn build_documentation_with_dependencies(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    dependencies: HashMap<EcoString, Dependency>,
+    manifest: &Manifest,
+    built: &mut Built,
+    format: DocsFormat,
+    published_versions: &[PublishedVersion],
+) -> Result<Vec<OutputFile>, Error> {
+    built.root_package.attach_doc_and_module_comments();
+    cli::print_generating_documentation();
+
+    let mut dependency_modules: HashMap<EcoString, Vec<Module>> = HashMap::new();
+    for mut module in std::mem::take(&mut built.dependency_modules) {
+        module.attach_doc_and_module_comments();
+        dependency_modules
+            .entry(module.ast.type_info.package.clone())
+            .or_default()
+            .push(module);
+    }
+
+    let mut packages = vec![];
+    for package in gleam_packages(manifest) {
+        // Dev dependencies are not compiled when building the documentation
+        let Some(modules) = dependency_modules.remove(&package.name) else {
+            continue;
+        };
+        let root = crate::config::package_root(package, paths);
+        let config = crate::config::read(root.join("gleam.toml"))?;
+        // The pages of a dependency are relative to its own root
+        let pages = documentation_pages(&ProjectPaths::new(root.clone()), &config)
+            .into_iter()
+            .map(|page| DocsPage {
+                source: root.join(page.source),
+                ..page
+            })
+            .collect_vec();
+        packages.push((config, modules, pages));
+    }
+
+    let root_pages = documentation_pages(paths, config);
+    let documentation_config =
+        |package_config, analysed, docs_pages, published_versions| DocumentationConfig {
+            package_config,
+            dependencies: dependencies.clone(),
+            dependency_links: DependencyLinks::Local,
+            analysed,
+            docs_pages,
+            rendering_timestamp: SystemTime::now(),
+            context: DocContext::Build,
+            published_versions,
+        };
+    let configs = std::iter::once(documentation_config(
+        config,
+        built.root_package.modules.as_slice(),
+        root_pages.as_slice(),
+        published_versions,
+    ))
+    .chain(packages.iter().map(|(config, modules, pages)| {
+        documentation_config(config, modules.as_slice(), pages.as_slice(), &[])
+    }))
+    .collect_vec();
+
+    let mut outputs = match format {
+        DocsFormat::Html => {
+            gleam_core::docs::generate_html_with_dependencies(paths, configs, ProjectIO::new())
+        }
+        DocsFormat::Markdown => {
+            gleam_core::docs::generate_markdown_with_dependencies(paths, configs, ProjectIO::new())
+        }
+    };
+
+    outputs.push(gleam_core::docs::generate_json_package_interface(
+        Utf8PathBuf::from(config.name.as_str()).join("package-interface.json"),
+        &built.root_package,
+        &built.module_interfaces,
+    ));
+    Ok(outputs)
+}
+
+/// The README and any additional pages to render for a package.
+fn documentation_pages(paths: &ProjectPaths, config: &PackageConfig) -> Vec<DocsPage> {
+    let mut pages = vec![DocsPage {
+        title: "README".into(),
+        path: "index.html".into(),
+        source: paths.readme(), // TODO: support non markdown READMEs. Or a default if there is none.
+    }];
+    pages.extend(config.documentation.pages.iter().cloned());
+    pages
+}
+
+fn gleam_packages(manifest: &Manifest) -> impl Iterator<Item = &ManifestPackage> {
+    manifest
+        .packages
+        .iter()
+        .filter(|package| package.build_tools.contains(&"gleam".into()))
+}
+
 pub fn publish(paths: &ProjectPaths) -> Result<()> {
     let config = crate::config::root_config(paths)?;
     let http = HttpClient::new();
@@ -221,6 +429,8 @@ pub fn publish(paths: &ProjectPaths) -> Result<()> {
             mode: Mode::Prod,
             target: None,
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
         manifest,
     )?;
@@ -231,6 +441,8 @@ pub fn publish(paths: &ProjectPaths) -> Result<()> {
         &mut built.root_package,
         DocContext::HexPublish,
         &built.module_interfaces,
+        DocsFormat::Html,
+        &[],
     )?;
     let archive = crate::fs::create_tar_archive(outputs)?;
 
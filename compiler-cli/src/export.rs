@@ -1,15 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-FileCopyrightText: 2022 The Gleam contributors
 
-use crate::fs::{self, ZipArchive};
+use crate::fs::{self, ProjectIO, ZipArchive};
 use camino::Utf8PathBuf;
+use ecow::EcoString;
 use gleam_core::{
     Result,
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Target},
+    build::{
+        Codegen, Compile, Mode, ModuleFormat, Options, Target, package_compiler::StdlibPackage,
+    },
+    codegen::{CoreErlang, JavaScript, TypeScriptDeclarations},
     paths::ProjectPaths,
     type_::ModuleFunction,
 };
+use itertools::Itertools;
 use std::io::Cursor;
 
 static ENTRYPOINT_FILENAME_POWERSHELL: &str = "entrypoint.ps1";
@@ -41,6 +46,8 @@ pub fn escript(paths: &ProjectPaths) -> Result<()> {
         mode,
         target: Some(target),
         no_print_progress: false,
+        doctests: false,
+        inline: false,
     };
     let built = crate::build::main(paths, build_options, manifest)?;
     let package_name = &built.root_package.config.name;
@@ -146,6 +153,8 @@ pub(crate) fn erlang_shipment(paths: &ProjectPaths) -> Result<()> {
             mode,
             target: Some(target),
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
         crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
     )?;
@@ -204,6 +213,249 @@ one of the following scripts:
     Ok(())
 }
 
+/// Generate a Core Erlang module for each Gleam module of the project and its
+/// dependencies, to be used with static analysis tools such as Dialyzer.
+///
+/// The modules of each package are put in their own directory, so they can be
+/// analysed along with the Erlang FFI modules and the BEAM files in the build
+/// directory.
+pub(crate) fn core_erlang(paths: &ProjectPaths) -> Result<()> {
+    let target = Target::Erlang;
+    let mode = Mode::Prod;
+    let build = paths.build_directory_for_target(mode, target);
+    let out = paths.core_erlang_directory();
+
+    // Reset the directories to ensure we have a clean slate and no old code.
+    // Every module is compiled again as the Core Erlang code is generated from
+    // the typed AST of each module.
+    fs::delete_directory(&build)?;
+    fs::delete_directory(&out)?;
+    fs::mkdir(&out)?;
+
+    // Build project in production mode
+    let built = crate::build::main(
+        paths,
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            codegen: Codegen::All,
+            compile: Compile::All,
+            mode,
+            target: Some(target),
+            no_print_progress: false,
+            doctests: false,
+            inline: false,
+        },
+        crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
+    )?;
+
+    let io = ProjectIO::new();
+    for module in built
+        .root_package
+        .modules
+        .iter()
+        .chain(&built.dependency_modules)
+    {
+        let package = &module.ast.type_info.package;
+        CoreErlang::new(&out.join(package.as_str())).render(
+            io.clone(),
+            std::slice::from_ref(module),
+            paths.root(),
+        )?;
+    }
+
+    crate::cli::print_exported(&built.root_package.config.name);
+
+    println!(
+        "
+Your Core Erlang modules have been generated to {out}.
+
+They can be checked with Dialyzer, for example:
+    dialyzer --src {out}/*/*.core
+",
+    );
+
+    Ok(())
+}
+
+/// Generate a directory of precompiled JavaScript along with a start script.
+/// Suitable for deployment to a server.
+///
+/// Only the functions, constants, and custom types that can be reached from
+/// the entry function of the entrypoint module are generated. The FFI modules
+/// and the prelude are copied as they are, while TypeScript declarations and
+/// source maps are not included.
+pub(crate) fn javascript_shipment(
+    paths: &ProjectPaths,
+    module: Option<String>,
+    function: String,
+) -> Result<()> {
+    let target = Target::JavaScript;
+    let mode = Mode::Prod;
+    let build = paths.build_directory_for_target(mode, target);
+    let out = paths.javascript_shipment_directory();
+
+    // Reset the directories to ensure we have a clean slate and no old code.
+    // Every module is compiled again as the reachability analysis needs the
+    // typed AST of every module of the program.
+    fs::delete_directory(&build)?;
+    fs::delete_directory(&out)?;
+    fs::mkdir(&out)?;
+
+    // Build project in production mode
+    let built = crate::build::main(
+        paths,
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            codegen: Codegen::All,
+            compile: Compile::All,
+            mode,
+            target: Some(target),
+            no_print_progress: false,
+            doctests: false,
+            inline: false,
+        },
+        crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
+    )?;
+    let package_name = &built.root_package.config.name;
+    let module_format = built.root_package.config.javascript.module_format;
+    let extension = module_format.extension();
+    let entrypoint: EcoString = module.map_or_else(|| package_name.clone(), Into::into);
+
+    // The entry function must exist for the entrypoint to call. This will
+    // return an error if it could not be found.
+    let _: ModuleFunction = built.get_entrypoint_function(&entrypoint, &function, target)?;
+
+    let io = ProjectIO::new();
+    let modules = built
+        .root_package
+        .modules
+        .iter()
+        .chain(&built.dependency_modules);
+    let reachable = gleam_core::javascript::reachable_definitions(
+        &io,
+        &build,
+        modules.clone().map(|module| &module.ast),
+        module_format,
+        &entrypoint,
+        &function,
+    )?;
+
+    fs::copy_dir(&build, &out)?;
+
+    // Modules using `echo` print dictionaries with the standard library's
+    // `gleam/dict` module when it is part of the program.
+    let stdlib_package = if modules.clone().any(|module| module.name == "gleam/dict") {
+        StdlibPackage::Present
+    } else {
+        StdlibPackage::Missing
+    };
+
+    for module in modules {
+        let package = &module.ast.type_info.package;
+        let package_out = out.join(package.as_str());
+        JavaScript::new(
+            &package_out,
+            TypeScriptDeclarations::None,
+            false,
+            module_format,
+            &Utf8PathBuf::from(format!("../prelude.{extension}")),
+            paths.root(),
+            Some(&reachable),
+        )
+        .render(&io, std::slice::from_ref(module), stdlib_package)?;
+
+        // These were generated for the complete module, so they would no
+        // longer match the code.
+        for extension in [
+            module_format.declaration_extension().to_string(),
+            format!("{extension}.map"),
+        ] {
+            let path = package_out.join(format!("{}.{extension}", module.name));
+            if path.is_file() {
+                fs::delete_file(&path)?;
+            }
+        }
+    }
+
+    for entry in fs::read_dir(&out)?.filter_map(Result::ok) {
+        let artefacts = entry
+            .path()
+            .join(gleam_core::paths::ARTEFACT_DIRECTORY_NAME);
+        if artefacts.is_dir() {
+            fs::delete_directory(&artefacts)?;
+        }
+    }
+
+    let function = gleam_core::javascript::maybe_escape_identifier(&function);
+    let (entrypoint_filename, entrypoint_source) = match module_format {
+        ModuleFormat::Esm => (
+            "entrypoint.mjs",
+            format!(
+                r#"import {{ {function} }} from "./{package_name}/{entrypoint}.mjs";
+{function}();
+"#
+            ),
+        ),
+        ModuleFormat::Cjs => (
+            "entrypoint.cjs",
+            format!(
+                r#"const {{ {function} }} = require("./{package_name}/{entrypoint}.cjs");
+{function}();
+"#
+            ),
+        ),
+        // Global scripts are meant for browsers, so they are loaded by a page
+        // in the order they depend on each other.
+        ModuleFormat::Global => {
+            let key = gleam_core::javascript::global::module_key(package_name, &entrypoint);
+            let scripts = gleam_core::javascript::global::load_order(&io, &out, &key)?
+                .into_iter()
+                .map(|key| format!("    <script src=\"{key}.js\"></script>\n"))
+                .join("");
+            (
+                "index.html",
+                format!(
+                    r#"<!doctype html>
+<html>
+  <head>
+    <meta charset="utf-8">
+{scripts}    <script>globalThis.Gleam["{key}"].{function}();</script>
+  </head>
+</html>
+"#
+                ),
+            )
+        }
+    };
+    fs::write(&out.join(entrypoint_filename), &entrypoint_source)?;
+
+    crate::cli::print_exported(package_name);
+
+    match module_format {
+        ModuleFormat::Esm | ModuleFormat::Cjs => println!(
+            "
+Your JavaScript shipment has been generated to {out}.
+
+It can be copied to a server with a JavaScript runtime installed and run with
+the {entrypoint_filename} module, for example:
+    node {entrypoint_filename}
+",
+        ),
+        ModuleFormat::Global => println!(
+            "
+Your JavaScript shipment has been generated to {out}.
+
+It can be served by any web server, and run by opening its {entrypoint_filename}
+page in a browser.
+",
+        ),
+    }
+
+    Ok(())
+}
+
 fn write_entrypoint_script(
     entrypoint_output_path: &Utf8PathBuf,
     entrypoint_template_path: &str,
@@ -230,8 +482,8 @@ Your hex tarball has been generated in {}.
     Ok(())
 }
 
-pub fn javascript_prelude() -> Result<()> {
-    print!("{}", gleam_core::javascript::PRELUDE);
+pub fn javascript_prelude(module_format: ModuleFormat) -> Result<()> {
+    print!("{}", gleam_core::javascript::prelude(module_format));
     Ok(())
 }
 
@@ -252,6 +504,8 @@ pub fn package_interface(paths: &ProjectPaths, out: Utf8PathBuf) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
         crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
     )?;
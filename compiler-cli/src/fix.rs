@@ -31,6 +31,8 @@ pub fn run(paths: &ProjectPaths) -> Result<()> {
             mode: Mode::Dev,
             target: None,
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
         build::download_dependencies(paths, cli::Reporter::new())?,
         warnings.clone(),
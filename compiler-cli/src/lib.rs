@@ -66,6 +66,7 @@ mod build_lock;
 mod cli;
 mod compile_package;
 mod config;
+mod coverage;
 mod dependencies;
 mod docs;
 mod export;
@@ -74,6 +75,7 @@ mod format;
 pub mod fs;
 mod hex;
 mod http;
+mod lint;
 mod lsp;
 mod new;
 mod owner;
@@ -95,7 +97,8 @@ use clap::{
 };
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, NullTelemetry, Options, Runtime, Target},
+    build::{Codegen, Compile, Mode, ModuleFormat, NullTelemetry, Options, Runtime, Target},
+    docs::DocsFormat,
     hex::RetirementReason,
     paths::ProjectPaths,
     version::COMPILER_VERSION,
@@ -167,6 +170,11 @@ pub enum Command {
 
         #[arg(long, help = no_print_progress_doc())]
         no_print_progress: bool,
+
+        /// Inline calls to functions marked with `@inline`, as well as to some
+        /// standard library functions, and fold constant expressions
+        #[arg(long)]
+        inline: bool,
     },
 
     /// Type check the project
@@ -176,6 +184,13 @@ pub enum Command {
         target: Option<Target>,
     },
 
+    /// Check the project for common style and correctness problems
+    Lint {
+        /// Which compilation target to use
+        #[arg(short, long, ignore_case = true, help = target_doc())]
+        target: Option<Target>,
+    },
+
     /// Publish the project to the Hex package repository
     ///
     /// Please ensure your package is suitable for production use before
@@ -361,6 +376,16 @@ pub enum Command {
         #[arg(long, ignore_case = true, help = runtime_doc())]
         runtime: Option<Runtime>,
 
+        /// Run the Gleam code examples in the documentation of the project
+        /// instead of the `<PROJECT_NAME>_test` module
+        #[arg(long)]
+        doc: bool,
+
+        /// Collect the code coverage of the project's `src` modules, writing
+        /// an lcov report to `build/dev/coverage/lcov.info`
+        #[arg(long)]
+        coverage: bool,
+
         arguments: Vec<String>,
     },
 
@@ -457,9 +482,16 @@ impl Command {
                 target,
                 warnings_as_errors,
                 no_print_progress,
+                inline,
             } => {
                 let paths = find_project_paths(directory)?;
-                command_build(&paths, target, warnings_as_errors, no_print_progress)
+                command_build(
+                    &paths,
+                    target,
+                    warnings_as_errors,
+                    no_print_progress,
+                    inline,
+                )
             }
 
             Self::Check { target } => {
@@ -467,9 +499,29 @@ impl Command {
                 command_check(&paths, target)
             }
 
-            Self::Docs(Docs::Build { open, target }) => {
+            Self::Lint { target } => {
                 let paths = find_project_paths(directory)?;
-                docs::build(&paths, docs::BuildOptions { open, target })
+                lint::run(&paths, target)
+            }
+
+            Self::Docs(Docs::Build {
+                open,
+                target,
+                with_deps,
+                format,
+                versions,
+            }) => {
+                let paths = find_project_paths(directory)?;
+                docs::build(
+                    &paths,
+                    docs::BuildOptions {
+                        open,
+                        target,
+                        with_deps,
+                        format,
+                        versions,
+                    },
+                )
             }
 
             Self::Docs(Docs::Publish) => {
@@ -540,6 +592,7 @@ impl Command {
                     module,
                     run::Which::Src,
                     no_print_progress,
+                    false,
                 )
             }
 
@@ -547,16 +600,17 @@ impl Command {
                 target,
                 arguments,
                 runtime,
+                doc,
+                coverage,
             } => {
                 let paths = find_project_paths(directory)?;
+                let which = if doc {
+                    run::Which::DocTest
+                } else {
+                    run::Which::Test
+                };
                 run::command(
-                    &paths,
-                    arguments,
-                    target,
-                    runtime,
-                    None,
-                    run::Which::Test,
-                    false,
+                    &paths, arguments, target, runtime, None, which, false, coverage,
                 )
             }
 
@@ -575,6 +629,7 @@ impl Command {
                     None,
                     run::Which::Dev,
                     no_print_progress,
+                    false,
                 )
             }
 
@@ -641,15 +696,25 @@ impl Command {
                 let paths = find_project_paths(directory)?;
                 export::erlang_shipment(&paths)
             }
+            Self::Export(ExportTarget::JavascriptShipment { module, function }) => {
+                let paths = find_project_paths(directory)?;
+                export::javascript_shipment(&paths, module, function)
+            }
             Self::Export(ExportTarget::Escript) => {
                 let paths = find_project_paths(directory)?;
                 export::escript(&paths)
             }
+            Self::Export(ExportTarget::CoreErlang) => {
+                let paths = find_project_paths(directory)?;
+                export::core_erlang(&paths)
+            }
             Self::Export(ExportTarget::HexTarball) => {
                 let paths = find_project_paths(directory)?;
                 export::hex_tarball(&paths)
             }
-            Self::Export(ExportTarget::JavascriptPrelude) => export::javascript_prelude(),
+            Self::Export(ExportTarget::JavascriptPrelude { module_format }) => {
+                export::javascript_prelude(module_format)
+            }
             Self::Export(ExportTarget::TypescriptPrelude) => export::typescript_prelude(),
             Self::Export(ExportTarget::PackageInterface { output }) => {
                 let paths = find_project_paths(directory)?;
@@ -686,10 +751,27 @@ pub enum ExportTarget {
     Escript,
     /// Precompiled Erlang, suitable for deployment
     ErlangShipment,
+    /// Precompiled JavaScript with only the code used by the program, suitable
+    /// for deployment
+    JavascriptShipment {
+        /// The module with the function to run. Defaults to the module with
+        /// the same name as the package
+        #[arg(long)]
+        module: Option<String>,
+        /// The function to run from the entrypoint module
+        #[arg(long, default_value = "main")]
+        function: String,
+    },
+    /// Core Erlang code for each module, suitable for static analysis tools
+    CoreErlang,
     /// The package bundled into a tarball, suitable for publishing to Hex
     HexTarball,
     /// The JavaScript prelude module
-    JavascriptPrelude,
+    JavascriptPrelude {
+        /// The kind of JavaScript module to export the prelude as
+        #[arg(long, ignore_case = true, default_value = "esm")]
+        module_format: ModuleFormat,
+    },
     /// The TypeScript prelude module
     TypescriptPrelude,
     /// Information on the modules, functions, and types in the project in JSON format
@@ -886,7 +968,7 @@ pub enum Owner {
 
 #[derive(Subcommand, Debug)]
 pub enum Docs {
-    /// Render HTML docs locally
+    /// Render docs locally
     Build {
         /// Opens the docs in a browser after rendering
         #[arg(long)]
@@ -895,6 +977,19 @@ pub enum Docs {
         /// Which compilation target to use
         #[arg(short, long, ignore_case = true, help = target_doc())]
         target: Option<Target>,
+
+        /// Also render the docs of all dependencies, linking to them locally
+        #[arg(long)]
+        with_deps: bool,
+
+        /// The format to render the docs in
+        #[arg(long, ignore_case = true, default_value = "html")]
+        format: DocsFormat,
+
+        /// Add a version selector and mark when each item was introduced,
+        /// using the versions of the package published to Hex
+        #[arg(long)]
+        versions: bool,
     },
 
     /// Publish HTML docs to HexDocs
@@ -955,6 +1050,8 @@ fn command_check(paths: &ProjectPaths, target: Option<Target>) -> Result<()> {
             mode: Mode::Dev,
             target,
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
         build::download_dependencies(paths, cli::Reporter::new())?,
     )?;
@@ -966,6 +1063,7 @@ fn command_build(
     target: Option<Target>,
     warnings_as_errors: bool,
     no_print_progress: bool,
+    inline: bool,
 ) -> Result<()> {
     let manifest = if no_print_progress {
         build::download_dependencies(paths, NullTelemetry)?
@@ -982,6 +1080,8 @@ fn command_build(
             mode: Mode::Dev,
             target,
             no_print_progress,
+            doctests: false,
+            inline,
         },
         manifest,
     )?;
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use gleam_core::{
+    Error, Result,
+    analyse::TargetSupport,
+    build::{Codegen, Compile, Mode, Options, Target},
+    lint::Linter,
+    paths::ProjectPaths,
+};
+
+use crate::{build, cli};
+
+pub fn run(paths: &ProjectPaths, target: Option<Target>) -> Result<()> {
+    let config = crate::config::root_config(paths)?;
+    let target = target.unwrap_or(config.target);
+
+    // Modules loaded from the cache don't have the typed AST the lints are
+    // checked on, so the root package has to be analysed again.
+    crate::fs::delete_directory(&paths.build_directory_for_package(
+        Mode::Dev,
+        target,
+        &config.name,
+    ))?;
+
+    let built = build::main(
+        paths,
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            codegen: Codegen::DepsOnly,
+            compile: Compile::All,
+            mode: Mode::Dev,
+            target: Some(target),
+            no_print_progress: false,
+            doctests: false,
+            inline: false,
+        },
+        build::download_dependencies(paths, cli::Reporter::new())?,
+    )?;
+
+    let linter = Linter::with_builtin_rules();
+    let buffer_writer = cli::stderr_buffer_writer();
+    let mut count = 0;
+    for module in &built.root_package.modules {
+        for lint in linter.lint_module(module) {
+            let mut buffer = buffer_writer.buffer();
+            lint.pretty(&mut buffer);
+            buffer_writer
+                .print(&buffer)
+                .expect("Writing lint to stderr");
+            count += 1;
+        }
+    }
+
+    if count > 0 {
+        return Err(Error::LintsFound { count });
+    }
+    Ok(())
+}
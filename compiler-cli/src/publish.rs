@@ -10,7 +10,7 @@ use gleam_core::{
     ast::{CallArg, Statement, TypedExpr, TypedFunction},
     build::{Codegen, Compile, Mode, Options, Package, Target},
     config::{GleamVersion, PackageConfig, SpdxLicense},
-    docs::{Dependency, DependencyKind, DocContext},
+    docs::{Dependency, DependencyKind, DocContext, DocsFormat},
     error::{InvalidReadmeReason, SmallVersion, wrap},
     hex,
     manifest::ManifestPackageSource,
@@ -62,6 +62,8 @@ pub fn command(paths: &ProjectPaths, replace: bool, i_am_sure: bool) -> Result<(
         &mut compile_result,
         DocContext::HexPublish,
         &cached_modules,
+        DocsFormat::Html,
+        &[],
     )?)?;
 
     // Ask user if this is correct
@@ -453,6 +455,8 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
             codegen: Codegen::All,
             compile: Compile::All,
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
         manifest,
     )?;
@@ -544,7 +548,7 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
     // Collect all the files we want to include in the tarball
     let generated_files = match target {
         Target::Erlang => generated_erlang_files(paths, &built.root_package)?,
-        Target::JavaScript => vec![],
+        Target::JavaScript | Target::Wasm => vec![],
     };
     let src_files = project_files(Utf8Path::new(""))?;
     let contents_tar_gz = contents_tarball(paths, &src_files, &generated_files)?;
@@ -1,31 +1,41 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-FileCopyrightText: 2020 The Gleam contributors
 
-use std::sync::OnceLock;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::OnceLock,
+};
 
 use camino::Utf8PathBuf;
 use ecow::EcoString;
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Built, Codegen, Compile, Mode, NullTelemetry, Options, Runtime, Target, Telemetry},
+    build::{
+        Built, Codegen, Compile, Mode, ModuleFormat, NullTelemetry, Options, Runtime, Target,
+        Telemetry,
+    },
     config::{DenoFlag, PackageConfig},
+    doctest,
     error::Error,
     io::{Command, CommandExecutor, Stdio},
-    paths::ProjectPaths,
+    paths::{ARTEFACT_DIRECTORY_NAME, ProjectPaths},
     type_::ModuleFunction,
     version::COMPILER_VERSION,
 };
+use itertools::Itertools;
 use regex::Regex;
 
-use crate::{config::PackageKind, fs::ProjectIO};
+use crate::{config::PackageKind, coverage::CoverageRun, fs::ProjectIO};
 
 #[derive(Debug, Clone, Copy)]
 pub enum Which {
     Src,
     Test,
     Dev,
+    DocTest,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn command(
     paths: &ProjectPaths,
     arguments: Vec<String>,
@@ -34,10 +44,11 @@ pub fn command(
     module: Option<String>,
     which: Which,
     no_print_progress: bool,
+    coverage: bool,
 ) -> Result<(), Error> {
     // Don't exit on ctrl+c as it is used by child erlang shell
     ctrlc::set_handler(move || {}).expect("Error setting Ctrl-C handler");
-    let command = setup(
+    let (command, coverage) = setup(
         paths,
         arguments,
         target,
@@ -45,11 +56,16 @@ pub fn command(
         module,
         which,
         no_print_progress,
+        coverage,
     )?;
     let status = ProjectIO::new().exec(command)?;
+    if let Some(coverage) = coverage {
+        coverage.report(paths)?;
+    }
     std::process::exit(status);
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn setup(
     paths: &ProjectPaths,
     arguments: Vec<String>,
@@ -58,7 +74,8 @@ pub fn setup(
     module: Option<String>,
     which: Which,
     no_print_progress: bool,
-) -> Result<Command, Error> {
+    coverage: bool,
+) -> Result<(Command, Option<CoverageRun>), Error> {
     // Validate the module path
     if let Some(mod_path) = &module
         && !is_gleam_module(mod_path)
@@ -99,10 +116,25 @@ pub fn setup(
         Which::Src => root_config.name.to_string(),
         Which::Test => format!("{}_test", root_config.name),
         Which::Dev => format!("{}_dev", root_config.name),
+        Which::DocTest => doctest::RUNNER_MODULE.to_string(),
     });
 
     let target = target.unwrap_or(mod_config.target);
 
+    if coverage && target == Target::Wasm {
+        return Err(Error::CoverageNotSupportedOnTarget { target });
+    }
+
+    // Coverage on JavaScript can only be collected by NodeJS.
+    if coverage && target.is_javascript() {
+        match runtime.unwrap_or(mod_config.javascript.runtime) {
+            Runtime::NodeJs => (),
+            runtime @ (Runtime::Deno | Runtime::Bun) => {
+                return Err(Error::CoverageNotSupported { runtime });
+            }
+        }
+    }
+
     let options = Options {
         warnings_as_errors: false,
         compile: match package_kind {
@@ -124,9 +156,25 @@ pub fn setup(
             PackageKind::Dependency => TargetSupport::NotEnforced,
         },
         no_print_progress,
+        doctests: matches!(which, Which::DocTest),
+        inline: false,
+    };
+
+    let built = if coverage && target.is_javascript() {
+        // The JavaScript coverage is mapped back to the Gleam code using the
+        // source maps, so they have to be generated.
+        let mut config = root_config.clone();
+        config.javascript.source_maps = true;
+        crate::build::main_with_config(paths, config, options, manifest)?
+    } else {
+        crate::build::main(paths, options, manifest)?
     };
 
-    let built = crate::build::main(paths, options, manifest)?;
+    let coverage = if coverage {
+        Some(CoverageRun::new(paths, &root_config, target, &built)?)
+    } else {
+        None
+    };
 
     // A module can not be run if it does not exist or does not have a public main function.
     let main_function = get_or_suggest_main_function(built, &module, target)?;
@@ -134,13 +182,19 @@ pub fn setup(
     telemetry.running(&format!("{module}.main"));
 
     // Get the command to run the project.
-    match target {
+    let command = match target {
         Target::Erlang => match runtime {
             Some(r) => Err(Error::InvalidRuntime {
                 target: Target::Erlang,
                 invalid_runtime: r,
             }),
-            _ => run_erlang_command(paths, &root_config.name, &module, arguments),
+            _ => run_erlang_command(
+                paths,
+                &root_config.name,
+                &module,
+                arguments,
+                coverage.as_ref(),
+            ),
         },
         Target::JavaScript => match runtime.unwrap_or(mod_config.javascript.runtime) {
             Runtime::Deno => run_javascript_deno_command(
@@ -150,14 +204,32 @@ pub fn setup(
                 &module,
                 arguments,
             ),
-            Runtime::NodeJs => {
-                run_javascript_node_command(paths, &main_function.package, &module, arguments)
-            }
-            Runtime::Bun => {
-                run_javascript_bun_command(paths, &main_function.package, &module, arguments)
-            }
+            Runtime::NodeJs => run_javascript_node_command(
+                paths,
+                &root_config,
+                &main_function.package,
+                &module,
+                arguments,
+                coverage.as_ref(),
+            ),
+            Runtime::Bun => run_javascript_bun_command(
+                paths,
+                &root_config,
+                &main_function.package,
+                &module,
+                arguments,
+            ),
         },
-    }
+        Target::Wasm => match runtime {
+            Some(r) => Err(Error::InvalidRuntime {
+                target: Target::Wasm,
+                invalid_runtime: r,
+            }),
+            _ => run_wasm_command(paths, &main_function.package, &module, arguments),
+        },
+    }?;
+
+    Ok((command, coverage))
 }
 
 fn run_erlang_command(
@@ -165,6 +237,7 @@ fn run_erlang_command(
     package: &str,
     module: &str,
     arguments: Vec<String>,
+    coverage: Option<&CoverageRun>,
 ) -> Result<Command, Error> {
     let mut args = vec![];
 
@@ -180,7 +253,14 @@ fn run_erlang_command(
     let module = module.replace('/', "@");
 
     args.push("-eval".into());
-    args.push(format!("{package}@@main:run({module})"));
+    match coverage {
+        None => args.push(format!("{package}@@main:run({module})")),
+        Some(coverage) => args.push(format!(
+            "{package}@@main:run_with_coverage({module}, [{modules}], \"{output}\")",
+            modules = coverage.erlang_modules().join(", "),
+            output = crate::coverage::erlang_string(&coverage.erlang_analysis_path()),
+        )),
+    }
 
     // Don't run the Erlang shell
     args.push("-noshell".into());
@@ -200,14 +280,126 @@ fn run_erlang_command(
     })
 }
 
+fn run_wasm_command(
+    paths: &ProjectPaths,
+    package: &str,
+    module: &str,
+    arguments: Vec<String>,
+) -> Result<Command, Error> {
+    let build = paths.build_directory_for_target(Mode::Dev, Target::Wasm);
+
+    // Every compiled module is preloaded by Wasmtime under its Gleam name, so
+    // the imports between them can be resolved.
+    let mut modules = HashMap::new();
+    for entry in crate::fs::read_dir(&build)?.filter_map(Result::ok) {
+        let directory = entry.into_path();
+        if !directory.is_dir() {
+            continue;
+        }
+        for path in crate::fs::priv_directory_files(&directory) {
+            if path.extension() != Some("wat")
+                || path
+                    .components()
+                    .any(|part| part.as_str() == ARTEFACT_DIRECTORY_NAME)
+            {
+                continue;
+            }
+            let name = path
+                .strip_prefix(&directory)
+                .expect("module path in package directory")
+                .with_extension("")
+                .as_str()
+                .replace('\\', "/");
+            let _ = modules.insert(name, path);
+        }
+    }
+
+    let entry = build.join(package).join(format!("{module}.wat"));
+    let mut args = vec![
+        "run".to_string(),
+        "-W".to_string(),
+        "gc=y,function-references=y".to_string(),
+        "--preload".to_string(),
+        format!("gleam={}", build.join("prelude.wat")),
+    ];
+
+    // A module can only be preloaded once the modules it imports are.
+    for name in wasm_imports(&modules, module)? {
+        if let Some(path) = modules.get(&name)
+            && name != module
+        {
+            args.push("--preload".into());
+            args.push(format!("{name}={path}"));
+        }
+    }
+
+    args.push("--invoke".into());
+    args.push("main".into());
+    args.push(entry.to_string());
+
+    for argument in arguments.into_iter() {
+        args.push(argument);
+    }
+
+    Ok(Command {
+        program: "wasmtime".to_string(),
+        args,
+        env: vec![],
+        cwd: None,
+        stdio: Stdio::Inherit,
+    })
+}
+
+/// The compiled modules the given module depends on, directly or not, with
+/// each module coming after the modules it imports.
+///
+fn wasm_imports(
+    modules: &HashMap<String, Utf8PathBuf>,
+    module: &str,
+) -> Result<Vec<String>, Error> {
+    fn visit(
+        modules: &HashMap<String, Utf8PathBuf>,
+        name: &str,
+        visited: &mut HashSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), Error> {
+        if !visited.insert(name.to_string()) {
+            return Ok(());
+        }
+        if let Some(path) = modules.get(name) {
+            let wat = crate::fs::read(path)?;
+            for import in wasm_import_regex().captures_iter(&wat) {
+                if let Some(imported) = import.get(1)
+                    && modules.contains_key(imported.as_str())
+                {
+                    visit(modules, imported.as_str(), visited, order)?;
+                }
+            }
+        }
+        order.push(name.to_string());
+        Ok(())
+    }
+
+    let mut order = vec![];
+    visit(modules, module, &mut HashSet::new(), &mut order)?;
+    Ok(order)
+}
+
+fn wasm_import_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+    REGEX.get_or_init(|| Regex::new(r#"\(import "([^"]+)""#).expect("import regex"))
+}
+
 fn run_javascript_bun_command(
     paths: &ProjectPaths,
+    config: &PackageConfig,
     package: &str,
     module: &str,
     arguments: Vec<String>,
 ) -> Result<Command, Error> {
     let mut args = vec!["run".to_string()];
-    let entry = write_javascript_entrypoint(paths, package, module)?;
+    let entry =
+        write_javascript_entrypoint(paths, config.javascript.module_format, package, module)?;
 
     args.push(entry.to_string());
 
@@ -226,12 +418,15 @@ fn run_javascript_bun_command(
 
 fn run_javascript_node_command(
     paths: &ProjectPaths,
+    config: &PackageConfig,
     package: &str,
     module: &str,
     arguments: Vec<String>,
+    coverage: Option<&CoverageRun>,
 ) -> Result<Command, Error> {
     let mut args = vec![];
-    let entry = write_javascript_entrypoint(paths, package, module)?;
+    let entry =
+        write_javascript_entrypoint(paths, config.javascript.module_format, package, module)?;
 
     args.push(entry.to_string());
 
@@ -239,10 +434,20 @@ fn run_javascript_node_command(
         args.push(argument);
     }
 
+    // NodeJS writes the coverage collected by V8 to the given directory once
+    // the program is over.
+    let env = match coverage {
+        None => vec![],
+        Some(coverage) => vec![(
+            "NODE_V8_COVERAGE".to_string(),
+            coverage.v8_directory().to_string(),
+        )],
+    };
+
     Ok(Command {
         program: "node".to_string(),
         args,
-        env: vec![],
+        env,
         cwd: None,
         stdio: Stdio::Inherit,
     })
@@ -250,18 +455,48 @@ fn run_javascript_node_command(
 
 fn write_javascript_entrypoint(
     paths: &ProjectPaths,
+    module_format: ModuleFormat,
     package: &str,
     module: &str,
 ) -> Result<Utf8PathBuf, Error> {
+    // Global scripts can't load each other, so they are loaded by an ES module
+    // in the order they depend on each other.
+    let extension = match module_format {
+        ModuleFormat::Esm | ModuleFormat::Cjs => module_format.extension(),
+        ModuleFormat::Global => "mjs",
+    };
+    let build = paths.build_directory_for_target(Mode::Dev, Target::JavaScript);
     let path = paths
         .build_directory_for_package(Mode::Dev, Target::JavaScript, package)
         .to_path_buf()
-        .join(format!("gleam@@private_main_v{}.mjs", COMPILER_VERSION));
-    let module = format!(
-        r#"import {{ main }} from "./{module}.mjs";
+        .join(format!(
+            "gleam@@private_main_v{COMPILER_VERSION}.{extension}"
+        ));
+    let module = match module_format {
+        ModuleFormat::Esm => format!(
+            r#"import {{ main }} from "./{module}.mjs";
 main();
 "#,
-    );
+        ),
+        ModuleFormat::Cjs => format!(
+            r#"const {{ main }} = require("./{module}.cjs");
+main();
+"#,
+        ),
+        ModuleFormat::Global => {
+            let key = gleam_core::javascript::global::module_key(package, module);
+            let imports =
+                gleam_core::javascript::global::load_order(&ProjectIO::new(), &build, &key)?
+                    .into_iter()
+                    .map(|key| format!("import \"../{key}.js\";\n"))
+                    .join("");
+            format!(
+                r#"{imports}const {{ main }} = globalThis.Gleam["{key}"];
+main();
+"#,
+            )
+        }
+    };
     crate::fs::write(&path, &module)?;
     Ok(path)
 }
@@ -332,7 +567,8 @@ fn run_javascript_deno_command(
         );
     }
 
-    let entrypoint = write_javascript_entrypoint(paths, package, module)?;
+    let entrypoint =
+        write_javascript_entrypoint(paths, config.javascript.module_format, package, module)?;
     args.push(entrypoint.to_string());
 
     for argument in arguments.into_iter() {
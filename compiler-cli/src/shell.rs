@@ -21,6 +21,8 @@ pub fn command(paths: &ProjectPaths) -> Result<(), Error> {
             mode: Mode::Dev,
             target: Some(Target::Erlang),
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         },
         crate::build::download_dependencies(paths, crate::cli::Reporter::new())?,
     )?;
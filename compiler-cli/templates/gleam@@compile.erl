@@ -90,7 +90,7 @@ worker_loop(Parent, Out) ->
     receive
         {module, Module} ->
             log({compiling, Module}),
-            case compile:file(Module, Options) of
+            case compile_module(Module, Out, Options) of
                 {ok, ModuleName} ->
                     Beam = filename:join(Out, ModuleName) ++ ".beam",
                     Message = {compiled, ModuleName, Beam},
@@ -103,6 +103,28 @@ worker_loop(Parent, Out) ->
             worker_loop(Parent, Out)
     end.
 
+% Gleam modules are compiled from their abstract forms, encoded in the external
+% term format, so that the Erlang compiler doesn't have to parse the generated
+% source code. Any other Erlang module is compiled from its source.
+compile_module(Module, Out, Options) ->
+    case filename:extension(Module) of
+        ".etf" -> compile_forms(Module, Out, Options);
+        _ -> compile:file(Module, Options)
+    end.
+
+compile_forms(Module, Out, Options) ->
+    {ok, Binary} = file:read_file(Module),
+    Forms = erlang:binary_to_term(Binary),
+    Source = filename:rootname(Module) ++ ".erl",
+    case compile:forms(Forms, [{source, Source} | Options]) of
+        {ok, ModuleName, Beam} ->
+            BeamPath = filename:join(Out, ModuleName) ++ ".beam",
+            ok = file:write_file(BeamPath, Beam),
+            {ok, ModuleName};
+        error ->
+            error
+    end.
+
 compile_elixir(Modules, Out) ->
     Error = [
         "The program elixir was not found. Is it installed?",
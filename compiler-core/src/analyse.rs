@@ -37,7 +37,7 @@ use crate::{
         prelude::*,
     },
     uid::UniqueIdGenerator,
-    warning::TypeWarningEmitter,
+    warning::{TypeWarningEmitter, WarningKind, WarningLevel, WarningName},
 };
 use camino::Utf8PathBuf;
 use ecow::{EcoString, eco_format};
@@ -187,6 +187,7 @@ impl<A> ModuleAnalyzerConstructor<'_, A> {
             module_name: module.name.clone(),
             inline_functions: HashMap::new(),
             minimum_required_version: Version::new(0, 1, 0),
+            allowed_warnings: vec![],
         }
         .infer_module(module)
     }
@@ -213,6 +214,10 @@ struct ModuleAnalyzer<'a, A> {
 
     /// The minimum Gleam version required to compile the analysed module.
     minimum_required_version: Version,
+
+    /// The kinds of warning allowed by an `@allow` attribute, along with the
+    /// location of the definition the attribute is on.
+    allowed_warnings: Vec<(SrcSpan, WarningName)>,
 }
 
 impl<'a, A> ModuleAnalyzer<'a, A> {
@@ -239,6 +244,17 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         }
         .build();
 
+        self.allowed_warnings = module
+            .definitions
+            .iter()
+            .filter(|definition| definition.is_for(self.target))
+            .flat_map(|definition| {
+                let location = definition.definition.full_location();
+                let names = definition.allowed_warnings.iter();
+                names.map(move |name| (location, *name))
+            })
+            .collect();
+
         let definitions = GroupedDefinitions::new(module.into_iter_definitions(self.target));
 
         // Register any modules, types, and values being imported
@@ -376,7 +392,8 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         // make the output predictable.
         self.problems.sort();
 
-        let warnings = self.problems.take_warnings();
+        let mut warnings = self.problems.take_warnings();
+        warnings.retain(|warning| !self.is_allowed(warning));
         for warning in &warnings {
             // TODO: remove this clone
             self.warnings.emit(warning.clone());
@@ -456,6 +473,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             has_body: true,
             has_erlang_external: false,
             has_javascript_external: false,
+            has_wasm_external: false,
         };
         let mut expr_typer = ExprTyper::new(environment, definition, &mut self.problems);
         let typed_expr = expr_typer.infer_const(&annotation, *value);
@@ -547,8 +565,10 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             return_annotation,
             end_position: end_location,
             deprecation,
+            inline,
             external_erlang,
             external_javascript,
+            external_wasm,
             return_type: (),
             implementations: _,
             purity: _,
@@ -573,14 +593,19 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         self.assert_valid_javascript_external(&name, external_javascript.as_ref(), location);
 
         // Find the external implementation for the current target, if one has been given.
-        let external =
-            target_function_implementation(target, &external_erlang, &external_javascript);
+        let external = target_function_implementation(
+            target,
+            &external_erlang,
+            &external_javascript,
+            &external_wasm,
+        );
 
         // The function must have at least one implementation somewhere.
         let has_implementation = self.ensure_function_has_an_implementation(
             &body,
             &external_erlang,
             &external_javascript,
+            &external_wasm,
             location,
         );
 
@@ -597,6 +622,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             has_body,
             has_erlang_external: external_erlang.is_some(),
             has_javascript_external: external_javascript.is_some(),
+            has_wasm_external: external_wasm.is_some(),
         };
 
         // We have already registered the function in the `register_value_from_function`
@@ -605,6 +631,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         environment.references.set_current_node(name.clone());
 
         let mut typed_arguments = Vec::with_capacity(arguments.len());
+        let typed_holes = self.warnings.level(WarningKind::TypedHole) != WarningLevel::Allow;
 
         // Infer the type using the preregistered args + return types as a starting point
         let result = environment.in_new_scope(&mut self.problems, |environment, problems| {
@@ -634,12 +661,16 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                 .remove(&name)
                 .expect("Could not find hydrator for fn");
 
-            let (arguments, body) = expr_typer.infer_fn_with_known_types(
+            let result = expr_typer.infer_fn_with_known_types(
                 Some(name.clone()),
                 typed_arguments.clone(),
                 body,
                 Some(prereg_return_type.clone()),
-            )?;
+            );
+            // Holes are reported even if the function couldn't be inferred,
+            // they might be what's needed to fix it.
+            expr_typer.report_holes(typed_holes);
+            let (arguments, body) = result?;
             let arguments_types = arguments.iter().map(|a| a.type_.clone()).collect();
             let return_type = body
                 .last()
@@ -790,6 +821,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             external_javascript: external_javascript
                 .as_ref()
                 .map(|(m, f, _)| (m.clone(), f.clone())),
+            external_wasm: external_wasm
+                .as_ref()
+                .map(|(m, f, _)| (m.clone(), f.clone())),
             field_map,
             module: environment.current_module.clone(),
             arity: typed_arguments.len(),
@@ -820,6 +854,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             name: Some((name_location, name.clone())),
             publicity,
             deprecation,
+            inline,
             arguments: typed_arguments,
             body_start,
             end_position: end_location,
@@ -830,6 +865,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             body,
             external_erlang,
             external_javascript,
+            external_wasm,
             implementations,
             purity,
         };
@@ -840,6 +876,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             &function,
         ) {
             _ = self.inline_functions.insert(name, inline_function);
+        } else if let Some(location) = inline {
+            self.problems
+                .warning(Warning::UninlinableFunction { location });
         }
 
         function
@@ -907,10 +946,11 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         body: &[UntypedStatement],
         external_erlang: &Option<(EcoString, EcoString, SrcSpan)>,
         external_javascript: &Option<(EcoString, EcoString, SrcSpan)>,
+        external_wasm: &Option<(EcoString, EcoString, SrcSpan)>,
         location: SrcSpan,
     ) -> bool {
-        match (external_erlang, external_javascript) {
-            (None, None) if body.is_empty() => {
+        match (external_erlang, external_javascript, external_wasm) {
+            (None, None, None) if body.is_empty() => {
                 self.problems.error(Error::NoImplementation { location });
                 false
             }
@@ -918,6 +958,17 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         }
     }
 
+    /// Whether the warning is in a definition with an `@allow` attribute for
+    /// its kind.
+    ///
+    fn is_allowed(&self, warning: &Warning) -> bool {
+        let kind = warning.kind();
+        let location = warning.location();
+        self.allowed_warnings
+            .iter()
+            .any(|(definition, name)| definition.contains(location.start) && name.includes(kind))
+    }
+
     fn analyse_import(
         &mut self,
         i: UntypedImport,
@@ -948,11 +999,14 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             && module_info.package != self.package_config.name
             && !self.direct_dependencies.contains_key(&module_info.package)
         {
-            self.warnings.emit(Warning::TransitiveDependencyImported {
+            let warning = Warning::TransitiveDependencyImported {
                 location,
                 module: module_info.name.clone(),
                 package: module_info.package.clone(),
-            });
+            };
+            if !self.is_allowed(&warning) {
+                self.warnings.emit(warning);
+            }
         }
 
         Some(Import {
@@ -1006,6 +1060,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             deprecation,
             external_erlang,
             external_javascript,
+            external_wasm,
             ..
         } = t;
 
@@ -1115,7 +1170,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                 });
         }
 
-        if external_erlang.is_some() || external_javascript.is_some() {
+        if external_erlang.is_some() || external_javascript.is_some() || external_wasm.is_some() {
             self.track_feature_usage(FeatureKind::ExternalCustomType, location);
 
             if !constructors.is_empty() {
@@ -1138,6 +1193,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             deprecation,
             external_erlang,
             external_javascript,
+            external_wasm,
         })
     }
 
@@ -1204,13 +1260,16 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             let mut arguments_types = Vec::with_capacity(constructor.arguments.len());
             let mut fields = Vec::with_capacity(constructor.arguments.len());
 
-            for RecordConstructorArg {
-                label,
-                ast,
-                location,
-                doc,
-                ..
-            } in constructor.arguments.iter()
+            for (
+                field_index,
+                RecordConstructorArg {
+                    label,
+                    ast,
+                    location,
+                    doc,
+                    ..
+                },
+            ) in constructor.arguments.iter().enumerate()
             {
                 // Build a type from the annotation AST
                 let t = match hydrator.type_from_ast(ast, environment, &mut self.problems) {
@@ -1244,6 +1303,24 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                     );
                 }
 
+                // Fields of a private type can only be read from within this
+                // module, so we can report them if they never are. The fields
+                // of an opaque type can still be read by other modules, by
+                // comparing or printing its values.
+                if publicity.is_private() {
+                    environment.insert_unread_field(
+                        FieldId {
+                            type_name: name.clone(),
+                            variant_index: index as u16,
+                            index: field_index as u64,
+                        },
+                        UnreadField {
+                            location: *location,
+                            constructor: constructor.name.clone(),
+                        },
+                    );
+                }
+
                 // Register the label for this parameter
                 if let Err(error) = field_map_builder.add(label, label_location) {
                     self.problems.error(error);
@@ -1594,7 +1671,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             documentation,
             external_erlang,
             external_javascript,
+            external_wasm,
             deprecation,
+            inline: _,
             end_position: _,
             body: _,
             body_start: _,
@@ -1631,7 +1710,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         // When external implementations are present then the type annotations
         // must be given in full, so we disallow holes in the annotations.
-        hydrator.permit_holes(external_erlang.is_none() && external_javascript.is_none());
+        hydrator.permit_holes(
+            external_erlang.is_none() && external_javascript.is_none() && external_wasm.is_none(),
+        );
 
         let arguments_types = arguments
             .iter()
@@ -1673,6 +1754,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             external_javascript: external_javascript
                 .as_ref()
                 .map(|(m, f, _)| (m.clone(), f.clone())),
+            external_wasm: external_wasm
+                .as_ref()
+                .map(|(m, f, _)| (m.clone(), f.clone())),
             module: environment.current_module.clone(),
             arity: arguments.len(),
             location: *location,
@@ -1772,10 +1856,12 @@ fn target_function_implementation<'a>(
     target: Target,
     external_erlang: &'a Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: &'a Option<(EcoString, EcoString, SrcSpan)>,
+    external_wasm: &'a Option<(EcoString, EcoString, SrcSpan)>,
 ) -> &'a Option<(EcoString, EcoString, SrcSpan)> {
     match target {
         Target::Erlang => external_erlang,
         Target::JavaScript => external_javascript,
+        Target::Wasm => external_wasm,
     }
 }
 
@@ -1931,6 +2017,7 @@ fn generalise_function(
         name,
         publicity,
         deprecation,
+        inline,
         arguments,
         body,
         return_annotation,
@@ -1939,6 +2026,7 @@ fn generalise_function(
         return_type,
         external_erlang,
         external_javascript,
+        external_wasm,
         implementations,
         purity,
     } = function;
@@ -1965,6 +2053,9 @@ fn generalise_function(
         external_javascript: external_javascript
             .as_ref()
             .map(|(m, f, _)| (m.clone(), f.clone())),
+        external_wasm: external_wasm
+            .as_ref()
+            .map(|(m, f, _)| (m.clone(), f.clone())),
         module: module_name.clone(),
         arity: arguments.len(),
         location,
@@ -1994,6 +2085,7 @@ fn generalise_function(
         name: Some((name_location, name)),
         publicity,
         deprecation,
+        inline,
         arguments,
         end_position: end_location,
         body_start,
@@ -2002,6 +2094,7 @@ fn generalise_function(
         body,
         external_erlang,
         external_javascript,
+        external_wasm,
         implementations,
         purity,
     }
@@ -28,6 +28,7 @@ use crate::type_::{
     self, Deprecation, HasType, ModuleValueConstructor, PatternConstructor, Type, TypedCallArg,
     ValueConstructor, ValueConstructorVariant, nil,
 };
+use crate::warning::WarningName;
 use itertools::Itertools;
 use num_traits::Zero;
 use std::collections::HashSet;
@@ -134,10 +135,19 @@ pub struct TypedDefinitions {
 /// pub fn main(a) { ...}
 /// ```
 ///
+/// Kinds of warning can also be allowed for a single definition with the
+/// `@allow` attribute:
+///
+/// ```gleam
+/// @allow(unused_variable)
+/// pub fn main(a) { ...}
+/// ```
+///
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TargetedDefinition {
     pub definition: UntypedDefinition,
     pub target: Option<Target>,
+    pub allowed_warnings: Vec<WarningName>,
 }
 
 impl TargetedDefinition {
@@ -866,11 +876,14 @@ pub struct Function<T, Expr> {
     pub body: Vec<Statement<T, Expr>>,
     pub publicity: Publicity,
     pub deprecation: Deprecation,
+    /// The location of the `@inline` attribute, if the function has one.
+    pub inline: Option<SrcSpan>,
     pub return_annotation: Option<TypeAst>,
     pub return_type: T,
     pub documentation: Option<(u32, EcoString)>,
     pub external_erlang: Option<(EcoString, EcoString, SrcSpan)>,
     pub external_javascript: Option<(EcoString, EcoString, SrcSpan)>,
+    pub external_wasm: Option<(EcoString, EcoString, SrcSpan)>,
     pub implementations: Implementations,
     pub purity: Purity,
 }
@@ -1132,6 +1145,7 @@ pub struct CustomType<T> {
     pub typed_parameters: Vec<T>,
     pub external_erlang: Option<(EcoString, EcoString, SrcSpan)>,
     pub external_javascript: Option<(EcoString, EcoString, SrcSpan)>,
+    pub external_wasm: Option<(EcoString, EcoString, SrcSpan)>,
 }
 
 impl<T> CustomType<T> {
@@ -1265,6 +1279,22 @@ impl<A, B, C> Definition<A, B, C> {
         }
     }
 
+    /// The location of the entire definition, including its body.
+    ///
+    pub fn full_location(&self) -> SrcSpan {
+        match self {
+            Definition::Function(function) => function.full_location(),
+            Definition::CustomType(custom_type) => custom_type.full_location(),
+            Definition::TypeAlias(TypeAlias {
+                location, type_ast, ..
+            }) => location.merge(&type_ast.location()),
+            Definition::ModuleConstant(ModuleConstant {
+                location, value, ..
+            }) => location.merge(&value.location()),
+            Definition::Import(Import { location, .. }) => *location,
+        }
+    }
+
     /// Returns `true` if the definition is [`Import`].
     ///
     /// [`Import`]: Definition::Import
@@ -4326,9 +4356,13 @@ impl BitArrayOption<TypedConstant> {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum TodoKind {
     Keyword,
-    EmptyFunction { function_location: SrcSpan },
+    EmptyFunction {
+        function_location: SrcSpan,
+    },
     IncompleteUse,
     EmptyBlock,
+    /// A `_` used in place of an expression.
+    Hole,
 }
 
 #[derive(Debug, Default)]
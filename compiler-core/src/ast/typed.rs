@@ -314,7 +314,7 @@ impl TypedExpr {
                 .or_else(|| self.self_if_contains_location(byte_index)),
 
             Self::Todo { kind, message, .. } => match kind {
-                TodoKind::Keyword => message
+                TodoKind::Keyword | TodoKind::Hole => message
                     .as_ref()
                     .and_then(|message| message.find_node(byte_index))
                     .or_else(|| self.self_if_contains_location(byte_index)),
@@ -659,7 +659,7 @@ impl TypedExpr {
                 TodoKind::EmptyFunction { .. } | TodoKind::IncompleteUse | TodoKind::EmptyBlock => {
                     None
                 }
-                TodoKind::Keyword => message
+                TodoKind::Keyword | TodoKind::Hole => message
                     .as_ref()
                     .and_then(|message| message.find_statement(byte_index)),
             },
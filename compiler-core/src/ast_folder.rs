@@ -32,36 +32,60 @@ pub trait UntypedModuleFolder: TypeAstFolder + UntypedExprFolder {
             .definitions
             .into_iter()
             .map(|definition| {
-                let TargetedDefinition { definition, target } = definition;
+                let TargetedDefinition {
+                    definition,
+                    target,
+                    allowed_warnings,
+                } = definition;
                 match definition {
                     Definition::Function(function) => {
                         let function = self.fold_function_definition(function, target);
                         let definition = self.walk_function_definition(function);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::TypeAlias(type_alias) => {
                         let type_alias = self.fold_type_alias(type_alias, target);
                         let definition = self.walk_type_alias(type_alias);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::CustomType(custom_type) => {
                         let custom_type = self.fold_custom_type(custom_type, target);
                         let definition = self.walk_custom_type(custom_type);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::Import(import) => {
                         let import = self.fold_import(import, target);
                         let definition = self.walk_import(import);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
 
                     Definition::ModuleConstant(constant) => {
                         let constant = self.fold_module_constant(constant, target);
                         let definition = self.walk_module_constant(constant);
-                        TargetedDefinition { definition, target }
+                        TargetedDefinition {
+                            definition,
+                            target,
+                            allowed_warnings,
+                        }
                     }
                 }
             })
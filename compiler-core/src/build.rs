@@ -69,6 +69,7 @@ pub enum Target {
     #[serde(alias = "js")]
     #[clap(alias = "js")]
     JavaScript,
+    Wasm,
 }
 
 impl Target {
@@ -76,6 +77,7 @@ impl Target {
         match self {
             Target::Erlang => "Erlang",
             Target::JavaScript => "JavaScript",
+            Target::Wasm => "WebAssembly",
         }
     }
 
@@ -167,16 +169,54 @@ impl Runtime {
     }
 }
 
+/// The kind of JavaScript modules generated when targeting JavaScript.
+///
+#[derive(Default, Debug, Serialize, Deserialize, ValueEnum, Clone, Copy, PartialEq, Eq)]
+#[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleFormat {
+    /// ECMAScript modules, using `import` and `export` statements.
+    #[default]
+    Esm,
+    /// CommonJS modules, using `require` and `module.exports`.
+    Cjs,
+    /// Plain scripts for browsers, which register their values on the
+    /// `globalThis.Gleam` object and read the values of other modules there.
+    Global,
+}
+
+impl ModuleFormat {
+    /// The extension of the generated JavaScript modules.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ModuleFormat::Esm => "mjs",
+            ModuleFormat::Cjs => "cjs",
+            ModuleFormat::Global => "js",
+        }
+    }
+
+    /// The extension of the generated TypeScript declaration files.
+    pub fn declaration_extension(&self) -> &'static str {
+        match self {
+            ModuleFormat::Esm => "d.mts",
+            ModuleFormat::Cjs => "d.cts",
+            ModuleFormat::Global => "d.ts",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum TargetCodegenConfiguration {
     JavaScript {
         emit_typescript_definitions: bool,
         emit_source_maps: bool,
+        module_format: ModuleFormat,
         prelude_location: Utf8PathBuf,
     },
     Erlang {
         app_file: Option<ErlangAppCodegenConfiguration>,
     },
+    Wasm,
 }
 
 impl TargetCodegenConfiguration {
@@ -184,6 +224,7 @@ impl TargetCodegenConfiguration {
         match self {
             Self::JavaScript { .. } => Target::JavaScript,
             Self::Erlang { .. } => Target::Erlang,
+            Self::Wasm => Target::Wasm,
         }
     }
 }
@@ -329,6 +370,12 @@ impl Module {
         path
     }
 
+    pub fn compiled_erlang_forms_path(&self) -> Utf8PathBuf {
+        let mut path = Utf8PathBuf::from(&module_erlang_name(&self.name));
+        assert!(path.set_extension("etf"), "Couldn't set file extension");
+        path
+    }
+
     pub fn find_node(&self, byte_index: u32) -> Option<Located<'_>> {
         self.ast.find_node(byte_index)
     }
@@ -934,6 +981,19 @@ impl SourceFingerprint {
     }
 }
 
+/// A hash of the parts of a module's interface that other modules can depend
+/// on. When it doesn't change the modules importing it don't need to be
+/// analysed again, even if the module's own code did change.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct InterfaceHash(u64);
+
+impl InterfaceHash {
+    pub fn new(module: &type_::ModuleInterface) -> Self {
+        InterfaceHash(crate::metadata::interface_hash(module))
+    }
+}
+
 /// Like a `Result`, but the operation can partially succeed or fail.
 ///
 #[derive(Debug)]
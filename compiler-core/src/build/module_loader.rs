@@ -131,6 +131,8 @@ where
             source_path: file.path,
             origin: self.origin,
             name: file.module_name,
+            interface_hash: meta.interface_hash,
+            dependency_hashes: meta.dependency_hashes,
         }
     }
 }
@@ -151,13 +153,11 @@ where
 {
     let code: EcoString = io.read(&path)?.into();
 
-    let parsed = crate::parse::parse_module(path.clone(), &code, &emitter).map_err(|error| {
-        Error::Parse {
-            path: path.clone(),
-            src: code.clone(),
-            error: Box::new(error),
-        }
-    })?;
+    // Modules with syntax errors are still analysed, so the language server
+    // can provide information about the definitions that could be parsed. The
+    // errors are reported once the module has been type checked.
+    let (parsed, parse_errors) =
+        crate::parse::parse_module_with_recovery(path.clone(), &code, &emitter);
     let mut ast = parsed.module;
     let extra = parsed.extra;
     let dependencies = ast.dependencies(target);
@@ -173,6 +173,8 @@ where
         name,
         code,
         ast,
+        parse_errors,
+        reusable_cache: None,
     };
     Ok(module)
 }
@@ -5,7 +5,7 @@ use src_span::LineNumbers;
 
 use super::*;
 use crate::{
-    build::SourceFingerprint,
+    build::{InterfaceHash, SourceFingerprint},
     io::{FileSystemWriter, memory::InMemoryFileSystem},
 };
 use std::time::Duration;
@@ -203,6 +203,8 @@ fn write_cache(
         dependencies: vec![],
         fingerprint: SourceFingerprint::new(source),
         line_numbers,
+        interface_hash: InterfaceHash(0),
+        dependency_hashes: vec![],
     };
     let path = Utf8Path::new(path);
     fs.write_bytes(&path, &cache_metadata.to_binary()).unwrap();
@@ -7,37 +7,42 @@ mod tests;
 use crate::analyse::TargetSupport;
 use crate::build::package_loader::CacheFiles;
 
-use crate::error::{DefinedModuleOrigin, FailedModule, SkipReason, SkippedModule};
+use crate::error::{DefinedModuleOrigin, FailedModule, SkipReason, SkippedModule, UnparsedModule};
 
 use crate::metadata;
 use crate::type_::PRELUDE_MODULE_NAME;
 use crate::type_::printer::Names;
 use crate::{
     Error, Result, Warning,
-    ast::UntypedModule,
+    ast::{TypedModule, UntypedModule},
     build::{
-        Mode, Module, Origin, Outcome, SourceFingerprint, Target,
+        InterfaceHash, Mode, Module, ModuleFormat, Origin, Outcome, SourceFingerprint, Target,
         elixir_libraries::ElixirLibraries,
         native_file_copier::NativeFileCopier,
         package_loader::{CodegenRequired, PackageLoader, StaleTracker},
     },
-    codegen::{Erlang, ErlangApp, JavaScript, TypeScriptDeclarations},
+    codegen::{Erlang, ErlangApp, JavaScript, TypeScriptDeclarations, Wasm},
     config::PackageConfig,
+    constant_folding, doctest, inline,
     io::{BeamCompilerIO, CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
-    parse::extra::ModuleExtra,
+    parse::{error::ParseError, extra::ModuleExtra},
     paths, type_,
     uid::UniqueIdGenerator,
     warning::{TypeWarningEmitter, WarningEmitter},
 };
 use askama::Template;
 use ecow::EcoString;
+use itertools::Itertools;
 use src_span::{LineNumbers, SrcSpan};
 use std::{
     collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+    sync::Mutex,
     time::SystemTime,
 };
 
 use camino::{Utf8Path, Utf8PathBuf};
+use vec1::Vec1;
 
 use super::{ErlangAppCodegenConfiguration, TargetCodegenConfiguration, Telemetry};
 
@@ -75,6 +80,10 @@ pub struct PackageCompiler<'a, IO> {
     pub target_support: TargetSupport,
     pub cached_warnings: CachedWarnings,
     pub check_module_conflicts: CheckModuleConflicts,
+    pub doctests: bool,
+    /// Whether to inline function calls and fold constant expressions before
+    /// generating code, see the `inline` and `constant_folding` modules.
+    pub inline: bool,
 }
 
 impl<'a, IO> PackageCompiler<'a, IO>
@@ -111,6 +120,8 @@ where
             target_support: TargetSupport::NotEnforced,
             cached_warnings: CachedWarnings::Ignore,
             check_module_conflicts: CheckModuleConflicts::DoNotCheck,
+            doctests: false,
+            inline: false,
         }
     }
 
@@ -130,6 +141,10 @@ where
         let span = tracing::info_span!("compile", package = %self.config.name.as_str());
         let _enter = span.enter();
 
+        // The package's warnings are reported at the levels set in its
+        // `gleam.toml`.
+        let warnings = &warnings.with_levels(self.config.warnings.clone());
+
         // Ensure that the package is compatible with this version of Gleam
         if let Err(error) = self.config.check_gleam_compatibility() {
             return error.into();
@@ -156,6 +171,7 @@ where
             stale_modules,
             already_defined_modules,
             incomplete_modules,
+            self.doctests,
         );
 
         let loaded = if self.compile_modules {
@@ -168,6 +184,7 @@ where
         };
 
         let mut cached_module_names = Vec::new();
+        let mut interface_hashes = loaded.interface_hashes;
 
         // Load the cached modules that have previously been compiled
         for module in loaded.cached.into_iter() {
@@ -196,6 +213,7 @@ where
 
         // Type check the modules that are new or have changed
         tracing::info!(count=%loaded.to_compile.len(), "analysing_modules");
+        let mut reused_caches = Vec::new();
         let outcome = analyse(
             self.config,
             self.target.target(),
@@ -203,12 +221,27 @@ where
             &self.ids,
             loaded.to_compile,
             existing_modules,
+            &mut interface_hashes,
+            &mut reused_caches,
             warnings,
             self.target_support,
             incomplete_modules,
         );
 
-        let mut modules = match outcome {
+        // Some of the modules importing changed modules didn't need to be
+        // analysed again as the interfaces they depend on stayed the same.
+        // They are treated just like the other cached modules.
+        for cache in reused_caches {
+            if let Err(error) = self.restore_cache(&cache) {
+                return error.into();
+            }
+            if let Err(error) = self.emit_warnings(warnings, &cache.interface) {
+                return error.into();
+            }
+            cached_module_names.push(cache.interface.name);
+        }
+
+        let modules = match outcome {
             Outcome::Ok(modules) => modules,
             Outcome::PartialFailure(modules, errors) => {
                 return Outcome::PartialFailure(
@@ -224,26 +257,28 @@ where
 
         tracing::debug!("performing_code_generation");
 
-        // Inlining is currently disabled. See
+        // Inlining is disabled unless explicitly enabled. See
         // https://github.com/gleam-lang/gleam/pull/5010 for information.
-
-        // let modules = if self.perform_codegen {
-        //     modules
-        //         .into_iter()
-        //         .map(|mut module| {
-        //             module.ast = inline::module(module.ast, &existing_modules);
-        //             module
-        //         })
-        //         .collect()
-        // } else {
-        //     modules
-        // };
+        let mut modules = if self.perform_codegen && self.inline {
+            modules
+                .into_iter()
+                .map(|mut module| {
+                    let ast = inline::module(module.ast, existing_modules);
+                    module.ast = constant_folding::module(ast);
+                    module
+                })
+                .collect()
+        } else {
+            modules
+        };
 
         if let Err(error) = self.perform_codegen(&modules, &cached_module_names) {
             return error.into();
         }
 
-        if let Err(error) = self.encode_and_write_metadata(&mut modules) {
+        if let Err(error) =
+            self.encode_and_write_metadata(&mut modules, existing_modules, &mut interface_hashes)
+        {
             return error.into();
         }
 
@@ -307,7 +342,12 @@ where
         Ok(())
     }
 
-    fn encode_and_write_metadata(&mut self, modules: &mut [Module]) -> Result<()> {
+    fn encode_and_write_metadata(
+        &mut self,
+        modules: &mut [Module],
+        existing_modules: &im::HashMap<EcoString, type_::ModuleInterface>,
+        interface_hashes: &mut InterfaceHashes,
+    ) -> Result<()> {
         if !self.write_metadata {
             tracing::debug!("package_metadata_writing_disabled");
             return Ok(());
@@ -339,12 +379,22 @@ where
             self.io.write_bytes(&cache_files.cache_path, &bytes)?;
 
             // Write cache metadata
+            let dependency_hashes = module
+                .dependencies
+                .iter()
+                .filter_map(|(dependency, _)| {
+                    let hash = interface_hashes.get(dependency, existing_modules)?;
+                    Some((dependency.clone(), hash))
+                })
+                .collect();
             let info = CacheMetadata {
                 mtime: module.mtime,
                 codegen_performed: self.perform_codegen,
                 dependencies: module.dependencies.clone(),
                 fingerprint: SourceFingerprint::new(&module.code),
                 line_numbers: module.ast.type_info.line_numbers.clone(),
+                interface_hash: InterfaceHash::new(&module.ast.type_info),
+                dependency_hashes,
             };
             self.io
                 .write_bytes(&cache_files.meta_path, &info.to_binary())?;
@@ -352,6 +402,21 @@ where
         Ok(())
     }
 
+    /// Writes back the cache files of a module that didn't need to be analysed
+    /// again. The package loader deletes them until the module is checked
+    /// against the new interfaces of the modules it imports.
+    ///
+    fn restore_cache(&mut self, cache: &ReusableCache) -> Result<()> {
+        if !self.write_metadata {
+            return Ok(());
+        }
+
+        let artefact_dir = self.out.join(paths::ARTEFACT_DIRECTORY_NAME);
+        let cache_files = CacheFiles::new(&artefact_dir, &cache.interface.name);
+        self.io.write_bytes(&cache_files.cache_path, &cache.cache)?;
+        self.io.write_bytes(&cache_files.meta_path, &cache.metadata)
+    }
+
     fn perform_codegen(
         &mut self,
         modules: &[Module],
@@ -366,16 +431,19 @@ where
             TargetCodegenConfiguration::JavaScript {
                 emit_typescript_definitions,
                 emit_source_maps,
+                module_format,
                 prelude_location,
             } => self.perform_javascript_codegen(
                 modules,
                 *emit_typescript_definitions,
                 *emit_source_maps,
+                *module_format,
                 prelude_location,
             ),
             TargetCodegenConfiguration::Erlang { app_file } => {
                 self.perform_erlang_codegen(modules, cached_module_names, app_file.as_ref())
             }
+            TargetCodegenConfiguration::Wasm => self.perform_wasm_codegen(modules),
         }
     }
 
@@ -408,10 +476,17 @@ where
         // we overwrite any precompiled Erlang that was included in the Hex
         // package. Otherwise we will build the potentially outdated precompiled
         // version and not the newly compiled version.
-        Erlang::new(&build_dir, &include_dir).render(io.clone(), modules, self.root)?;
+        Erlang::new(&build_dir, &include_dir, self.compile_beam_bytecode).render(
+            io.clone(),
+            modules,
+            self.root,
+        )?;
 
         let native_modules: Vec<EcoString> = if self.compile_beam_bytecode {
-            written.extend(modules.iter().map(Module::compiled_erlang_path));
+            // Gleam modules are compiled from their abstract forms rather than
+            // from the generated Erlang source code, which is still written so
+            // it can be included in Hex packages and read by humans.
+            written.extend(modules.iter().map(Module::compiled_erlang_forms_path));
             self.compile_erlang_to_beam(&written)?
         } else {
             tracing::debug!("skipping_erlang_bytecode_compilation");
@@ -435,6 +510,7 @@ where
         modules: &[Module],
         typescript: bool,
         sourcemaps: bool,
+        module_format: ModuleFormat,
         prelude_location: &Utf8Path,
     ) -> Result<(), Error> {
         let mut written = HashSet::new();
@@ -447,8 +523,10 @@ where
             self.out,
             typescript,
             sourcemaps,
+            module_format,
             prelude_location,
             self.root,
+            None,
         )
         .render(&self.io, modules, self.stdlib_package())?;
 
@@ -470,6 +548,19 @@ where
         Ok(())
     }
 
+    fn perform_wasm_codegen(&mut self, modules: &[Module]) -> Result<(), Error> {
+        let mut written = HashSet::new();
+        Wasm::new(self.out).render(&self.io, modules)?;
+
+        if self.copy_native_files {
+            self.copy_project_native_files(self.out, &mut written)?;
+        } else {
+            tracing::debug!("skipping_native_file_copying");
+        }
+
+        Ok(())
+    }
+
     /// Link the module source .gleam file to the output directory.
     /// This is done if we are generating source maps because source
     /// maps include a reference to the source file, so the source
@@ -566,6 +657,8 @@ fn analyse(
     ids: &UniqueIdGenerator,
     parsed_modules: Vec<UncompiledModule>,
     module_types: &mut im::HashMap<EcoString, type_::ModuleInterface>,
+    interface_hashes: &mut InterfaceHashes,
+    reused_caches: &mut Vec<ReusableCache>,
     warnings: &WarningEmitter,
     target_support: TargetSupport,
     incomplete_modules: &mut HashSet<EcoString>,
@@ -583,182 +676,435 @@ fn analyse(
 
     let mut skipped_modules: HashMap<EcoString, SkippedModule> = HashMap::new();
     let mut failed_modules = HashMap::new();
+    let mut modules_with_parse_errors = HashSet::new();
+    let mut unparsed_modules = vec![];
+
+    // The modules are analysed out of order, so their warnings are only
+    // emitted once they have all been analysed, in the order they'd have been
+    // emitted if the modules were analysed one after the other.
+    let mut module_warnings = Vec::with_capacity(parsed_modules.len());
+
+    for round in analysis_rounds(parsed_modules) {
+        let mut jobs = Vec::with_capacity(round.len());
+
+        for (index, mut module) in round {
+            tracing::debug!(module = ?module.name, "Type checking");
+
+            // We first need to check if the module can actually be compiled.
+            // If we weren't able to compile one of the modules it depends on, then
+            // we have to skip this one to avoid reporting false errors.
+            let skipped_dependency =
+                module
+                    .dependencies
+                    .iter()
+                    .find_map(|(dependency, location)| {
+                        if failed_modules.contains_key(dependency)
+                            || modules_with_parse_errors.contains(dependency)
+                        {
+                            // This module imports a module with an error.
+                            let reason = SkipReason::DependencyHasError {
+                                name: dependency.clone(),
+                            };
+                            Some((*location, reason))
+                        } else if let Some(skipped_module) = skipped_modules.get(dependency) {
+                            // This module imports a module that has been skipped too.
+                            let reason = SkipReason::DependencyWasSkipped {
+                                name: dependency.clone(),
+                                erroring_module: skipped_module.reason.erroring_module(),
+                            };
+                            Some((*location, reason))
+                        } else {
+                            None
+                        }
+                    });
 
-    for UncompiledModule {
-        name,
-        code,
-        ast,
-        path,
-        mtime,
-        origin,
-        dependencies,
-        extra,
-        ..
-    } in parsed_modules
-    {
-        tracing::debug!(module = ?name, "Type checking");
-
-        // We first need to check if the module can actually be compiled.
-        // If we weren't able to compile one of the modules it depends on, then
-        // we have to skip this one to avoid reporting false errors.
-        let skipped_dependency = dependencies.iter().find_map(|(dependency, location)| {
-            if let Some(_failed_module) = failed_modules.get(dependency) {
-                // This module imports a module with an error.
-                let reason = SkipReason::DependencyHasError {
-                    name: dependency.clone(),
-                };
-                Some((*location, reason))
-            } else if let Some(skipped_module) = skipped_modules.get(dependency) {
-                // This module imports a module that has been skipped too.
-                let reason = SkipReason::DependencyWasSkipped {
-                    name: dependency.clone(),
-                    erroring_module: skipped_module.reason.erroring_module(),
-                };
-                Some((*location, reason))
-            } else {
-                None
+            // The module does depend on some other module that had to be skipped,
+            // so we have to skip this one as well.
+            if let Some((location, reason)) = skipped_dependency {
+                let _ = skipped_modules.insert(
+                    module.name.clone(),
+                    SkippedModule {
+                        path: module.path,
+                        name: module.name,
+                        code: module.code,
+                        location,
+                        reason,
+                    },
+                );
+                continue;
             }
-        });
 
-        // The module does depend on some other module that had to be skipped,
-        // so we have to skip this one as well.
-        if let Some((location, reason)) = skipped_dependency {
-            let _ = skipped_modules.insert(
-                name.clone(),
-                SkippedModule {
-                    path,
-                    name,
-                    code: code.clone(),
-                    location,
-                    reason,
-                },
-            );
-            continue;
-        }
+            // The module's code hasn't changed, so if the modules it imports still
+            // have the interfaces it was checked against it can be used as cached.
+            if let Some(cache) = module.reusable_cache.take()
+                && cache.is_up_to_date(&module.dependencies, module_types, interface_hashes)
+            {
+                tracing::debug!(module = ?module.name, "reusing_cached_module");
+                interface_hashes.insert(module.name.clone(), cache.interface_hash);
+                let _ = module_types.insert(module.name, cache.interface.clone());
+                reused_caches.push(*cache);
+                continue;
+            }
 
-        let line_numbers = LineNumbers::new(&code);
+            jobs.push((index, module));
+        }
 
-        let analysis = crate::analyse::ModuleAnalyzerConstructor {
-            target,
-            ids,
-            origin,
-            importable_modules: module_types,
-            warnings: &TypeWarningEmitter::new(path.clone(), code.clone(), warnings.clone()),
-            direct_dependencies: &direct_dependencies,
-            dev_dependencies: &dev_dependencies,
-            target_support,
-            package_config,
-        }
-        .infer_module(ast, line_numbers, path.clone());
-
-        match analysis {
-            Outcome::Ok(ast) => {
-                // Module has compiled successfully.
-                // Make sure it isn't marked as incomplete.
-                let _ = incomplete_modules.remove(&name.clone());
-
-                let mut module = Module {
-                    dependencies,
-                    origin,
-                    extra,
-                    mtime,
-                    name,
-                    code,
-                    ast,
-                    input_path: path,
-                };
-                module.attach_doc_and_module_comments();
-
-                // Register the types from this module so they can be imported into
-                // other modules.
-                let _ = module_types.insert(module.name.clone(), module.ast.type_info.clone());
-
-                // Check for empty modules and emit warning
-                // Only emit the empty module warning if the module has no definitions at all.
-                // Modules with only private definitions already emit their own warnings.
-                if module_types
-                    .get(&module.name)
-                    .map(|interface| interface.values.is_empty() && interface.types.is_empty())
-                    .unwrap_or(false)
-                {
-                    warnings.emit(Warning::EmptyModule {
-                        path: module.input_path.clone(),
-                        name: module.name.clone(),
-                    });
-                }
+        // Modules analysed at the same time each get their own range of ids.
+        // A module analysed on its own keeps using the shared ids, so they are
+        // the same as if all the modules were analysed one after the other.
+        let analysed_alone = jobs.len() == 1;
+        let jobs = jobs
+            .into_iter()
+            .map(|(index, module)| AnalysisJob {
+                index,
+                module,
+                ids: if analysed_alone {
+                    ids.clone()
+                } else {
+                    ids.reserve_range()
+                },
+            })
+            .collect_vec();
+
+        // All the modules of a round only import modules from the previous
+        // rounds, so they can be analysed at the same time.
+        let importable_modules = &*module_types;
+        let analysed = parallel_map(jobs, |AnalysisJob { index, module, ids }| {
+            let UncompiledModule {
+                name,
+                code,
+                ast,
+                path,
+                mtime,
+                origin,
+                dependencies,
+                extra,
+                parse_errors,
+                ..
+            } = module;
+
+            let line_numbers = LineNumbers::new(&code);
+
+            // The modules checking documentation examples import everything from
+            // the documented module, warning about unused imports would be noise.
+            // The same goes for modules with syntax errors, where the code that
+            // couldn't be parsed might be using what looks unused.
+            let (emitter, emitted) = WarningEmitter::vector();
+            let emitter = if doctest::is_doctest_module(&name) || !parse_errors.is_empty() {
+                WarningEmitter::null()
+            } else {
+                emitter
+            };
 
-                // Register the successfully type checked module data so that it can be
-                // used for code generation and in the language server.
-                modules.push(module);
+            let analysis = crate::analyse::ModuleAnalyzerConstructor {
+                target,
+                ids: &ids,
+                origin,
+                importable_modules,
+                warnings: &TypeWarningEmitter::new(path.clone(), code.clone(), emitter),
+                direct_dependencies: &direct_dependencies,
+                dev_dependencies: &dev_dependencies,
+                target_support,
+                package_config,
             }
+            .infer_module(ast, line_numbers, path.clone());
+
+            AnalysedModule {
+                index,
+                name,
+                code,
+                path,
+                mtime,
+                origin,
+                dependencies,
+                extra,
+                parse_errors,
+                analysis,
+                warnings: emitted.take(),
+            }
+        });
+
+        for AnalysedModule {
+            index,
+            name,
+            code,
+            path,
+            mtime,
+            origin,
+            dependencies,
+            extra,
+            parse_errors,
+            analysis,
+            warnings,
+        } in analysed
+        {
+            module_warnings.push((index, warnings));
 
-            Outcome::PartialFailure(ast, errors) => {
-                // Mark as incomplete so that this module isn't reloaded from
-                // cache.
+            // A module with syntax errors is only type checked so that the language
+            // server can provide information about the definitions that could be
+            // parsed. It never counts as successfully compiled, and its syntax
+            // errors are reported in place of any type error.
+            if let Ok(errors) = Vec1::try_from_vec(parse_errors) {
                 let _ = incomplete_modules.insert(name.clone());
-                // Register the partially type checked module data so that it
-                // can be used in the language server.
-                let names = ast.names.clone();
-                let mut module = Module {
-                    dependencies,
-                    origin,
-                    extra,
-                    mtime,
-                    name,
-                    code: code.clone(),
-                    ast,
-                    input_path: path.clone(),
-                };
-                module.attach_doc_and_module_comments();
-
-                let _ = module_types.insert(module.ast.name.clone(), module.ast.type_info.clone());
-                let _ = failed_modules.insert(
-                    module.name.clone(),
-                    FailedModule {
-                        names: Box::new(names),
-                        path,
-                        src: code,
-                        errors,
-                    },
-                );
-                modules.push(module);
+                let _ = modules_with_parse_errors.insert(name.clone());
+                unparsed_modules.push(UnparsedModule {
+                    path: path.clone(),
+                    src: code.clone(),
+                    errors,
+                });
+
+                match analysis {
+                    Outcome::Ok(ast) | Outcome::PartialFailure(ast, _) => {
+                        let mut module = Module {
+                            dependencies,
+                            origin,
+                            extra,
+                            mtime,
+                            name,
+                            code,
+                            ast,
+                            input_path: path,
+                        };
+                        module.attach_doc_and_module_comments();
+                        let _ =
+                            module_types.insert(module.name.clone(), module.ast.type_info.clone());
+                        modules.push((index, module));
+                    }
+                    Outcome::TotalFailure(_) => (),
+                }
+                continue;
             }
 
-            Outcome::TotalFailure(errors) => {
-                let _ = failed_modules.insert(
-                    name.clone(),
-                    FailedModule {
-                        names: Box::new(Names::new()),
-                        path: path.clone(),
-                        src: code.clone(),
-                        errors,
-                    },
-                );
-            }
-        };
+            match analysis {
+                Outcome::Ok(ast) => {
+                    // Module has compiled successfully.
+                    // Make sure it isn't marked as incomplete.
+                    let _ = incomplete_modules.remove(&name.clone());
+
+                    let mut module = Module {
+                        dependencies,
+                        origin,
+                        extra,
+                        mtime,
+                        name,
+                        code,
+                        ast,
+                        input_path: path,
+                    };
+                    module.attach_doc_and_module_comments();
+
+                    // Register the types from this module so they can be imported into
+                    // other modules.
+                    let _ = module_types.insert(module.name.clone(), module.ast.type_info.clone());
+
+                    // Check for empty modules and emit warning
+                    // Only emit the empty module warning if the module has no definitions at all.
+                    // Modules with only private definitions already emit their own warnings.
+                    if module_types
+                        .get(&module.name)
+                        .map(|interface| interface.values.is_empty() && interface.types.is_empty())
+                        .unwrap_or(false)
+                    {
+                        let warning = Warning::EmptyModule {
+                            path: module.input_path.clone(),
+                            name: module.name.clone(),
+                        };
+                        module_warnings.push((index, vec![warning]));
+                    }
+
+                    // Register the successfully type checked module data so that it can be
+                    // used for code generation and in the language server.
+                    modules.push((index, module));
+                }
+
+                Outcome::PartialFailure(ast, errors) => {
+                    // Mark as incomplete so that this module isn't reloaded from
+                    // cache.
+                    let _ = incomplete_modules.insert(name.clone());
+                    // Register the partially type checked module data so that it
+                    // can be used in the language server.
+                    let names = ast.names.clone();
+                    let mut module = Module {
+                        dependencies,
+                        origin,
+                        extra,
+                        mtime,
+                        name,
+                        code: code.clone(),
+                        ast,
+                        input_path: path.clone(),
+                    };
+                    module.attach_doc_and_module_comments();
+
+                    let _ =
+                        module_types.insert(module.ast.name.clone(), module.ast.type_info.clone());
+                    let _ = failed_modules.insert(
+                        module.name.clone(),
+                        FailedModule {
+                            names: Box::new(names),
+                            path,
+                            src: code,
+                            errors,
+                        },
+                    );
+                    modules.push((index, module));
+                }
+
+                Outcome::TotalFailure(errors) => {
+                    let _ = failed_modules.insert(
+                        name.clone(),
+                        FailedModule {
+                            names: Box::new(Names::new()),
+                            path: path.clone(),
+                            src: code.clone(),
+                            errors,
+                        },
+                    );
+                }
+            };
+        }
+    }
+
+    module_warnings.sort_by_key(|(index, _)| *index);
+    for warning in module_warnings
+        .into_iter()
+        .flat_map(|(_, warnings)| warnings)
+    {
+        warnings.emit(warning);
     }
 
+    modules.sort_by_key(|(index, _)| *index);
+    let modules = modules.into_iter().map(|(_, module)| module).collect_vec();
+
     // Now we need to check if any module has failed and return the appropriate
     // outcome.
     let skipped_modules = skipped_modules.into_values().collect();
 
-    if failed_modules.is_empty() {
+    if failed_modules.is_empty() && unparsed_modules.is_empty() {
         Outcome::Ok(modules)
     } else if modules.is_empty() {
         let error = Error::Type {
             skipped_modules,
             failed_modules,
+            unparsed_modules,
         };
         Outcome::TotalFailure(error)
     } else {
         let error = Error::Type {
             skipped_modules,
             failed_modules,
+            unparsed_modules,
         };
         Outcome::PartialFailure(modules, error)
     }
 }
 
+/// A module to type check, along with its position in the order modules would
+/// be analysed in if they were analysed one after the other.
+struct AnalysisJob {
+    index: usize,
+    module: UncompiledModule,
+    ids: UniqueIdGenerator,
+}
+
+struct AnalysedModule {
+    index: usize,
+    name: EcoString,
+    code: EcoString,
+    path: Utf8PathBuf,
+    mtime: SystemTime,
+    origin: Origin,
+    dependencies: Vec<(EcoString, SrcSpan)>,
+    extra: ModuleExtra,
+    parse_errors: Vec<ParseError>,
+    analysis: Outcome<TypedModule, Vec1<type_::Error>>,
+    warnings: Vec<Warning>,
+}
+
+/// Splits the modules into rounds where each module only imports modules from
+/// the previous rounds, so all the modules of a round can be analysed at the
+/// same time. Modules are paired with their position in the original order,
+/// where they always come after the modules they import.
+///
+fn analysis_rounds(modules: Vec<UncompiledModule>) -> Vec<Vec<(usize, UncompiledModule)>> {
+    let mut rounds: Vec<Vec<(usize, UncompiledModule)>> = vec![];
+    let mut module_rounds = HashMap::new();
+
+    for (index, module) in modules.into_iter().enumerate() {
+        let round = module
+            .dependencies
+            .iter()
+            .filter_map(|(dependency, _)| module_rounds.get(dependency))
+            .map(|round| round + 1)
+            .max()
+            .unwrap_or(0);
+        let _ = module_rounds.insert(module.name.clone(), round);
+
+        match rounds.get_mut(round) {
+            Some(round) => round.push((index, module)),
+            None => rounds.push(vec![(index, module)]),
+        }
+    }
+
+    rounds
+}
+
+/// The stack size of the threads analysing modules. Type checking is deeply
+/// recursive, so this is the same as the usual stack size of a main thread
+/// rather than the smaller default for spawned threads.
+const ANALYSIS_THREAD_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Calls the function on each of the jobs, spreading them over the available
+/// threads. The results are in the same order as the jobs.
+///
+/// Where threads are not available, such as when compiling to WebAssembly, the
+/// jobs are run one after the other on the current thread.
+///
+fn parallel_map<Job, Output>(jobs: Vec<Job>, function: impl Fn(Job) -> Output + Sync) -> Vec<Output>
+where
+    Job: Send,
+    Output: Send,
+{
+    let threads = std::thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(jobs.len());
+    if threads <= 1 {
+        return jobs.into_iter().map(function).collect();
+    }
+
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let next_job = || queue.lock().expect("Job queue lock poisoned").next();
+
+    let mut results = std::thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                std::thread::Builder::new()
+                    .stack_size(ANALYSIS_THREAD_STACK_SIZE)
+                    .spawn_scoped(scope, || {
+                        let mut results = vec![];
+                        while let Some((index, job)) = next_job() {
+                            results.push((index, function(job)));
+                        }
+                        results
+                    })
+                    .expect("Spawning analysis thread")
+            })
+            .collect_vec();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect_vec()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, output)| output).collect()
+}
+
 #[derive(Debug)]
 pub(crate) enum Input {
     New(Box<UncompiledModule>),
@@ -812,6 +1158,8 @@ pub(crate) struct CachedModule {
     pub origin: Origin,
     pub dependencies: Vec<(EcoString, SrcSpan)>,
     pub source_path: Utf8PathBuf,
+    pub interface_hash: InterfaceHash,
+    pub dependency_hashes: Vec<(EcoString, InterfaceHash)>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -821,6 +1169,10 @@ pub(crate) struct CacheMetadata {
     pub dependencies: Vec<(EcoString, SrcSpan)>,
     pub fingerprint: SourceFingerprint,
     pub line_numbers: LineNumbers,
+    pub interface_hash: InterfaceHash,
+    /// The interface hashes of the imported modules when this module was
+    /// analysed.
+    pub dependency_hashes: Vec<(EcoString, InterfaceHash)>,
 }
 
 impl CacheMetadata {
@@ -840,6 +1192,7 @@ impl CacheMetadata {
 pub(crate) struct Loaded {
     pub to_compile: Vec<UncompiledModule>,
     pub cached: Vec<type_::ModuleInterface>,
+    pub interface_hashes: InterfaceHashes,
 }
 
 impl Loaded {
@@ -847,6 +1200,7 @@ impl Loaded {
         Self {
             to_compile: vec![],
             cached: vec![],
+            interface_hashes: InterfaceHashes::default(),
         }
     }
 }
@@ -862,6 +1216,72 @@ pub(crate) struct UncompiledModule {
     pub dependencies: Vec<(EcoString, SrcSpan)>,
     pub ast: UntypedModule,
     pub extra: ModuleExtra,
+    /// The syntax errors found in the module. If there's any, `ast` only holds
+    /// the definitions that could be parsed.
+    pub parse_errors: Vec<ParseError>,
+    /// The previous cache of a module whose code hasn't changed, but which
+    /// imports modules that have.
+    pub reusable_cache: Option<Box<ReusableCache>>,
+}
+
+/// The cache of a module that needs to be checked again because some of the
+/// modules it imports have changed. If their interfaces are still the same the
+/// cache can be used instead of analysing the module.
+///
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ReusableCache {
+    pub interface: type_::ModuleInterface,
+    pub interface_hash: InterfaceHash,
+    pub dependency_hashes: Vec<(EcoString, InterfaceHash)>,
+    /// The contents of the cache files, to write them back if the cache ends
+    /// up being used.
+    pub cache: Vec<u8>,
+    pub metadata: Vec<u8>,
+}
+
+impl ReusableCache {
+    fn is_up_to_date(
+        &self,
+        dependencies: &[(EcoString, SrcSpan)],
+        modules: &im::HashMap<EcoString, type_::ModuleInterface>,
+        interface_hashes: &mut InterfaceHashes,
+    ) -> bool {
+        dependencies.iter().all(|(dependency, _)| {
+            let previous = self
+                .dependency_hashes
+                .iter()
+                .find(|(name, _)| name == dependency)
+                .map(|(_, hash)| *hash);
+            previous.is_some() && previous == interface_hashes.get(dependency, modules)
+        })
+    }
+}
+
+/// The interface hashes of the modules available to a package, computed the
+/// first time they are needed.
+///
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct InterfaceHashes {
+    hashes: HashMap<EcoString, InterfaceHash>,
+}
+
+impl InterfaceHashes {
+    pub fn insert(&mut self, name: EcoString, hash: InterfaceHash) {
+        let _ = self.hashes.insert(name, hash);
+    }
+
+    pub fn get(
+        &mut self,
+        name: &EcoString,
+        modules: &im::HashMap<EcoString, type_::ModuleInterface>,
+    ) -> Option<InterfaceHash> {
+        if let Some(hash) = self.hashes.get(name) {
+            return Some(*hash);
+        }
+        let hash = InterfaceHash::new(modules.get(name)?);
+        self.insert(name.clone(), hash);
+        Some(hash)
+    }
 }
 
 #[derive(Template)]
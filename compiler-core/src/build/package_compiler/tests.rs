@@ -3,14 +3,17 @@
 
 use std::collections::HashSet;
 
-use camino::Utf8Path;
+use std::time::{Duration, SystemTime};
+
+use camino::{Utf8Path, Utf8PathBuf};
 use ecow::EcoString;
+use itertools::Itertools;
 
 use crate::{
-    Error,
+    Error, Warning,
     build::{
-        self, NullTelemetry, Outcome, PackageCompiler, StaleTracker, TargetCodegenConfiguration,
-        package_compiler::Compiled,
+        self, ModuleFormat, NullTelemetry, Outcome, PackageCompiler, StaleTracker,
+        TargetCodegenConfiguration, package_compiler::Compiled,
     },
     config::PackageConfig,
     error::DefinedModuleOrigin,
@@ -88,3 +91,154 @@ pub fn same_package_defining_duplicate_module() {
 
     insta::assert_snapshot!(insta::internals::AutoName, output);
 }
+
+const WIBBLE: &str = "pub fn wibble() { helper() }
+fn helper() { 1 }";
+
+const WOBBLE: &str = "import wibble
+pub fn wobble() { wibble.wibble() }";
+
+fn write_module(fs: &InMemoryFileSystem, name: &str, seconds: u64, src: &str) {
+    let path = Utf8PathBuf::from(format!("/src/{name}.gleam"));
+    fs.write(&path, src).expect("write module");
+    fs.set_modification_time(&path, SystemTime::UNIX_EPOCH + Duration::from_secs(seconds));
+}
+
+fn compile_package(fs: &InMemoryFileSystem) -> (Vec<EcoString>, Vec<EcoString>) {
+    compile_package_with_warnings(fs, &WarningEmitter::null())
+}
+
+fn compile_package_with_warnings(
+    fs: &InMemoryFileSystem,
+    warnings: &WarningEmitter,
+) -> (Vec<EcoString>, Vec<EcoString>) {
+    let compiled = compile_package_outcome(fs, warnings)
+        .into_result()
+        .expect("should compile");
+
+    let analysed = compiled.modules.into_iter().map(|module| module.name);
+    (analysed.collect(), compiled.cached_module_names)
+}
+
+fn compile_package_outcome(
+    fs: &InMemoryFileSystem,
+    warnings: &WarningEmitter,
+) -> Outcome<Compiled, Error> {
+    let mut config = PackageConfig::default();
+    config.name = "my_package".into();
+    let target = TargetCodegenConfiguration::JavaScript {
+        emit_typescript_definitions: false,
+        emit_source_maps: false,
+        module_format: ModuleFormat::Esm,
+        prelude_location: Utf8PathBuf::from("../prelude.mjs"),
+    };
+
+    let compiler = PackageCompiler::new(
+        &config,
+        build::Mode::Dev,
+        Utf8Path::new("/"),
+        Utf8Path::new("/out"),
+        Utf8Path::new("/lib"),
+        &target,
+        UniqueIdGenerator::new(),
+        fs.clone(),
+    );
+
+    compiler.compile(
+        warnings,
+        &mut im::HashMap::new(),
+        &mut im::HashMap::new(),
+        &mut StaleTracker::default(),
+        &mut HashSet::new(),
+        &NullTelemetry,
+    )
+}
+
+#[test]
+fn importing_modules_are_not_analysed_again_if_interface_is_unchanged() {
+    let fs = InMemoryFileSystem::new();
+    write_module(&fs, "wibble", 0, WIBBLE);
+    write_module(&fs, "wobble", 0, WOBBLE);
+    let (analysed, _) = compile_package(&fs);
+    assert_eq!(analysed, vec!["wibble", "wobble"]);
+
+    // Only the body of a private function changes
+    write_module(&fs, "wibble", 1, &WIBBLE.replace("1", "2"));
+    let (analysed, cached) = compile_package(&fs);
+    assert_eq!(analysed, vec!["wibble"]);
+    assert_eq!(cached, vec!["wobble"]);
+
+    // The restored cache is used as usual the next time
+    let (analysed, cached) = compile_package(&fs);
+    assert!(analysed.is_empty());
+    assert_eq!(cached, vec!["wibble", "wobble"]);
+}
+
+#[test]
+fn importing_modules_are_analysed_again_if_interface_changes() {
+    let fs = InMemoryFileSystem::new();
+    write_module(&fs, "wibble", 0, WIBBLE);
+    write_module(&fs, "wobble", 0, WOBBLE);
+    let _ = compile_package(&fs);
+
+    // The return type of the public function changes
+    write_module(&fs, "wibble", 1, &WIBBLE.replace("1", "1.0"));
+    let (analysed, cached) = compile_package(&fs);
+    assert_eq!(analysed, vec!["wibble", "wobble"]);
+    assert!(cached.is_empty());
+}
+
+#[test]
+fn modules_analysed_in_parallel_keep_their_order_and_warnings_order() {
+    let compile = || {
+        let fs = InMemoryFileSystem::new();
+        for name in ["wibble", "wobble", "wubble", "wabble"] {
+            write_module(&fs, name, 0, "fn unused() { 1 }\npub fn main() { Nil }");
+        }
+        write_module(
+            &fs,
+            "everything",
+            0,
+            "import wibble\nimport wobble\nimport wubble\nimport wabble\nfn unused() { 1 }",
+        );
+        let (emitter, warnings) = WarningEmitter::vector();
+        let (analysed, _) = compile_package_with_warnings(&fs, &emitter);
+        (analysed, warnings.take())
+    };
+
+    let (analysed, warnings) = compile();
+    assert_eq!(analysed.last().map(EcoString::as_str), Some("everything"));
+
+    // The warnings are emitted in the same order as the modules
+    let warned_modules = warnings
+        .iter()
+        .filter_map(|warning| {
+            if let Warning::Type { path, .. } = warning {
+                path.file_stem().map(EcoString::from)
+            } else {
+                None
+            }
+        })
+        .dedup()
+        .collect_vec();
+    assert_eq!(warned_modules, analysed);
+
+    for _ in 0..10 {
+        assert_eq!(compile(), (analysed.clone(), warnings.clone()));
+    }
+}
+
+#[test]
+fn syntax_errors_of_every_module_are_reported_along_with_type_errors() {
+    let fs = InMemoryFileSystem::new();
+    write_module(&fs, "wibble", 0, "pub fn wibble() { let x = }");
+    write_module(&fs, "wobble", 0, "pub fn wobble() -> Int { \"wobble\" }");
+    write_module(&fs, "wubble", 0, "pub type Wubble {");
+
+    let output = compile_package_outcome(&fs, &WarningEmitter::null())
+        .into_result()
+        .expect_err("should produce an error")
+        .pretty_string();
+
+    insta::assert_snapshot!(insta::internals::AutoName, output);
+}
@@ -7,6 +7,7 @@ mod tests;
 use std::{
     collections::{HashMap, HashSet},
     sync::OnceLock,
+    time::SystemTime,
 };
 
 use camino::{Utf8Path, Utf8PathBuf};
@@ -16,7 +17,7 @@ use camino::{Utf8Path, Utf8PathBuf};
 use crate::{
     Error, Result,
     build::{Origin, module_loader::ModuleLoader},
-    dep_tree,
+    dep_tree, doctest,
     error::{DefinedModuleOrigin, FileIoAction, FileKind, ImportCycleLocationDetails},
     io::{CommandExecutor, FileSystemReader, FileSystemWriter, files_with_extension},
     metadata,
@@ -34,7 +35,9 @@ use vec1::Vec1;
 use super::{
     Mode, Target,
     module_loader::read_source,
-    package_compiler::{CachedModule, CachedWarnings, Input, Loaded, UncompiledModule},
+    package_compiler::{
+        CachedModule, CachedWarnings, Input, Loaded, ReusableCache, UncompiledModule,
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -68,6 +71,9 @@ pub struct PackageLoader<'a, IO> {
     already_defined_modules: &'a mut im::HashMap<EcoString, DefinedModuleOrigin>,
     incomplete_modules: &'a HashSet<EcoString>,
     cached_warnings: CachedWarnings,
+    /// Whether to load the test modules checking the code examples in the
+    /// documentation of the package, see the `doctest` module.
+    doctests: bool,
 }
 
 impl<'a, IO> PackageLoader<'a, IO>
@@ -89,6 +95,7 @@ where
         stale_modules: &'a mut StaleTracker,
         already_defined_modules: &'a mut im::HashMap<EcoString, DefinedModuleOrigin>,
         incomplete_modules: &'a HashSet<EcoString>,
+        doctests: bool,
     ) -> Self {
         Self {
             io,
@@ -104,6 +111,7 @@ where
             stale_modules,
             already_defined_modules,
             incomplete_modules,
+            doctests,
         }
     }
 
@@ -160,8 +168,10 @@ where
                 }
 
                 // A cached module with dependencies that are stale must be
-                // recompiled as the changes in the dependencies may have affect
-                // the output, making the cache invalid.
+                // checked again as the changes in the dependencies may have
+                // affected the output, making the cache invalid. If the
+                // interfaces of the dependencies turn out to be the same the
+                // cache is used after all, see `ReusableCache`.
                 Input::Cached(info) if self.stale_modules.includes_any(&info.dependencies) => {
                     tracing::debug!(module = %info.name, "stale_module_to_be_compiled");
                     self.stale_modules.add(info.name.clone());
@@ -173,6 +183,9 @@ where
                 // and does not need to be recompiled.
                 Input::Cached(info) => {
                     tracing::debug!(module = %info.name, "module_to_load_from_cache");
+                    loaded
+                        .interface_hashes
+                        .insert(info.name.clone(), info.interface_hash);
                     let module = self.load_cached_module(info)?;
                     loaded.cached.push(module);
                 }
@@ -185,7 +198,15 @@ where
     fn load_cached_module(&self, info: CachedModule) -> Result<type_::ModuleInterface, Error> {
         let cache_files = CacheFiles::new(self.artefact_directory, &info.name);
         let bytes = self.io.read_bytes(&cache_files.cache_path)?;
-        let mut module = match metadata::decode(bytes.as_slice(), self.ids.clone()) {
+        self.decode_cache(cache_files, &bytes)
+    }
+
+    fn decode_cache(
+        &self,
+        cache_files: CacheFiles,
+        bytes: &[u8],
+    ) -> Result<type_::ModuleInterface, Error> {
+        let mut module = match metadata::decode(bytes, self.ids.clone()) {
             Ok(module) => module,
             Err(e) => {
                 return Err(Error::FileIo {
@@ -211,9 +232,14 @@ where
         let span = tracing::info_span!("load");
         let _enter = span.enter();
 
-        let mut inputs = Inputs::new(self.package_name.clone(), self.already_defined_modules);
-
         let src = self.paths.src_directory();
+        let doctests = if self.doctests && self.mode.includes_dev_code() {
+            self.read_doctests(&src)?
+        } else {
+            vec![]
+        };
+
+        let mut inputs = Inputs::new(self.package_name.clone(), self.already_defined_modules);
         let mut loader = ModuleLoader {
             io: self.io.clone(),
             warnings: self.warnings,
@@ -264,6 +290,10 @@ where
                     Err(warning) => self.warnings.emit(warning),
                 }
             }
+
+            for input in doctests {
+                inputs.insert(input)?;
+            }
         }
 
         // If we are compiling for Erlang then modules all live in a single
@@ -282,9 +312,114 @@ where
         Ok(inputs.collection)
     }
 
+    /// Generates a test module for each module in the `src` directory with
+    /// code examples in its documentation, and a module to run them all.
+    ///
+    /// These are never cached as their source code doesn't exist on disc,
+    /// errors in the examples are reported at the documented module instead.
+    ///
+    fn read_doctests(&self, src: &Utf8Path) -> Result<Vec<Input>> {
+        let mut inputs = vec![];
+        let mut doctest_modules = vec![];
+
+        // Any warning for the documented modules has already been emitted
+        // when they were loaded.
+        let warnings = WarningEmitter::null();
+
+        for file in GleamFile::iterate_files_in_directory(&self.io, src).filter_map(Result::ok) {
+            let mtime = self.io.modification_time(&file.path)?;
+            let documented = read_source(
+                self.io.clone(),
+                self.target,
+                Origin::Src,
+                file.path.clone(),
+                file.module_name.clone(),
+                self.package_name.clone(),
+                mtime,
+                warnings.clone(),
+            )?;
+
+            // The examples of a module with syntax errors are not checked,
+            // the module has to be fixed first.
+            if !documented.parse_errors.is_empty() {
+                continue;
+            }
+
+            let Some(code) = doctest::module_source(
+                &documented.ast,
+                &documented.code,
+                &documented.extra,
+                self.target,
+            ) else {
+                continue;
+            };
+
+            let name = doctest::module_name(&documented.name);
+            let module = self.doctest_module(name.clone(), file.path, code, mtime)?;
+            inputs.push(Input::New(Box::new(module)));
+            doctest_modules.push(name);
+        }
+
+        if doctest_modules.is_empty() {
+            return Ok(inputs);
+        }
+
+        let name = EcoString::from(doctest::RUNNER_MODULE);
+        let path = self.artefact_directory.join(format!("{name}.gleam"));
+        let code = doctest::runner_source(&doctest_modules);
+        let module = self.doctest_module(name, path, code, SystemTime::now())?;
+        inputs.push(Input::New(Box::new(module)));
+        Ok(inputs)
+    }
+
+    fn doctest_module(
+        &self,
+        name: EcoString,
+        path: Utf8PathBuf,
+        code: EcoString,
+        mtime: SystemTime,
+    ) -> Result<UncompiledModule> {
+        let parsed = crate::parse::parse_module(path.clone(), &code, &WarningEmitter::null())
+            .map_err(|errors| Error::Parse {
+                path: path.clone(),
+                src: code.clone(),
+                errors,
+            })?;
+        let mut ast = parsed.module;
+        ast.name = name.clone();
+
+        Ok(UncompiledModule {
+            package: self.package_name.clone(),
+            dependencies: ast.dependencies(self.target),
+            origin: Origin::Test,
+            extra: parsed.extra,
+            mtime,
+            path,
+            name,
+            code,
+            ast,
+            parse_errors: vec![],
+            reusable_cache: None,
+        })
+    }
+
     fn load_stale_module(&self, cached: CachedModule) -> Result<UncompiledModule> {
         let mtime = self.io.modification_time(&cached.source_path)?;
 
+        // The cache is kept in memory as it can still be used if the interfaces
+        // of the stale dependencies haven't actually changed.
+        let cache_files = CacheFiles::new(self.artefact_directory, &cached.name);
+        let cache = self.io.read_bytes(&cache_files.cache_path)?;
+        let metadata = self.io.read_bytes(&cache_files.meta_path)?;
+        let interface = self.decode_cache(cache_files, &cache)?;
+        let reusable_cache = ReusableCache {
+            interface,
+            interface_hash: cached.interface_hash,
+            dependency_hashes: cached.dependency_hashes,
+            cache,
+            metadata,
+        };
+
         // We need to delete any existing cache files for this module.
         // While we figured it out this time because the module has stale dependencies,
         // next time the dependencies might no longer be stale, but we still need to be able to tell
@@ -292,7 +427,7 @@ where
         // This can happen if the stale dependency includes breaking changes.
         CacheFiles::new(self.artefact_directory, &cached.name).delete(&self.io)?;
 
-        read_source(
+        let mut module = read_source(
             self.io.clone(),
             self.target,
             cached.origin,
@@ -301,7 +436,9 @@ where
             self.package_name.clone(),
             mtime,
             self.warnings.clone(),
-        )
+        )?;
+        module.reusable_cache = Some(Box::new(reusable_cache));
+        Ok(module)
     }
 
     fn convert_deps_tree_error(
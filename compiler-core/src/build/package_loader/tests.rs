@@ -8,6 +8,7 @@ use src_span::LineNumbers;
 use super::*;
 use crate::{
     Warning,
+    build::InterfaceHash,
     build::SourceFingerprint,
     build::package_compiler::CacheMetadata,
     io::{FileSystemWriter, memory::InMemoryFileSystem},
@@ -20,6 +21,8 @@ use std::time::{Duration, SystemTime};
 struct LoaderTestOutput {
     to_compile: Vec<EcoString>,
     cached: Vec<EcoString>,
+    /// The modules to compile which have a cache that could be used instead.
+    reusable: Vec<EcoString>,
     warnings: Vec<Warning>,
 }
 
@@ -41,18 +44,6 @@ fn write_cache(
 ) {
     let line_numbers = LineNumbers::new(src);
     let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
-    let cache_metadata = CacheMetadata {
-        mtime,
-        codegen_performed: true,
-        dependencies: deps,
-        fingerprint: SourceFingerprint::new(src),
-        line_numbers: line_numbers.clone(),
-    };
-
-    let artefact_name = name.replace("/", "@");
-    let path = Utf8Path::new("/artefact").join(format!("{artefact_name}.cache_meta"));
-    fs.write_bytes(&path, &cache_metadata.to_binary()).unwrap();
-
     let cache = type_::ModuleInterface {
         name: name.into(),
         origin: Origin::Src,
@@ -72,12 +63,38 @@ fn write_cache(
         references: References::default(),
         inline_functions: HashMap::new(),
     };
+    let artefact_name = name.replace("/", "@");
     let path = Utf8Path::new("/artefact").join(format!("{artefact_name}.cache"));
     fs.write_bytes(&path, &metadata::encode(&cache).unwrap())
         .unwrap();
+
+    let dependency_hashes = deps
+        .iter()
+        .map(|(name, _)| (name.clone(), InterfaceHash(0)))
+        .collect();
+    let cache_metadata = CacheMetadata {
+        mtime,
+        codegen_performed: true,
+        dependencies: deps,
+        fingerprint: SourceFingerprint::new(src),
+        line_numbers,
+        interface_hash: InterfaceHash::new(&cache),
+        dependency_hashes,
+    };
+    let path = Utf8Path::new("/artefact").join(format!("{artefact_name}.cache_meta"));
+    fs.write_bytes(&path, &cache_metadata.to_binary()).unwrap();
 }
 
 fn run_loader(fs: InMemoryFileSystem, root: &Utf8Path, artefact: &Utf8Path) -> LoaderTestOutput {
+    run_loader_with_doctests(fs, root, artefact, false)
+}
+
+fn run_loader_with_doctests(
+    fs: InMemoryFileSystem,
+    root: &Utf8Path,
+    artefact: &Utf8Path,
+    doctests: bool,
+) -> LoaderTestOutput {
     let mut defined = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
     let (emitter, warnings) = WarningEmitter::vector();
@@ -96,10 +113,17 @@ fn run_loader(fs: InMemoryFileSystem, root: &Utf8Path, artefact: &Utf8Path) -> L
         already_defined_modules: &mut defined,
         incomplete_modules: &mut HashSet::new(),
         cached_warnings: CachedWarnings::Ignore,
+        doctests,
     };
     let loaded = loader.run().unwrap();
 
     LoaderTestOutput {
+        reusable: loaded
+            .to_compile
+            .iter()
+            .filter(|m| m.reusable_cache.is_some())
+            .map(|m| m.name.clone())
+            .collect(),
         to_compile: loaded.to_compile.into_iter().map(|m| m.name).collect(),
         cached: loaded.cached.into_iter().map(|m| m.name).collect(),
         warnings: warnings.take(),
@@ -143,6 +167,50 @@ fn one_test_module() {
     assert!(loaded.cached.is_empty());
 }
 
+#[test]
+fn documentation_examples_are_loaded_as_test_modules() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    write_src(
+        &fs,
+        "/src/main.gleam",
+        0,
+        "/// ```gleam\n/// assert x == 1\n/// ```\npub const x = 1",
+    );
+    write_src(&fs, "/src/other.gleam", 0, "pub const y = 1");
+
+    let loaded = run_loader_with_doctests(fs, root, artefact, true);
+    assert_eq!(
+        loaded.to_compile,
+        vec![
+            EcoString::from("main"),
+            EcoString::from("gleam_doctest/main"),
+            EcoString::from("gleam_doctest"),
+            EcoString::from("other"),
+        ]
+    );
+    assert!(loaded.cached.is_empty());
+}
+
+#[test]
+fn documentation_examples_are_not_loaded_by_default() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    write_src(
+        &fs,
+        "/src/main.gleam",
+        0,
+        "/// ```gleam\n/// assert x == 1\n/// ```\npub const x = 1",
+    );
+
+    let loaded = run_loader(fs, root, artefact);
+    assert_eq!(loaded.to_compile, vec![EcoString::from("main")]);
+}
+
 #[test]
 fn one_dev_module() {
     let fs = InMemoryFileSystem::new();
@@ -238,6 +306,38 @@ fn module_is_stale_if_deps_are_stale() {
     assert_eq!(loaded.cached, vec![EcoString::from("three")]);
 }
 
+#[test]
+fn module_with_stale_deps_keeps_its_cache_in_case_interfaces_are_unchanged() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    // Cache is stale
+    write_src(&fs, "/src/one.gleam", 1, TEST_SOURCE_2);
+    write_cache(&fs, "one", 0, vec![], TEST_SOURCE_1);
+
+    // Cache is fresh but dep is stale
+    write_src(&fs, "/src/two.gleam", 1, "import one");
+    write_cache(
+        &fs,
+        "two",
+        2,
+        vec![(EcoString::from("one"), SrcSpan { start: 0, end: 0 })],
+        "import one",
+    );
+
+    let loaded = run_loader(fs.clone(), root, artefact);
+    assert_eq!(
+        loaded.to_compile,
+        vec![EcoString::from("one"), EcoString::from("two")]
+    );
+    assert_eq!(loaded.reusable, vec![EcoString::from("two")]);
+
+    // The cache files are deleted until the module has been checked again
+    assert!(!fs.is_file(Utf8Path::new("/artefact/two.cache")));
+    assert!(!fs.is_file(Utf8Path::new("/artefact/two.cache_meta")));
+}
+
 #[test]
 fn module_is_stale_if_deps_removed() {
     let fs = InMemoryFileSystem::new();
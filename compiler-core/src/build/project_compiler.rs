@@ -5,7 +5,7 @@ use crate::{
     Error, Result,
     analyse::TargetSupport,
     build::{
-        Mode, Module, Package, Target, package_compiler::PackageCompiler,
+        Mode, Module, ModuleFormat, Package, Target, package_compiler::PackageCompiler,
         package_loader::StaleTracker, telemetry::Telemetry,
     },
     config::PackageConfig,
@@ -57,11 +57,20 @@ pub struct Options {
     pub warnings_as_errors: bool,
     pub root_target_support: TargetSupport,
     pub no_print_progress: bool,
+    /// Whether to check the code examples in the documentation of the root
+    /// package, see the `doctest` module.
+    pub doctests: bool,
+    /// Whether to inline function calls when generating code, see the
+    /// `inline` module.
+    pub inline: bool,
 }
 
 #[derive(Debug)]
 pub struct Built {
     pub root_package: Package,
+    /// The modules of dependency packages that were compiled from source
+    /// during this build. Modules loaded from the cache are not included.
+    pub dependency_modules: Vec<Module>,
     pub module_interfaces: im::HashMap<EcoString, type_::ModuleInterface>,
 }
 
@@ -70,9 +79,18 @@ impl Built {
         &self,
         module: &EcoString,
         target: Target,
+    ) -> Result<ModuleFunction, Error> {
+        self.get_entrypoint_function(module, "main", target)
+    }
+
+    pub fn get_entrypoint_function(
+        &self,
+        module: &EcoString,
+        function: &str,
+        target: Target,
     ) -> Result<ModuleFunction, Error> {
         match self.module_interfaces.get(module) {
-            Some(module_data) => module_data.get_main_function(target),
+            Some(module_data) => module_data.get_entrypoint_function(function, target),
             None => Err(Error::ModuleDoesNotExist {
                 module: module.clone(),
                 suggestion: None,
@@ -205,7 +223,7 @@ where
         self.write_prelude()?;
 
         // Dependencies are compiled first.
-        let _ = self.compile_dependencies()?;
+        let dependency_modules = self.compile_dependencies()?;
 
         // We reset the warning count as we don't want to fail the build if a
         // dependency has warnings, only if the root package does.
@@ -213,6 +231,12 @@ where
 
         let root_package = self.compile_root_package().into_result()?;
 
+        if self.warnings.denied_count() > 0 {
+            return Err(Error::DeniedWarnings {
+                count: self.warnings.denied_count(),
+            });
+        }
+
         // TODO: test
         if self.options.warnings_as_errors && self.warnings.count() > 0 {
             return Err(Error::ForbiddenWarnings {
@@ -222,6 +246,7 @@ where
 
         Ok(Built {
             root_package,
+            dependency_modules,
             module_interfaces: self.importable_modules,
         })
     }
@@ -259,6 +284,14 @@ where
         let mut flags = BitFlags::new();
         flags.set(0, js && self.config.javascript.typescript_declarations);
         flags.set(1, js && self.config.javascript.source_maps);
+        flags.set(
+            2,
+            js && self.config.javascript.module_format == ModuleFormat::Cjs,
+        );
+        flags.set(
+            3,
+            js && self.config.javascript.module_format == ModuleFormat::Global,
+        );
 
         let fingerprint = format!("{COMPILER_VERSION} {}", flags.bits);
 
@@ -303,24 +336,36 @@ where
     }
 
     fn write_prelude(&self) -> Result<()> {
-        // Only the JavaScript target has a prelude to write.
-        if !self.target().is_javascript() {
-            return Ok(());
-        }
-
         let build = self
             .paths
             .build_directory_for_target(self.mode(), self.target());
 
+        match self.target() {
+            Target::Erlang => return Ok(()),
+            Target::JavaScript => (),
+            // The WebAssembly prelude is the runtime module that all the
+            // generated modules import from.
+            Target::Wasm => {
+                let path = build.join("prelude.wat");
+                if !self.io.is_file(&path) {
+                    self.io.write(&path, crate::wasm::PRELUDE)?;
+                }
+                return Ok(());
+            }
+        }
+
+        let module_format = self.config.javascript.module_format;
+
         // Write the JavaScript prelude
-        let path = build.join("prelude.mjs");
+        let path = build.join(format!("prelude.{}", module_format.extension()));
         if !self.io.is_file(&path) {
-            self.io.write(&path, crate::javascript::PRELUDE)?;
+            self.io
+                .write(&path, &crate::javascript::prelude(module_format))?;
         }
 
         // Write the TypeScript prelude, if asked for
         if self.config.javascript.typescript_declarations {
-            let path = build.join("prelude.d.mts");
+            let path = build.join(format!("prelude.{}", module_format.declaration_extension()));
             if !self.io.is_file(&path) {
                 self.io.write(&path, crate::javascript::PRELUDE_TS_DEF)?;
             }
@@ -600,9 +645,15 @@ where
             Target::JavaScript => super::TargetCodegenConfiguration::JavaScript {
                 emit_typescript_definitions: self.config.javascript.typescript_declarations,
                 emit_source_maps: self.config.javascript.source_maps,
+                module_format: self.config.javascript.module_format,
                 // This path is relative to each package output directory
-                prelude_location: Utf8PathBuf::from("../prelude.mjs"),
+                prelude_location: Utf8PathBuf::from(format!(
+                    "../prelude.{}",
+                    self.config.javascript.module_format.extension()
+                )),
             },
+
+            Target::Wasm => super::TargetCodegenConfiguration::Wasm,
         };
 
         let mut compiler = PackageCompiler::new(
@@ -621,6 +672,8 @@ where
         compiler.compile_beam_bytecode = self.options.codegen.should_codegen(is_root);
         compiler.compile_modules = !(self.options.compile == Compile::DepsOnly && is_root);
         compiler.subprocess_stdio = self.subprocess_stdio;
+        compiler.doctests = is_root && self.options.doctests;
+        compiler.inline = self.options.inline;
         compiler.target_support = if is_root {
             // When compiling the root package it is context specific as to whether we need to
             // enforce that all functions have an implementation for the current target.
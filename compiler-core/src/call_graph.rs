@@ -6,6 +6,8 @@
 
 #[cfg(test)]
 mod into_dependency_order_tests;
+mod reachability;
+mod tail_calls;
 
 use crate::{
     Result,
@@ -20,6 +22,9 @@ use itertools::Itertools;
 use petgraph::{Directed, stable_graph::NodeIndex, stable_graph::StableGraph};
 use src_span::SrcSpan;
 
+pub use reachability::{ReachabilityAnalyser, ReachableDefinitions};
+pub use tail_calls::mutually_tail_recursive_groups;
+
 #[derive(Debug, Default)]
 struct CallGraphBuilder<'a> {
     names: im::HashMap<&'a str, Option<(NodeIndex, SrcSpan)>>,
@@ -27,6 +32,9 @@ struct CallGraphBuilder<'a> {
     current_function: NodeIndex,
 }
 
+// The nodes are only built once per module to be moved into analysis, so
+// boxing the larger function variant wouldn't save anything.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CallGraphNode {
     Function(UntypedFunction),
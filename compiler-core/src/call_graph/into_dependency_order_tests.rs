@@ -42,17 +42,21 @@ fn parse_and_order(
             return_annotation: None,
             publicity: Publicity::Public,
             deprecation: Deprecation::NotDeprecated,
+            inline: None,
             end_position: src.len() as u32,
             return_type: (),
             documentation: None,
             external_erlang: None,
             external_javascript: None,
+            external_wasm: None,
             implementations: Implementations {
                 gleam: true,
                 uses_erlang_externals: true,
                 uses_javascript_externals: false,
+                uses_wasm_externals: false,
                 can_run_on_erlang: true,
                 can_run_on_javascript: true,
+                can_run_on_wasm: true,
             },
             purity: Purity::Impure,
         })
@@ -73,8 +77,10 @@ fn parse_and_order(
                     gleam: true,
                     uses_erlang_externals: true,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: true,
                 },
                 type_: (),
                 deprecation: Deprecation::NotDeprecated,
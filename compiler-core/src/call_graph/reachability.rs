@@ -0,0 +1,345 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Finding which top level definitions of a whole program can be reached from
+//! its entrypoint, so code generators can leave out all the others.
+//!
+//! Unlike the rest of the call graph, which works within a single module, this
+//! works across all the modules of a program and on their typed AST, where
+//! each reference has already been resolved to the module it comes from.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use ecow::EcoString;
+use src_span::SrcSpan;
+
+use crate::{
+    analyse::Inferred,
+    ast::{
+        CallArg, RecordBeingUpdated, RecordUpdateArg, TypedConstant, TypedExpr, TypedModule,
+        TypedPattern,
+        visit::{self, Visit},
+    },
+    type_::{
+        FieldMap, ModuleValueConstructor, PatternConstructor, Type, ValueConstructor,
+        ValueConstructorVariant,
+    },
+};
+
+/// The top level functions, constants and record constructors of a program
+/// that can be reached from its entrypoint.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReachableDefinitions {
+    values: HashMap<EcoString, HashSet<EcoString>>,
+}
+
+impl ReachableDefinitions {
+    /// Whether the value with the given name, defined in the given module, is
+    /// reachable.
+    ///
+    pub fn contains(&self, module: &str, name: &str) -> bool {
+        self.values
+            .get(module)
+            .is_some_and(|names| names.contains(name))
+    }
+
+    /// Returns true if this is the first time the value is inserted.
+    fn insert(&mut self, module: &str, name: &str) -> bool {
+        self.values
+            .entry(module.into())
+            .or_default()
+            .insert(name.into())
+    }
+}
+
+/// Walks the definitions of a program starting from the ones it is told are
+/// referenced, such as the entrypoint of the program, marking everything they
+/// reference in turn as reachable.
+///
+/// Custom types are kept or removed as a whole: if any of the record
+/// constructors of a type is reachable then all of them are, as the code
+/// pattern matching on a value needs all of its variants to exist.
+///
+#[derive(Debug)]
+pub struct ReachabilityAnalyser<'a> {
+    modules: HashMap<&'a str, &'a TypedModule>,
+    reachable: ReachableDefinitions,
+    to_visit: Vec<(EcoString, EcoString)>,
+}
+
+impl<'a> ReachabilityAnalyser<'a> {
+    pub fn new(modules: impl IntoIterator<Item = &'a TypedModule>) -> Self {
+        Self {
+            modules: modules
+                .into_iter()
+                .map(|module| (module.name.as_str(), module))
+                .collect(),
+            reachable: ReachableDefinitions::default(),
+            to_visit: vec![],
+        }
+    }
+
+    /// Marks a value as reachable, along with everything it references.
+    /// Values from modules that are not part of the program, such as the
+    /// prelude, are ignored.
+    ///
+    pub fn reference(&mut self, module: &str, name: &str) {
+        self.mark(module, name);
+
+        while let Some((module, name)) = self.to_visit.pop() {
+            self.visit(&module, &name);
+        }
+    }
+
+    /// Marks all the values defined in a module as reachable, for when it is
+    /// used in a way that we can't track, such as being imported as a whole
+    /// by some FFI code.
+    ///
+    pub fn reference_module(&mut self, module: &str) {
+        let Some(module) = self.modules.get(module) else {
+            return;
+        };
+
+        let definitions = &module.definitions;
+        let names = definitions
+            .functions
+            .iter()
+            .filter_map(|function| function.name.as_ref().map(|(_, name)| name))
+            .chain(definitions.constants.iter().map(|constant| &constant.name))
+            .chain(
+                definitions
+                    .custom_types
+                    .iter()
+                    .filter_map(|custom_type| custom_type.constructors.first())
+                    .map(|constructor| &constructor.name),
+            );
+
+        let module_name = &module.name;
+        for name in names {
+            self.reference(module_name, name);
+        }
+    }
+
+    pub fn reachable_definitions(&self) -> &ReachableDefinitions {
+        &self.reachable
+    }
+
+    pub fn into_reachable_definitions(self) -> ReachableDefinitions {
+        self.reachable
+    }
+
+    fn mark(&mut self, module: &str, name: &str) {
+        if self.modules.contains_key(module) && self.reachable.insert(module, name) {
+            self.to_visit.push((module.into(), name.into()));
+        }
+    }
+
+    fn visit(&mut self, module_name: &str, name: &str) {
+        let Some(module) = self.modules.get(module_name).copied() else {
+            return;
+        };
+
+        let mut references = References::default();
+        let definitions = &module.definitions;
+
+        if let Some(function) = definitions
+            .functions
+            .iter()
+            .find(|function| function.name.as_ref().is_some_and(|(_, n)| n == name))
+        {
+            references.visit_typed_function(function);
+        } else if let Some(constant) = definitions
+            .constants
+            .iter()
+            .find(|constant| constant.name == name)
+        {
+            references.visit_typed_module_constant(constant);
+        } else if let Some(custom_type) = definitions.custom_types.iter().find(|custom_type| {
+            custom_type
+                .constructors
+                .iter()
+                .any(|constructor| constructor.name == name)
+        }) {
+            for constructor in &custom_type.constructors {
+                references
+                    .values
+                    .push((module_name.into(), constructor.name.clone()));
+            }
+        }
+
+        for (module, name) in references.values {
+            self.mark(&module, &name);
+        }
+    }
+}
+
+/// Collects the module values referenced by a definition.
+///
+#[derive(Debug, Default)]
+struct References {
+    values: Vec<(EcoString, EcoString)>,
+}
+
+impl References {
+    fn value_constructor(&mut self, constructor: &ValueConstructor) {
+        match &constructor.variant {
+            ValueConstructorVariant::ModuleConstant { module, name, .. }
+            | ValueConstructorVariant::ModuleFn { module, name, .. }
+            | ValueConstructorVariant::Record { module, name, .. } => {
+                self.values.push((module.clone(), name.clone()));
+            }
+            ValueConstructorVariant::LocalVariable { .. } => {}
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for References {
+    fn visit_typed_expr_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        _name: &'ast EcoString,
+    ) {
+        self.value_constructor(constructor);
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        _location: &'ast SrcSpan,
+        _field_start: &'ast u32,
+        _type_: &'ast Arc<Type>,
+        label: &'ast EcoString,
+        module_name: &'ast EcoString,
+        _module_alias: &'ast EcoString,
+        _constructor: &'ast ModuleValueConstructor,
+    ) {
+        self.values.push((module_name.clone(), label.clone()));
+    }
+
+    fn visit_typed_expr_echo(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        expression: &'ast Option<Box<TypedExpr>>,
+        message: &'ast Option<Box<TypedExpr>>,
+    ) {
+        // The JavaScript implementation of `echo` uses the standard library to
+        // print dictionaries.
+        self.values.push(("gleam/dict".into(), "new".into()));
+        self.values.push(("gleam/dict".into(), "fold".into()));
+        visit::visit_typed_expr_echo(self, location, type_, expression, message);
+    }
+
+    fn visit_typed_clause_guard_module_select(
+        &mut self,
+        _location: &'ast SrcSpan,
+        _field_start: &'ast u32,
+        _definition_location: &'ast SrcSpan,
+        _type_: &'ast Arc<Type>,
+        label: &'ast EcoString,
+        module_name: &'ast EcoString,
+        _module_alias: &'ast EcoString,
+        _literal: &'ast TypedConstant,
+    ) {
+        self.values.push((module_name.clone(), label.clone()));
+    }
+
+    fn visit_typed_pattern_constructor(
+        &mut self,
+        location: &'ast SrcSpan,
+        name_location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        arguments: &'ast Vec<CallArg<TypedPattern>>,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        constructor: &'ast Inferred<PatternConstructor>,
+        spread: &'ast Option<SrcSpan>,
+        type_: &'ast Arc<Type>,
+    ) {
+        if let Inferred::Known(constructor) = constructor {
+            self.values
+                .push((constructor.module.clone(), constructor.name.clone()));
+        }
+        visit::visit_typed_pattern_constructor(
+            self,
+            location,
+            name_location,
+            name,
+            arguments,
+            module,
+            constructor,
+            spread,
+            type_,
+        );
+    }
+
+    fn visit_typed_constant_record(
+        &mut self,
+        location: &'ast SrcSpan,
+        arguments_start_position: &'ast u32,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        name: &'ast EcoString,
+        arguments: &'ast Option<Vec<CallArg<TypedConstant>>>,
+        type_: &'ast Arc<Type>,
+        field_map: &'ast Inferred<FieldMap>,
+        record_constructor: &'ast Option<Box<ValueConstructor>>,
+    ) {
+        if let Some(constructor) = record_constructor {
+            self.value_constructor(constructor);
+        }
+        visit::visit_typed_constant_record(
+            self,
+            location,
+            arguments_start_position,
+            module,
+            name,
+            arguments,
+            type_,
+            field_map,
+            record_constructor,
+        );
+    }
+
+    fn visit_typed_constant_record_update(
+        &mut self,
+        location: &'ast SrcSpan,
+        constructor_location: &'ast SrcSpan,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        name: &'ast EcoString,
+        record: &'ast RecordBeingUpdated<TypedConstant>,
+        arguments: &'ast [RecordUpdateArg<TypedConstant>],
+        type_: &'ast Arc<Type>,
+        field_map: &'ast Inferred<FieldMap>,
+    ) {
+        // The record constructor is defined in the same module as the type of
+        // the updated record.
+        if let Some((type_module, _)) = type_.named_type_name() {
+            self.values.push((type_module, name.clone()));
+        }
+        visit::visit_typed_constant_record_update(
+            self,
+            location,
+            constructor_location,
+            module,
+            name,
+            record,
+            arguments,
+            type_,
+            field_map,
+        );
+    }
+
+    fn visit_typed_constant_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        _module: &'ast Option<(EcoString, SrcSpan)>,
+        _name: &'ast EcoString,
+        constructor: &'ast Option<Box<ValueConstructor>>,
+        _type_: &'ast Arc<Type>,
+    ) {
+        if let Some(constructor) = constructor {
+            self.value_constructor(constructor);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Finding the groups of module functions that call each other in tail
+//! position, so code generators that can't rely on the runtime to optimise
+//! tail calls can compile them into a single loop.
+//!
+//! Like the reachability analysis this works on the typed AST, where a
+//! reference to a module function can't be confused with a local variable
+//! shadowing it.
+
+use std::collections::HashMap;
+
+use ecow::EcoString;
+use itertools::Itertools;
+use petgraph::{Directed, stable_graph::NodeIndex, stable_graph::StableGraph};
+
+use crate::{
+    ast::{Statement, TypedExpr, TypedFunction, TypedStatement},
+    type_::{ValueConstructor, ValueConstructorVariant},
+};
+
+/// Returns the groups of mutually recursive functions: functions that can all
+/// reach each other through tail calls alone. Functions that only ever tail
+/// call themselves are not part of any group.
+///
+/// The functions of each group, and the groups themselves, are in the order
+/// they appear in the source code.
+///
+pub fn mutually_tail_recursive_groups<'a>(
+    module: &str,
+    functions: impl IntoIterator<Item = &'a TypedFunction>,
+) -> Vec<Vec<EcoString>> {
+    let functions = functions
+        .into_iter()
+        .filter_map(|function| {
+            let (_, name) = function.name.as_ref()?;
+            Some((name, function))
+        })
+        .sorted_by_key(|(_, function)| function.location.start)
+        .collect_vec();
+
+    let mut graph = StableGraph::<(), (), Directed>::new();
+    let nodes = functions
+        .iter()
+        .map(|(name, _)| (*name, graph.add_node(())))
+        .collect_vec();
+    let indices: HashMap<&EcoString, NodeIndex> = nodes.iter().copied().collect();
+
+    for ((_, function), (_, caller)) in functions.iter().zip(nodes) {
+        let mut finder = TailCallFinder {
+            module,
+            called: vec![],
+        };
+        finder.statements(&function.body);
+        for called in finder.called {
+            if let Some(callee) = indices.get(called) {
+                let _ = graph.update_edge(caller, *callee, ());
+            }
+        }
+    }
+
+    petgraph::algo::tarjan_scc(&graph)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| component.into_iter().sorted().collect_vec())
+        .sorted()
+        .map(|component| {
+            component
+                .into_iter()
+                .filter_map(|index| functions.get(index.index()))
+                .map(|(name, _)| (*name).clone())
+                .collect_vec()
+        })
+        .collect()
+}
+
+/// Collects the names of the module functions called in tail position.
+///
+struct TailCallFinder<'module, 'a> {
+    module: &'module str,
+    called: Vec<&'a EcoString>,
+}
+
+impl<'a> TailCallFinder<'_, 'a> {
+    fn statements(&mut self, statements: &'a [TypedStatement]) {
+        match statements.last() {
+            Some(Statement::Expression(expression)) => self.tail_expression(expression),
+            Some(Statement::Use(use_)) => self.tail_expression(&use_.call),
+            Some(Statement::Assignment(_) | Statement::Assert(_)) | None => {}
+        }
+    }
+
+    fn tail_expression(&mut self, expression: &'a TypedExpr) {
+        match expression {
+            TypedExpr::Call { fun, .. } => {
+                if let TypedExpr::Var {
+                    constructor:
+                        ValueConstructor {
+                            variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                            ..
+                        },
+                    ..
+                } = fun.as_ref()
+                    && module == self.module
+                {
+                    self.called.push(name);
+                }
+            }
+
+            TypedExpr::Block { statements, .. } => self.statements(statements),
+
+            TypedExpr::Case { clauses, .. } => {
+                for clause in clauses {
+                    self.tail_expression(&clause.then);
+                }
+            }
+
+            TypedExpr::Pipeline { finally, .. } => self.tail_expression(finally),
+
+            TypedExpr::Int { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Var { .. }
+            | TypedExpr::Fn { .. }
+            | TypedExpr::List { .. }
+            | TypedExpr::BinOp { .. }
+            | TypedExpr::RecordAccess { .. }
+            | TypedExpr::PositionalAccess { .. }
+            | TypedExpr::ModuleSelect { .. }
+            | TypedExpr::Tuple { .. }
+            | TypedExpr::TupleIndex { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Panic { .. }
+            | TypedExpr::Echo { .. }
+            | TypedExpr::BitArray { .. }
+            | TypedExpr::RecordUpdate { .. }
+            | TypedExpr::NegateBool { .. }
+            | TypedExpr::NegateInt { .. }
+            | TypedExpr::Invalid { .. } => {}
+        }
+    }
+}
@@ -2,14 +2,17 @@
 // SPDX-FileCopyrightText: 2020 The Gleam contributors
 
 use crate::{
-    Result,
+    Error, Result,
     build::{
-        ErlangAppCodegenConfiguration, Module, module_erlang_name, package_compiler::StdlibPackage,
+        ErlangAppCodegenConfiguration, Module, ModuleFormat, module_erlang_name,
+        package_compiler::StdlibPackage,
     },
+    call_graph::ReachableDefinitions,
     config::PackageConfig,
     erlang,
     io::FileSystemWriter,
     javascript::{self, ModuleConfig},
+    wasm,
 };
 use ecow::EcoString;
 use erlang::escape_atom_string;
@@ -21,17 +24,28 @@ use camino::Utf8Path;
 
 /// A code generator that creates a .erl Erlang module and record header files
 /// for each Gleam module in the package.
+///
+/// If the modules are going to be compiled to BEAM bytecode it also creates a
+/// .etf file for each of them, holding the module's Erlang Abstract Format
+/// representation. This is what gets compiled, so that the Erlang compiler
+/// doesn't have to parse the generated source code back again.
 #[derive(Debug)]
 pub struct Erlang<'a> {
     build_directory: &'a Utf8Path,
     include_directory: &'a Utf8Path,
+    abstract_forms: bool,
 }
 
 impl<'a> Erlang<'a> {
-    pub fn new(build_directory: &'a Utf8Path, include_directory: &'a Utf8Path) -> Self {
+    pub fn new(
+        build_directory: &'a Utf8Path,
+        include_directory: &'a Utf8Path,
+        abstract_forms: bool,
+    ) -> Self {
         Self {
             build_directory,
             include_directory,
+            abstract_forms,
         }
     }
 
@@ -44,6 +58,9 @@ impl<'a> Erlang<'a> {
         for module in modules {
             let erl_name = module.erlang_name();
             self.erlang_module(&writer, module, &erl_name, root)?;
+            if self.abstract_forms {
+                self.erlang_module_forms(&writer, module, &erl_name, root)?;
+            }
             self.erlang_record_headers(&writer, module, &erl_name)?;
         }
         Ok(())
@@ -64,6 +81,21 @@ impl<'a> Erlang<'a> {
         writer.write(&path, &output)
     }
 
+    fn erlang_module_forms<Writer: FileSystemWriter>(
+        &self,
+        writer: &Writer,
+        module: &Module,
+        erl_name: &str,
+        root: &Utf8Path,
+    ) -> Result<()> {
+        let name = format!("{erl_name}.etf");
+        let path = self.build_directory.join(&name);
+        let line_numbers = LineNumbers::new(&module.code);
+        let output = erlang::module_forms(&module.ast, line_numbers, root);
+        tracing::debug!(name = ?name, "Generated Erlang abstract forms");
+        writer.write_bytes(&path, &output)
+    }
+
     fn erlang_record_headers<Writer: FileSystemWriter>(
         &self,
         writer: &Writer,
@@ -79,6 +111,37 @@ impl<'a> Erlang<'a> {
     }
 }
 
+/// A code generator that creates a .core Core Erlang module for each Gleam
+/// module in the package. These are not compiled, they're meant to be read by
+/// static analysis tools such as Dialyzer.
+#[derive(Debug)]
+pub struct CoreErlang<'a> {
+    output_directory: &'a Utf8Path,
+}
+
+impl<'a> CoreErlang<'a> {
+    pub fn new(output_directory: &'a Utf8Path) -> Self {
+        Self { output_directory }
+    }
+
+    pub fn render<Writer: FileSystemWriter>(
+        &self,
+        writer: Writer,
+        modules: &[Module],
+        root: &Utf8Path,
+    ) -> Result<()> {
+        for module in modules {
+            let name = format!("{}.core", module.erlang_name());
+            let path = self.output_directory.join(&name);
+            let line_numbers = LineNumbers::new(&module.code);
+            let output = erlang::module_core(&module.ast, line_numbers, root);
+            tracing::debug!(name = ?name, "Generated Core Erlang module");
+            writer.write(&path, &output)?;
+        }
+        Ok(())
+    }
+}
+
 /// A code generator that creates a .app Erlang application file for the package
 #[derive(Debug)]
 pub struct ErlangApp<'a> {
@@ -183,6 +246,8 @@ pub struct JavaScript<'a> {
     project_root: &'a Utf8Path,
     typescript: TypeScriptDeclarations,
     source_map: bool,
+    module_format: ModuleFormat,
+    reachable_definitions: Option<&'a ReachableDefinitions>,
 }
 
 impl<'a> JavaScript<'a> {
@@ -190,8 +255,10 @@ impl<'a> JavaScript<'a> {
         output_directory: &'a Utf8Path,
         typescript: TypeScriptDeclarations,
         source_map: bool,
+        module_format: ModuleFormat,
         prelude_location: &'a Utf8Path,
         project_root: &'a Utf8Path,
+        reachable_definitions: Option<&'a ReachableDefinitions>,
     ) -> Self {
         Self {
             prelude_location,
@@ -199,6 +266,8 @@ impl<'a> JavaScript<'a> {
             project_root,
             typescript,
             source_map,
+            module_format,
+            reachable_definitions,
         }
     }
 
@@ -220,22 +289,50 @@ impl<'a> JavaScript<'a> {
     }
 
     fn write_prelude(&self, writer: &impl FileSystemWriter) -> Result<()> {
-        let rexport = format!("export * from \"{}\";\n", self.prelude_location);
-        let prelude_path = &self.output_directory.join("gleam.mjs");
+        let extension = self.module_format.extension();
+        let rexport = match self.module_format {
+            ModuleFormat::Esm => Some(format!("export * from \"{}\";\n", self.prelude_location)),
+            ModuleFormat::Cjs => Some(format!(
+                "module.exports = require(\"{}\");\n",
+                self.prelude_location
+            )),
+            // Global scripts read the values of the prelude script directly.
+            ModuleFormat::Global => None,
+        };
+        let prelude_path = &self.output_directory.join(format!("gleam.{extension}"));
 
         // This check skips unnecessary `gleam.mjs` writes which confuse
         // watchers and HMR build tools
-        if !writer.exists(prelude_path) {
+        if let Some(rexport) = rexport
+            && !writer.exists(prelude_path)
+        {
             writer.write(prelude_path, &rexport)?;
         }
 
+        // FFI modules are often ES modules importing the prelude from
+        // `gleam.mjs`, so that is written for CommonJS packages too. It
+        // re-exports the CommonJS prelude so both share the same classes.
+        if self.module_format == ModuleFormat::Cjs {
+            let prelude_path = &self.output_directory.join("gleam.mjs");
+            if !writer.exists(prelude_path) {
+                let rexport = format!("export * from \"{}\";\n", self.prelude_location);
+                writer.write(prelude_path, &rexport)?;
+            }
+        }
+
         if self.typescript == TypeScriptDeclarations::Emit {
+            let declaration_extension = self.module_format.declaration_extension();
             let rexport = format!(
                 "export * from \"{}\";\nexport type * from \"{}\";\n",
                 self.prelude_location,
-                self.prelude_location.as_str().replace(".mjs", ".d.mts")
+                self.prelude_location.as_str().replace(
+                    &format!(".{extension}"),
+                    &format!(".{declaration_extension}")
+                )
             );
-            let prelude_declaration_path = &self.output_directory.join("gleam.d.mts");
+            let prelude_declaration_path = &self
+                .output_directory
+                .join(format!("gleam.{declaration_extension}"));
 
             // Type declaration may trigger badly configured watchers
             if !writer.exists(prelude_declaration_path) {
@@ -252,9 +349,9 @@ impl<'a> JavaScript<'a> {
         module: &Module,
         js_name: &str,
     ) -> Result<()> {
-        let name = format!("{js_name}.d.mts");
+        let name = format!("{js_name}.{}", self.module_format.declaration_extension());
         let path = self.output_directory.join(name);
-        let output = javascript::ts_declaration(&module.ast);
+        let output = javascript::ts_declaration(&module.ast, self.module_format);
         tracing::debug!(name = ?js_name, "Generated TS declaration");
         writer.write(&path, &output)
     }
@@ -266,7 +363,12 @@ impl<'a> JavaScript<'a> {
         js_name: &str,
         stdlib_package: StdlibPackage,
     ) -> Result<()> {
-        let name = format!("{js_name}.mjs");
+        if self.module_format == ModuleFormat::Global {
+            ensure_no_es_module_ffi(module)?;
+        }
+
+        let extension = self.module_format.extension();
+        let name = format!("{js_name}.{extension}");
         let path = self.output_directory.join(name);
         let line_numbers = LineNumbers::new(&module.code);
         let (output, source_map) = javascript::module(ModuleConfig {
@@ -278,6 +380,8 @@ impl<'a> JavaScript<'a> {
             typescript: self.typescript,
             source_map: self.source_map,
             stdlib_package,
+            reachable_definitions: self.reachable_definitions,
+            module_format: self.module_format,
         });
         tracing::debug!(name = ?js_name, "Generated js module");
         writer.write(&path, &output)?;
@@ -295,7 +399,9 @@ impl<'a> JavaScript<'a> {
                 .expect("Failed to write sourcemap to memory.");
             let content =
                 String::from_utf8(output).expect("Sourcemap did not generate valid UTF-8.");
-            let source_map_path = self.output_directory.join(format!("{js_name}.mjs.map"));
+            let source_map_path = self
+                .output_directory
+                .join(format!("{js_name}.{extension}.map"));
             tracing::debug!(path = ?source_map_path, name = ?js_name, "Emitting sourcemap for module");
             writer.write(&source_map_path, &content)?;
         }
@@ -303,6 +409,62 @@ impl<'a> JavaScript<'a> {
     }
 }
 
+/// Global scripts can't import ES modules, so their external functions can't be
+/// implemented in one.
+///
+fn ensure_no_es_module_ffi(module: &Module) -> Result<()> {
+    let es_module = module
+        .ast
+        .definitions
+        .functions
+        .iter()
+        .find_map(|function| {
+            let (path, _, location) = function.external_javascript.as_ref()?;
+            let extension = Utf8Path::new(path.as_str()).extension()?;
+            matches!(extension, "mjs" | "mts").then_some((path, location))
+        });
+
+    match es_module {
+        None => Ok(()),
+        Some((path, location)) => Err(Error::EsModuleFfiInGlobalScript {
+            path: module.input_path.clone(),
+            src: module.code.clone(),
+            location: *location,
+            module: path.clone(),
+        }),
+    }
+}
+
+/// A code generator that creates a .wat WebAssembly text format module for
+/// each Gleam module in the package.
+#[derive(Debug)]
+pub struct Wasm<'a> {
+    output_directory: &'a Utf8Path,
+}
+
+impl<'a> Wasm<'a> {
+    pub fn new(output_directory: &'a Utf8Path) -> Self {
+        Self { output_directory }
+    }
+
+    pub fn render(&self, writer: &impl FileSystemWriter, modules: &[Module]) -> Result<()> {
+        for module in modules {
+            let path = self.output_directory.join(format!("{}.wat", module.name));
+            let output = wasm::module(&module.ast).map_err(|unsupported| {
+                Error::UnsupportedWasmFeature {
+                    path: module.input_path.clone(),
+                    src: module.code.clone(),
+                    location: unsupported.location,
+                    feature: unsupported.feature,
+                }
+            })?;
+            tracing::debug!(name = ?module.name, "Generated wasm module");
+            writer.write(&path, &output)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,6 +8,7 @@ use crate::io::ordered_map;
 use crate::manifest::Manifest;
 use crate::requirement::Requirement;
 use crate::version::COMPILER_VERSION;
+use crate::warning::WarningLevels;
 use crate::{Error, Result};
 use camino::{Utf8Path, Utf8PathBuf};
 use ecow::EcoString;
@@ -25,7 +26,7 @@ use std::sync::OnceLock;
 #[cfg(test)]
 use crate::manifest::ManifestPackage;
 
-use crate::build::{Mode, Runtime, Target};
+use crate::build::{Mode, ModuleFormat, Runtime, Target};
 
 fn default_version() -> Version {
     Version::parse("0.1.0").expect("default version")
@@ -185,6 +186,8 @@ pub struct PackageConfig {
     pub target: Target,
     #[serde(default)]
     pub internal_modules: Option<Vec<Glob>>,
+    #[serde(default, skip_serializing_if = "WarningLevels::is_empty")]
+    pub warnings: WarningLevels,
 }
 
 pub fn serialise_gleam_version<S>(
@@ -326,10 +329,11 @@ impl PackageConfig {
                 | Repository::SourceHut { tag_prefix, .. }
                 | Repository::Gitea { tag_prefix, .. }
                 | Repository::Forgejo { tag_prefix, .. }
-                | Repository::Tangled { tag_prefix, .. },
+                | Repository::Tangled { tag_prefix, .. }
+                | Repository::Custom { tag_prefix, .. },
             ) => tag_prefix.as_ref(),
 
-            Some(Repository::Custom { .. }) | None => None,
+            None => None,
         };
 
         match prefix {
@@ -736,6 +740,7 @@ impl Default for PackageConfig {
             links: vec![],
             internal_modules: None,
             target: Target::Erlang,
+            warnings: WarningLevels::default(),
         }
     }
 }
@@ -766,6 +771,8 @@ pub struct JavaScriptConfig {
     pub runtime: Runtime,
     #[serde(default, rename = "deno")]
     pub deno: DenoConfig,
+    #[serde(default)]
+    pub module_format: ModuleFormat,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -947,8 +954,18 @@ pub enum Repository {
     #[serde(rename = "custom")]
     Custom {
         url: String,
+        path: Option<String>,
         #[serde(alias = "tag-prefix")]
         tag_prefix: Option<String>,
+        /// The URL of a range of lines in a source file, used to link to the
+        /// source of definitions in the documentation. It can use the `{tag}`,
+        /// `{path}`, `{line_start}` and `{line_end}` placeholders, and if it
+        /// is not an absolute URL it is relative to the repository's `url`.
+        ///
+        /// For example: `-/blob/{tag}/{path}#L{line_start}-{line_end}`.
+        ///
+        #[serde(alias = "file-url-template")]
+        file_url_template: Option<String>,
     },
 }
 
@@ -996,9 +1013,8 @@ impl Repository {
             | Repository::SourceHut { path, .. }
             | Repository::Tangled { path, .. }
             | Repository::Gitea { path, .. }
-            | Repository::Forgejo { path, .. } => path.as_ref(),
-
-            Repository::Custom { .. } => None,
+            | Repository::Forgejo { path, .. }
+            | Repository::Custom { path, .. } => path.as_ref(),
         }
     }
 }
@@ -1266,6 +1282,27 @@ name = "1"
     )
 }
 
+#[test]
+fn custom_repository() {
+    let input = r#"
+name = "wibble"
+version = "1.2.3"
+repository = { type = "custom", url = "https://git.example.com/team/wibble", tag-prefix = "wibble-", file-url-template = "-/blob/{tag}/{path}#L{line_start}-{line_end}" }
+"#;
+
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(
+        config.repository,
+        Some(Repository::Custom {
+            url: "https://git.example.com/team/wibble".into(),
+            path: None,
+            tag_prefix: Some("wibble-".into()),
+            file_url_template: Some("-/blob/{tag}/{path}#L{line_start}-{line_end}".into()),
+        })
+    );
+    assert_eq!(config.tag_for_version(&config.version), "wibble-v1.2.3");
+}
+
 #[test]
 fn docs_dot_dot_string_path() {
     let input = r#"
@@ -1426,6 +1463,102 @@ pages = [{ title = "My Page", path = "stuff.html", source = "/etc/passwd" }]
     );
 }
 
+#[test]
+fn warning_levels() {
+    use crate::warning::{WarningKind, WarningLevel};
+
+    let input = r#"
+name = "one_two"
+
+[warnings]
+all = "deny"
+unused = "allow"
+unused_variable = "warn"
+todo = "allow"
+"#;
+
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    let level = |kind| config.warnings.level(kind);
+    assert_eq!(level(WarningKind::Todo), WarningLevel::Allow);
+    assert_eq!(level(WarningKind::UnusedVariable), WarningLevel::Warn);
+    assert_eq!(level(WarningKind::UnusedImport), WarningLevel::Allow);
+    assert_eq!(level(WarningKind::Deprecated), WarningLevel::Deny);
+}
+
+#[test]
+fn warning_levels_default_to_warn() {
+    use crate::warning::{WarningKind, WarningLevel};
+
+    let config = toml::from_str::<PackageConfig>(r#"name = "one_two""#).unwrap();
+    assert_eq!(
+        config.warnings.level(WarningKind::UnreachableCaseClause),
+        WarningLevel::Warn
+    );
+}
+
+#[test]
+fn opt_in_warnings_are_not_enabled_by_groups() {
+    use crate::warning::{WarningKind, WarningLevel};
+
+    let input = r#"
+name = "one_two"
+
+[warnings]
+all = "deny"
+"#;
+
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(
+        config.warnings.level(WarningKind::InexhaustiveLetAssert),
+        WarningLevel::Allow
+    );
+}
+
+#[test]
+fn opt_in_warnings_can_be_enabled() {
+    use crate::warning::{WarningKind, WarningLevel};
+
+    let input = r#"
+name = "one_two"
+
+[warnings]
+inexhaustive_let_assert = "deny"
+"#;
+
+    let config = toml::from_str::<PackageConfig>(input).unwrap();
+    assert_eq!(
+        config.warnings.level(WarningKind::InexhaustiveLetAssert),
+        WarningLevel::Deny
+    );
+}
+
+#[test]
+fn unknown_warning_name() {
+    let input = r#"
+name = "one_two"
+
+[warnings]
+unused_wibble = "deny"
+"#;
+
+    let error = toml::from_str::<PackageConfig>(input)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("`unused_wibble` is not a known kind or group of warnings"));
+}
+
+#[test]
+fn unknown_warning_level() {
+    let input = r#"
+name = "one_two"
+
+[warnings]
+todo = "forbid"
+"#;
+
+    assert!(toml::from_str::<PackageConfig>(input).is_err());
+}
+
 #[test]
 fn package_config_to_json() {
     let input = r#"
@@ -0,0 +1,1268 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! This module implements the constant folding optimisation: expressions
+//! whose value is known at compile time are replaced with that value before
+//! code is generated, so the work isn't done again each time the code runs.
+//!
+//! Like function inlining, this is performed on the typed AST so it is shared
+//! by all targets, and it's most useful once calls have been inlined, as
+//! inlining often results in code like the following:
+//!
+//! ```gleam
+//! case 1 > 0 {
+//!   True -> Ok(1 + 1)
+//!   False -> Error("negative")
+//! }
+//! ```
+//!
+//! Which is folded to just `Ok(2)`.
+//!
+//! These expressions are folded:
+//! - Operators on literal values, such as `1 + 2`, `"a" <> "b"` or
+//!   `1.0 <. 2.0`, as well as `&&` and `||` with a literal `True` or `False`.
+//! - Negations of literal values.
+//! - `case` expressions whose subjects are known, such as literals or records
+//!   built from literals. The compiled decision tree of the `case` is used to
+//!   find the clause that matches, and the `case` is replaced with that
+//!   clause's body.
+//!
+//! When only some of the subjects of a `case` are known, the checks on those
+//! subjects are resolved in its decision tree and any clause that can no
+//! longer be reached is dropped.
+//!
+//! An expression is only folded when the result is the same on every target.
+//! For example, an integer operation is only folded if its result fits in the
+//! range of integers a JavaScript number can represent exactly, and float
+//! operations resulting in an infinite value are left alone, as that raises an
+//! error on the Erlang target.
+//!
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use ecow::EcoString;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use src_span::SrcSpan;
+
+use crate::{
+    ast::{
+        Assignment, AssignmentKind, BinOp, CallArg, Pattern, Publicity, Statement, TypedAssignment,
+        TypedClause, TypedDefinitions, TypedExpr, TypedFunction, TypedModule,
+        TypedPipelineAssignment, TypedStatement,
+    },
+    exhaustiveness::{Body, BoundValue, CompiledCase, Decision, FallbackCheck, RuntimeCheck},
+    parse::LiteralFloatValue,
+    type_::{
+        self, Deprecation, ModuleValueConstructor, PRELUDE_MODULE_NAME, Type, ValueConstructor,
+        ValueConstructorVariant, error::VariableOrigin,
+    },
+};
+
+/// The largest integer that has the same value on every target: JavaScript
+/// numbers can't represent all the integers past this one.
+///
+const MAX_SAFE_INTEGER: i64 = 9_007_199_254_740_991;
+
+/// Perform constant folding across an entire module, applying it to each
+/// individual function.
+///
+pub fn module(mut module: TypedModule) -> TypedModule {
+    module.definitions = TypedDefinitions {
+        functions: module
+            .definitions
+            .functions
+            .into_iter()
+            .map(function)
+            .collect(),
+        ..module.definitions
+    };
+
+    module
+}
+
+fn function(mut function: TypedFunction) -> TypedFunction {
+    function.body = function.body.into_iter().map(statement).collect();
+    function
+}
+
+fn statement(statement: TypedStatement) -> TypedStatement {
+    match statement {
+        Statement::Expression(expression) => Statement::Expression(self::expression(expression)),
+        Statement::Assignment(assignment) => {
+            Statement::Assignment(Box::new(self::assignment(*assignment)))
+        }
+        Statement::Use(mut use_) => {
+            use_.call = boxed_expression(use_.call);
+            Statement::Use(use_)
+        }
+        Statement::Assert(mut assert) => {
+            assert.value = expression(assert.value);
+            assert.message = assert.message.map(expression);
+            Statement::Assert(assert)
+        }
+    }
+}
+
+fn assignment(assignment: TypedAssignment) -> TypedAssignment {
+    let Assignment {
+        location,
+        value,
+        pattern,
+        kind,
+        compiled_case,
+        annotation,
+    } = assignment;
+
+    let kind = match kind {
+        AssignmentKind::Let => AssignmentKind::Let,
+        AssignmentKind::Generated => AssignmentKind::Generated,
+        AssignmentKind::Assert {
+            location,
+            assert_keyword_start,
+            message,
+        } => AssignmentKind::Assert {
+            location,
+            assert_keyword_start,
+            message: message.map(expression),
+        },
+    };
+
+    Assignment {
+        location,
+        value: expression(value),
+        pattern,
+        kind,
+        compiled_case,
+        annotation,
+    }
+}
+
+fn boxed_expression(boxed: Box<TypedExpr>) -> Box<TypedExpr> {
+    Box::new(expression(*boxed))
+}
+
+fn expressions(expressions: Vec<TypedExpr>) -> Vec<TypedExpr> {
+    expressions.into_iter().map(expression).collect()
+}
+
+fn arguments(arguments: Vec<CallArg<TypedExpr>>) -> Vec<CallArg<TypedExpr>> {
+    arguments
+        .into_iter()
+        .map(|mut argument| {
+            argument.value = expression(argument.value);
+            argument
+        })
+        .collect()
+}
+
+fn pipeline_assignment(mut assignment: TypedPipelineAssignment) -> TypedPipelineAssignment {
+    assignment.value = boxed_expression(assignment.value);
+    assignment
+}
+
+/// Folds an expression. The expressions it is made of are folded first, so
+/// their values might become known.
+///
+fn expression(expression: TypedExpr) -> TypedExpr {
+    match expression {
+        TypedExpr::Int { .. }
+        | TypedExpr::Float { .. }
+        | TypedExpr::String { .. }
+        | TypedExpr::Var { .. }
+        | TypedExpr::ModuleSelect { .. }
+        | TypedExpr::Invalid { .. } => expression,
+
+        TypedExpr::Block {
+            location,
+            statements,
+        } => TypedExpr::Block {
+            location,
+            statements: statements.mapped(statement),
+        },
+
+        TypedExpr::Pipeline {
+            location,
+            first_value,
+            assignments,
+            finally,
+            finally_kind,
+        } => TypedExpr::Pipeline {
+            location,
+            first_value: pipeline_assignment(first_value),
+            assignments: assignments
+                .into_iter()
+                .map(|(assignment, kind)| (pipeline_assignment(assignment), kind))
+                .collect(),
+            finally: boxed_expression(finally),
+            finally_kind,
+        },
+
+        TypedExpr::Fn {
+            location,
+            type_,
+            kind,
+            arguments,
+            body,
+            return_annotation,
+            purity,
+        } => TypedExpr::Fn {
+            location,
+            type_,
+            kind,
+            arguments,
+            body: body.mapped(statement),
+            return_annotation,
+            purity,
+        },
+
+        TypedExpr::List {
+            location,
+            type_,
+            elements,
+            tail,
+        } => TypedExpr::List {
+            location,
+            type_,
+            elements: expressions(elements),
+            tail: tail.map(boxed_expression),
+        },
+
+        TypedExpr::Call {
+            location,
+            type_,
+            fun,
+            arguments,
+            open_parenthesis,
+        } => TypedExpr::Call {
+            location,
+            type_,
+            fun: boxed_expression(fun),
+            arguments: self::arguments(arguments),
+            open_parenthesis,
+        },
+
+        TypedExpr::BinOp {
+            location,
+            type_,
+            operator,
+            operator_start,
+            left,
+            right,
+        } => bin_op(
+            location,
+            type_,
+            operator,
+            operator_start,
+            boxed_expression(left),
+            boxed_expression(right),
+        ),
+
+        TypedExpr::Case {
+            location,
+            type_,
+            subjects,
+            clauses,
+            compiled_case,
+        } => case(location, type_, subjects, clauses, compiled_case),
+
+        TypedExpr::RecordAccess {
+            location,
+            field_start,
+            type_,
+            label,
+            index,
+            record,
+            documentation,
+        } => TypedExpr::RecordAccess {
+            location,
+            field_start,
+            type_,
+            label,
+            index,
+            record: boxed_expression(record),
+            documentation,
+        },
+
+        TypedExpr::PositionalAccess {
+            location,
+            type_,
+            index,
+            record,
+        } => TypedExpr::PositionalAccess {
+            location,
+            type_,
+            index,
+            record: boxed_expression(record),
+        },
+
+        TypedExpr::Tuple {
+            location,
+            type_,
+            elements,
+        } => TypedExpr::Tuple {
+            location,
+            type_,
+            elements: expressions(elements),
+        },
+
+        TypedExpr::TupleIndex {
+            location,
+            type_,
+            index,
+            tuple,
+        } => TypedExpr::TupleIndex {
+            location,
+            type_,
+            index,
+            tuple: boxed_expression(tuple),
+        },
+
+        TypedExpr::Todo {
+            location,
+            message,
+            kind,
+            type_,
+        } => TypedExpr::Todo {
+            location,
+            message: message.map(boxed_expression),
+            kind,
+            type_,
+        },
+
+        TypedExpr::Panic {
+            location,
+            message,
+            type_,
+        } => TypedExpr::Panic {
+            location,
+            message: message.map(boxed_expression),
+            type_,
+        },
+
+        TypedExpr::Echo {
+            location,
+            type_,
+            expression,
+            message,
+        } => TypedExpr::Echo {
+            location,
+            type_,
+            expression: expression.map(boxed_expression),
+            message: message.map(boxed_expression),
+        },
+
+        TypedExpr::BitArray {
+            location,
+            type_,
+            segments,
+        } => TypedExpr::BitArray {
+            location,
+            type_,
+            segments: segments
+                .into_iter()
+                .map(|mut segment| {
+                    segment.value = boxed_expression(segment.value);
+                    segment
+                })
+                .collect(),
+        },
+
+        TypedExpr::RecordUpdate {
+            location,
+            spread_start,
+            type_,
+            updated_record,
+            updated_record_assigned_name,
+            constructor,
+            arguments,
+        } => TypedExpr::RecordUpdate {
+            location,
+            spread_start,
+            type_,
+            updated_record: boxed_expression(updated_record),
+            updated_record_assigned_name,
+            constructor,
+            arguments: self::arguments(arguments),
+        },
+
+        TypedExpr::NegateBool { location, value } => {
+            let value = boxed_expression(value);
+            match bool_literal(&value) {
+                Some(value) => bool(location, !value),
+                None => TypedExpr::NegateBool { location, value },
+            }
+        }
+
+        TypedExpr::NegateInt { location, value } => {
+            let value = boxed_expression(value);
+            match int_literal(&value).and_then(|value| int(location, type_::int(), -value)) {
+                Some(negated) => negated,
+                None => TypedExpr::NegateInt { location, value },
+            }
+        }
+    }
+}
+
+fn bin_op(
+    location: SrcSpan,
+    type_: Arc<Type>,
+    operator: BinOp,
+    operator_start: u32,
+    left: Box<TypedExpr>,
+    right: Box<TypedExpr>,
+) -> TypedExpr {
+    let folded = match operator {
+        // The left hand side of a boolean operator is always evaluated, so it
+        // can only be dropped if it's a literal. The right hand side is only
+        // dropped when it wouldn't have been evaluated in the first place.
+        BinOp::And => match (bool_literal(&left), bool_literal(&right)) {
+            (Some(true), _) => return *right,
+            (Some(false), _) | (None, Some(true)) => return *left,
+            (None, Some(false) | None) => None,
+        },
+        BinOp::Or => match (bool_literal(&left), bool_literal(&right)) {
+            (Some(false), _) => return *right,
+            (Some(true), _) | (None, Some(false)) => return *left,
+            (None, Some(true) | None) => None,
+        },
+
+        BinOp::Eq => literals_equal(&left, &right).map(|equal| bool(location, equal)),
+        BinOp::NotEq => literals_equal(&left, &right).map(|equal| bool(location, !equal)),
+
+        BinOp::LtInt => compare_ints(location, &left, &right, |left, right| left < right),
+        BinOp::LtEqInt => compare_ints(location, &left, &right, |left, right| left <= right),
+        BinOp::GtInt => compare_ints(location, &left, &right, |left, right| left > right),
+        BinOp::GtEqInt => compare_ints(location, &left, &right, |left, right| left >= right),
+
+        BinOp::LtFloat => compare_floats(location, &left, &right, |left, right| left < right),
+        BinOp::LtEqFloat => compare_floats(location, &left, &right, |left, right| left <= right),
+        BinOp::GtFloat => compare_floats(location, &left, &right, |left, right| left > right),
+        BinOp::GtEqFloat => compare_floats(location, &left, &right, |left, right| left >= right),
+
+        BinOp::AddInt => int_operation(location, &type_, &left, &right, |left, right| left + right),
+        BinOp::SubInt => int_operation(location, &type_, &left, &right, |left, right| left - right),
+        BinOp::MultInt => {
+            int_operation(location, &type_, &left, &right, |left, right| left * right)
+        }
+        // Division and remainder by zero are defined to be zero, and both
+        // truncate towards zero on every target.
+        BinOp::DivInt => int_operation(location, &type_, &left, &right, |left, right| {
+            if *right == BigInt::ZERO {
+                BigInt::ZERO
+            } else {
+                left / right
+            }
+        }),
+        BinOp::RemainderInt => int_operation(location, &type_, &left, &right, |left, right| {
+            if *right == BigInt::ZERO {
+                BigInt::ZERO
+            } else {
+                left % right
+            }
+        }),
+
+        BinOp::AddFloat => {
+            float_operation(location, &type_, &left, &right, |left, right| left + right)
+        }
+        BinOp::SubFloat => {
+            float_operation(location, &type_, &left, &right, |left, right| left - right)
+        }
+        BinOp::MultFloat => {
+            float_operation(location, &type_, &left, &right, |left, right| left * right)
+        }
+        // Division by zero is defined to be zero on every target.
+        BinOp::DivFloat => float_operation(location, &type_, &left, &right, |left, right| {
+            if right == 0.0 { 0.0 } else { left / right }
+        }),
+
+        BinOp::Concatenate => {
+            string_literal(&left)
+                .zip(string_literal(&right))
+                .map(|(left, right)| TypedExpr::String {
+                    location,
+                    type_: type_.clone(),
+                    value: EcoString::from(left) + right,
+                })
+        }
+    };
+
+    folded.unwrap_or(TypedExpr::BinOp {
+        location,
+        type_,
+        operator,
+        operator_start,
+        left,
+        right,
+    })
+}
+
+fn compare_ints(
+    location: SrcSpan,
+    left: &TypedExpr,
+    right: &TypedExpr,
+    compare: impl Fn(&BigInt, &BigInt) -> bool,
+) -> Option<TypedExpr> {
+    let (left, right) = int_literal(left).zip(int_literal(right))?;
+    Some(bool(location, compare(left, right)))
+}
+
+fn compare_floats(
+    location: SrcSpan,
+    left: &TypedExpr,
+    right: &TypedExpr,
+    compare: impl Fn(f64, f64) -> bool,
+) -> Option<TypedExpr> {
+    let (left, right) = float_literal(left).zip(float_literal(right))?;
+    Some(bool(location, compare(left, right)))
+}
+
+fn int_operation(
+    location: SrcSpan,
+    type_: &Arc<Type>,
+    left: &TypedExpr,
+    right: &TypedExpr,
+    operation: impl Fn(&BigInt, &BigInt) -> BigInt,
+) -> Option<TypedExpr> {
+    let (left, right) = int_literal(left).zip(int_literal(right))?;
+    int(location, type_.clone(), operation(left, right))
+}
+
+fn float_operation(
+    location: SrcSpan,
+    type_: &Arc<Type>,
+    left: &TypedExpr,
+    right: &TypedExpr,
+    operation: impl Fn(f64, f64) -> f64,
+) -> Option<TypedExpr> {
+    let (left, right) = float_literal(left).zip(float_literal(right))?;
+    float(location, type_.clone(), operation(left, right))
+}
+
+/// Whether two literals are equal, or `None` if that can't be known at compile
+/// time.
+///
+fn literals_equal(left: &TypedExpr, right: &TypedExpr) -> Option<bool> {
+    if let Some((left, right)) = int_literal(left).zip(int_literal(right)) {
+        Some(left == right)
+    } else if let Some((left, right)) = bool_literal(left).zip(bool_literal(right)) {
+        Some(left == right)
+    } else {
+        string_literal(left)
+            .zip(string_literal(right))
+            .map(|(left, right)| left == right)
+    }
+}
+
+fn case(
+    location: SrcSpan,
+    type_: Arc<Type>,
+    subjects: Vec<TypedExpr>,
+    clauses: Vec<TypedClause>,
+    compiled_case: CompiledCase,
+) -> TypedExpr {
+    let subjects = expressions(subjects);
+    let clauses = clauses
+        .into_iter()
+        .map(|mut clause| {
+            clause.then = expression(clause.then);
+            clause
+        })
+        .collect_vec();
+
+    let mut values: HashMap<usize, TypedExpr> = compiled_case
+        .subject_variables
+        .iter()
+        .map(|variable| variable.id)
+        .zip(subjects.iter().cloned())
+        .collect();
+
+    // If all the subjects can be evaluated again, or not at all, with no
+    // observable difference, and their values are known enough to pick a
+    // clause, the whole `case` is replaced with that clause.
+    if subjects.iter().all(is_pure)
+        && let Some(body) = resolve(&compiled_case.tree, &mut values)
+        && let Some(block) = clause_body(location, body, &clauses, &values)
+    {
+        return block;
+    }
+
+    let tree = prune(compiled_case.tree, &values);
+    let (tree, clauses) = drop_unreachable_clauses(tree, clauses);
+
+    TypedExpr::Case {
+        location,
+        type_,
+        subjects,
+        clauses,
+        compiled_case: CompiledCase {
+            tree,
+            subject_variables: compiled_case.subject_variables,
+        },
+    }
+}
+
+/// Goes down a decision tree using the known values of the variables it
+/// checks, returning the body that would be run. If a value that is checked is
+/// not known, or a guard has to be evaluated, this returns `None`.
+///
+/// The values of any variables bound by the checks along the way are added
+/// to `values`.
+///
+fn resolve<'a>(decision: &'a Decision, values: &mut HashMap<usize, TypedExpr>) -> Option<&'a Body> {
+    match decision {
+        Decision::Run { body } => Some(body),
+        Decision::Guard { .. } | Decision::Fail => None,
+        Decision::Switch {
+            var,
+            choices,
+            fallback,
+            fallback_check,
+        } => {
+            let value = values.get(&var.id)?.clone();
+            for (check, decision) in choices {
+                let (matches, bindings) = check_value(check, &value)?;
+                if matches {
+                    values.extend(bindings);
+                    return resolve(decision, values);
+                }
+            }
+
+            if let FallbackCheck::RuntimeCheck { check } = fallback_check.as_ref() {
+                let (true, bindings) = check_value(check, &value)? else {
+                    return None;
+                };
+                values.extend(bindings);
+            }
+            resolve(fallback, values)
+        }
+    }
+}
+
+/// Replaces the `case` with the body of the clause that matched, preceded by
+/// the assignments of the variables bound by its pattern.
+///
+fn clause_body(
+    location: SrcSpan,
+    body: &Body,
+    clauses: &[TypedClause],
+    values: &HashMap<usize, TypedExpr>,
+) -> Option<TypedExpr> {
+    let clause = clauses.get(body.clause_index)?;
+
+    // The variables bound by the pattern are referenced by their location in
+    // the body of the clause, so the assignments have to use the same ones.
+    // With alternative patterns it's not known which one has matched.
+    if !clause.alternative_patterns.is_empty() && !body.bindings.is_empty() {
+        return None;
+    }
+    let pattern_variables: HashMap<EcoString, SrcSpan> = clause
+        .pattern
+        .iter()
+        .flat_map(|pattern| pattern.bound_variables())
+        .map(|variable| (variable.name(), variable.location))
+        .collect();
+
+    let mut bound_names = HashSet::new();
+    let mut statements = Vec::with_capacity(body.bindings.len() + 1);
+    for (name, value) in &body.bindings {
+        let value = match value {
+            BoundValue::Variable(variable) => values.get(&variable.id)?.clone(),
+            BoundValue::LiteralInt(value) => int(location, type_::int(), value.clone())?,
+            BoundValue::LiteralString(value) => TypedExpr::String {
+                location,
+                type_: type_::string(),
+                value: value.clone(),
+            },
+            BoundValue::LiteralFloat(value) => float(
+                location,
+                type_::float(),
+                LiteralFloatValue::parse(value)?.value(),
+            )?,
+            BoundValue::BitArraySlice { .. } | BoundValue::StringSlice { .. } => return None,
+        };
+
+        // The variables are assigned one after the other, so a value can't
+        // refer to a variable with the same name as one that has been bound
+        // already, as it would refer to the new one.
+        let mut referenced = HashSet::new();
+        referenced_variables(&value, &mut referenced);
+        if referenced.iter().any(|name| bound_names.contains(name)) {
+            return None;
+        }
+        let _ = bound_names.insert(name.clone());
+
+        let type_ = value.type_();
+        statements.push(Statement::Assignment(Box::new(Assignment {
+            location,
+            value,
+            pattern: Pattern::Variable {
+                location: *pattern_variables.get(name)?,
+                name: name.clone(),
+                type_: type_.clone(),
+                origin: VariableOrigin::generated(),
+            },
+            kind: AssignmentKind::Generated,
+            compiled_case: CompiledCase::simple_variable_assignment(name.clone(), type_),
+            annotation: None,
+        })));
+    }
+
+    if statements.is_empty() {
+        return Some(clause.then.clone());
+    }
+
+    statements.push(Statement::Expression(clause.then.clone()));
+    Some(TypedExpr::Block {
+        location,
+        statements: statements.try_into().expect("non empty statements"),
+    })
+}
+
+/// Removes the checks of a decision tree whose outcome is known. Only the
+/// checks that don't bind any new variable are removed, as the code generators
+/// rely on those checks to introduce the variables.
+///
+fn prune(decision: Decision, values: &HashMap<usize, TypedExpr>) -> Decision {
+    match decision {
+        Decision::Run { .. } | Decision::Fail => decision,
+
+        Decision::Guard {
+            guard,
+            if_true,
+            if_false,
+        } => Decision::Guard {
+            guard,
+            if_true,
+            if_false: Box::new(prune(*if_false, values)),
+        },
+
+        Decision::Switch {
+            var,
+            choices,
+            fallback,
+            fallback_check,
+        } => {
+            let taken = values
+                .get(&var.id)
+                .and_then(|value| taken_branch(&choices, &fallback_check, value));
+
+            match taken {
+                Some(Branch::Choice(index)) => {
+                    let (_, decision) = choices.into_iter().nth(index).expect("taken choice index");
+                    prune(decision, values)
+                }
+                Some(Branch::Fallback) => prune(*fallback, values),
+                None => Decision::Switch {
+                    var,
+                    choices: choices
+                        .into_iter()
+                        .map(|(check, decision)| (check, prune(decision, values)))
+                        .collect(),
+                    fallback: Box::new(prune(*fallback, values)),
+                    fallback_check,
+                },
+            }
+        }
+    }
+}
+
+/// A branch of a switch in a decision tree.
+///
+enum Branch {
+    /// The choice at the given index.
+    Choice(usize),
+    Fallback,
+}
+
+/// Returns the branch of a switch that is taken by a known value, or `None` if
+/// that can't be known or if its check would bind new variables.
+///
+fn taken_branch(
+    choices: &[(RuntimeCheck, Decision)],
+    fallback_check: &FallbackCheck,
+    value: &TypedExpr,
+) -> Option<Branch> {
+    for (index, (check, _)) in choices.iter().enumerate() {
+        match check_value(check, value)? {
+            (true, bindings) if bindings.is_empty() && !binds_variables(check) => {
+                return Some(Branch::Choice(index));
+            }
+            (true, _) => return None,
+            (false, _) => {}
+        }
+    }
+
+    match fallback_check {
+        FallbackCheck::InfiniteCatchAll | FallbackCheck::CatchAll { .. } => Some(Branch::Fallback),
+        FallbackCheck::RuntimeCheck { check } if binds_variables(check) => None,
+        FallbackCheck::RuntimeCheck { .. } => Some(Branch::Fallback),
+    }
+}
+
+fn binds_variables(check: &RuntimeCheck) -> bool {
+    match check {
+        RuntimeCheck::Int { .. }
+        | RuntimeCheck::Float { .. }
+        | RuntimeCheck::String { .. }
+        | RuntimeCheck::EmptyList => false,
+        RuntimeCheck::Tuple { elements, .. } => !elements.is_empty(),
+        RuntimeCheck::Variant { fields, .. } => !fields.is_empty(),
+        RuntimeCheck::StringPrefix { .. }
+        | RuntimeCheck::BitArray { .. }
+        | RuntimeCheck::NonEmptyList { .. } => true,
+    }
+}
+
+/// Checks if a value passes a runtime check, returning the values of the
+/// variables the check binds if it does. Returns `None` if the value is not
+/// known enough to tell.
+///
+fn check_value(check: &RuntimeCheck, value: &TypedExpr) -> Option<(bool, Vec<(usize, TypedExpr)>)> {
+    match check {
+        RuntimeCheck::Int { int_value } => Some((int_literal(value)? == int_value, vec![])),
+
+        RuntimeCheck::String { value: expected } => {
+            let value = string_literal(value)?;
+            if has_escapes(expected) {
+                return None;
+            }
+            Some((value == expected.as_str(), vec![]))
+        }
+
+        RuntimeCheck::StringPrefix { prefix, rest } => {
+            let value = string_literal(value)?;
+            if has_escapes(prefix) {
+                return None;
+            }
+            match value.strip_prefix(prefix.as_str()) {
+                Some(suffix) => Some((
+                    true,
+                    vec![(
+                        rest.id,
+                        TypedExpr::String {
+                            location: SrcSpan::default(),
+                            type_: type_::string(),
+                            value: suffix.into(),
+                        },
+                    )],
+                )),
+                None => Some((false, vec![])),
+            }
+        }
+
+        RuntimeCheck::Tuple { elements, .. } => {
+            let TypedExpr::Tuple {
+                elements: values, ..
+            } = value
+            else {
+                return None;
+            };
+            let bindings = elements
+                .iter()
+                .map(|element| element.id)
+                .zip(values.iter().cloned())
+                .collect();
+            Some((true, bindings))
+        }
+
+        RuntimeCheck::Variant {
+            index,
+            match_,
+            fields,
+            ..
+        } => {
+            let (variant_index, name, arguments) = record_literal(value)?;
+            if variant_index != *index {
+                return Some((false, vec![]));
+            }
+            if *name != match_.variant_name() || arguments.len() != fields.len() {
+                return None;
+            }
+            let bindings = fields
+                .iter()
+                .map(|field| field.id)
+                .zip(arguments.into_iter().cloned())
+                .collect();
+            Some((true, bindings))
+        }
+
+        RuntimeCheck::EmptyList => {
+            let TypedExpr::List { elements, tail, .. } = value else {
+                return None;
+            };
+            match (elements.is_empty(), tail) {
+                (false, _) => Some((false, vec![])),
+                (true, None) => Some((true, vec![])),
+                (true, Some(_)) => None,
+            }
+        }
+
+        RuntimeCheck::NonEmptyList { first, rest } => {
+            let TypedExpr::List {
+                location,
+                type_,
+                elements,
+                tail,
+            } = value
+            else {
+                return None;
+            };
+            match elements.split_first() {
+                Some((head, elements)) => {
+                    let rest_value = TypedExpr::List {
+                        location: *location,
+                        type_: type_.clone(),
+                        elements: elements.to_vec(),
+                        tail: tail.clone(),
+                    };
+                    Some((true, vec![(first.id, head.clone()), (rest.id, rest_value)]))
+                }
+                None if tail.is_none() => Some((false, vec![])),
+                None => None,
+            }
+        }
+
+        // Floats can't be reliably matched at compile time, as `0.0` and
+        // `-0.0` are considered to be the same by some targets and not others.
+        RuntimeCheck::Float { .. } | RuntimeCheck::BitArray { .. } => None,
+    }
+}
+
+/// Whether an expression has no side effects, so the code generated for it
+/// can be run more than once, or not at all, without changing the meaning of
+/// the program.
+///
+fn is_pure(expression: &TypedExpr) -> bool {
+    match expression {
+        TypedExpr::Int { .. }
+        | TypedExpr::Float { .. }
+        | TypedExpr::String { .. }
+        | TypedExpr::Var { .. }
+        | TypedExpr::ModuleSelect { .. } => true,
+
+        TypedExpr::Tuple { elements, .. } => elements.iter().all(is_pure),
+        TypedExpr::List { elements, tail, .. } => {
+            elements.iter().all(is_pure) && tail.as_deref().is_none_or(is_pure)
+        }
+        TypedExpr::Call { .. } => record_literal(expression)
+            .is_some_and(|(_, _, arguments)| arguments.into_iter().all(is_pure)),
+        TypedExpr::NegateInt { value, .. } | TypedExpr::NegateBool { value, .. } => is_pure(value),
+
+        TypedExpr::Block { .. }
+        | TypedExpr::Pipeline { .. }
+        | TypedExpr::Fn { .. }
+        | TypedExpr::BinOp { .. }
+        | TypedExpr::Case { .. }
+        | TypedExpr::RecordAccess { .. }
+        | TypedExpr::PositionalAccess { .. }
+        | TypedExpr::TupleIndex { .. }
+        | TypedExpr::Todo { .. }
+        | TypedExpr::Panic { .. }
+        | TypedExpr::Echo { .. }
+        | TypedExpr::BitArray { .. }
+        | TypedExpr::RecordUpdate { .. }
+        | TypedExpr::Invalid { .. } => false,
+    }
+}
+
+/// Collects the names of the local variables a pure expression refers to.
+///
+fn referenced_variables(expression: &TypedExpr, names: &mut HashSet<EcoString>) {
+    match expression {
+        TypedExpr::Var {
+            name, constructor, ..
+        } if constructor.is_local_variable() => {
+            let _ = names.insert(name.clone());
+        }
+        TypedExpr::Tuple { elements, .. } => {
+            for element in elements {
+                referenced_variables(element, names);
+            }
+        }
+        TypedExpr::List { elements, tail, .. } => {
+            for element in elements.iter().chain(tail.as_deref()) {
+                referenced_variables(element, names);
+            }
+        }
+        TypedExpr::Call { arguments, .. } => {
+            for argument in arguments {
+                referenced_variables(&argument.value, names);
+            }
+        }
+        TypedExpr::NegateInt { value, .. } | TypedExpr::NegateBool { value, .. } => {
+            referenced_variables(value, names);
+        }
+
+        TypedExpr::Int { .. }
+        | TypedExpr::Float { .. }
+        | TypedExpr::String { .. }
+        | TypedExpr::Var { .. }
+        | TypedExpr::ModuleSelect { .. }
+        | TypedExpr::Block { .. }
+        | TypedExpr::Pipeline { .. }
+        | TypedExpr::Fn { .. }
+        | TypedExpr::BinOp { .. }
+        | TypedExpr::Case { .. }
+        | TypedExpr::RecordAccess { .. }
+        | TypedExpr::PositionalAccess { .. }
+        | TypedExpr::TupleIndex { .. }
+        | TypedExpr::Todo { .. }
+        | TypedExpr::Panic { .. }
+        | TypedExpr::Echo { .. }
+        | TypedExpr::BitArray { .. }
+        | TypedExpr::RecordUpdate { .. }
+        | TypedExpr::Invalid { .. } => {}
+    }
+}
+
+/// Removes the clauses of a `case` expression that can't be reached anymore
+/// in its decision tree, updating the indices of the remaining ones.
+///
+fn drop_unreachable_clauses(
+    tree: Decision,
+    clauses: Vec<TypedClause>,
+) -> (Decision, Vec<TypedClause>) {
+    let mut reachable = HashSet::new();
+    reachable_clauses(&tree, &mut reachable);
+    if reachable.len() == clauses.len() {
+        return (tree, clauses);
+    }
+
+    let mut new_indices = HashMap::new();
+    let clauses = clauses
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| reachable.contains(index))
+        .enumerate()
+        .map(|(new_index, (index, clause))| {
+            let _ = new_indices.insert(index, new_index);
+            clause
+        })
+        .collect();
+
+    (reindex_clauses(tree, &new_indices), clauses)
+}
+
+fn reachable_clauses(decision: &Decision, reachable: &mut HashSet<usize>) {
+    match decision {
+        Decision::Run { body } => {
+            let _ = reachable.insert(body.clause_index);
+        }
+        Decision::Guard {
+            guard,
+            if_true,
+            if_false,
+        } => {
+            let _ = reachable.insert(*guard);
+            let _ = reachable.insert(if_true.clause_index);
+            reachable_clauses(if_false, reachable);
+        }
+        Decision::Switch {
+            choices, fallback, ..
+        } => {
+            for (_, decision) in choices {
+                reachable_clauses(decision, reachable);
+            }
+            reachable_clauses(fallback, reachable);
+        }
+        Decision::Fail => {}
+    }
+}
+
+fn reindex_clauses(decision: Decision, new_indices: &HashMap<usize, usize>) -> Decision {
+    let reindex = |index: usize| *new_indices.get(&index).expect("reachable clause index");
+    match decision {
+        Decision::Run { mut body } => {
+            body.clause_index = reindex(body.clause_index);
+            Decision::Run { body }
+        }
+        Decision::Guard {
+            guard,
+            mut if_true,
+            if_false,
+        } => {
+            if_true.clause_index = reindex(if_true.clause_index);
+            Decision::Guard {
+                guard: reindex(guard),
+                if_true,
+                if_false: Box::new(reindex_clauses(*if_false, new_indices)),
+            }
+        }
+        Decision::Switch {
+            var,
+            choices,
+            fallback,
+            fallback_check,
+        } => Decision::Switch {
+            var,
+            choices: choices
+                .into_iter()
+                .map(|(check, decision)| (check, reindex_clauses(decision, new_indices)))
+                .collect(),
+            fallback: Box::new(reindex_clauses(*fallback, new_indices)),
+            fallback_check,
+        },
+        Decision::Fail => Decision::Fail,
+    }
+}
+
+fn int_literal(expression: &TypedExpr) -> Option<&BigInt> {
+    if let TypedExpr::Int { int_value, .. } = expression {
+        Some(int_value)
+    } else {
+        None
+    }
+}
+
+fn float_literal(expression: &TypedExpr) -> Option<f64> {
+    if let TypedExpr::Float { float_value, .. } = expression {
+        Some(float_value.value())
+    } else {
+        None
+    }
+}
+
+/// The value of a string literal. Only strings with no escape sequences are
+/// considered, as the same string can be written with different escapes.
+///
+fn string_literal(expression: &TypedExpr) -> Option<&str> {
+    if let TypedExpr::String { value, .. } = expression
+        && !has_escapes(value)
+    {
+        Some(value.as_str())
+    } else {
+        None
+    }
+}
+
+fn has_escapes(string: &str) -> bool {
+    string.contains('\\')
+}
+
+fn bool_literal(expression: &TypedExpr) -> Option<bool> {
+    let (_, name, arguments) = record_literal(expression)?;
+    if !arguments.is_empty() || !expression.type_().is_bool() {
+        return None;
+    }
+    match name.as_str() {
+        "True" => Some(true),
+        "False" => Some(false),
+        _ => None,
+    }
+}
+
+/// If an expression is a record, returns the index of its variant, its name
+/// and its arguments.
+///
+fn record_literal(expression: &TypedExpr) -> Option<(usize, &EcoString, Vec<&TypedExpr>)> {
+    let (constructor, arguments) = match expression {
+        TypedExpr::Call { fun, arguments, .. } => {
+            (fun.as_ref(), arguments.iter().map(|a| &a.value).collect())
+        }
+        TypedExpr::Var { .. } | TypedExpr::ModuleSelect { .. } => (expression, vec![]),
+        TypedExpr::Int { .. }
+        | TypedExpr::Float { .. }
+        | TypedExpr::String { .. }
+        | TypedExpr::Block { .. }
+        | TypedExpr::Pipeline { .. }
+        | TypedExpr::Fn { .. }
+        | TypedExpr::List { .. }
+        | TypedExpr::BinOp { .. }
+        | TypedExpr::Case { .. }
+        | TypedExpr::RecordAccess { .. }
+        | TypedExpr::PositionalAccess { .. }
+        | TypedExpr::Tuple { .. }
+        | TypedExpr::TupleIndex { .. }
+        | TypedExpr::Todo { .. }
+        | TypedExpr::Panic { .. }
+        | TypedExpr::Echo { .. }
+        | TypedExpr::BitArray { .. }
+        | TypedExpr::RecordUpdate { .. }
+        | TypedExpr::NegateBool { .. }
+        | TypedExpr::NegateInt { .. }
+        | TypedExpr::Invalid { .. } => return None,
+    };
+
+    let (TypedExpr::Var {
+        constructor:
+            ValueConstructor {
+                variant:
+                    ValueConstructorVariant::Record {
+                        variant_index,
+                        name,
+                        arity,
+                        ..
+                    },
+                ..
+            },
+        ..
+    }
+    | TypedExpr::ModuleSelect {
+        constructor:
+            ModuleValueConstructor::Record {
+                variant_index,
+                name,
+                arity,
+                ..
+            },
+        ..
+    }) = constructor
+    else {
+        return None;
+    };
+
+    (usize::from(*arity) == arguments.len()).then_some((
+        usize::from(*variant_index),
+        name,
+        arguments,
+    ))
+}
+
+fn int(location: SrcSpan, type_: Arc<Type>, value: BigInt) -> Option<TypedExpr> {
+    if value > BigInt::from(MAX_SAFE_INTEGER) || value < BigInt::from(-MAX_SAFE_INTEGER) {
+        return None;
+    }
+    Some(TypedExpr::Int {
+        location,
+        type_,
+        value: value.to_string().into(),
+        int_value: value,
+    })
+}
+
+fn float(location: SrcSpan, type_: Arc<Type>, value: f64) -> Option<TypedExpr> {
+    if !value.is_finite() {
+        return None;
+    }
+    Some(TypedExpr::Float {
+        location,
+        type_,
+        value: format!("{value:?}").into(),
+        float_value: LiteralFloatValue::parse(&format!("{value:?}"))?,
+    })
+}
+
+fn bool(location: SrcSpan, value: bool) -> TypedExpr {
+    let name: EcoString = if value { "True" } else { "False" }.into();
+    TypedExpr::Var {
+        location,
+        name: name.clone(),
+        constructor: ValueConstructor {
+            publicity: Publicity::Public,
+            deprecation: Deprecation::NotDeprecated,
+            variant: ValueConstructorVariant::Record {
+                name,
+                arity: 0,
+                field_map: None,
+                location: SrcSpan::default(),
+                module: PRELUDE_MODULE_NAME.into(),
+                variants_count: 2,
+                variant_index: if value { 0 } else { 1 },
+                documentation: None,
+            },
+            type_: type_::bool_with_variant(Some(value)),
+        },
+    }
+}
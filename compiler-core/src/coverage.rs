@@ -0,0 +1,320 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Reporting the code coverage of a run of a Gleam program.
+//!
+//! The coverage is collected by the runtime the program is run with: the
+//! Erlang `cover` tool counts how many times each line of a module is
+//! executed, while the V8 JavaScript engine counts how many times each
+//! function and block of code is executed. These counts are mapped back to
+//! the lines of the Gleam modules using the line annotations of the generated
+//! Erlang code and the source maps of the generated JavaScript code.
+//!
+//! Branches are found in the Gleam code itself: each clause of a `case`
+//! expression is a branch, taken as many times as the first line of its body
+//! is executed.
+//!
+//! The coverage is then reported in the lcov format understood by most
+//! coverage tools:
+//! https://github.com/linux-test-project/lcov/blob/master/man/geninfo.1
+//!
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+use serde::Deserialize;
+use sourcemap::SourceMap;
+use src_span::{LineNumbers, SrcSpan};
+
+use crate::{
+    ast::{UntypedClause, UntypedExpr},
+    ast_folder::{
+        PatternFolder, TypeAstFolder, UntypedConstantFolder, UntypedExprFolder, UntypedModuleFolder,
+    },
+    parse,
+    warning::WarningEmitter,
+};
+
+/// The coverage of the modules of a package.
+///
+#[derive(Debug, Default)]
+pub struct Coverage {
+    modules: BTreeMap<EcoString, ModuleCoverage>,
+}
+
+#[derive(Debug)]
+pub struct ModuleCoverage {
+    /// The path of the module's source file, as it's going to appear in the
+    /// report.
+    path: Utf8PathBuf,
+    /// The number of times each line with some code on it has been executed.
+    lines: BTreeMap<u32, u64>,
+    /// The branches of each `case` expression in the module: the line the
+    /// body of each of its clauses starts on.
+    cases: Vec<Vec<u32>>,
+}
+
+impl Coverage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a module to the report. Only the coverage of the modules added
+    /// with this function is going to be reported, any other module is
+    /// ignored.
+    ///
+    pub fn add_module(&mut self, name: EcoString, path: Utf8PathBuf, src: &str) {
+        let line_numbers = LineNumbers::new(src);
+        let cases = match parse::parse_module(path.clone(), src, &WarningEmitter::null()) {
+            Ok(parsed) => {
+                let mut finder = BranchFinder {
+                    line_numbers: &line_numbers,
+                    cases: vec![],
+                };
+                let _ = finder.fold_module(parsed.module);
+                finder.cases
+            }
+            // The module has already been compiled so this can't really
+            // happen. If it does, the branches are just not reported.
+            Err(_) => vec![],
+        };
+
+        let _ = self.modules.insert(
+            name,
+            ModuleCoverage {
+                path,
+                lines: BTreeMap::new(),
+                cases,
+            },
+        );
+    }
+
+    /// Adds the coverage collected by Erlang's `cover` tool. The analysis has
+    /// a line for each line of code of the covered modules, with the name of
+    /// the Erlang module, the line, and the number of times it was executed,
+    /// separated by spaces.
+    ///
+    pub fn add_erlang_analysis(&mut self, analysis: &str) {
+        for line in analysis.lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(module), Some(line), Some(hits)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            let (Ok(line), Ok(hits)) = (line.parse::<u32>(), hits.parse::<u64>()) else {
+                continue;
+            };
+            // Code generated by the compiler itself is annotated with line 0.
+            if line == 0 {
+                continue;
+            }
+
+            let module = module.replace('@', "/");
+            if let Some(module) = self.modules.get_mut(module.as_str()) {
+                *module.lines.entry(line).or_default() += hits;
+            }
+        }
+    }
+
+    /// Adds the coverage collected by V8 for the JavaScript code a module has
+    /// been compiled to. `script` is the coverage of that module's file, or
+    /// `None` if the module was never loaded.
+    ///
+    /// Each bit of generated code that is mapped to the Gleam source by the
+    /// module's source map gets the execution count of the innermost function
+    /// or block containing it. The line of Gleam code it's mapped to is
+    /// considered to have been executed if any of its code has been executed.
+    ///
+    pub fn add_javascript_coverage(
+        &mut self,
+        module: &str,
+        javascript: &str,
+        source_map: &[u8],
+        script: Option<&V8ScriptCoverage>,
+    ) {
+        let Some(module) = self.modules.get_mut(module) else {
+            return;
+        };
+        let Ok(source_map) = SourceMap::from_slice(source_map) else {
+            return;
+        };
+
+        // V8 offsets are counted in UTF-16 code units from the start of the
+        // file, while source maps use lines and columns.
+        let line_starts: Vec<u32> = std::iter::once(0)
+            .chain(javascript.split_inclusive('\n').scan(0, |offset, line| {
+                *offset += line.encode_utf16().count() as u32;
+                Some(*offset)
+            }))
+            .collect();
+
+        let ranges = script
+            .iter()
+            .flat_map(|script| &script.functions)
+            .flat_map(|function| &function.ranges)
+            .collect::<Vec<_>>();
+
+        let mut lines = BTreeMap::new();
+        for token in source_map.tokens() {
+            if !token.has_source() {
+                continue;
+            }
+            let Some(line_start) = line_starts.get(token.get_dst_line() as usize) else {
+                continue;
+            };
+            let offset = line_start + token.get_dst_col();
+            let hits = ranges
+                .iter()
+                .filter(|range| range.start_offset <= offset && offset < range.end_offset)
+                .min_by_key(|range| range.end_offset - range.start_offset)
+                .map(|range| range.count)
+                .unwrap_or(0);
+
+            let line = lines.entry(token.get_src_line() + 1).or_default();
+            *line = hits.max(*line);
+        }
+
+        for (line, hits) in lines {
+            *module.lines.entry(line).or_default() += hits;
+        }
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = (&EcoString, &ModuleCoverage)> {
+        self.modules.iter()
+    }
+
+    /// Renders the coverage in the lcov tracefile format.
+    ///
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for module in self.modules.values() {
+            writeln!(lcov, "TN:").expect("write lcov");
+            writeln!(lcov, "SF:{}", module.path).expect("write lcov");
+
+            for (case, branches) in module.cases.iter().enumerate() {
+                for (branch, line) in branches.iter().enumerate() {
+                    let taken = match module.lines.get(line) {
+                        Some(hits) => hits.to_string(),
+                        // The body of the clause has no code of its own
+                        // that has been tracked.
+                        None => "-".into(),
+                    };
+                    writeln!(lcov, "BRDA:{line},{case},{branch},{taken}").expect("write lcov");
+                }
+            }
+            writeln!(lcov, "BRF:{}", module.branches_found()).expect("write lcov");
+            writeln!(lcov, "BRH:{}", module.branches_hit()).expect("write lcov");
+
+            for (line, hits) in &module.lines {
+                writeln!(lcov, "DA:{line},{hits}").expect("write lcov");
+            }
+            writeln!(lcov, "LF:{}", module.lines_found()).expect("write lcov");
+            writeln!(lcov, "LH:{}", module.lines_hit()).expect("write lcov");
+            writeln!(lcov, "end_of_record").expect("write lcov");
+        }
+        lcov
+    }
+}
+
+impl ModuleCoverage {
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+
+    pub fn lines_found(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|hits| **hits > 0).count()
+    }
+
+    pub fn branches_found(&self) -> usize {
+        self.cases.iter().map(Vec::len).sum()
+    }
+
+    pub fn branches_hit(&self) -> usize {
+        self.cases
+            .iter()
+            .flatten()
+            .filter(|line| self.lines.get(line).is_some_and(|hits| *hits > 0))
+            .count()
+    }
+}
+
+/// Finds the clauses of all the `case` expressions in a module.
+///
+struct BranchFinder<'a> {
+    line_numbers: &'a LineNumbers,
+    cases: Vec<Vec<u32>>,
+}
+
+impl UntypedExprFolder for BranchFinder<'_> {
+    fn fold_case(
+        &mut self,
+        location: SrcSpan,
+        subjects: Vec<UntypedExpr>,
+        clauses: Option<Vec<UntypedClause>>,
+    ) -> UntypedExpr {
+        if let Some(clauses) = &clauses {
+            let branches = clauses
+                .iter()
+                .map(|clause| {
+                    let start = if let UntypedExpr::Block { statements, .. } = &clause.then {
+                        statements.first().location().start
+                    } else {
+                        clause.then.location().start
+                    };
+                    self.line_numbers.line_number(start)
+                })
+                .collect();
+            self.cases.push(branches);
+        }
+
+        UntypedExpr::Case {
+            location,
+            subjects,
+            clauses,
+        }
+    }
+}
+
+impl UntypedModuleFolder for BranchFinder<'_> {}
+impl TypeAstFolder for BranchFinder<'_> {}
+impl UntypedConstantFolder for BranchFinder<'_> {}
+impl PatternFolder for BranchFinder<'_> {}
+
+/// The coverage of a single script, as collected by V8.
+/// https://chromedevtools.github.io/devtools-protocol/tot/Profiler/#type-ScriptCoverage
+///
+#[derive(Debug, Clone, Deserialize)]
+pub struct V8ScriptCoverage {
+    pub url: EcoString,
+    pub functions: Vec<V8FunctionCoverage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct V8FunctionCoverage {
+    pub ranges: Vec<V8CoverageRange>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct V8CoverageRange {
+    pub start_offset: u32,
+    pub end_offset: u32,
+    pub count: u64,
+}
+
+/// The contents of the files written to the `NODE_V8_COVERAGE` directory by
+/// Node.js.
+///
+#[derive(Debug, Clone, Deserialize)]
+pub struct V8Coverage {
+    pub result: Vec<V8ScriptCoverage>,
+}
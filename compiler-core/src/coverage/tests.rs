@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use super::*;
+use crate::javascript::tests::compile_js_with_source_map;
+
+const SRC: &str = r#"pub fn main() {
+  wibble(True)
+}
+
+pub fn wibble(x) {
+  case x {
+    True -> 1
+    False -> {
+      let y = 2
+      y + 1
+    }
+  }
+}
+
+pub fn unused() {
+  3
+}
+"#;
+
+fn coverage_of(src: &str) -> Coverage {
+    let mut coverage = Coverage::new();
+    coverage.add_module(
+        "wibble/wobble".into(),
+        "src/wibble/wobble.gleam".into(),
+        src,
+    );
+    coverage
+}
+
+#[test]
+fn erlang_analysis() {
+    let mut coverage = coverage_of(SRC);
+    coverage.add_erlang_analysis(
+        "wibble@wobble 2 1
+wibble@wobble 6 1
+wibble@wobble 7 1
+wibble@wobble 9 0
+wibble@wobble 10 0
+wibble@wobble 16 0
+wibble@wobble 0 4
+other@module 1 4
+",
+    );
+
+    insta::assert_snapshot!(coverage.to_lcov());
+}
+
+#[test]
+fn erlang_analysis_for_multiple_runs_is_added_up() {
+    let mut coverage = coverage_of(SRC);
+    coverage.add_erlang_analysis("wibble@wobble 2 1\nwibble@wobble 16 0\n");
+    coverage.add_erlang_analysis("wibble@wobble 2 2\nwibble@wobble 16 1\n");
+
+    let (_, module) = coverage.modules().next().expect("module coverage");
+    assert_eq!(module.lines_found(), 2);
+    assert_eq!(module.lines_hit(), 2);
+    assert_eq!(module.lines.get(&2), Some(&3));
+}
+
+#[test]
+fn javascript_coverage() {
+    let (javascript, source_map) = compile_js_with_source_map(SRC, vec![]);
+    let mut source_map_bytes = vec![];
+    source_map
+        .to_writer(&mut source_map_bytes)
+        .expect("write source map");
+
+    let function_range = |name: &str, count| {
+        let start = javascript
+            .find(&format!("function {name}("))
+            .expect("function in javascript");
+        let end = start
+            + javascript
+                .get(start..)
+                .and_then(|function| function.find("\n}\n"))
+                .expect("end of function")
+            + 2;
+        V8FunctionCoverage {
+            ranges: vec![V8CoverageRange {
+                start_offset: start as u32,
+                end_offset: end as u32,
+                count,
+            }],
+        }
+    };
+    let script = V8ScriptCoverage {
+        url: "file:///build/dev/javascript/wibble/wibble/wobble.mjs".into(),
+        functions: vec![
+            V8FunctionCoverage {
+                ranges: vec![V8CoverageRange {
+                    start_offset: 0,
+                    end_offset: javascript.len() as u32,
+                    count: 1,
+                }],
+            },
+            function_range("main", 1),
+            function_range("wibble", 1),
+            function_range("unused", 0),
+        ],
+    };
+
+    let mut coverage = coverage_of(SRC);
+    coverage.add_javascript_coverage(
+        "wibble/wobble",
+        &javascript,
+        &source_map_bytes,
+        Some(&script),
+    );
+
+    let (_, module) = coverage.modules().next().expect("module coverage");
+    assert_eq!(module.lines.get(&2), Some(&1));
+    assert_eq!(module.lines.get(&16), Some(&0));
+}
+
+#[test]
+fn javascript_coverage_of_modules_that_were_never_loaded() {
+    let (javascript, source_map) = compile_js_with_source_map(SRC, vec![]);
+    let mut source_map_bytes = vec![];
+    source_map
+        .to_writer(&mut source_map_bytes)
+        .expect("write source map");
+
+    let mut coverage = coverage_of(SRC);
+    coverage.add_javascript_coverage("wibble/wobble", &javascript, &source_map_bytes, None);
+
+    let (_, module) = coverage.modules().next().expect("module coverage");
+    assert!(module.lines_found() > 0);
+    assert_eq!(module.lines_hit(), 0);
+}
+
+#[test]
+fn v8_coverage_is_parsed() {
+    let coverage: V8Coverage = serde_json::from_str(
+        r#"{
+  "result": [
+    {
+      "scriptId": "1",
+      "url": "file:///build/dev/javascript/wibble/wibble.mjs",
+      "functions": [
+        {
+          "functionName": "",
+          "ranges": [{ "startOffset": 0, "endOffset": 120, "count": 1 }],
+          "isBlockCoverage": true
+        }
+      ]
+    }
+  ],
+  "timestamp": 1
+}"#,
+    )
+    .expect("parse coverage");
+
+    let [script] = coverage.result.as_slice() else {
+        panic!("expected a single script");
+    };
+    assert_eq!(script.url, "file:///build/dev/javascript/wibble/wibble.mjs");
+    let range = script
+        .functions
+        .first()
+        .and_then(|function| function.ranges.first())
+        .expect("range");
+    assert_eq!(
+        (range.start_offset, range.end_offset, range.count),
+        (0, 120, 1)
+    );
+}
@@ -5,6 +5,7 @@ mod printer;
 mod source_links;
 #[cfg(test)]
 mod tests;
+mod versions;
 
 use std::{collections::HashMap, time::SystemTime};
 
@@ -15,7 +16,7 @@ use printer::Printer;
 use crate::{
     build::{Module, Package},
     config::{DocsPage, PackageConfig},
-    docs::source_links::SourceLinker,
+    docs::{source_links::SourceLinker, versions::VersionHistory},
     io::{Content, FileSystemReader, OutputFile},
     package_interface::PackageInterface,
     paths::ProjectPaths,
@@ -23,11 +24,14 @@ use crate::{
     version::COMPILER_VERSION,
 };
 use askama::Template;
+use clap::ValueEnum;
 use ecow::EcoString;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use serde_json::to_string as serde_to_string;
 
+pub use versions::PublishedVersion;
+
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum DocContext {
     HexPublish,
@@ -55,14 +59,43 @@ pub enum DependencyKind {
     Git,
 }
 
+/// Where links to the documentation of other packages should point to.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum DependencyLinks {
+    /// Types from Hex packages link to HexDocs, types from Git and path
+    /// dependencies are not linked.
+    HexDocs,
+    /// Types from any package link to documentation rendered in a sibling
+    /// directory named after the package, as done by
+    /// `gleam docs build --with-deps`.
+    Local,
+}
+
+/// The format to render the documentation of a package in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum DocsFormat {
+    /// Browsable HTML pages, as published to HexDocs.
+    #[default]
+    Html,
+    /// One Markdown file for each module, suitable to be read offline or by
+    /// other tools.
+    Markdown,
+}
+
 #[derive(Debug)]
 pub struct DocumentationConfig<'a> {
     pub package_config: &'a PackageConfig,
     pub dependencies: HashMap<EcoString, Dependency>,
+    pub dependency_links: DependencyLinks,
     pub analysed: &'a [Module],
     pub docs_pages: &'a [DocsPage],
     pub rendering_timestamp: SystemTime,
     pub context: DocContext,
+    /// The versions of the package that have been published to Hex. When
+    /// present the documentation has a version selector and each type and
+    /// value is marked with the version it was introduced in.
+    pub published_versions: &'a [PublishedVersion],
 }
 
 pub fn generate_html<IO: FileSystemReader>(
@@ -70,15 +103,245 @@ pub fn generate_html<IO: FileSystemReader>(
     config: DocumentationConfig<'_>,
     fs: IO,
 ) -> Vec<OutputFile> {
+    let (mut files, search_items) = render_package(paths, config, &[], &fs);
+    files.push(search_data_file(search_items));
+    files
+}
+
+/// Renders the documentation of several packages into sibling directories,
+/// one for each package. Links to types defined in any of the packages point
+/// to the locally rendered pages, and every package gets the same search
+/// index, containing the items of all the packages.
+///
+/// The paths of the returned files are relative to the directory containing
+/// the documentation of all packages.
+///
+pub fn generate_html_with_dependencies<IO: FileSystemReader>(
+    paths: &ProjectPaths,
+    packages: Vec<DocumentationConfig<'_>>,
+    fs: IO,
+) -> Vec<OutputFile> {
+    let package_links = packages
+        .iter()
+        .map(|package| Link {
+            name: package.package_config.name.to_string(),
+            path: format!("{}/index.html", package.package_config.name),
+        })
+        .sorted()
+        .collect_vec();
+
+    let mut files = vec![];
+    let mut search_items = vec![];
+    let mut package_names = vec![];
+
+    for package in packages {
+        let name = package.package_config.name.clone();
+        let (package_files, package_search_items) =
+            render_package(paths, package, &package_links, &fs);
+
+        files.extend(package_files.into_iter().map(|file| OutputFile {
+            path: Utf8PathBuf::from(name.as_str()).join(file.path),
+            content: file.content,
+        }));
+
+        // Search items are referenced relative to the root of the package
+        // being viewed, so we go up one level to find the right package.
+        search_items.extend(package_search_items.into_iter().map(|item| SearchItem {
+            reference: format!("../{name}/{}", item.reference),
+            ..item
+        }));
+        package_names.push(name);
+    }
+
+    for name in package_names {
+        let mut file = search_data_file(search_items.clone());
+        file.path = Utf8PathBuf::from(name.as_str()).join(file.path);
+        files.push(file);
+    }
+
+    files
+}
+
+/// Renders the documentation of a package as Markdown: an `index.md` file
+/// listing the modules and pages of the package, followed by its README, and
+/// one file for each module with the signatures and documentation of its
+/// public types and values.
+///
+pub fn generate_markdown<IO: FileSystemReader>(
+    paths: &ProjectPaths,
+    config: DocumentationConfig<'_>,
+    fs: IO,
+) -> Vec<OutputFile> {
+    render_markdown_package(paths, config, &fs)
+}
+
+/// Renders the Markdown documentation of several packages into sibling
+/// directories, one for each package.
+///
+/// The paths of the returned files are relative to the directory containing
+/// the documentation of all packages.
+///
+pub fn generate_markdown_with_dependencies<IO: FileSystemReader>(
+    paths: &ProjectPaths,
+    packages: Vec<DocumentationConfig<'_>>,
+    fs: IO,
+) -> Vec<OutputFile> {
+    packages
+        .into_iter()
+        .flat_map(|package| {
+            let name = package.package_config.name.clone();
+            render_markdown_package(paths, package, &fs)
+                .into_iter()
+                .map(move |file| OutputFile {
+                    path: Utf8PathBuf::from(name.as_str()).join(file.path),
+                    content: file.content,
+                })
+        })
+        .collect()
+}
+
+fn render_markdown_package<IO: FileSystemReader>(
+    paths: &ProjectPaths,
+    config: DocumentationConfig<'_>,
+    fs: &IO,
+) -> Vec<OutputFile> {
+    let DocumentationConfig {
+        package_config: config,
+        dependencies,
+        dependency_links,
+        analysed,
+        docs_pages,
+        rendering_timestamp: _,
+        context: _,
+        published_versions,
+    } = config;
+
+    let history = VersionHistory::new(&config.version, published_versions);
+
+    let modules = analysed
+        .iter()
+        .filter(|module| module.origin.is_src())
+        .filter(|module| !config.is_internal_module(&module.name))
+        .sorted_by(|a, b| a.name.cmp(&b.name))
+        .collect_vec();
+
+    let mut files = vec![];
+    let mut readme = String::new();
+    let mut pages = vec![];
+
+    // The README is included in the index, other pages are copied as they
+    // already are Markdown.
+    for page in docs_pages {
+        let content = fs.read(&page.source).unwrap_or_default();
+        let path = page.path.with_extension("md");
+        if path == "index.md" {
+            readme = content;
+            continue;
+        }
+        pages.push(Link {
+            name: page.title.clone(),
+            path: path.to_string(),
+        });
+        files.push(OutputFile {
+            path,
+            content: Content::Text(content),
+        });
+    }
+
+    let links = config
+        .links
+        .iter()
+        .map(|link| Link {
+            name: link.title.clone(),
+            path: link.href.to_string(),
+        })
+        .chain(config.repository.as_ref().map(|repository| Link {
+            name: "Repository".into(),
+            path: repository.url(),
+        }))
+        .collect_vec();
+
+    let module_links = modules
+        .iter()
+        .map(|module| Link {
+            name: module.name.to_string(),
+            path: format!("{}.md", module.name),
+        })
+        .collect_vec();
+
+    let index = MarkdownIndexTemplate {
+        project_name: &config.name,
+        project_version: &config.version.to_string(),
+        description: &config.description,
+        links: &links,
+        pages: &pages,
+        modules: &module_links,
+        readme: readme.trim_end(),
+    };
+    files.push(OutputFile {
+        path: Utf8PathBuf::from("index.md"),
+        content: Content::Text(index.render().expect("Markdown index template rendering")),
+    });
+
+    for module in modules {
+        let source_links = SourceLinker::new(paths, config, module);
+        let mut printer = Printer::new(
+            module.ast.type_info.package.clone(),
+            module.name.clone(),
+            &module.ast.names,
+            &dependencies,
+            dependency_links,
+        );
+
+        let mut types = printer.type_definitions(&source_links, &module.ast.definitions);
+        let mut values = printer.value_definitions(&source_links, &module.ast.definitions);
+        mark_since(&history, &module.name, &mut types, &mut values);
+
+        let template = MarkdownModuleTemplate {
+            module_name: &module.name,
+            documentation: strip_comment_indentation(&module.ast.documentation.iter().join("\n"))
+                .trim_end()
+                .to_string(),
+            types,
+            values,
+        };
+
+        files.push(OutputFile {
+            path: Utf8PathBuf::from(format!("{}.md", module.name)),
+            content: Content::Text(
+                template
+                    .render()
+                    .expect("Markdown module template rendering"),
+            ),
+        });
+    }
+
+    files
+}
+
+/// Renders all the pages and static assets for the documentation of a
+/// package, returning them along with the items to include in the search
+/// index.
+fn render_package<IO: FileSystemReader>(
+    paths: &ProjectPaths,
+    config: DocumentationConfig<'_>,
+    package_links: &[Link],
+    fs: &IO,
+) -> (Vec<OutputFile>, Vec<SearchItem>) {
     let DocumentationConfig {
         package_config: config,
         dependencies,
+        dependency_links,
         analysed,
         docs_pages,
         rendering_timestamp,
         context: is_hex_publish,
+        published_versions,
     } = config;
 
+    let history = VersionHistory::new(&config.version, published_versions);
+    let versions = versions::version_links(config, published_versions);
+
     let modules = analysed
         .iter()
         .filter(|module| module.origin.is_src())
@@ -171,6 +434,8 @@ pub fn generate_html<IO: FileSystemReader>(
             links: &links,
             pages: &pages,
             modules: &modules_links,
+            packages: package_links,
+            versions: &versions,
             project_name: &config.name,
             page_title: &page_title,
             page_meta_description: &page_meta_description,
@@ -211,10 +476,12 @@ pub fn generate_html<IO: FileSystemReader>(
             module.name.clone(),
             &module.ast.names,
             &dependencies,
+            dependency_links,
         );
 
-        let types = printer.type_definitions(&source_links, &module.ast.definitions);
-        let values = printer.value_definitions(&source_links, &module.ast.definitions);
+        let mut types = printer.type_definitions(&source_links, &module.ast.definitions);
+        let mut values = printer.value_definitions(&source_links, &module.ast.definitions);
+        mark_since(&history, &module.name, &mut types, &mut values);
 
         types
             .iter()
@@ -237,6 +504,8 @@ pub fn generate_html<IO: FileSystemReader>(
             pages: &pages,
             documentation: rendered_documentation,
             modules: &modules_links,
+            packages: package_links,
+            versions: &versions,
             project_name: &config.name,
             page_title: &page_title,
             page_meta_description,
@@ -330,17 +599,6 @@ pub fn generate_html<IO: FileSystemReader>(
         content: Content::Text(std::include_str!("../templates/docs-js/lunr.min.js").to_string()),
     });
 
-    let search_data_json = serde_to_string(&SearchData {
-        items: search_items,
-        programming_language: SearchProgrammingLanguage::Gleam,
-    })
-    .expect("search index serialization");
-
-    files.push(OutputFile {
-        path: Utf8PathBuf::from("search-data.json"),
-        content: Content::Text(search_data_json),
-    });
-
     files.push(OutputFile {
         path: Utf8PathBuf::from("js/index.js"),
         content: Content::Text(std::include_str!("../templates/docs-js/index.js").to_string()),
@@ -429,7 +687,35 @@ pub fn generate_html<IO: FileSystemReader>(
         ),
     });
 
-    files
+    (files, search_items)
+}
+
+/// Marks each type and value with the version it was introduced in.
+fn mark_since(
+    history: &VersionHistory<'_>,
+    module: &str,
+    types: &mut [TypeDefinition<'_>],
+    values: &mut [DocsValues<'_>],
+) {
+    for type_ in types {
+        type_.since = history.type_since(module, type_.name);
+    }
+    for value in values {
+        value.since = history.value_since(module, value.name);
+    }
+}
+
+fn search_data_file(items: Vec<SearchItem>) -> OutputFile {
+    let search_data_json = serde_to_string(&SearchData {
+        items,
+        programming_language: SearchProgrammingLanguage::Gleam,
+    })
+    .expect("search index serialization");
+
+    OutputFile {
+        path: Utf8PathBuf::from("search-data.json"),
+        content: Content::Text(search_data_json),
+    }
 }
 
 fn search_item_for_page(package: &str, path: &str, content: String) -> SearchItem {
@@ -455,7 +741,9 @@ fn search_item_for_type(module: &str, type_: &TypeDefinition<'_>) -> SearchItem
 
             format!(
                 "{}\n{}\n{}",
-                constructor.raw_definition, constructor.text_documentation, arguments
+                code_block(&constructor.raw_definition),
+                constructor.text_documentation,
+                arguments
             )
         })
         .join("\n");
@@ -466,7 +754,7 @@ fn search_item_for_type(module: &str, type_: &TypeDefinition<'_>) -> SearchItem
         title: type_.name.to_string(),
         content: format!(
             "{}\n{}\n{}\n{}",
-            type_.raw_definition,
+            code_block(&type_.raw_definition),
             type_.text_documentation,
             constructors,
             import_synonyms(module, type_.name)
@@ -482,7 +770,7 @@ fn search_item_for_value(module: &str, value: &DocsValues<'_>) -> SearchItem {
         title: value.name.to_string(),
         content: format!(
             "{}\n{}\n{}",
-            value.raw_definition,
+            code_block(&value.raw_definition),
             value.text_documentation,
             import_synonyms(module, value.name)
         ),
@@ -557,6 +845,10 @@ fn page_unnest_test() {
     assert_eq!(page_unnest("gleam/string/inspect"), "../..");
 }
 
+fn code_block(code: &str) -> String {
+    format!("```\n{code}\n```")
+}
+
 fn import_synonyms(parent: &str, child: &str) -> String {
     format!("Synonyms:\n{parent}.{child}\n{parent} {child}")
 }
@@ -571,6 +863,12 @@ fn text_documentation(doc: &Option<(u32, EcoString)>) -> String {
     raw_text.replace("```gleam", "").replace("```", "")
 }
 
+fn raw_documentation(doc: &Option<(u32, EcoString)>) -> String {
+    doc.as_ref()
+        .map(|(_, doc)| strip_comment_indentation(doc).trim_end().to_string())
+        .unwrap_or_default()
+}
+
 fn markdown_documentation(doc: &Option<(u32, EcoString)>) -> String {
     doc.as_ref()
         .map(|(_, doc)| render_markdown(doc, MarkdownSource::Comment))
@@ -590,12 +888,7 @@ enum MarkdownSource {
 fn render_markdown(text: &str, source: MarkdownSource) -> String {
     let text = match source {
         MarkdownSource::Standalone => text.into(),
-        // Doc comments start with "///\s", which can confuse the markdown parser
-        // and prevent tables from rendering correctly, so remove that first space.
-        MarkdownSource::Comment => text
-            .split('\n')
-            .map(|s| s.strip_prefix(' ').unwrap_or(s))
-            .join("\n"),
+        MarkdownSource::Comment => strip_comment_indentation(text),
     };
 
     let mut s = String::with_capacity(text.len() * 3 / 2);
@@ -604,6 +897,14 @@ fn render_markdown(text: &str, source: MarkdownSource) -> String {
     s
 }
 
+/// Doc comments start with "///\s", which can confuse the markdown parser
+/// and prevent tables from rendering correctly, so remove that first space.
+fn strip_comment_indentation(text: &str) -> String {
+    text.split('\n')
+        .map(|s| s.strip_prefix(' ').unwrap_or(s))
+        .join("\n")
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
 struct Link {
     name: String,
@@ -616,6 +917,7 @@ struct TypeConstructor {
     raw_definition: String,
     documentation: String,
     text_documentation: String,
+    raw_documentation: String,
     arguments: Vec<TypeConstructorArg>,
 }
 
@@ -624,6 +926,7 @@ struct TypeConstructorArg {
     name: String,
     doc: String,
     text_documentation: String,
+    raw_documentation: String,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -634,8 +937,11 @@ struct TypeDefinition<'a> {
     documentation: String,
     constructors: Vec<TypeConstructor>,
     text_documentation: String,
+    raw_documentation: String,
     source_url: String,
     deprecation_message: String,
+    /// The version this type was introduced in, if known.
+    since: String,
     opaque: bool,
 }
 
@@ -646,8 +952,11 @@ struct DocsValues<'a> {
     raw_definition: String,
     documentation: String,
     text_documentation: String,
+    raw_documentation: String,
     source_url: String,
     deprecation_message: String,
+    /// The version this value was introduced in, if known.
+    since: String,
 }
 
 #[derive(Template)]
@@ -664,6 +973,8 @@ struct PageTemplate<'a> {
     pages: &'a [Link],
     links: &'a [Link],
     modules: &'a [Link],
+    packages: &'a [Link],
+    versions: &'a [Link],
     content: String,
     rendering_timestamp: &'a str,
 }
@@ -683,12 +994,35 @@ struct ModuleTemplate<'a> {
     pages: &'a [Link],
     links: &'a [Link],
     modules: &'a [Link],
+    packages: &'a [Link],
+    versions: &'a [Link],
     types: Vec<TypeDefinition<'a>>,
     values: Vec<DocsValues<'a>>,
     documentation: String,
     rendering_timestamp: &'a str,
 }
 
+#[derive(Template)]
+#[template(path = "documentation_index.md", escape = "none")]
+struct MarkdownIndexTemplate<'a> {
+    project_name: &'a str,
+    project_version: &'a str,
+    description: &'a str,
+    links: &'a [Link],
+    pages: &'a [Link],
+    modules: &'a [Link],
+    readme: &'a str,
+}
+
+#[derive(Template)]
+#[template(path = "documentation_module.md", escape = "none")]
+struct MarkdownModuleTemplate<'a> {
+    module_name: &'a str,
+    documentation: String,
+    types: Vec<TypeDefinition<'a>>,
+    values: Vec<DocsValues<'a>>,
+}
+
 /// Search data for use by Hexdocs search, as well as the search built-in to
 /// generated documentation
 #[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
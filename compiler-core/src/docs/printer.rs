@@ -7,6 +7,7 @@ use std::{
 };
 
 use ecow::{EcoString, eco_format};
+use itertools::Itertools;
 
 use crate::{
     ast::{
@@ -22,8 +23,9 @@ use pretty_arena::*;
 use src_span::SrcSpan;
 
 use super::{
-    Dependency, DependencyKind, DocsValues, TypeConstructor, TypeConstructorArg, TypeDefinition,
-    markdown_documentation, source_links::SourceLinker, text_documentation,
+    Dependency, DependencyKind, DependencyLinks, DocsValues, TypeConstructor, TypeConstructorArg,
+    TypeDefinition, markdown_documentation, raw_documentation, source_links::SourceLinker,
+    text_documentation,
 };
 
 #[derive(Clone, Copy)]
@@ -59,6 +61,7 @@ pub struct Printer<'a> {
     next_type_variable_id: u64,
 
     dependencies: &'a HashMap<EcoString, Dependency>,
+    dependency_links: DependencyLinks,
 }
 
 impl<'a, 'doc> Printer<'a> {
@@ -67,6 +70,7 @@ impl<'a, 'doc> Printer<'a> {
         module: EcoString,
         names: &'a Names,
         dependencies: &'a HashMap<EcoString, Dependency>,
+        dependency_links: DependencyLinks,
     ) -> Printer<'a> {
         Printer {
             options: PrintOptions::all(),
@@ -77,6 +81,7 @@ impl<'a, 'doc> Printer<'a> {
             printed_type_variable_names: HashSet::new(),
             next_type_variable_id: 0,
             dependencies,
+            dependency_links,
         }
     }
 
@@ -124,6 +129,7 @@ impl<'a, 'doc> Printer<'a> {
                     .raw(|this| this.custom_type(&arena, name, parameters, constructors, *opaque)),
                 documentation: markdown_documentation(documentation),
                 text_documentation: text_documentation(documentation),
+                raw_documentation: raw_documentation(documentation),
                 deprecation_message: match deprecation {
                     Deprecation::NotDeprecated => "".to_string(),
                     Deprecation::Deprecated { message } => message.to_string(),
@@ -139,6 +145,7 @@ impl<'a, 'doc> Printer<'a> {
                                 .raw(|this| this.record_constructor(&arena, constructor)),
                             documentation: markdown_documentation(&constructor.documentation),
                             text_documentation: text_documentation(&constructor.documentation),
+                            raw_documentation: raw_documentation(&constructor.documentation),
                             arguments: constructor
                                 .arguments
                                 .iter()
@@ -147,6 +154,7 @@ impl<'a, 'doc> Printer<'a> {
                                     name: label.trim_end().to_string(),
                                     doc: markdown_documentation(&argument.doc),
                                     text_documentation: text_documentation(&argument.doc),
+                                    raw_documentation: raw_documentation(&argument.doc),
                                 })
                                 .filter(|arg| !arg.doc.is_empty())
                                 .collect(),
@@ -154,6 +162,7 @@ impl<'a, 'doc> Printer<'a> {
                         .collect()
                 },
                 source_url: source_links.url(*location),
+                since: String::new(),
                 opaque: *opaque,
             });
         }
@@ -184,12 +193,14 @@ impl<'a, 'doc> Printer<'a> {
                 }),
                 documentation: markdown_documentation(documentation),
                 text_documentation: text_documentation(documentation),
+                raw_documentation: raw_documentation(documentation),
                 constructors: vec![],
                 source_url: source_links.url(*location),
                 deprecation_message: match deprecation {
                     Deprecation::NotDeprecated => "".to_string(),
                     Deprecation::Deprecated { message } => message.to_string(),
                 },
+                since: String::new(),
                 opaque: false,
             });
         }
@@ -212,7 +223,7 @@ impl<'a, 'doc> Printer<'a> {
         let result = print(definition(self));
         // Restore previous options
         self.options = options;
-        format!("```\n{result}\n```")
+        result
     }
 
     pub fn value_definitions(
@@ -252,11 +263,13 @@ impl<'a, 'doc> Printer<'a> {
                     .raw(|this| this.function_signature(&arena, name, arguments, return_type)),
                 documentation: markdown_documentation(documentation),
                 text_documentation: text_documentation(documentation),
+                raw_documentation: raw_documentation(documentation),
                 source_url: source_links.url(*location),
                 deprecation_message: match deprecation {
                     Deprecation::NotDeprecated => "".to_string(),
                     Deprecation::Deprecated { message } => message.to_string(),
                 },
+                since: String::new(),
             });
         }
 
@@ -280,11 +293,13 @@ impl<'a, 'doc> Printer<'a> {
                 raw_definition: self.raw(|this| this.constant(&arena, name, type_)),
                 documentation: markdown_documentation(documentation),
                 text_documentation: text_documentation(documentation),
+                raw_documentation: raw_documentation(documentation),
                 source_url: source_links.url(*location),
                 deprecation_message: match deprecation {
                     Deprecation::NotDeprecated => "".to_string(),
                     Deprecation::Deprecated { message } => message.to_string(),
                 },
+                since: String::new(),
             });
         }
 
@@ -729,8 +744,20 @@ impl<'a, 'doc> Printer<'a> {
         let title = eco_format!("{module}.{{type {name}}}");
 
         // We can't reliably link to documentation if the type is from a path
-        // or git dependency
+        // or git dependency, unless its documentation is rendered next to the
+        // documentation of this package.
         match self.dependencies.get(package) {
+            Some(_) if self.dependency_links == DependencyLinks::Local => {
+                // The documentation for the other package lives in a sibling
+                // directory, so we go back to the root of this package first.
+                let unnest = self.module.split('/').skip(1).map(|_| "../").join("");
+                self.link(
+                    arena,
+                    eco_format!("{unnest}../{package}/{module}.html#{name}"),
+                    qualified_name,
+                    Some(title),
+                )
+            }
             Some(Dependency {
                 kind: DependencyKind::Hex,
                 version,
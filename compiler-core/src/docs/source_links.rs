@@ -11,7 +11,15 @@ use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
 
 pub struct SourceLinker {
     line_numbers: LineNumbers,
-    url_pattern: Option<(String, String)>,
+    url_pattern: Option<UrlPattern>,
+}
+
+enum UrlPattern {
+    /// A URL the line numbers are appended to, separated by the given string
+    /// when linking to more than one line.
+    Lines { base: String, separator: String },
+    /// A URL with `{line_start}` and `{line_end}` placeholders.
+    Template(String),
 }
 
 impl SourceLinker {
@@ -44,30 +52,36 @@ impl SourceLinker {
             .repository
             .as_ref()
             .map(|r| match r {
-                Repository::GitHub { user, repo, .. } => Some((
-                    format!("https://github.com/{user}/{repo}/blob/{tag}/{path_in_repo}#L"),
-                    "-L".into(),
-                )),
-                Repository::GitLab { user, repo, .. } => Some((
-                    format!("https://gitlab.com/{user}/{repo}/-/blob/{tag}/{path_in_repo}#L"),
-                    "-".into(),
-                )),
-                Repository::BitBucket { user, repo, .. } => Some((
-                    format!("https://bitbucket.com/{user}/{repo}/src/{tag}/{path_in_repo}#lines-"),
-                    ":".into(),
-                )),
-                Repository::Codeberg { user, repo, .. } => Some((
-                    format!("https://codeberg.org/{user}/{repo}/src/tag/{tag}/{path_in_repo}#L"),
-                    "-".into(),
-                )),
-                Repository::SourceHut { user, repo, .. } => Some((
-                    format!("https://git.sr.ht/~{user}/{repo}/tree/{tag}/item/{path_in_repo}#L"),
-                    "-".into(),
-                )),
-                Repository::Tangled { user, repo, .. } => Some((
-                    format!("https://tangled.org/{user}/{repo}/blob/{tag}/{path_in_repo}#L"),
-                    "-".into(),
-                )),
+                Repository::GitHub { user, repo, .. } => Some(UrlPattern::Lines {
+                    base: format!("https://github.com/{user}/{repo}/blob/{tag}/{path_in_repo}#L"),
+                    separator: "-L".into(),
+                }),
+                Repository::GitLab { user, repo, .. } => Some(UrlPattern::Lines {
+                    base: format!("https://gitlab.com/{user}/{repo}/-/blob/{tag}/{path_in_repo}#L"),
+                    separator: "-".into(),
+                }),
+                Repository::BitBucket { user, repo, .. } => Some(UrlPattern::Lines {
+                    base: format!(
+                        "https://bitbucket.com/{user}/{repo}/src/{tag}/{path_in_repo}#lines-"
+                    ),
+                    separator: ":".into(),
+                }),
+                Repository::Codeberg { user, repo, .. } => Some(UrlPattern::Lines {
+                    base: format!(
+                        "https://codeberg.org/{user}/{repo}/src/tag/{tag}/{path_in_repo}#L"
+                    ),
+                    separator: "-".into(),
+                }),
+                Repository::SourceHut { user, repo, .. } => Some(UrlPattern::Lines {
+                    base: format!(
+                        "https://git.sr.ht/~{user}/{repo}/tree/{tag}/item/{path_in_repo}#L"
+                    ),
+                    separator: "-".into(),
+                }),
+                Repository::Tangled { user, repo, .. } => Some(UrlPattern::Lines {
+                    base: format!("https://tangled.org/{user}/{repo}/blob/{tag}/{path_in_repo}#L"),
+                    separator: "-".into(),
+                }),
                 Repository::Gitea {
                     user, repo, host, ..
                 }
@@ -76,12 +90,33 @@ impl SourceLinker {
                 } => {
                     let string_host = host.to_string();
                     let cleaned_host = string_host.trim_end_matches('/');
-                    Some((
-                        format!("{cleaned_host}/{user}/{repo}/src/tag/{tag}/{path_in_repo}#L",),
-                        "-L".into(),
-                    ))
+                    Some(UrlPattern::Lines {
+                        base: format!(
+                            "{cleaned_host}/{user}/{repo}/src/tag/{tag}/{path_in_repo}#L",
+                        ),
+                        separator: "-L".into(),
+                    })
                 }
-                Repository::Custom { .. } => None,
+                Repository::Custom {
+                    url,
+                    file_url_template: Some(template),
+                    ..
+                } => {
+                    let template = template
+                        .replace("{tag}", &tag)
+                        .replace("{path}", &path_in_repo);
+                    if template.contains("://") {
+                        Some(UrlPattern::Template(template))
+                    } else {
+                        let url = url.trim_end_matches('/');
+                        let template = template.trim_start_matches('/');
+                        Some(UrlPattern::Template(format!("{url}/{template}")))
+                    }
+                }
+                Repository::Custom {
+                    file_url_template: None,
+                    ..
+                } => None,
             })
             .unwrap_or_default();
 
@@ -92,17 +127,21 @@ impl SourceLinker {
     }
 
     pub fn url(&self, span: SrcSpan) -> String {
+        let start_line = self.line_numbers.line_number(span.start);
+        let end_line = self.line_numbers.line_number(span.end);
         match &self.url_pattern {
-            Some((base, line_sep)) => {
-                let start_line = self.line_numbers.line_number(span.start);
-                let end_line = self.line_numbers.line_number(span.end);
+            Some(UrlPattern::Lines { base, separator }) => {
                 if start_line == end_line {
                     format!("{base}{start_line}")
                 } else {
-                    format!("{base}{start_line}{line_sep}{end_line}")
+                    format!("{base}{start_line}{separator}{end_line}")
                 }
             }
 
+            Some(UrlPattern::Template(template)) => template
+                .replace("{line_start}", &start_line.to_string())
+                .replace("{line_end}", &end_line.to_string()),
+
             None => "".into(),
         }
     }
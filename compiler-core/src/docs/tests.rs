@@ -7,8 +7,8 @@ use std::{
 };
 
 use super::{
-    Dependency, DependencyKind, DocumentationConfig, SearchData, SearchItem, SearchItemType,
-    SearchProgrammingLanguage,
+    Dependency, DependencyKind, DependencyLinks, DocumentationConfig, PublishedVersion, SearchData,
+    SearchItem, SearchItemType, SearchProgrammingLanguage,
     printer::{PrintOptions, Printer},
     source_links::SourceLinker,
 };
@@ -37,6 +37,8 @@ use serde_json::to_string as serde_to_string;
 #[derive(Default)]
 struct CompileWithMarkdownPagesOpts {
     hex_publish: Option<DocContext>,
+    markdown_output: bool,
+    published_versions: Vec<PublishedVersion>,
 }
 
 fn compile_with_markdown_pages(
@@ -103,37 +105,44 @@ fn compile_with_markdown_pages(
         })
         .collect_vec();
 
-    super::generate_html(
-        &paths,
-        DocumentationConfig {
-            package_config: &config,
-            dependencies: HashMap::new(),
-            analysed: &modules,
-            docs_pages: &docs_pages,
-            rendering_timestamp: SystemTime::UNIX_EPOCH,
-            context: if let Some(doc_context) = opts.hex_publish {
-                doc_context
-            } else {
-                DocContext::HexPublish
-            },
+    let documentation_config = DocumentationConfig {
+        package_config: &config,
+        dependencies: HashMap::new(),
+        dependency_links: DependencyLinks::HexDocs,
+        analysed: &modules,
+        docs_pages: &docs_pages,
+        rendering_timestamp: SystemTime::UNIX_EPOCH,
+        context: if let Some(doc_context) = opts.hex_publish {
+            doc_context
+        } else {
+            DocContext::HexPublish
         },
-        pages_fs,
-    )
-    .into_iter()
-    .filter(|file| file.path.extension() == Some("html"))
-    .sorted_by(|a, b| a.path.cmp(&b.path))
-    .flat_map(|file| {
-        Some(format!(
-            "//// {}\n\n{}\n\n",
-            file.path.as_str(),
-            file.content
-                .text()?
-                .replace(COMPILER_VERSION, "GLEAM_VERSION_HERE")
-        ))
-    })
-    .collect::<String>()
-    .chars()
-    .collect()
+        published_versions: &opts.published_versions,
+    };
+    let (files, extension) = if opts.markdown_output {
+        let files = super::generate_markdown(&paths, documentation_config, pages_fs);
+        (files, "md")
+    } else {
+        let files = super::generate_html(&paths, documentation_config, pages_fs);
+        (files, "html")
+    };
+
+    files
+        .into_iter()
+        .filter(|file| file.path.extension() == Some(extension))
+        .sorted_by(|a, b| a.path.cmp(&b.path))
+        .flat_map(|file| {
+            Some(format!(
+                "//// {}\n\n{}\n\n",
+                file.path.as_str(),
+                file.content
+                    .text()?
+                    .replace(COMPILER_VERSION, "GLEAM_VERSION_HERE")
+            ))
+        })
+        .collect::<String>()
+        .chars()
+        .collect()
 }
 
 pub fn compile(config: PackageConfig, modules: Vec<(&str, &str)>) -> EcoString {
@@ -145,11 +154,28 @@ pub fn compile(config: PackageConfig, modules: Vec<(&str, &str)>) -> EcoString {
     )
 }
 
+fn compile_markdown(
+    config: PackageConfig,
+    modules: Vec<(&str, &str)>,
+    markdown_pages: Vec<(&str, &str)>,
+) -> EcoString {
+    compile_with_markdown_pages(
+        config,
+        modules,
+        markdown_pages,
+        CompileWithMarkdownPagesOpts {
+            markdown_output: true,
+            ..Default::default()
+        },
+    )
+}
+
 fn compile_documentation(
     module_name: &str,
     module_src: &str,
     modules: Vec<(&str, &str, &str)>,
     dependency_kind: DependencyKind,
+    dependency_links: DependencyLinks,
     options: PrintOptions,
 ) -> EcoString {
     let module = type_::tests::compile_module(module_name, module_src, None, modules.clone())
@@ -190,6 +216,7 @@ fn compile_documentation(
         module.name.clone(),
         &module.names,
         &dependencies,
+        dependency_links,
     );
     printer.set_options(options);
 
@@ -264,7 +291,14 @@ macro_rules! assert_documentation {
     };
 
     ($src:literal, $options:expr $(,)?) => {
-        let output = compile_documentation("main", $src, Vec::new(), DependencyKind::Hex, $options);
+        let output = compile_documentation(
+            "main",
+            $src,
+            Vec::new(),
+            DependencyKind::Hex,
+            DependencyLinks::HexDocs,
+            $options,
+        );
         insta::assert_snapshot!(output);
     };
 
@@ -274,6 +308,7 @@ macro_rules! assert_documentation {
             $src,
             vec![$(("thepackage", $name, $module_src)),*],
             DependencyKind::Hex,
+            DependencyLinks::HexDocs,
             PrintOptions::all(),
         );
         insta::assert_snapshot!(output);
@@ -285,6 +320,7 @@ macro_rules! assert_documentation {
             $src,
             vec![$(("thepackage", $name, $module_src)),*],
             DependencyKind::Hex,
+            DependencyLinks::HexDocs,
             $options,
         );
         insta::assert_snapshot!(output);
@@ -296,6 +332,7 @@ macro_rules! assert_documentation {
             $src,
             vec![$(("thepackage", $name, $module_src)),*],
             DependencyKind::Hex,
+            DependencyLinks::HexDocs,
             $options,
         );
         insta::assert_snapshot!(output);
@@ -307,6 +344,7 @@ macro_rules! assert_documentation {
             $src,
             vec![$(($package, $name, $module_src)),*],
             DependencyKind::Hex,
+            DependencyLinks::HexDocs,
             PrintOptions::all(),
         );
         insta::assert_snapshot!(output);
@@ -318,6 +356,7 @@ macro_rules! assert_documentation {
             $src,
             vec![$(($package, $name, $module_src)),*],
             DependencyKind::Hex,
+            DependencyLinks::HexDocs,
             $options,
         );
         insta::assert_snapshot!(output);
@@ -329,6 +368,7 @@ macro_rules! assert_documentation {
             $src,
             vec![$(($package, $name, $module_src)),*],
             DependencyKind::Git,
+            DependencyLinks::HexDocs,
             $options,
         );
         insta::assert_snapshot!(output);
@@ -340,6 +380,19 @@ macro_rules! assert_documentation {
             $src,
             vec![$(($package, $name, $module_src)),*],
             DependencyKind::Path,
+            DependencyLinks::HexDocs,
+            $options,
+        );
+        insta::assert_snapshot!(output);
+    };
+
+    (local: $(($package:expr, $name:expr, $module_src:literal)),+, $main_module:literal, $src:literal, $options:expr $(,)?) => {
+        let output = compile_documentation(
+            $main_module,
+            $src,
+            vec![$(($package, $name, $module_src)),*],
+            DependencyKind::Path,
+            DependencyLinks::Local,
             $options,
         );
         insta::assert_snapshot!(output);
@@ -639,6 +692,58 @@ fn source_link_for_github_repository_with_path_and_tag_prefix() {
     ));
 }
 
+#[test]
+fn source_link_for_custom_repository_with_relative_template() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    config.repository = Some(Repository::Custom {
+        url: "https://git.example.com/team/wobble/".into(),
+        path: None,
+        tag_prefix: None,
+        file_url_template: Some("-/blob/{tag}/{path}#L{line_start}-{line_end}".into()),
+    });
+
+    let modules = vec![("app.gleam", "pub type Wibble = Int")];
+    assert!(
+        compile(config, modules)
+            .contains("https://git.example.com/team/wobble/-/blob/v0.1.0/src/app.gleam#L1-1")
+    );
+}
+
+#[test]
+fn source_link_for_custom_repository_with_absolute_template_path_and_tag_prefix() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    config.repository = Some(Repository::Custom {
+        url: "https://git.example.com/team/wobble".into(),
+        path: Some("path/to/package".into()),
+        tag_prefix: Some("subdir-".into()),
+        file_url_template: Some(
+            "https://code.example.com/wobble/src/{tag}/{path}?lines={line_start}:{line_end}".into(),
+        ),
+    });
+
+    let modules = vec![("app.gleam", "pub type Wibble = Int")];
+    assert!(compile(config, modules).contains(
+        "https://code.example.com/wobble/src/subdir-v0.1.0/path/to/package/src/app.gleam?lines=1:1"
+    ));
+}
+
+#[test]
+fn no_source_link_for_custom_repository_without_template() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    config.repository = Some(Repository::Custom {
+        url: "https://git.example.com/team/wobble".into(),
+        path: None,
+        tag_prefix: None,
+        file_url_template: None,
+    });
+
+    let modules = vec![("app.gleam", "pub type Wibble = Int")];
+    assert!(!compile(config, modules).contains("View Source"));
+}
+
 #[test]
 fn canonical_link() {
     let mut config = PackageConfig::default();
@@ -714,11 +819,322 @@ pub fn one() {
         modules,
         pages,
         CompileWithMarkdownPagesOpts {
-            hex_publish: Some(DocContext::Build)
+            hex_publish: Some(DocContext::Build),
+            ..Default::default()
         }
     ));
 }
 
+fn documentation_module(package: &str, module_name: &str, module_src: &str) -> build::Module {
+    let module = type_::tests::compile_module(module_name, module_src, None, Vec::new())
+        .expect("Module should compile successfully");
+
+    build::Module {
+        name: module_name.into(),
+        code: module_src.into(),
+        mtime: SystemTime::now(),
+        input_path: format!("/{package}/src/{module_name}.gleam").into(),
+        origin: Origin::Src,
+        ast: module,
+        extra: ModuleExtra::new(),
+        dependencies: vec![],
+    }
+}
+
+#[test]
+fn documentation_with_dependencies_is_rendered_in_sibling_directories() {
+    let mut wibble_config = PackageConfig::default();
+    wibble_config.name = "wibble".into();
+    let mut wobble_config = PackageConfig::default();
+    wobble_config.name = "wobble".into();
+
+    let wibble_modules = [documentation_module(
+        "wibble",
+        "wibble",
+        "pub fn one() { 1 }",
+    )];
+    let wobble_modules = [documentation_module(
+        "wobble",
+        "wobble/two",
+        "pub fn two() { 2 }",
+    )];
+
+    let documentation_config = |config, modules| DocumentationConfig {
+        package_config: config,
+        dependencies: HashMap::new(),
+        dependency_links: DependencyLinks::Local,
+        analysed: modules,
+        docs_pages: &[],
+        rendering_timestamp: SystemTime::UNIX_EPOCH,
+        context: DocContext::Build,
+        published_versions: &[],
+    };
+
+    let files = super::generate_html_with_dependencies(
+        &ProjectPaths::new("/".into()),
+        vec![
+            documentation_config(&wibble_config, &wibble_modules),
+            documentation_config(&wobble_config, &wobble_modules),
+        ],
+        InMemoryFileSystem::new(),
+    );
+
+    let paths = files.iter().map(|file| file.path.as_str()).collect_vec();
+    assert!(paths.contains(&"wibble/wibble.html"));
+    assert!(paths.contains(&"wobble/wobble/two.html"));
+
+    let search_data = |package: &str| {
+        files
+            .iter()
+            .find(|file| file.path == Utf8PathBuf::from(format!("{package}/search-data.json")))
+            .and_then(|file| file.content.text())
+            .expect("search data")
+            .to_string()
+    };
+
+    // Every package gets the same search index, with items from all packages
+    let search_data_json = search_data("wibble");
+    assert_eq!(search_data_json, search_data("wobble"));
+    assert!(search_data_json.contains(r#""ref":"../wibble/wibble.html#one""#));
+    assert!(search_data_json.contains(r#""ref":"../wobble/wobble/two.html#two""#));
+
+    // And the sidebar links to all packages
+    let module_page = files
+        .iter()
+        .find(|file| file.path == Utf8PathBuf::from("wobble/wobble/two.html"))
+        .and_then(|file| file.content.text())
+        .expect("module page");
+    assert!(module_page.contains(r#"<a href="../../wibble/index.html">wibble</a>"#));
+}
+
+#[test]
+fn markdown_module_documentation() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    config.repository = Some(Repository::GitHub {
+        user: "wibble".to_string(),
+        repo: "wobble".to_string(),
+        path: None,
+        tag_prefix: None,
+    });
+
+    let modules = vec![(
+        "app.gleam",
+        r#"
+//// Module documentation, with a table:
+////
+//// | a | b |
+//// |---|---|
+//// | 1 | 2 |
+
+/// A type with documented constructors.
+pub type Wibble {
+  /// The first constructor.
+  Wibble(
+    /// The name of the wibble.
+    name: String,
+    count: Int,
+  )
+  Wobble
+}
+
+pub opaque type Secret {
+  Secret(Int)
+}
+
+/// Returns the name.
+///
+/// ```gleam
+/// name(Wibble("wobble", 1))
+/// ```
+pub fn name(wibble: Wibble) -> String {
+  case wibble {
+    Wibble(name:, ..) -> name
+    Wobble -> "wobble"
+  }
+}
+
+@deprecated("Use `name` instead")
+pub fn old_name(wibble: Wibble) -> String {
+  name(wibble)
+}
+
+pub const answer = 42
+
+fn private() { Nil }
+"#,
+    )];
+
+    insta::assert_snapshot!(compile_markdown(config, modules, vec![]));
+}
+
+#[test]
+fn markdown_index_lists_pages_and_modules() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("app");
+    config.description = EcoString::from("A package for testing.");
+
+    let modules = vec![
+        ("app.gleam", "pub const one = 1"),
+        ("app/nested.gleam", "pub const two = 2"),
+        ("app/internal.gleam", "pub const three = 3"),
+    ];
+    let pages = vec![
+        ("index", "# README\n\nHello, Joe!\n"),
+        ("guide", "# A guide\n"),
+    ];
+
+    insta::assert_snapshot!(compile_markdown(config, modules, pages));
+}
+
+#[test]
+fn markdown_documentation_with_dependencies_is_rendered_in_sibling_directories() {
+    let mut wibble_config = PackageConfig::default();
+    wibble_config.name = "wibble".into();
+    let mut wobble_config = PackageConfig::default();
+    wobble_config.name = "wobble".into();
+
+    let wibble_modules = [documentation_module(
+        "wibble",
+        "wibble",
+        "pub fn one() { 1 }",
+    )];
+    let wobble_modules = [documentation_module(
+        "wobble",
+        "wobble/two",
+        "pub fn two() { 2 }",
+    )];
+
+    let documentation_config = |config, modules| DocumentationConfig {
+        package_config: config,
+        dependencies: HashMap::new(),
+        dependency_links: DependencyLinks::Local,
+        analysed: modules,
+        docs_pages: &[],
+        rendering_timestamp: SystemTime::UNIX_EPOCH,
+        context: DocContext::Build,
+        published_versions: &[],
+    };
+
+    let files = super::generate_markdown_with_dependencies(
+        &ProjectPaths::new("/".into()),
+        vec![
+            documentation_config(&wibble_config, &wibble_modules),
+            documentation_config(&wobble_config, &wobble_modules),
+        ],
+        InMemoryFileSystem::new(),
+    );
+
+    let paths = files
+        .iter()
+        .map(|file| file.path.as_str())
+        .sorted()
+        .collect_vec();
+    assert_eq!(
+        paths,
+        vec![
+            "wibble/index.md",
+            "wibble/wibble.md",
+            "wobble/index.md",
+            "wobble/wobble/two.md",
+        ]
+    );
+}
+
+fn published_version(version: &str, interface: Option<&str>) -> PublishedVersion {
+    PublishedVersion {
+        version: Version::parse(version).unwrap(),
+        interface: interface.map(|json| serde_json::from_str(json).unwrap()),
+    }
+}
+
+fn versioned_documentation(markdown_output: bool) -> EcoString {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    config.version = Version::parse("1.2.0").unwrap();
+
+    let modules = vec![(
+        "app.gleam",
+        r#"
+pub type Wibble { Wibble }
+pub type Wobble = Int
+pub type Wabble { Wabble }
+
+pub fn one() { 1 }
+pub const two = 2
+pub fn three() { 3 }
+"#,
+    )];
+
+    let published_versions = vec![
+        published_version(
+            "1.0.0",
+            Some(r#"{"modules": {"app": {"types": {"Wibble": {}}, "functions": {"one": {}}}}}"#),
+        ),
+        published_version(
+            "1.1.0",
+            Some(
+                r#"{"modules": {"app": {
+                    "types": {"Wibble": {}},
+                    "type-aliases": {"Wobble": {}},
+                    "functions": {"one": {}},
+                    "constants": {"two": {}}
+                }}}"#,
+            ),
+        ),
+        // Newer versions are listed in the version selector, but they have
+        // nothing to do with when items were introduced.
+        published_version("2.0.0", Some(r#"{"modules": {}}"#)),
+    ];
+
+    compile_with_markdown_pages(
+        config,
+        modules,
+        vec![],
+        CompileWithMarkdownPagesOpts {
+            markdown_output,
+            published_versions,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn version_selector_and_since_markers() {
+    insta::assert_snapshot!(versioned_documentation(false));
+}
+
+#[test]
+fn markdown_since_markers() {
+    insta::assert_snapshot!(versioned_documentation(true));
+}
+
+#[test]
+fn since_markers_skip_versions_without_an_interface() {
+    let interface = r#"{"modules": {"app": {"functions": {"one": {}}}}}"#;
+    let published = [
+        published_version("1.0.0", Some(interface)),
+        published_version("1.1.0", None),
+        published_version("1.2.0", Some(r#"{"modules": {}}"#)),
+        published_version("1.3.0", Some(interface)),
+    ];
+    let current = Version::parse("1.4.0").unwrap();
+    let history = super::versions::VersionHistory::new(&current, &published);
+
+    // Removed in 1.2.0 and added back in 1.3.0
+    assert_eq!(history.value_since("app", "one"), "1.3.0");
+    assert_eq!(history.value_since("app", "two"), "1.4.0");
+    assert_eq!(history.type_since("app", "one"), "1.4.0");
+}
+
+#[test]
+fn no_since_markers_without_any_interface() {
+    let published = [published_version("1.0.0", None)];
+    let current = Version::parse("1.1.0").unwrap();
+    let history = super::versions::VersionHistory::new(&current, &published);
+    assert_eq!(history.value_since("app", "one"), "");
+}
+
 fn create_sample_search_data() -> SearchData {
     SearchData {
         items: vec![
@@ -984,6 +1400,34 @@ pub fn make_dict() -> dict.Dict(a, b) { todo }
     );
 }
 
+#[test]
+fn local_link_to_type_in_path_dependency() {
+    assert_documentation!(
+        local: ("gleam_stdlib", "gleam/dict", "pub type Dict(a, b)"),
+        "main",
+        "
+import gleam/dict
+
+pub fn make_dict() -> dict.Dict(a, b) { todo }
+",
+        ONLY_LINKS
+    );
+}
+
+#[test]
+fn local_link_to_type_in_dependency_from_nested_module() {
+    assert_documentation!(
+        local: ("gleam_stdlib", "gleam/dict", "pub type Dict(a, b)"),
+        "wibble/wobble",
+        "
+import gleam/dict
+
+pub fn make_dict() -> dict.Dict(a, b) { todo }
+",
+        ONLY_LINKS
+    );
+}
+
 #[test]
 fn no_links_to_prelude_types() {
     assert_documentation!(
@@ -1316,6 +1760,7 @@ fn generate_search_data(module_name: &str, module_src: &str) -> EcoString {
         module.name.clone(),
         &module.names,
         &dependencies,
+        DependencyLinks::HexDocs,
     );
 
     let mut search_items = Vec::new();
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use hexpm::version::Version;
+use itertools::Itertools;
+
+use crate::{config::PackageConfig, package_interface::PackageInterfaceSummary};
+
+use super::Link;
+
+/// A version of the package being documented that has been published to Hex.
+#[derive(Debug, Clone)]
+pub struct PublishedVersion {
+    pub version: Version,
+    /// The public interface of the package at that version, if it could be
+    /// retrieved. It's used to tell in which version each type and value was
+    /// introduced.
+    pub interface: Option<PackageInterfaceSummary>,
+}
+
+/// The interfaces of the versions published before the one being documented,
+/// from the most recent to the oldest.
+pub(super) struct VersionHistory<'a> {
+    current: &'a Version,
+    interfaces: Vec<(&'a Version, &'a PackageInterfaceSummary)>,
+}
+
+impl<'a> VersionHistory<'a> {
+    pub fn new(current: &'a Version, published: &'a [PublishedVersion]) -> Self {
+        let interfaces = published
+            .iter()
+            .filter(|published| &published.version < current)
+            .filter_map(|published| Some((&published.version, published.interface.as_ref()?)))
+            .sorted_by(|(one, _), (other, _)| other.cmp(one))
+            .collect_vec();
+        Self {
+            current,
+            interfaces,
+        }
+    }
+
+    /// The version a type was introduced in, or an empty string if it's been
+    /// there since the oldest version we know the interface of.
+    pub fn type_since(&self, module: &str, name: &str) -> String {
+        self.since(|interface| interface.has_type(module, name))
+    }
+
+    /// The version a value was introduced in, or an empty string if it's been
+    /// there since the oldest version we know the interface of.
+    pub fn value_since(&self, module: &str, name: &str) -> String {
+        self.since(|interface| interface.has_value(module, name))
+    }
+
+    fn since(&self, is_defined: impl Fn(&PackageInterfaceSummary) -> bool) -> String {
+        // Going back in time, an item was introduced right after the most
+        // recent version that doesn't have it. An item that was removed and
+        // then added back is only considered from when it was added back.
+        let mut since = self.current;
+        for (version, interface) in &self.interfaces {
+            if !is_defined(interface) {
+                return since.to_string();
+            }
+            since = version;
+        }
+        String::new()
+    }
+}
+
+/// Links to the HexDocs documentation of all the published versions of a
+/// package, from the most recent to the oldest. If there's no published
+/// versions the list is empty and no version selector is shown.
+pub(super) fn version_links(config: &PackageConfig, published: &[PublishedVersion]) -> Vec<Link> {
+    if published.is_empty() {
+        return vec![];
+    }
+
+    let package = config.name.replace("_", "-");
+    published
+        .iter()
+        .map(|published| &published.version)
+        .chain(std::iter::once(&config.version))
+        .sorted_by(|one, other| other.cmp(one))
+        .dedup()
+        .map(|version| Link {
+            name: format!("v{version}"),
+            path: format!("https://{package}.hexdocs.pm/{version}/"),
+        })
+        .collect()
+}
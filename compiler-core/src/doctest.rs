@@ -0,0 +1,353 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! The Gleam code examples found in documentation comments can be checked by
+//! compiling them as part of a test module, one for each documented module.
+//! Examples making assertions are then run by `gleam test --doc`.
+//!
+//! The generated test module keeps each example at the exact same position it
+//! has in the documented module's source: everything else is replaced with
+//! whitespace. This way any error in an example, be it a compile time or a
+//! runtime one, is reported at the doc comment it comes from.
+//!
+//! For example, this documented module:
+//!
+//! ```gleam
+//! /// ```gleam
+//! /// import gleam/list
+//! /// assert list.map([1], double) == [2]
+//! /// ```
+//! pub fn double(x) { x * 2 }
+//! ```
+//!
+//! Becomes a test module looking like this:
+//!
+//! ```gleam
+//! fn e0() {
+//!
+//!     assert list.map([1], double) == [2]
+//! }
+//!
+//!
+//! import wibble.{double}
+//! import gleam/list
+//!
+//! pub fn main() -> Nil {
+//!   e0()
+//!   Nil
+//! }
+//! ```
+//!
+
+#[cfg(test)]
+mod tests;
+
+use ecow::{EcoString, eco_format};
+use itertools::Itertools;
+use src_span::{LineNumbers, SrcSpan};
+
+use crate::{
+    ast::{CustomType, Definition, Function, ModuleConstant, TypeAlias, UntypedModule},
+    build::Target,
+    parse::extra::ModuleExtra,
+};
+
+/// The name of the module running all the examples of a package. The test
+/// modules generated for each documented module are nested inside it.
+pub const RUNNER_MODULE: &str = "gleam_doctest";
+
+/// The name of the function running all the examples of a test module.
+const MAIN_FUNCTION: &str = "main";
+
+/// Returns the name of the test module checking the examples in the
+/// documentation of the given module.
+pub fn module_name(module: &str) -> EcoString {
+    eco_format!("{RUNNER_MODULE}/{module}")
+}
+
+pub fn is_doctest_module(module: &str) -> bool {
+    module
+        .strip_prefix(RUNNER_MODULE)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// A fenced Gleam code block found in a documentation comment.
+#[derive(Debug)]
+struct Example {
+    /// Where the doc comment with the opening fence starts, slashes included.
+    start: u32,
+    /// Where the doc comment with the closing fence starts, slashes included.
+    end: u32,
+    /// The text of each line inside the code block, without the slashes.
+    lines: Vec<SrcSpan>,
+}
+
+/// Generates the source code of the test module checking the examples in the
+/// documentation of the given module. Returns `None` if there's no examples
+/// to check.
+///
+/// Only code blocks marked as `gleam` are checked, so examples that are not
+/// meant to compile can use a different info string, like `gleam ignore`.
+/// Examples defining functions or types are not checked either, as they can
+/// only be written at the top level of a module.
+///
+pub fn module_source(
+    module: &UntypedModule,
+    code: &str,
+    extra: &ModuleExtra,
+    target: Target,
+) -> Option<EcoString> {
+    let mut source = code
+        .bytes()
+        .map(|byte| if byte == b'\n' { byte } else { b' ' })
+        .collect_vec();
+    let mut imports: Vec<EcoString> = vec![];
+    let mut runnable = vec![];
+    let mut checked_any = false;
+
+    for (index, example) in examples(code, extra).into_iter().enumerate() {
+        let Some(body) = example_body(&module.name, code, &example, &mut imports) else {
+            continue;
+        };
+
+        // The function wrapping the example takes the place of the opening
+        // fence, if there's not enough room for it we can't check this one.
+        let header = format!("fn e{index}() {{");
+        let available = code
+            .get(example.start as usize..)
+            .and_then(|rest| rest.split('\n').next())
+            .unwrap_or_default();
+        if header.len() > available.len() {
+            continue;
+        }
+
+        write_at(&mut source, example.start, &header);
+        for line in &body {
+            write_at(&mut source, line.start, slice(code, line));
+        }
+        write_at(&mut source, example.end, "}");
+
+        checked_any = true;
+        if body.iter().any(|line| is_assertion(slice(code, line))) {
+            runnable.push(index);
+        }
+    }
+
+    if !checked_any {
+        return None;
+    }
+
+    let mut source =
+        EcoString::from(String::from_utf8(source).expect("Doctest module source is not UTF-8"));
+    source.push_str("\n\n");
+    source.push_str(&import_module(module, target));
+    for import in imports {
+        source.push('\n');
+        source.push_str(&import);
+    }
+    source.push_str(&eco_format!("\n\npub fn {MAIN_FUNCTION}() -> Nil {{\n"));
+    for index in runnable {
+        source.push_str(&eco_format!("  e{index}()\n"));
+    }
+    source.push_str("  Nil\n}\n");
+
+    Some(source)
+}
+
+/// Generates the source code of the module running the examples of all the
+/// given test modules.
+pub fn runner_source(modules: &[EcoString]) -> EcoString {
+    let mut source =
+        EcoString::from("//// Runs the examples in the documentation of the package.\n\n");
+    for (index, module) in modules.iter().enumerate() {
+        source.push_str(&eco_format!("import {module} as doctest{index}\n"));
+    }
+    source.push_str(&eco_format!("\npub fn {MAIN_FUNCTION}() -> Nil {{\n"));
+    for index in 0..modules.len() {
+        source.push_str(&eco_format!("  doctest{index}.{MAIN_FUNCTION}()\n"));
+    }
+    source.push_str("  Nil\n}\n");
+    source
+}
+
+/// Finds all the fenced Gleam code blocks in the module and doc comments.
+fn examples(code: &str, extra: &ModuleExtra) -> Vec<Example> {
+    let line_numbers = LineNumbers::new(code);
+    let comments = (extra.module_comments.iter().map(|span| ("////", span)))
+        .chain(extra.doc_comments.iter().map(|span| ("///", span)))
+        .sorted_by_key(|(_, span)| span.start);
+
+    let mut examples = vec![];
+    let mut current: Option<Example> = None;
+    let mut previous_line = 0;
+
+    for (slashes, span) in comments {
+        // A code block can't go on past the end of a documentation comment.
+        let line = line_numbers.line_number(span.start);
+        if line != previous_line + 1 {
+            current = None;
+        }
+        previous_line = line;
+
+        let text = slice(code, span).trim();
+        let start = span.start - slashes.len() as u32;
+        current = match current {
+            None if text == "```gleam" => Some(Example {
+                start,
+                end: start,
+                lines: vec![],
+            }),
+            None => None,
+            Some(mut example) if text == "```" => {
+                example.end = start;
+                examples.push(example);
+                None
+            }
+            Some(mut example) => {
+                example.lines.push(*span);
+                Some(example)
+            }
+        };
+    }
+
+    examples
+}
+
+/// Returns the lines of an example to be put inside of a function, after
+/// moving its imports to the given list. Returns `None` if the example is
+/// not something that can be checked.
+fn example_body(
+    module: &str,
+    code: &str,
+    example: &Example,
+    imports: &mut Vec<EcoString>,
+) -> Option<Vec<SrcSpan>> {
+    let mut lines = example.lines.iter().peekable();
+
+    // Imports come first. They could span multiple lines if they have a lot
+    // of unqualified items.
+    while let Some(line) = lines.next_if(|line| {
+        let text = slice(code, line).trim();
+        text.is_empty() || text.starts_with("//") || text.starts_with("import ")
+    }) {
+        let mut import = EcoString::from(slice(code, line).trim());
+        if !import.starts_with("import ") {
+            continue;
+        }
+        while import.contains('{') && !import.contains('}') {
+            let line = lines.next()?;
+            import.push('\n');
+            import.push_str(slice(code, line).trim_end());
+        }
+
+        // The documented module is already imported by the test module.
+        let imported = import
+            .trim_start_matches("import ")
+            .split(['.', ' ', '\n'])
+            .next()
+            .unwrap_or_default();
+        if imported != module && !imports.contains(&import) {
+            imports.push(import);
+        }
+    }
+
+    let body = lines.copied().collect_vec();
+    let mut code_lines = body
+        .iter()
+        .map(|line| slice(code, line).trim())
+        .filter(|text| !text.is_empty() && !text.starts_with("//"))
+        .peekable();
+
+    // Definitions can't be written inside a function.
+    let first = code_lines.peek()?;
+    let is_definition = ["pub ", "fn ", "type ", "const ", "@"]
+        .iter()
+        .any(|keyword| first.starts_with(keyword));
+    if is_definition || code_lines.any(|text| text.starts_with("import ")) {
+        return None;
+    }
+
+    Some(body)
+}
+
+fn is_assertion(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("assert ") || line.starts_with("let assert ")
+}
+
+/// Imports all the public types and values of the documented module, so the
+/// examples can refer to them both qualified and unqualified.
+fn import_module(module: &UntypedModule, target: Target) -> String {
+    let mut items = vec![];
+    for definition in module.iter_definitions(target) {
+        match definition {
+            Definition::Function(Function {
+                name: Some((_, name)),
+                publicity,
+                ..
+            })
+            | Definition::ModuleConstant(ModuleConstant {
+                name, publicity, ..
+            }) if !publicity.is_private() => items.push(name.to_string()),
+
+            Definition::TypeAlias(TypeAlias {
+                alias, publicity, ..
+            }) if !publicity.is_private() => items.push(format!("type {alias}")),
+
+            Definition::CustomType(CustomType {
+                name,
+                publicity,
+                constructors,
+                opaque,
+                ..
+            }) if !publicity.is_private() => {
+                items.push(format!("type {name}"));
+                if !opaque {
+                    items.extend(
+                        constructors
+                            .iter()
+                            .map(|constructor| constructor.name.to_string()),
+                    );
+                }
+            }
+
+            Definition::Function(_)
+            | Definition::ModuleConstant(_)
+            | Definition::TypeAlias(_)
+            | Definition::CustomType(_)
+            | Definition::Import(_) => (),
+        }
+    }
+
+    // The names of the generated functions would clash with imported values
+    // of the same name, those can still be used qualified.
+    let items = items
+        .into_iter()
+        .filter(|item| !is_generated_function_name(item))
+        .join(", ");
+
+    if items.is_empty() {
+        format!("import {}", module.name)
+    } else {
+        format!("import {}.{{{items}}}", module.name)
+    }
+}
+
+fn is_generated_function_name(name: &str) -> bool {
+    name == MAIN_FUNCTION
+        || name
+            .strip_prefix('e')
+            .is_some_and(|index| !index.is_empty() && index.bytes().all(|c| c.is_ascii_digit()))
+}
+
+fn slice<'a>(code: &'a str, span: &SrcSpan) -> &'a str {
+    code.get(span.start as usize..span.end as usize)
+        .unwrap_or_default()
+}
+
+fn write_at(source: &mut [u8], position: u32, text: &str) {
+    let position = position as usize;
+    if let Some(bytes) = source.get_mut(position..position + text.len()) {
+        bytes.copy_from_slice(text.as_bytes());
+    }
+}
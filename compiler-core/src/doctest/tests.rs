@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+
+use crate::{build::Target, warning::WarningEmitter};
+
+fn doctest_source(src: &str) -> Option<EcoString> {
+    let parsed = crate::parse::parse_module(
+        Utf8PathBuf::from("wibble.gleam"),
+        src,
+        &WarningEmitter::null(),
+    )
+    .expect("Module should parse");
+    let mut module = parsed.module;
+    module.name = "wibble".into();
+    super::module_source(&module, src, &parsed.extra, Target::Erlang)
+}
+
+fn doctest_output(src: &str) -> String {
+    let output = doctest_source(src).expect("Module should have examples");
+    format!("----- SOURCE CODE\n{src}\n\n----- DOCTEST MODULE\n{output}")
+}
+
+macro_rules! assert_doctest {
+    ($src:expr $(,)?) => {
+        insta::assert_snapshot!(insta::internals::AutoName, doctest_output($src), $src);
+    };
+}
+
+#[test]
+fn module_name_is_nested_in_runner_module() {
+    assert_eq!(
+        super::module_name("wibble/wobble"),
+        "gleam_doctest/wibble/wobble"
+    );
+    assert!(super::is_doctest_module("gleam_doctest/wibble/wobble"));
+    assert!(super::is_doctest_module("gleam_doctest"));
+    assert!(!super::is_doctest_module("gleam_doctest_wibble"));
+    assert!(!super::is_doctest_module("wibble"));
+}
+
+#[test]
+fn no_examples() {
+    assert_eq!(
+        doctest_source(
+            "/// Doubles a number.
+pub fn double(x) { x * 2 }"
+        ),
+        None
+    );
+}
+
+#[test]
+fn examples_in_other_languages_are_not_checked() {
+    assert_eq!(
+        doctest_source(
+            "/// ```erlang
+/// wibble:double(1).
+/// ```
+///
+/// ```gleam ignore
+/// double(\"not a number\")
+/// ```
+pub fn double(x) { x * 2 }"
+        ),
+        None
+    );
+}
+
+#[test]
+fn examples_with_definitions_are_not_checked() {
+    assert_eq!(
+        doctest_source(
+            "/// ```gleam
+/// pub fn quadruple(x) { double(double(x)) }
+/// ```
+pub fn double(x) { x * 2 }"
+        ),
+        None
+    );
+}
+
+#[test]
+fn unterminated_example_is_not_checked() {
+    assert_eq!(
+        doctest_source(
+            "/// ```gleam
+/// double(1)
+pub fn double(x) { x * 2 }"
+        ),
+        None
+    );
+}
+
+#[test]
+fn function_example() {
+    assert_doctest!(
+        "/// Doubles a number.
+///
+/// ```gleam
+/// assert double(2) == 4
+/// ```
+pub fn double(x) { x * 2 }
+
+fn private() { Nil }"
+    );
+}
+
+#[test]
+fn example_without_assertion_is_only_type_checked() {
+    assert_doctest!(
+        "/// ```gleam
+/// double(2)
+/// ```
+pub fn double(x) { x * 2 }"
+    );
+}
+
+#[test]
+fn module_comment_example() {
+    assert_doctest!(
+        "//// ```gleam
+//// let assert Ok(x) = parse(\"1\")
+//// ```
+
+pub fn parse(x) { Ok(x) }"
+    );
+}
+
+#[test]
+fn example_imports_are_moved_to_the_top_level() {
+    assert_doctest!(
+        "/// ```gleam
+/// import gleam/list
+/// import gleam/option.{
+///   type Option, Some,
+/// }
+/// import wibble
+///
+/// assert list.map([1], wibble.double) == [2]
+/// ```
+///
+/// ```gleam
+/// import gleam/list
+/// assert list.length([]) == 0
+/// ```
+pub fn double(x) { x * 2 }"
+    );
+}
+
+#[test]
+fn types_and_constructors_are_imported() {
+    assert_doctest!(
+        "/// ```gleam
+/// let wobble: Wobble = Wibble(1)
+/// ```
+pub type Wobble { Wibble(Int) Wabble }
+
+pub opaque type Secret { Secret }
+
+pub type Alias = Wobble
+
+pub const answer = 42
+
+@target(javascript)
+pub fn javascript_only() { Nil }
+
+pub fn main() { Nil }"
+    );
+}
+
+#[test]
+fn runner_module() {
+    insta::assert_snapshot!(super::runner_source(&[
+        "gleam_doctest/wibble".into(),
+        "gleam_doctest/wibble/wobble".into(),
+    ]));
+}
@@ -20,7 +20,8 @@ use crate::{
 use camino::Utf8Path;
 use ecow::{EcoString, eco_format};
 use erlang_generation::{
-    BitArraySegmentSpecifier, DocContent, ErlangBuilder, ErlangModuleName, ErlangSourceBuilder,
+    BitArraySegmentSpecifier, DocContent, ErlangAbstractFormatBuilder, ErlangBuilder,
+    ErlangCoreBuilder, ErlangModuleName, ErlangSourceBuilder,
 };
 use itertools::Itertools;
 use num_bigint::BigInt;
@@ -3584,10 +3585,44 @@ pub fn module<'a>(
     line_numbers: LineNumbers,
     root: &'a Utf8Path,
 ) -> String {
+    module_with_builder::<ErlangSourceBuilder, _>(module, line_numbers, root)
+}
+
+/// Generates the Erlang Abstract Format representation of a module, encoded
+/// using the Erlang Term Format. This can be compiled directly to BEAM bytecode
+/// with `compile:forms/2`, without having to go through Erlang source code.
+pub fn module_forms<'a>(
+    module: &'a TypedModule,
+    line_numbers: LineNumbers,
+    root: &'a Utf8Path,
+) -> Vec<u8> {
+    module_with_builder::<ErlangAbstractFormatBuilder, _>(module, line_numbers, root)
+}
+
+/// Generates the Core Erlang representation of a module. This is not used to
+/// compile the module, but it's what static analysis tools like Dialyzer work
+/// with.
+pub fn module_core<'a>(
+    module: &'a TypedModule,
+    line_numbers: LineNumbers,
+    root: &'a Utf8Path,
+) -> String {
+    module_with_builder::<ErlangCoreBuilder, _>(module, line_numbers, root)
+}
+
+fn module_with_builder<'a, Builder, Output>(
+    module: &'a TypedModule,
+    line_numbers: LineNumbers,
+    root: &'a Utf8Path,
+) -> Output
+where
+    Builder: ErlangBuilder<Output>,
+{
     let mut generator = Generator::new(module, &line_numbers, root);
 
     let module_name = ErlangModuleName::new(&module.name);
-    let mut builder = ErlangSourceBuilder::new(Some(module_name));
+    let mut builder = Builder::new(Some(module_name));
+    builder.source_line_numbers(line_numbers.clone());
     generator.module_document(&mut builder);
     builder.into_output()
 }
@@ -3965,6 +4000,7 @@ fn variable_name(name: &str) -> EcoString {
 /// Erlang.
 /// This function converts a usize into base 26 A-Z for this purpose.
 fn id_to_type_var_str(id: u64) -> EcoString {
+    let id = crate::uid::offset_in_range(id);
     if id < 26 {
         let mut name = EcoString::from("");
         name.push(char::from_u32((id % 26 + 65) as u32).expect("id_to_type_var 0"));
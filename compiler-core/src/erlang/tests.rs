@@ -26,6 +26,7 @@ mod binops;
 mod bit_arrays;
 mod case;
 mod conditional_compilation;
+mod constant_folding;
 mod consts;
 mod custom_types;
 mod documentation;
@@ -53,6 +54,23 @@ pub fn compile_test_project(
     src: &str,
     src_path: &str,
     dependencies: Vec<(&str, &str, &str)>,
+) -> String {
+    compile_test_project_with_options(src, src_path, dependencies, false)
+}
+
+pub fn compile_test_project_with_constant_folding(
+    src: &str,
+    src_path: &str,
+    dependencies: Vec<(&str, &str, &str)>,
+) -> String {
+    compile_test_project_with_options(src, src_path, dependencies, true)
+}
+
+fn compile_test_project_with_options(
+    src: &str,
+    src_path: &str,
+    dependencies: Vec<(&str, &str, &str)>,
+    fold_constants: bool,
 ) -> String {
     let mut modules = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
@@ -117,6 +135,11 @@ pub fn compile_test_project(
     .expect("should successfully infer root Erlang");
 
     let ast = inline::module(ast, &modules);
+    let ast = if fold_constants {
+        crate::constant_folding::module(ast)
+    } else {
+        ast
+    };
 
     // After building everything we still need to attach the module comments, to
     // do that we're reusing the `attach_doc_and_module_comments` that's used
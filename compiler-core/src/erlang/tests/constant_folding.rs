@@ -0,0 +1,134 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+macro_rules! assert_folded_erl {
+    ($src:expr $(,)?) => {{
+        let compiled = $crate::erlang::tests::compile_test_project_with_constant_folding(
+            $src,
+            "/root/project/test/my/mod.gleam",
+            Vec::new(),
+        );
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED ERLANG\n{}",
+            $src, compiled,
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+}
+
+#[test]
+fn int_operators_are_folded() {
+    assert_folded_erl!(
+        "
+pub fn main() {
+  #(1 + 2 * 3 - 4 / 2, 7 % 3, -5 / 2, 10 / 0, 10 % 0)
+}
+"
+    );
+}
+
+#[test]
+fn ints_outside_of_the_safe_range_are_not_folded() {
+    assert_folded_erl!(
+        "
+pub fn main() {
+  9_007_199_254_740_991 + 1
+}
+"
+    );
+}
+
+#[test]
+fn float_operators_are_folded() {
+    assert_folded_erl!(
+        "
+pub fn main() {
+  #(1.5 *. 2.0 +. 0.25, 1.0 /. 0.0, 1.0 <. 2.0)
+}
+"
+    );
+}
+
+#[test]
+fn bool_operators_are_folded() {
+    assert_folded_erl!(
+        "
+pub fn main(x) {
+  #(True && x, x && True, False || x, x || False, !True, 1 == 1, \"a\" != \"a\")
+}
+"
+    );
+}
+
+#[test]
+fn bool_operators_with_a_side_effect_on_the_left_are_not_folded() {
+    assert_folded_erl!(
+        "
+pub fn main() {
+  #(wibble() && False, wibble() || True)
+}
+
+fn wibble() {
+  True
+}
+"
+    );
+}
+
+#[test]
+fn case_on_a_literal_is_replaced_with_the_matching_clause() {
+    assert_folded_erl!(
+        r#"
+pub fn main() {
+  case 1 + 1 {
+    1 -> "one"
+    2 -> "two"
+    _ -> "many"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_a_known_constructor_binds_its_fields() {
+    assert_folded_erl!(
+        "
+pub fn main(x) {
+  case Ok(#(x, 1)) {
+    Ok(#(a, b)) -> a + b
+    Error(_) -> 0
+  }
+}
+"
+    );
+}
+
+#[test]
+fn unreachable_clauses_are_dropped_when_some_subjects_are_known() {
+    assert_folded_erl!(
+        "
+pub fn main(x) {
+  case True, x {
+    False, _ -> 0
+    True, 1 -> 1
+    _, _ -> 2
+  }
+}
+"
+    );
+}
+
+#[test]
+fn case_with_a_guard_is_not_folded() {
+    assert_folded_erl!(
+        "
+pub fn main() {
+  case 1 {
+    x if x > 1 -> x
+    _ -> 0
+  }
+}
+"
+    );
+}
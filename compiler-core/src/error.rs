@@ -86,6 +86,18 @@ pub struct FailedModule {
     pub names: Box<Names>,
 }
 
+/// A module with syntax errors. It's still type checked, but only its syntax
+/// errors are reported.
+#[derive(Debug, Eq, PartialEq, Error, Clone)]
+#[error("failed to parse Gleam source code")]
+pub struct UnparsedModule {
+    pub path: Utf8PathBuf,
+    pub src: EcoString,
+    /// All the syntax errors found in the module, in the order they appear in
+    /// the source code.
+    pub errors: Vec1<crate::parse::error::ParseError>,
+}
+
 #[derive(Debug, Eq, PartialEq, Error, Clone)]
 #[error("module couldn't be analysed")]
 pub struct SkippedModule {
@@ -131,7 +143,9 @@ pub enum Error {
     Parse {
         path: Utf8PathBuf,
         src: EcoString,
-        error: Box<crate::parse::error::ParseError>,
+        /// All the syntax errors found in the module, in the order they appear
+        /// in the source code.
+        errors: Vec1<crate::parse::error::ParseError>,
     },
 
     #[error("type checking failed")]
@@ -142,6 +156,8 @@ pub enum Error {
         /// All the modules that were analysed and had an error.
         /// This maps from module name to information about the module.
         failed_modules: HashMap<EcoString, FailedModule>,
+        /// All the modules that have syntax errors.
+        unparsed_modules: Vec<UnparsedModule>,
     },
 
     #[error("unknown import {import}")]
@@ -252,16 +268,31 @@ pub enum Error {
     },
 
     #[error("{module} does not have a main function")]
-    ModuleDoesNotHaveMainFunction { module: EcoString, origin: Origin },
+    ModuleDoesNotHaveMainFunction {
+        module: EcoString,
+        function: EcoString,
+        origin: Origin,
+    },
 
     #[error("{module} does not have a public main function")]
-    MainFunctionIsPrivate { module: EcoString },
+    MainFunctionIsPrivate {
+        module: EcoString,
+        function: EcoString,
+    },
 
     #[error("{module}'s main function has the wrong arity so it can not be run")]
-    MainFunctionHasWrongArity { module: EcoString, arity: usize },
+    MainFunctionHasWrongArity {
+        module: EcoString,
+        function: EcoString,
+        arity: usize,
+    },
 
     #[error("{module}'s main function does not support the current target")]
-    MainFunctionDoesNotSupportTarget { module: EcoString, target: Target },
+    MainFunctionDoesNotSupportTarget {
+        module: EcoString,
+        function: EcoString,
+        target: Target,
+    },
 
     #[error("{input} is not a valid version. {error}")]
     InvalidVersionFormat { input: String, error: String },
@@ -291,12 +322,24 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
     #[error("warnings are not permitted")]
     ForbiddenWarnings { count: usize },
 
+    #[error("denied warnings were emitted")]
+    DeniedWarnings { count: usize },
+
+    #[error("lints were found")]
+    LintsFound { count: usize },
+
     #[error("Invalid runtime for target {target:?}: {invalid_runtime:?}")]
     InvalidRuntime {
         target: Target,
         invalid_runtime: Runtime,
     },
 
+    #[error("Coverage is not supported by runtime {runtime:?}")]
+    CoverageNotSupported { runtime: Runtime },
+
+    #[error("Coverage is not supported on target {target:?}")]
+    CoverageNotSupportedOnTarget { target: Target },
+
     #[error("package downloading failed: {error}")]
     DownloadPackageError {
         package_name: String,
@@ -375,6 +418,22 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
     #[error("The --javascript-prelude flag must be given when compiling to JavaScript")]
     JavaScriptPreludeRequired,
 
+    #[error("{feature} cannot be compiled to WebAssembly yet")]
+    UnsupportedWasmFeature {
+        path: Utf8PathBuf,
+        src: EcoString,
+        location: SrcSpan,
+        feature: EcoString,
+    },
+
+    #[error("The ES module {module} cannot be used by a global script")]
+    EsModuleFfiInGlobalScript {
+        path: Utf8PathBuf,
+        src: EcoString,
+        location: SrcSpan,
+        module: EcoString,
+    },
+
     #[error("The modules {unfinished:?} contain todo expressions and so cannot be published")]
     CannotPublishTodo { unfinished: Vec<EcoString> },
 
@@ -1229,35 +1288,43 @@ forward slash and must not end with a slash."
                 }]
             }
 
-            Error::ModuleDoesNotHaveMainFunction { module, origin } => vec![Diagnostic {
-                title: "Module does not have a main function".into(),
+            Error::ModuleDoesNotHaveMainFunction {
+                module,
+                function,
+                origin,
+            } => vec![Diagnostic {
+                title: format!("Module does not have a {function} function"),
                 text: wrap_format!(
-                    "`{module}` does not have a main function so the module can not be run."
+                    "`{module}` does not have a {function} function so the module can not be run."
                 ),
                 level: Level::Error,
                 location: None,
                 hint: Some(format!(
-                    "Add a public `main` function to `{}/{module}.gleam`.",
+                    "Add a public `{function}` function to `{}/{module}.gleam`.",
                     origin.folder_name()
                 )),
             }],
 
-            Error::MainFunctionIsPrivate { module } => vec![Diagnostic {
-                title: "Module does not have a public main function".into(),
+            Error::MainFunctionIsPrivate { module, function } => vec![Diagnostic {
+                title: format!("Module does not have a public {function} function"),
                 text: wrap_format!(
-                    "`{module}` has a main function, but it is private, so it cannot be run."
+                    "`{module}` has a {function} function, but it is private, so it cannot be run."
                 ),
                 level: Level::Error,
                 location: None,
                 hint: Some(wrap_format!(
-                    "Make the `main` function in the `{module}` module public."
+                    "Make the `{function}` function in the `{module}` module public."
                 )),
             }],
 
-            Error::MainFunctionDoesNotSupportTarget { module, target } => vec![Diagnostic {
+            Error::MainFunctionDoesNotSupportTarget {
+                module,
+                function,
+                target,
+            } => vec![Diagnostic {
                 title: "Target not supported".into(),
                 text: wrap_format!(
-                    "`{module}` has a main function, but it does not support the {target} \
+                    "`{module}` has a {function} function, but it does not support the {target} \
 target, so it cannot be run.",
                     target = target.as_presentable_str(),
                 ),
@@ -1266,16 +1333,20 @@ target, so it cannot be run.",
                 hint: None,
             }],
 
-            Error::MainFunctionHasWrongArity { module, arity } => vec![Diagnostic {
+            Error::MainFunctionHasWrongArity {
+                module,
+                function,
+                arity,
+            } => vec![Diagnostic {
                 title: "Main function has wrong arity".into(),
                 text: wrap_format!(
-                    "`{module}.main` should take no arguments, but it takes {arity}."
+                    "`{module}.{function}` should take no arguments, but it takes {arity}."
                 ),
                 level: Level::Error,
                 location: None,
-                hint: Some(
-                    "Change the function signature of main to `pub fn main() -> Nil`.".into(),
-                ),
+                hint: Some(format!(
+                    "Change the function signature of {function} to `pub fn {function}() -> Nil`."
+                )),
             }],
 
             Error::ProjectRootAlreadyExist { path } => vec![Diagnostic {
@@ -1960,46 +2031,28 @@ with `gleam hex authenticate`."
             Error::Type {
                 skipped_modules: _,
                 failed_modules,
-            } => failed_modules
-                .values()
-                .sorted_by_key(|failed_module| &failed_module.path)
-                .flat_map(failed_module_diagnostics)
-                .collect_vec(),
-
-            Error::Parse { path, src, error } => {
-                let location = if error.error == ParseErrorType::UnexpectedEof {
-                    SrcSpan {
-                        start: (src.len() - 1) as u32,
-                        end: (src.len() - 1) as u32,
-                    }
-                } else {
-                    error.location
-                };
-
-                let title = String::from("Syntax error");
-                let ParseErrorDetails {
-                    text,
-                    label_text,
-                    extra_labels,
-                    hint,
-                } = error.error.details();
-                vec![Diagnostic {
-                    title,
-                    text,
-                    level: Level::Error,
-                    location: Some(Location {
-                        src: src.clone(),
-                        path: path.clone(),
-                        label: Label {
-                            text: Some(label_text.into()),
-                            span: location,
-                        },
-                        extra_labels,
-                    }),
-                    hint,
-                }]
+                unparsed_modules,
+            } => {
+                let unparsed_modules = unparsed_modules.iter().map(|module| {
+                    let diagnostics =
+                        parse_error_diagnostics(&module.path, &module.src, &module.errors);
+                    (&module.path, diagnostics)
+                });
+                let failed_modules = failed_modules.values().map(|module| {
+                    (
+                        &module.path,
+                        failed_module_diagnostics(module).collect_vec(),
+                    )
+                });
+                unparsed_modules
+                    .chain(failed_modules)
+                    .sorted_by(|(one, _), (other, _)| one.cmp(other))
+                    .flat_map(|(_, diagnostics)| diagnostics)
+                    .collect_vec()
             }
 
+            Error::Parse { path, src, errors } => parse_error_diagnostics(path, src, errors),
+
             Error::ImportCycle { modules } => {
                 let first_location = &modules.first().1;
                 let rest_locations = modules
@@ -2127,6 +2180,38 @@ please break the cycle to continue.",
                 }]
             }
 
+            Error::DeniedWarnings { count } => {
+                let word_warning = match count {
+                    1 => "warning",
+                    _ => "warnings",
+                };
+                let text = "Your project's gleam.toml denies some kinds of warnings.
+Fix the warnings, or change their level in the `[warnings]` section of
+gleam.toml, and try again."
+                    .into();
+                vec![Diagnostic {
+                    title: format!("{count} denied {word_warning} generated."),
+                    text,
+                    hint: None,
+                    location: None,
+                    level: Level::Error,
+                }]
+            }
+
+            Error::LintsFound { count } => {
+                let word_problem = match count {
+                    1 => "problem",
+                    _ => "problems",
+                };
+                vec![Diagnostic {
+                    title: format!("{count} lint {word_problem} found."),
+                    text: "Fix the problems reported above and try again.".into(),
+                    hint: None,
+                    location: None,
+                    level: Level::Error,
+                }]
+            }
+
             Error::ForbiddenWarnings { count } => {
                 let word_warning = match count {
                     1 => "warning",
@@ -2438,6 +2523,10 @@ satisfying {required_version} but you are using v{gleam_version}.",
                         "You can not set a runtime for Erlang. Did you mean to target JavaScript?"
                             .into(),
                     ),
+                    Target::Wasm => Some(
+                        "You can not set a runtime for WebAssembly. Did you mean to target JavaScript?"
+                            .into(),
+                    ),
                 };
 
                 vec![Diagnostic {
@@ -2452,6 +2541,40 @@ satisfying {required_version} but you are using v{gleam_version}.",
                 }]
             }
 
+            Error::CoverageNotSupported { runtime } => {
+                let text = format!(
+                    "Code coverage can't be collected when running with {runtime}.",
+                    runtime = runtime.as_presentable_str(),
+                );
+                vec![Diagnostic {
+                    title: "Coverage not supported".into(),
+                    text,
+                    hint: Some(
+                        "Run the tests with NodeJS, or on the Erlang target, to collect their coverage."
+                            .into(),
+                    ),
+                    location: None,
+                    level: Level::Error,
+                }]
+            }
+
+            Error::CoverageNotSupportedOnTarget { target } => {
+                let text = format!(
+                    "Code coverage can't be collected when targeting {target}.",
+                    target = target.as_presentable_str(),
+                );
+                vec![Diagnostic {
+                    title: "Coverage not supported".into(),
+                    text,
+                    hint: Some(
+                        "Run the tests on the Erlang or JavaScript target to collect their coverage."
+                            .into(),
+                    ),
+                    location: None,
+                    level: Level::Error,
+                }]
+            }
+
             Error::JavaScriptPreludeRequired => vec![Diagnostic {
                 title: "JavaScript prelude required".into(),
                 text: "The --javascript-prelude flag must be given when compiling to JavaScript."
@@ -2460,6 +2583,56 @@ satisfying {required_version} but you are using v{gleam_version}.",
                 location: None,
                 hint: None,
             }],
+            Error::UnsupportedWasmFeature {
+                path,
+                src,
+                location,
+                feature,
+            } => vec![Diagnostic {
+                title: "Unsupported feature for compilation target".into(),
+                text: wrap_format!(
+                    "This uses {feature}, which the WebAssembly target doesn't support yet."
+                ),
+                hint: Some(wrap("Did you mean to build for a different target?")),
+                level: Level::Error,
+                location: Some(Location {
+                    path: path.clone(),
+                    src: src.clone(),
+                    label: Label {
+                        text: None,
+                        span: *location,
+                    },
+                    extra_labels: vec![],
+                }),
+            }],
+
+            Error::EsModuleFfiInGlobalScript {
+                path,
+                src,
+                location,
+                module,
+            } => vec![Diagnostic {
+                title: "Unsupported FFI module".into(),
+                text: wrap_format!(
+                    "This function is implemented in `{module}`, which is an ES module. \
+The global module format generates plain scripts, which can't import ES modules."
+                ),
+                hint: Some(wrap(
+                    "Implement it in a `.js` script that registers its values on \
+`globalThis.Gleam` instead.",
+                )),
+                level: Level::Error,
+                location: Some(Location {
+                    path: path.clone(),
+                    src: src.clone(),
+                    label: Label {
+                        text: None,
+                        span: *location,
+                    },
+                    extra_labels: vec![],
+                }),
+            }],
+
             Error::CorruptManifest => vec![Diagnostic {
                 title: "Corrupt manifest.toml".into(),
                 text: "The `manifest.toml` file is corrupt.".into(),
@@ -2527,6 +2700,49 @@ add `gleam add {name}` in this project."
     }
 }
 
+fn parse_error_diagnostics(
+    path: &Utf8PathBuf,
+    src: &EcoString,
+    errors: &Vec1<crate::parse::error::ParseError>,
+) -> Vec<Diagnostic> {
+    errors
+        .iter()
+        .map(|error| {
+            let location = if error.error == ParseErrorType::UnexpectedEof {
+                SrcSpan {
+                    start: (src.len() - 1) as u32,
+                    end: (src.len() - 1) as u32,
+                }
+            } else {
+                error.location
+            };
+
+            let title = String::from("Syntax error");
+            let ParseErrorDetails {
+                text,
+                label_text,
+                extra_labels,
+                hint,
+            } = error.error.details();
+            Diagnostic {
+                title,
+                text,
+                level: Level::Error,
+                location: Some(Location {
+                    src: src.clone(),
+                    path: path.clone(),
+                    label: Label {
+                        text: Some(label_text.into()),
+                        span: location,
+                    },
+                    extra_labels,
+                }),
+                hint,
+            }
+        })
+        .collect_vec()
+}
+
 fn failed_module_diagnostics(failed_module: &FailedModule) -> impl Iterator<Item = Diagnostic> {
     use crate::type_::Error as TypeError;
     let FailedModule {
@@ -4466,10 +4682,7 @@ The missing patterns are:\n",
             let text = wrap_format!(
                 "This value is not available as it is defined using externals, \
 and there is no implementation for the {} target.",
-                match current_target {
-                    Target::Erlang => "Erlang",
-                    Target::JavaScript => "JavaScript",
-                }
+                current_target.as_presentable_str()
             );
             let hint = wrap("Did you mean to build for a different target?");
             Diagnostic {
@@ -4494,10 +4707,7 @@ and there is no implementation for the {} target.",
             name,
             target,
         } => {
-            let target = match target {
-                Target::Erlang => "Erlang",
-                Target::JavaScript => "JavaScript",
-            };
+            let target = target.as_presentable_str();
             let text = wrap_format!(
                 "The `{name}` function is public but doesn't have an \
 implementation for the {target} target. All public functions of a package \
@@ -5067,6 +5277,30 @@ See: https://tour.gleam.run/basics/bools/"
             }),
         },
 
+        TypeError::TypedHoleNotEnabled { location } => Diagnostic {
+            title: "Typed holes are not enabled".to_string(),
+            text: wrap(
+                "A `_` can only be used in place of an expression when typed \
+holes are enabled. The compiler then reports the type the hole needs to have, \
+along with the values that could be used to fill it.",
+            ),
+            hint: Some(
+                "Enable typed holes by setting `typed_hole` to \"warn\" in the \
+`[warnings]` section of gleam.toml."
+                    .into(),
+            ),
+            level: Level::Error,
+            location: Some(Location {
+                label: Label {
+                    text: Some("This is a typed hole".into()),
+                    span: *location,
+                },
+                path: path.clone(),
+                src: src.clone(),
+                extra_labels: vec![],
+            }),
+        },
+
         TypeError::TodoConstant { location } => Diagnostic {
             title: "Constant todo found".to_string(),
             text: wrap(
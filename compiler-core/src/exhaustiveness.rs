@@ -68,6 +68,9 @@
 
 mod missing_patterns;
 pub mod printer;
+mod refinement;
+
+pub use refinement::IntRange;
 
 use crate::{
     ast::{
@@ -81,7 +84,7 @@ use crate::{
     },
     type_::{
         Environment, Opaque, Type, TypeValueConstructor, TypeValueConstructorField, TypeVar,
-        TypeVariantConstructors, collapse_links, error::UnreachablePatternReason,
+        TypeVarCell, TypeVariantConstructors, collapse_links, error::UnreachablePatternReason,
         is_prelude_module, string,
     },
 };
@@ -92,9 +95,9 @@ use itertools::Itertools;
 use num_bigint::{BigInt, Sign};
 use num_traits::ToPrimitive;
 use radix_trie::{Trie, TrieCommon};
+use refinement::{Fact, FactSubject, Facts, GuardCondition, GuardVariable, Refinement};
 use src_span::SrcSpan;
 use std::{
-    cell::RefCell,
     cmp::Ordering,
     collections::{HashMap, HashSet, VecDeque},
     hash::Hash,
@@ -143,10 +146,14 @@ struct Branch {
 
 /// A branch's guard, holding the index of the clause it belongs to and whether
 /// it references variables bound by the branch's patterns.
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 struct BranchGuard {
     clause_index: usize,
     uses_pattern_bindings: bool,
+    /// The condition checked by the guard, if it only depends on the value of
+    /// a single variable. This is used to tell if the guard is always (or
+    /// never) going to be true.
+    condition: Option<GuardCondition>,
 }
 
 impl Branch {
@@ -2168,6 +2175,12 @@ impl Decision {
     }
 }
 
+/// The maximum number of values of an `Int` with a known range that are
+/// reported one by one as missing patterns. If more than these are missing,
+/// the case expression will need a catch all pattern.
+///
+const MAX_REPORTED_MISSING_INT_VALUES: usize = 16;
+
 /// The `case` compiler itself (shocking, I know).
 ///
 #[derive(Debug)]
@@ -2176,6 +2189,9 @@ struct Compiler<'a> {
     patterns: Arena<Pattern>,
     variable_id: usize,
     diagnostics: Diagnostics,
+    /// What we know about the values of variables in the branch of the
+    /// decision tree that is currently being compiled.
+    facts: Facts,
 }
 
 /// The result of compiling a pattern match expression.
@@ -2200,6 +2216,18 @@ impl CompileCaseResult {
             .contains(&(clause, pattern_index))
         {
             Reachability::Unreachable(UnreachablePatternReason::ImpossibleVariant)
+        } else if self
+            .diagnostics
+            .match_impossible_values
+            .contains(&(clause, pattern_index))
+        {
+            Reachability::Unreachable(UnreachablePatternReason::ImpossibleValue)
+        } else if self
+            .diagnostics
+            .never_true_guards
+            .contains(&(clause, pattern_index))
+        {
+            Reachability::Unreachable(UnreachablePatternReason::GuardNeverTrue)
         } else if let Some(segments) = self
             .diagnostics
             .match_impossible_segments
@@ -2292,6 +2320,21 @@ pub struct Diagnostics {
     /// for sure will never match.
     ///
     pub match_impossible_segments: HashMap<(usize, usize), Vec<ImpossibleBitArraySegmentPattern>>,
+
+    /// Patterns matching on an `Int` value the compiler can tell the matched
+    /// variable will never have. For example `3` in `case x % 3 { ... }`.
+    ///
+    pub match_impossible_values: HashSet<(usize, usize)>,
+
+    /// Patterns whose guard the compiler can tell is never going to be true
+    /// when they're checked.
+    ///
+    pub never_true_guards: HashSet<(usize, usize)>,
+
+    /// The ids of the `Int` variables the compiler knows can only have a few
+    /// possible values, all of which are checked by the decision tree.
+    ///
+    pub finite_int_variables: HashSet<usize>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -2323,7 +2366,11 @@ impl<'a> Compiler<'a> {
                 reachable: HashSet::new(),
                 match_impossible_variants: HashSet::new(),
                 match_impossible_segments: HashMap::new(),
+                match_impossible_values: HashSet::new(),
+                never_true_guards: HashSet::new(),
+                finite_int_variables: HashSet::new(),
             },
+            facts: Facts::default(),
         }
     }
 
@@ -2360,6 +2407,25 @@ impl<'a> Compiler<'a> {
             .insert((branch.clause_index, branch.alternative_index));
     }
 
+    fn mark_as_matching_impossible_value(&mut self, branch: &Branch) {
+        // Unlike impossible variants, this only depends on what we know at
+        // this point of the decision tree: the branch might still be reached
+        // by a different path, in which case it's not reported.
+        let _ = self
+            .diagnostics
+            .match_impossible_values
+            .insert((branch.clause_index, branch.alternative_index));
+    }
+
+    fn mark_as_having_a_never_true_guard(&mut self, branch: &Branch) {
+        // As with impossible values, the branch might still be reached by a
+        // different path of the tree.
+        let _ = self
+            .diagnostics
+            .never_true_guards
+            .insert((branch.clause_index, branch.alternative_index));
+    }
+
     fn mark_as_matching_impossible_segment(
         &mut self,
         branch: &Branch,
@@ -2380,6 +2446,16 @@ impl<'a> Compiler<'a> {
             .iter_mut()
             .for_each(|branch| branch.move_unconditional_patterns(self));
 
+        // A branch with no checks left whose guard we know is never going to
+        // be true can never run, so we can skip over it entirely.
+        while let Some(first_branch) = branches.front()
+            && first_branch.checks.is_empty()
+            && self.guard_outcome(first_branch) == Some(false)
+        {
+            self.mark_as_having_a_never_true_guard(first_branch);
+            let _ = branches.pop_front();
+        }
+
         let Some(first_branch) = branches.front() else {
             // If there's no branches, that means we have a pattern that is not
             // exhaustive as there's nothing that could match!
@@ -2400,30 +2476,97 @@ impl<'a> Compiler<'a> {
             // its variable patterns as assignments into the body and there's no
             // additional checks remaining. So the only thing left that could result
             // in the match failing is the additional guard.
-            None => match first_branch.guard {
+            None => match &first_branch.guard {
                 // If there's no guard we're in the following situation:
                 // `∅ -> body`. It means that this branch will always match no
                 // matter what, all the remaining branches are just discarded and
                 // we can produce a terminating node to run the body
                 // unconditionally.
                 None => Decision::run(first_branch.body.clone()),
+                // The same goes if there's a guard we know is always going to be
+                // true at this point.
+                Some(_) if self.guard_outcome(first_branch) == Some(true) => {
+                    Decision::run(first_branch.body.clone())
+                }
                 // If we have a guard we're in this scenario:
                 // `∅ if condition -> body`. We can produce a `Guard` node:
                 // if the condition evaluates to `True` we can run its body.
                 // Otherwise, we'll have to keep looking at the remaining branches
                 // to know what to do if this branch doesn't match.
                 Some(BranchGuard { clause_index, .. }) => {
+                    let clause_index = *clause_index;
                     let if_true = first_branch.body.clone();
                     // All the remaining branches will be compiled and end up
-                    // in the path of the tree to choose if the guard is false.
+                    // in the path of the tree to choose if the guard is false,
+                    // where we know the guard's condition doesn't hold.
+                    let fact = self.negated_guard_fact(first_branch);
                     let _ = branches.pop_front();
-                    let if_false = self.compile(branches);
+                    let if_false = self.compile_with_fact(fact, branches);
                     Decision::guard(clause_index, if_true, if_false)
                 }
             },
         }
     }
 
+    /// Compiles the given branches knowing that the given fact holds for all
+    /// of them.
+    ///
+    fn compile_with_fact(&mut self, fact: Option<Fact>, branches: VecDeque<Branch>) -> Decision {
+        let Some(fact) = fact else {
+            return self.compile(branches);
+        };
+        self.facts.push(fact);
+        let decision = self.compile(branches);
+        self.facts.pop();
+        decision
+    }
+
+    /// The value a variable referenced by a branch's guard refers to. That's
+    /// either a variable of the decision tree, if it's bound by the branch's
+    /// patterns, or a variable defined outside of the case expression.
+    ///
+    fn fact_subject(&self, branch: &Branch, variable: &GuardVariable) -> Option<FactSubject> {
+        let bound_value = branch
+            .body
+            .bindings
+            .iter()
+            .rev()
+            .find(|(name, _)| *name == variable.name)
+            .map(|(_, value)| value);
+
+        match bound_value {
+            None => Some(FactSubject::Outer(variable.definition_location)),
+            Some(BoundValue::Variable(bound)) => Some(FactSubject::Variable(bound.id)),
+            Some(
+                BoundValue::LiteralString(_)
+                | BoundValue::LiteralInt(_)
+                | BoundValue::LiteralFloat(_)
+                | BoundValue::BitArraySlice { .. }
+                | BoundValue::StringSlice { .. },
+            ) => None,
+        }
+    }
+
+    /// Whether a branch's guard is always (or never) going to be true given
+    /// what we know at this point of the decision tree. Returns `None` if it
+    /// could be either, or if the branch has no guard.
+    ///
+    fn guard_outcome(&self, branch: &Branch) -> Option<bool> {
+        let condition = branch.guard.as_ref()?.condition.as_ref()?;
+        let subject = self.fact_subject(branch, &condition.variable)?;
+        self.facts.outcome(&subject, &condition.refinement)
+    }
+
+    /// What we know to be true if a branch's guard turns out to be false.
+    ///
+    fn negated_guard_fact(&self, branch: &Branch) -> Option<Fact> {
+        let condition = branch.guard.as_ref()?.condition.as_ref()?.negate()?;
+        Some(Fact {
+            subject: self.fact_subject(branch, &condition.variable)?,
+            refinement: condition.refinement,
+        })
+    }
+
     fn split_and_compile_with_pivot_var(
         &mut self,
         pivot_var: Variable,
@@ -2505,6 +2648,8 @@ impl<'a> Compiler<'a> {
         pivot_var: Variable,
         branch_mode: &BranchMode,
     ) {
+        let known_range = self.facts.int_range(&FactSubject::Variable(pivot_var.id));
+
         for mut branch in branches {
             let Some(pattern_check) = branch.pop_check_on_var(&pivot_var) else {
                 // If the branch doesn't perform any check on the pivot variable, it means
@@ -2517,7 +2662,14 @@ impl<'a> Compiler<'a> {
 
             let checked_pattern = self.pattern(pattern_check.pattern);
 
-            if checked_pattern.is_matching_on_unreachable_variant(branch_mode) {
+            if let Pattern::Int { int_value } = checked_pattern
+                && known_range
+                    .as_ref()
+                    .is_some_and(|range| !range.contains(int_value))
+            {
+                self.mark_as_matching_impossible_value(&branch);
+                continue;
+            } else if checked_pattern.is_matching_on_unreachable_variant(branch_mode) {
                 self.mark_as_matching_impossible_variant(&branch);
                 continue;
             } else if let Some(unreachable_segments) =
@@ -2536,6 +2688,10 @@ impl<'a> Compiler<'a> {
     /// branch.
     ///
     fn splitter_to_switch(&mut self, var: Variable, splitter: BranchSplitter) -> Decision {
+        if let Some(range) = self.facts.int_range(&FactSubject::Variable(var.id)) {
+            return self.splitter_to_int_range_switch(var, splitter, range);
+        }
+
         let choices = self.compile_all_choices(splitter.choices);
         let last_choice = self.compile(splitter.fallback);
         Decision::Switch {
@@ -2546,6 +2702,134 @@ impl<'a> Compiler<'a> {
         }
     }
 
+    /// Compiles the branches in a splitter down to a switch matching on an
+    /// `Int` variable we know can only have values in the given range.
+    ///
+    /// If the checked values cover the entire range there's no need for a
+    /// catch all: the last check is always going to succeed if none of the
+    /// others does. And if there's no catch all but only a handful of values
+    /// are not covered, we can report each one of those as missing.
+    ///
+    fn splitter_to_int_range_switch(
+        &mut self,
+        var: Variable,
+        splitter: BranchSplitter,
+        range: IntRange,
+    ) -> Decision {
+        let mut choices = splitter.choices;
+        let checked_values = choices
+            .iter()
+            .filter_map(|(check, _)| match check {
+                RuntimeCheck::Int { int_value } => Some(int_value.clone()),
+                RuntimeCheck::Float { .. }
+                | RuntimeCheck::String { .. }
+                | RuntimeCheck::StringPrefix { .. }
+                | RuntimeCheck::Tuple { .. }
+                | RuntimeCheck::BitArray { .. }
+                | RuntimeCheck::Variant { .. }
+                | RuntimeCheck::NonEmptyList { .. }
+                | RuntimeCheck::EmptyList => None,
+            })
+            .collect_vec();
+
+        let unchecked_values = range
+            .size()
+            .filter(|size| *size <= BigInt::from(MAX_REPORTED_MISSING_INT_VALUES))
+            .and_then(|_| range.values())
+            .map(|values| {
+                values
+                    .filter(|value| !checked_values.contains(value))
+                    .collect_vec()
+            });
+
+        match unchecked_values {
+            // Every possible value is checked, so the fallback branches are
+            // never going to run.
+            Some(unchecked_values) if unchecked_values.is_empty() && !choices.is_empty() => {}
+
+            // There's no catch all to handle the values that are not checked:
+            // we know exactly which values are missing.
+            Some(unchecked_values) if splitter.fallback.is_empty() => {
+                self.diagnostics.missing = true;
+                choices.extend(
+                    unchecked_values
+                        .into_iter()
+                        .map(|int_value| (RuntimeCheck::Int { int_value }, VecDeque::new())),
+                );
+            }
+
+            Some(_) | None => {
+                let choices = self.compile_all_int_choices(&var, choices);
+                let fact = Fact {
+                    subject: FactSubject::Variable(var.id),
+                    refinement: Refinement::IntRange(range.without_ends(&checked_values)),
+                };
+                let fallback = self.compile_with_fact(Some(fact), splitter.fallback);
+                return Decision::Switch {
+                    var,
+                    choices,
+                    fallback: Box::new(fallback),
+                    fallback_check: Box::new(FallbackCheck::InfiniteCatchAll),
+                };
+            }
+        }
+
+        let _ = self.diagnostics.finite_int_variables.insert(var.id);
+        let Some((last_check, last_choice)) = choices.pop() else {
+            // The range is empty, so there's no value this could ever match.
+            self.diagnostics.missing = true;
+            return Decision::Fail;
+        };
+        let choices = self.compile_all_int_choices(&var, choices);
+        let fallback = self.compile_int_choice(&var, &last_check, last_choice);
+        Decision::Switch {
+            var,
+            choices,
+            fallback: Box::new(fallback),
+            fallback_check: Box::new(FallbackCheck::RuntimeCheck { check: last_check }),
+        }
+    }
+
+    fn compile_all_int_choices(
+        &mut self,
+        var: &Variable,
+        choices: Vec<(RuntimeCheck, VecDeque<Branch>)>,
+    ) -> Vec<(RuntimeCheck, Decision)> {
+        choices
+            .into_iter()
+            .map(|(check, branches)| {
+                let decision = self.compile_int_choice(var, &check, branches);
+                (check, decision)
+            })
+            .collect_vec()
+    }
+
+    /// Compiles the branches to run if an `Int` variable has passed the given
+    /// check, knowing the exact value it has.
+    ///
+    fn compile_int_choice(
+        &mut self,
+        var: &Variable,
+        check: &RuntimeCheck,
+        branches: VecDeque<Branch>,
+    ) -> Decision {
+        let fact = match check {
+            RuntimeCheck::Int { int_value } => Some(Fact {
+                subject: FactSubject::Variable(var.id),
+                refinement: Refinement::IntRange(IntRange::single(int_value.clone())),
+            }),
+            RuntimeCheck::Float { .. }
+            | RuntimeCheck::String { .. }
+            | RuntimeCheck::StringPrefix { .. }
+            | RuntimeCheck::Tuple { .. }
+            | RuntimeCheck::BitArray { .. }
+            | RuntimeCheck::Variant { .. }
+            | RuntimeCheck::NonEmptyList { .. }
+            | RuntimeCheck::EmptyList => None,
+        };
+        self.compile_with_fact(fact, branches)
+    }
+
     /// Compiles the branches in a splitter down to a switch matching on a type
     /// with a finite known number of variants.
     ///
@@ -3186,6 +3470,7 @@ impl BranchSplitter {
             branches.iter().any(|branch| {
                 branch
                     .guard
+                    .as_ref()
                     .is_some_and(|guard| guard.uses_pattern_bindings)
             })
         })
@@ -3438,7 +3723,7 @@ impl ConstructorSpecialiser {
             },
 
             Type::Var { type_ } => Type::Var {
-                type_: Arc::new(RefCell::new(self.specialise_var(type_))),
+                type_: Arc::new(TypeVarCell::new(self.specialise_var(type_))),
             },
 
             Type::Tuple { elements } => Type::Tuple {
@@ -3450,7 +3735,7 @@ impl ConstructorSpecialiser {
         })
     }
 
-    fn specialise_var(&self, type_: &RefCell<TypeVar>) -> TypeVar {
+    fn specialise_var(&self, type_: &TypeVarCell) -> TypeVar {
         match &*type_.borrow() {
             TypeVar::Unbound { id } => TypeVar::Unbound { id: *id },
 
@@ -3480,6 +3765,9 @@ pub struct CaseToCompile {
     /// The number of clauses in this case to compile.
     number_of_clauses: usize,
     variable_id: usize,
+    /// What we know about the values of the subjects before any of the
+    /// patterns is checked.
+    subject_facts: Vec<Fact>,
 }
 
 impl CaseToCompile {
@@ -3500,9 +3788,23 @@ impl CaseToCompile {
             number_of_clauses: 0,
             subject_variables,
             variable_id,
+            subject_facts: vec![],
         }
     }
 
+    /// Records that the subject at the given index is an `Int` that can only
+    /// have values in the given range.
+    ///
+    pub fn set_subject_range(&mut self, index: usize, range: IntRange) {
+        let Some(subject) = self.subject_variables.get(index) else {
+            return;
+        };
+        self.subject_facts.push(Fact {
+            subject: FactSubject::Variable(subject.id),
+            refinement: Refinement::IntRange(range),
+        });
+    }
+
     /// Registers a `TypedClause` as one of the branches to be compiled.
     ///
     /// If you don't have a clause and just have a simple `TypedPattern` you want
@@ -3539,6 +3841,7 @@ impl CaseToCompile {
                 BranchGuard {
                     clause_index: self.number_of_clauses,
                     uses_pattern_bindings,
+                    condition: GuardCondition::from_guard(guard),
                 }
             });
 
@@ -3568,6 +3871,9 @@ impl CaseToCompile {
 
     pub fn compile(self, env: &Environment<'_>) -> CompileCaseResult {
         let mut compiler = Compiler::new(env, self.variable_id, self.patterns);
+        for fact in self.subject_facts {
+            compiler.facts.push(fact);
+        }
 
         let decision = if self.branches.is_empty() {
             let var = self
@@ -5,7 +5,8 @@ use super::{CompileCaseResult, Decision, FallbackCheck, RuntimeCheck, Variable,
 use crate::type_::environment::Environment;
 use ecow::EcoString;
 use indexmap::IndexSet;
-use std::collections::HashMap;
+use num_bigint::BigInt;
+use std::collections::{HashMap, HashSet};
 
 /// Returns a list of patterns not covered by the match expression.
 pub fn missing_patterns(
@@ -13,7 +14,8 @@ pub fn missing_patterns(
     environment: &Environment<'_>,
 ) -> Vec<EcoString> {
     let subjects = &result.compiled_case.subject_variables;
-    let mut generator = MissingPatternsGenerator::new(subjects, environment);
+    let finite_int_variables = &result.diagnostics.finite_int_variables;
+    let mut generator = MissingPatternsGenerator::new(subjects, finite_int_variables, environment);
     generator.add_missing_patterns(&result.compiled_case.tree);
 
     generator.missing.into_iter().collect()
@@ -42,6 +44,13 @@ pub enum Term {
     Infinite {
         variable: Variable,
     },
+    /// A single value of an `Int` that is known to only ever have a few
+    /// possible values, so that it's more useful to print each of the missing
+    /// ones rather than a catch all pattern.
+    Int {
+        variable: Variable,
+        value: BigInt,
+    },
     EmptyList {
         variable: Variable,
     },
@@ -58,6 +67,7 @@ impl Term {
             Term::Variant { variable, .. } => variable,
             Term::Tuple { variable, .. } => variable,
             Term::Infinite { variable } => variable,
+            Term::Int { variable, .. } => variable,
             Term::EmptyList { variable } => variable,
             Term::List { variable, .. } => variable,
         }
@@ -66,6 +76,7 @@ impl Term {
 
 struct MissingPatternsGenerator<'a, 'env> {
     subjects: &'a Vec<Variable>,
+    finite_int_variables: &'a HashSet<usize>,
     terms: Vec<Term>,
     missing: IndexSet<EcoString>,
     environment: &'a Environment<'env>,
@@ -73,9 +84,14 @@ struct MissingPatternsGenerator<'a, 'env> {
 }
 
 impl<'a, 'env> MissingPatternsGenerator<'a, 'env> {
-    fn new(subjects: &'a Vec<Variable>, environment: &'a Environment<'env>) -> Self {
+    fn new(
+        subjects: &'a Vec<Variable>,
+        finite_int_variables: &'a HashSet<usize>,
+        environment: &'a Environment<'env>,
+    ) -> Self {
         MissingPatternsGenerator {
             subjects,
+            finite_int_variables,
             terms: vec![],
             missing: IndexSet::new(),
             environment,
@@ -158,6 +174,13 @@ impl<'a, 'env> MissingPatternsGenerator<'a, 'env> {
 
     fn check_to_term(&self, variable: Variable, check: &RuntimeCheck) -> Term {
         match check {
+            RuntimeCheck::Int { int_value } if self.finite_int_variables.contains(&variable.id) => {
+                Term::Int {
+                    variable,
+                    value: int_value.clone(),
+                }
+            }
+
             RuntimeCheck::Int { .. }
             | RuntimeCheck::Float { .. }
             | RuntimeCheck::String { .. }
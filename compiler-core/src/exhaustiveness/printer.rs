@@ -124,6 +124,7 @@ impl<'a> Printer<'a> {
                             // `Person(name: _, age: _)`.
                             Term::Infinite { .. } if has_label => {}
                             Term::Infinite { .. }
+                            | Term::Int { .. }
                             | Term::Variant { .. }
                             | Term::Tuple { .. }
                             | Term::EmptyList { .. }
@@ -164,6 +165,7 @@ impl<'a> Printer<'a> {
                 buffer.push(')');
             }
             Term::Infinite { .. } => buffer.push('_'),
+            Term::Int { value, .. } => buffer.push_str(&value.to_string()),
             Term::EmptyList { .. } => buffer.push_str("[]"),
             Term::List { .. } => {
                 buffer.push('[');
@@ -181,7 +183,10 @@ impl<'a> Printer<'a> {
         buffer: &mut EcoString,
     ) {
         match term {
-            Term::Infinite { .. } | Term::Variant { .. } | Term::Tuple { .. } => buffer.push('_'),
+            Term::Infinite { .. }
+            | Term::Int { .. }
+            | Term::Variant { .. }
+            | Term::Tuple { .. } => buffer.push('_'),
 
             Term::EmptyList { .. } => {}
 
@@ -205,6 +210,7 @@ impl<'a> Printer<'a> {
                         Term::Variant { .. }
                         | Term::Tuple { .. }
                         | Term::Infinite { .. }
+                        | Term::Int { .. }
                         | Term::List { .. } => {
                             buffer.push_str(", ");
                             self.print_list(term, terms, mapping, buffer);
@@ -0,0 +1,598 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! A lightweight analysis of the values a variable can have at a given point
+//! of a decision tree.
+//!
+//! While compiling a case expression we can sometimes learn something about
+//! the values being matched on. The subject of `case x % 3 { ... }` can only
+//! ever be an `Int` between -2 and 2; and once the guard of a branch like
+//! `_ if n > 0 -> todo` has failed we know that `n` is at most 0 in all the
+//! branches that follow.
+//!
+//! The case compiler uses these facts to tell when a series of `Int` patterns
+//! covers all the values a variable can have, and when a guard is always (or
+//! never) going to be true.
+//!
+
+use ecow::EcoString;
+use num_bigint::BigInt;
+use src_span::SrcSpan;
+
+use crate::ast::{BinOp, ClauseGuard, Constant, TypedClauseGuard};
+
+/// A range of `Int`s, including both of its ends. A missing end means that the
+/// range is unbounded on that side.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntRange {
+    min: Option<BigInt>,
+    max: Option<BigInt>,
+}
+
+impl IntRange {
+    pub fn unbounded() -> Self {
+        Self {
+            min: None,
+            max: None,
+        }
+    }
+
+    pub fn new(min: impl Into<BigInt>, max: impl Into<BigInt>) -> Self {
+        Self {
+            min: Some(min.into()),
+            max: Some(max.into()),
+        }
+    }
+
+    pub fn single(value: BigInt) -> Self {
+        Self {
+            min: Some(value.clone()),
+            max: Some(value),
+        }
+    }
+
+    pub fn at_least(min: BigInt) -> Self {
+        Self {
+            min: Some(min),
+            max: None,
+        }
+    }
+
+    pub fn at_most(max: BigInt) -> Self {
+        Self {
+            min: None,
+            max: Some(max),
+        }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.min.is_none() && self.max.is_none()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) => min > max,
+            (_, _) => false,
+        }
+    }
+
+    pub fn contains(&self, value: &BigInt) -> bool {
+        self.min.as_ref().is_none_or(|min| min <= value)
+            && self.max.as_ref().is_none_or(|max| value <= max)
+    }
+
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let min_is_included = match (&other.min, &self.min) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(other_min), Some(min)) => other_min <= min,
+        };
+        let max_is_included = match (&other.max, &self.max) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(other_max), Some(max)) => max <= other_max,
+        };
+        min_is_included && max_is_included
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            min: biggest_lower_bound(&self.min, &other.min),
+            max: smallest_upper_bound(&self.max, &other.max),
+        }
+    }
+
+    /// The number of values in the range, if it is bounded on both sides.
+    ///
+    pub fn size(&self) -> Option<BigInt> {
+        let (min, max) = (self.min.as_ref()?, self.max.as_ref()?);
+        if min > max {
+            Some(BigInt::ZERO)
+        } else {
+            Some(max - min + 1)
+        }
+    }
+
+    /// All the values in the range, from the smallest to the biggest. This
+    /// returns `None` if the range is unbounded on one of its sides.
+    ///
+    pub fn values(&self) -> Option<impl Iterator<Item = BigInt>> {
+        let (min, max) = (self.min.clone()?, self.max.clone()?);
+        let first = (min <= max).then_some(min);
+        Some(std::iter::successors(first, move |value| {
+            (*value < max).then(|| value + 1)
+        }))
+    }
+
+    /// Removes the values at the ends of the range that are in the given list,
+    /// for as long as there are any.
+    ///
+    /// ```txt
+    /// [0, 10] without_ends [0, 1, 5, 10] -> [2, 9]
+    /// ```
+    ///
+    pub fn without_ends(&self, values: &[BigInt]) -> Self {
+        let mut range = self.clone();
+        while let Some(min) = &range.min
+            && values.contains(min)
+            && !range.is_empty()
+        {
+            range.min = Some(min + 1);
+        }
+        while let Some(max) = &range.max
+            && values.contains(max)
+            && !range.is_empty()
+        {
+            range.max = Some(max - 1);
+        }
+        range
+    }
+
+    /// The values that are not in this range, if they can be represented as
+    /// a single range.
+    ///
+    fn complement(&self) -> Option<Self> {
+        match (&self.min, &self.max) {
+            (Some(min), None) => Some(Self::at_most(min - 1)),
+            (None, Some(max)) => Some(Self::at_least(max + 1)),
+            (None, None) | (Some(_), Some(_)) => None,
+        }
+    }
+
+    /// The range of the values `a + b` can have, with `a` in this range and
+    /// `b` in the other one.
+    ///
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            min: add(&self.min, &other.min),
+            max: add(&self.max, &other.max),
+        }
+    }
+
+    /// The range of the values `a - b` can have, with `a` in this range and
+    /// `b` in the other one.
+    ///
+    pub fn subtract(&self, other: &Self) -> Self {
+        self.add(&other.negate())
+    }
+
+    pub fn negate(&self) -> Self {
+        Self {
+            min: self.max.as_ref().map(|max| -max),
+            max: self.min.as_ref().map(|min| -min),
+        }
+    }
+
+    /// The range of the values `a % b` can have, with `a` in this range and
+    /// `b` in the other one.
+    ///
+    /// In Gleam the remainder has the same sign as the dividend, its absolute
+    /// value is always smaller than the one of the divisor, and the remainder
+    /// of a division by zero is zero.
+    ///
+    pub fn remainder(&self, divisor: &Self) -> Self {
+        if let (Some(value), Some(divisor)) = (self.single_value(), divisor.single_value()) {
+            return if divisor == BigInt::ZERO {
+                Self::single(BigInt::ZERO)
+            } else {
+                Self::single(value % divisor)
+            };
+        }
+
+        // The remainder can't be bigger than the biggest divisor (in absolute
+        // value) minus one, or than the dividend itself.
+        let biggest = match (&divisor.min, &divisor.max) {
+            (Some(min), Some(max)) => Some(min.magnitude().max(max.magnitude()).clone()),
+            (_, _) => None,
+        };
+        let biggest_remainder =
+            biggest.map(|biggest| (BigInt::from(biggest) - BigInt::from(1)).max(BigInt::ZERO));
+
+        let min = if self.min.as_ref().is_some_and(|min| *min >= BigInt::ZERO) {
+            Some(BigInt::ZERO)
+        } else {
+            let min_remainder = biggest_remainder.as_ref().map(|biggest| -biggest);
+            biggest_lower_bound(&self.min, &min_remainder)
+        };
+        let max = if self.max.as_ref().is_some_and(|max| *max <= BigInt::ZERO) {
+            Some(BigInt::ZERO)
+        } else {
+            smallest_upper_bound(&self.max, &biggest_remainder)
+        };
+        Self { min, max }
+    }
+
+    fn single_value(&self) -> Option<BigInt> {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) if min == max => Some(min.clone()),
+            (_, _) => None,
+        }
+    }
+}
+
+fn add(one: &Option<BigInt>, other: &Option<BigInt>) -> Option<BigInt> {
+    Some(one.as_ref()? + other.as_ref()?)
+}
+
+/// The biggest of two lower bounds, where a missing bound means it's
+/// unbounded.
+///
+fn biggest_lower_bound(one: &Option<BigInt>, other: &Option<BigInt>) -> Option<BigInt> {
+    match (one, other) {
+        (None, None) => None,
+        (Some(bound), None) | (None, Some(bound)) => Some(bound.clone()),
+        (Some(one), Some(other)) => Some(one.max(other).clone()),
+    }
+}
+
+/// The smallest of two upper bounds, where a missing bound means it's
+/// unbounded.
+///
+fn smallest_upper_bound(one: &Option<BigInt>, other: &Option<BigInt>) -> Option<BigInt> {
+    match (one, other) {
+        (None, None) => None,
+        (Some(bound), None) | (None, Some(bound)) => Some(bound.clone()),
+        (Some(one), Some(other)) => Some(one.min(other).clone()),
+    }
+}
+
+/// Something we know about the value of a variable.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refinement {
+    /// The variable is a `Bool` with the given value.
+    Bool(bool),
+    /// The variable is an `Int` in the given range.
+    IntRange(IntRange),
+}
+
+impl Refinement {
+    /// What we know about a variable if this refinement doesn't hold. This
+    /// returns `None` if that can't be represented as a single refinement.
+    ///
+    fn negate(&self) -> Option<Self> {
+        match self {
+            Refinement::Bool(value) => Some(Refinement::Bool(!value)),
+            Refinement::IntRange(range) => range.complement().map(Refinement::IntRange),
+        }
+    }
+}
+
+/// A variable referenced by a guard.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardVariable {
+    pub name: EcoString,
+    pub definition_location: SrcSpan,
+}
+
+/// A guard that is true if, and only if, the value of a single variable has the
+/// given refinement. For example `n > 1` or `!is_valid`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardCondition {
+    pub variable: GuardVariable,
+    pub refinement: Refinement,
+}
+
+impl GuardCondition {
+    /// Returns the condition checked by a guard, if it only depends on the
+    /// value of a single variable.
+    ///
+    pub fn from_guard(guard: &TypedClauseGuard) -> Option<Self> {
+        match guard {
+            ClauseGuard::Block { value, .. } => Self::from_guard(value),
+            ClauseGuard::Not { expression, .. } => Self::from_guard(expression)?.negate(),
+
+            ClauseGuard::Var {
+                name,
+                definition_location,
+                type_,
+                ..
+            } if type_.is_bool() => Some(Self {
+                variable: GuardVariable {
+                    name: name.clone(),
+                    definition_location: *definition_location,
+                },
+                refinement: Refinement::Bool(true),
+            }),
+
+            ClauseGuard::BinaryOperator {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let (variable, operator, value) = match (left.as_ref(), right.as_ref()) {
+                    (
+                        ClauseGuard::Var {
+                            name,
+                            definition_location,
+                            ..
+                        },
+                        ClauseGuard::Constant(Constant::Int { int_value, .. }),
+                    ) => ((name, definition_location), *operator, int_value),
+                    // `1 < n` is the same as `n > 1`.
+                    (
+                        ClauseGuard::Constant(Constant::Int { int_value, .. }),
+                        ClauseGuard::Var {
+                            name,
+                            definition_location,
+                            ..
+                        },
+                    ) => ((name, definition_location), flip(*operator)?, int_value),
+                    (_, _) => return None,
+                };
+
+                let range = match operator {
+                    BinOp::Eq => IntRange::single(value.clone()),
+                    BinOp::GtInt => IntRange::at_least(value + 1),
+                    BinOp::GtEqInt => IntRange::at_least(value.clone()),
+                    BinOp::LtInt => IntRange::at_most(value - 1),
+                    BinOp::LtEqInt => IntRange::at_most(value.clone()),
+                    BinOp::And
+                    | BinOp::Or
+                    | BinOp::NotEq
+                    | BinOp::LtFloat
+                    | BinOp::LtEqFloat
+                    | BinOp::GtEqFloat
+                    | BinOp::GtFloat
+                    | BinOp::AddInt
+                    | BinOp::AddFloat
+                    | BinOp::SubInt
+                    | BinOp::SubFloat
+                    | BinOp::MultInt
+                    | BinOp::MultFloat
+                    | BinOp::DivInt
+                    | BinOp::DivFloat
+                    | BinOp::RemainderInt
+                    | BinOp::Concatenate => return None,
+                };
+
+                let (name, definition_location) = variable;
+                Some(Self {
+                    variable: GuardVariable {
+                        name: name.clone(),
+                        definition_location: *definition_location,
+                    },
+                    refinement: Refinement::IntRange(range),
+                })
+            }
+
+            ClauseGuard::Var { .. }
+            | ClauseGuard::TupleIndex { .. }
+            | ClauseGuard::FieldAccess { .. }
+            | ClauseGuard::ModuleSelect { .. }
+            | ClauseGuard::Constant(_)
+            | ClauseGuard::Invalid { .. } => None,
+        }
+    }
+
+    /// The condition that holds when this one doesn't, if it can be
+    /// represented.
+    ///
+    pub fn negate(&self) -> Option<Self> {
+        Some(Self {
+            variable: self.variable.clone(),
+            refinement: self.refinement.negate()?,
+        })
+    }
+}
+
+/// Swaps the operands of a comparison: `a < b` is the same as `b > a`.
+///
+fn flip(operator: BinOp) -> Option<BinOp> {
+    match operator {
+        BinOp::Eq => Some(BinOp::Eq),
+        BinOp::LtInt => Some(BinOp::GtInt),
+        BinOp::LtEqInt => Some(BinOp::GtEqInt),
+        BinOp::GtInt => Some(BinOp::LtInt),
+        BinOp::GtEqInt => Some(BinOp::LtEqInt),
+        BinOp::And
+        | BinOp::Or
+        | BinOp::NotEq
+        | BinOp::LtFloat
+        | BinOp::LtEqFloat
+        | BinOp::GtEqFloat
+        | BinOp::GtFloat
+        | BinOp::AddInt
+        | BinOp::AddFloat
+        | BinOp::SubInt
+        | BinOp::SubFloat
+        | BinOp::MultInt
+        | BinOp::MultFloat
+        | BinOp::DivInt
+        | BinOp::DivFloat
+        | BinOp::RemainderInt
+        | BinOp::Concatenate => None,
+    }
+}
+
+/// The value a fact is about: either one of the variables of the decision
+/// tree, or a variable defined outside of the case expression and referenced
+/// by its guards.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FactSubject {
+    Variable(usize),
+    Outer(SrcSpan),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fact {
+    pub subject: FactSubject,
+    pub refinement: Refinement,
+}
+
+/// All the facts known at a given point of the decision tree. Facts are added
+/// as the compiler goes down a path of the tree, and removed once it's done
+/// with it.
+///
+#[derive(Debug, Default)]
+pub struct Facts {
+    facts: Vec<Fact>,
+}
+
+impl Facts {
+    pub fn push(&mut self, fact: Fact) {
+        self.facts.push(fact);
+    }
+
+    pub fn pop(&mut self) {
+        let _ = self.facts.pop();
+    }
+
+    /// The range of values an `Int` can have, if anything is known about it.
+    ///
+    pub fn int_range(&self, subject: &FactSubject) -> Option<IntRange> {
+        self.facts
+            .iter()
+            .filter(|fact| fact.subject == *subject)
+            .filter_map(|fact| match &fact.refinement {
+                Refinement::IntRange(range) => Some(range),
+                Refinement::Bool(_) => None,
+            })
+            .fold(None, |known, range| match known {
+                None => Some(range.clone()),
+                Some(known) => Some(range.intersect(&known)),
+            })
+    }
+
+    fn bool_value(&self, subject: &FactSubject) -> Option<bool> {
+        self.facts
+            .iter()
+            .filter(|fact| fact.subject == *subject)
+            .find_map(|fact| match &fact.refinement {
+                Refinement::Bool(value) => Some(*value),
+                Refinement::IntRange(_) => None,
+            })
+    }
+
+    /// Whether a refinement is always true, or always false, given what we
+    /// know about its subject. Returns `None` if it could be either.
+    ///
+    pub fn outcome(&self, subject: &FactSubject, refinement: &Refinement) -> Option<bool> {
+        match refinement {
+            Refinement::Bool(value) => self.bool_value(subject).map(|known| known == *value),
+            Refinement::IntRange(range) => {
+                let known = self.int_range(subject)?;
+                if known.is_subset_of(range) {
+                    Some(true)
+                } else if known.intersect(range).is_empty() {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remainder_of_unbounded_value() {
+        let range = IntRange::unbounded().remainder(&IntRange::single(3.into()));
+        assert_eq!(range, IntRange::new(-2, 2));
+    }
+
+    #[test]
+    fn remainder_of_positive_value() {
+        let range = IntRange::at_least(0.into()).remainder(&IntRange::single(3.into()));
+        assert_eq!(range, IntRange::new(0, 2));
+    }
+
+    #[test]
+    fn remainder_of_negative_value() {
+        let range = IntRange::at_most((-1).into()).remainder(&IntRange::single((-4).into()));
+        assert_eq!(range, IntRange::new(-3, 0));
+    }
+
+    #[test]
+    fn remainder_of_small_value() {
+        let range = IntRange::new(-1, 1).remainder(&IntRange::single(10.into()));
+        assert_eq!(range, IntRange::new(-1, 1));
+    }
+
+    #[test]
+    fn remainder_of_constants() {
+        let range = IntRange::single((-7).into()).remainder(&IntRange::single(3.into()));
+        assert_eq!(range, IntRange::single((-1).into()));
+    }
+
+    #[test]
+    fn remainder_of_division_by_zero() {
+        let range = IntRange::unbounded().remainder(&IntRange::single(0.into()));
+        assert_eq!(range, IntRange::new(0, 0));
+    }
+
+    #[test]
+    fn remainder_by_unbounded_divisor() {
+        let range = IntRange::at_least(1.into()).remainder(&IntRange::unbounded());
+        assert_eq!(range, IntRange::at_least(0.into()));
+    }
+
+    #[test]
+    fn addition_of_ranges() {
+        let range = IntRange::new(-2, 2).add(&IntRange::new(1, 1));
+        assert_eq!(range, IntRange::new(-1, 3));
+    }
+
+    #[test]
+    fn subtraction_of_ranges() {
+        let range = IntRange::new(0, 2).subtract(&IntRange::at_least(1.into()));
+        assert_eq!(range, IntRange::at_most(1.into()));
+    }
+
+    #[test]
+    fn without_ends() {
+        let values = [0, 1, 5, 10].map(BigInt::from);
+        let range = IntRange::new(0, 10).without_ends(&values);
+        assert_eq!(range, IntRange::new(2, 9));
+    }
+
+    #[test]
+    fn outcome_of_refinements() {
+        let subject = FactSubject::Variable(0);
+        let mut facts = Facts::default();
+        facts.push(Fact {
+            subject: subject.clone(),
+            refinement: Refinement::IntRange(IntRange::at_most(0.into())),
+        });
+
+        let positive = Refinement::IntRange(IntRange::at_least(1.into()));
+        let small = Refinement::IntRange(IntRange::at_most(5.into()));
+        let zero = Refinement::IntRange(IntRange::single(0.into()));
+        assert_eq!(facts.outcome(&subject, &positive), Some(false));
+        assert_eq!(facts.outcome(&subject, &small), Some(true));
+        assert_eq!(facts.outcome(&subject, &zero), None);
+        assert_eq!(facts.outcome(&FactSubject::Variable(1), &small), None);
+    }
+}
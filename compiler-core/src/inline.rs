@@ -62,7 +62,10 @@
 //! more complex heuristic-based approach will likely be implemented in the
 //! future. For now though, a function can be inlined if:
 //! It is a standard library function within the hardcoded list - which can be
-//! found in the `inline_function` function - or, it is an anonymous function.
+//! found in the `is_inlinable` function - or, it is marked with the `@inline`
+//! attribute, or it is an anonymous function.
+//!
+//! Inlining is only performed when enabled with the `inline` build option.
 //!
 //! Inlining anonymous functions allows us to:
 //! - Remove calls to parameters of higher-order functions once those higher-
@@ -111,8 +114,6 @@
 //! works.
 //!
 
-#![allow(dead_code)]
-
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
@@ -1447,8 +1448,9 @@ fn expand_block(expression: TypedExpr) -> TypedExpr {
 /// perform inlining, while keeping the cache files to a minimum size.
 ///
 /// This function also determines whether a function is inlinable. Currently this
-/// just checks it against a list of stdlib functions we want to prioritise
-/// inlining, but later it will be changed to a more complicated heuristic.
+/// checks it against a list of stdlib functions we want to prioritise inlining,
+/// or whether it has been marked with the `@inline` attribute. Later it will be
+/// changed to a more complicated heuristic.
 ///
 pub fn function_to_inlinable(
     package: &str,
@@ -1457,7 +1459,21 @@ pub fn function_to_inlinable(
 ) -> Option<InlinableFunction> {
     let (_, name) = function.name.as_ref()?;
 
-    if !is_inlinable(package, module, name) {
+    let references = if is_inlinable(package, module, name) {
+        References::Any
+    } else if function.inline.is_some() {
+        References::PreludeOnly
+    } else {
+        return None;
+    };
+
+    // The Gleam body of a function with externals is only a fallback, and a
+    // function with no body at all has nothing to inline.
+    if function.external_erlang.is_some()
+        || function.external_javascript.is_some()
+        || function.external_wasm.is_some()
+        || function.body.is_empty()
+    {
         return None;
     }
 
@@ -1477,7 +1493,7 @@ pub fn function_to_inlinable(
         })
         .collect();
 
-    let mut converter = FunctionToInlinable::new(&function.arguments);
+    let mut converter = FunctionToInlinable::new(&function.arguments, references);
 
     let body = function
         .body
@@ -1533,6 +1549,20 @@ fn is_inlinable(package: &str, module: &str, name: &str) -> bool {
     }
 }
 
+/// Which module values an inlinable function can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum References {
+    /// The function can refer to the functions and records of any module.
+    /// This is only allowed for the hardcoded standard library functions, as
+    /// we know all of their references are themselves inlined or available.
+    Any,
+    /// The function can only refer to its own parameters and to the records
+    /// of the prelude. Once inlined, the function's body ends up in a module
+    /// which might not import - or be able to import - anything else it
+    /// references.
+    PreludeOnly,
+}
+
 /// Holds state for converting a `TypedFunction` into an `InlinableFunction`.
 struct FunctionToInlinable {
     /// A map of parameters to a boolean of whether they have been used. Since
@@ -1542,10 +1572,12 @@ struct FunctionToInlinable {
     /// If a parameter is used more than once, it is removed from the map, so it
     /// is no longer tracked as an inlinable parameter.
     parameter_references: HashMap<(EcoString, SrcSpan), bool>,
+    /// Which module values the function is allowed to refer to.
+    references: References,
 }
 
 impl FunctionToInlinable {
-    fn new(arguments: &[TypedArg]) -> Self {
+    fn new(arguments: &[TypedArg], references: References) -> Self {
         let parameter_references = arguments
             .iter()
             .filter_map(|argument| {
@@ -1565,6 +1597,14 @@ impl FunctionToInlinable {
 
         Self {
             parameter_references,
+            references,
+        }
+    }
+
+    fn can_reference(&self, module: &str) -> bool {
+        match self.references {
+            References::Any => true,
+            References::PreludeOnly => module == PRELUDE_MODULE_NAME,
         }
     }
 
@@ -1723,6 +1763,12 @@ impl FunctionToInlinable {
                 Some(InlinableValueConstructor::LocalVariable)
             }
             ValueConstructorVariant::ModuleConstant { .. } => None,
+            ValueConstructorVariant::ModuleFn { module, .. }
+            | ValueConstructorVariant::Record { module, .. }
+                if !self.can_reference(module) =>
+            {
+                None
+            }
             ValueConstructorVariant::ModuleFn { name, module, .. } => {
                 Some(InlinableValueConstructor::Function {
                     name: name.clone(),
@@ -1742,13 +1788,13 @@ impl FunctionToInlinable {
         let pattern = clause
             .pattern
             .iter()
-            .map(Self::pattern)
+            .map(|pattern| self.pattern(pattern))
             .collect::<Option<_>>()?;
         let body = self.expression(&clause.then)?;
         Some(InlinableClause { pattern, body })
     }
 
-    fn pattern(pattern: &TypedPattern) -> Option<InlinablePattern> {
+    fn pattern(&self, pattern: &TypedPattern) -> Option<InlinablePattern> {
         match pattern {
             TypedPattern::Variable { name, .. } => {
                 Some(InlinablePattern::Variable { name: name.clone() })
@@ -1758,13 +1804,13 @@ impl FunctionToInlinable {
                 arguments,
                 constructor: Inferred::Known(inferred),
                 ..
-            } => {
+            } if self.can_reference(&inferred.module) => {
                 let arguments = arguments
                     .iter()
                     .map(|argument| {
                         Some(InlinableArgument {
                             label: argument.label.clone(),
-                            value: Self::pattern(&argument.value)?,
+                            value: self.pattern(&argument.value)?,
                         })
                     })
                     .collect::<Option<_>>()?;
@@ -1776,10 +1822,7 @@ impl FunctionToInlinable {
                 })
             }
 
-            TypedPattern::Constructor {
-                constructor: Inferred::Unknown,
-                ..
-            } => None,
+            TypedPattern::Constructor { .. } => None,
 
             TypedPattern::Int { .. }
             | TypedPattern::Float { .. }
@@ -2006,6 +2049,7 @@ impl InlinableValueConstructor {
                     implementations: Implementations::supporting_all(),
                     external_erlang: None,
                     external_javascript: None,
+                    external_wasm: None,
                     purity: Purity::Unknown,
                 }
             }
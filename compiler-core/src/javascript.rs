@@ -3,9 +3,11 @@
 
 mod decision;
 mod expression;
+pub mod global;
 mod import;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
+mod tree_shaking;
 mod typescript;
 
 use std::cell::RefCell;
@@ -13,8 +15,9 @@ use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 use crate::ast::{Import, *};
-use crate::build::Target;
 use crate::build::package_compiler::StdlibPackage;
+use crate::build::{ModuleFormat, Target};
+use crate::call_graph::{ReachableDefinitions, mutually_tail_recursive_groups};
 use crate::codegen::TypeScriptDeclarations;
 use crate::type_::{PRELUDE_MODULE_NAME, RecordAccessor};
 use camino::Utf8Path;
@@ -29,12 +32,72 @@ use sourcemap::SourceMap;
 use src_span::{LineColumn, LineNumbers, SrcSpan};
 
 use self::import::{Imports, Member};
+pub use self::tree_shaking::{FfiImport, ffi_imports, reachable_definitions};
 
 const INDENT: isize = 2;
 
 pub const PRELUDE: &str = include_str!("../templates/prelude.mjs");
 pub const PRELUDE_TS_DEF: &str = include_str!("../templates/prelude.d.mts");
 
+/// The source of the prelude module in the given module format.
+///
+pub fn prelude(module_format: ModuleFormat) -> String {
+    match module_format {
+        ModuleFormat::Esm => PRELUDE.into(),
+        ModuleFormat::Cjs => {
+            let (prelude, exports) = prelude_without_exports();
+            format!("{prelude}\nmodule.exports = {{ {exports} }};\n")
+        }
+        ModuleFormat::Global => {
+            let (prelude, exports) = prelude_without_exports();
+            let key = global::PRELUDE_KEY;
+            format!(
+                "(() => {{\n\"use strict\";\n\n{prelude}
+(globalThis.Gleam ??= {{}})[\"{key}\"] = {{ {exports} }};\n}})();\n"
+            )
+        }
+    }
+}
+
+/// The prelude with the `export` keywords removed, along with the names of
+/// the values it exported, separated by commas.
+///
+fn prelude_without_exports() -> (String, String) {
+    let mut exports = vec![];
+    let mut prelude = String::with_capacity(PRELUDE.len());
+    for line in PRELUDE.lines() {
+        let line = match line.strip_prefix("export ") {
+            Some(declaration) => {
+                exports.extend(declaration_name(declaration));
+                declaration
+            }
+            None => line,
+        };
+        prelude.push_str(line);
+        prelude.push('\n');
+    }
+    (prelude, exports.join(", "))
+}
+
+/// The name defined by a top level JavaScript declaration, such as
+/// `function wibble() {`.
+///
+fn declaration_name(declaration: &str) -> Option<&str> {
+    let (_keyword, rest) = declaration.split_once(' ')?;
+    rest.split(|c: char| !c.is_alphanumeric() && c != '_' && c != '$')
+        .next()
+        .filter(|name| !name.is_empty())
+}
+
+/// The kinds of top level definitions in a generated JavaScript module.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Declaration {
+    Const,
+    Class,
+    Function,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JavaScriptCodegenTarget {
     JavaScript,
@@ -106,6 +169,14 @@ pub struct Generator<'a> {
     /// Relative path to the module, surrounded in `"`s to make it a string, and with `\`s escaped
     /// to `\\`.
     src_path: EcoString,
+    /// When generating code for a whole program, only the definitions that can
+    /// be reached from its entrypoint are generated.
+    reachable_definitions: Option<&'a ReachableDefinitions>,
+    module_format: ModuleFormat,
+    /// CommonJS modules have no `export` keyword, so the names of the values
+    /// exported by the module are collected here to be assigned to
+    /// `module.exports` once all the definitions have been generated.
+    exports: Vec<EcoString>,
 }
 
 impl<'a, 'doc> Generator<'a> {
@@ -119,6 +190,8 @@ impl<'a, 'doc> Generator<'a> {
             src: _,
             path: _,
             project_root,
+            reachable_definitions,
+            module_format,
         } = config;
         let current_module_name_segments_count = module.name.split('/').count();
 
@@ -138,7 +211,7 @@ impl<'a, 'doc> Generator<'a> {
             typescript,
             source_map_builder: if source_map {
                 let module_name = module.name.clone();
-                let output_path = format!("{module_name}.mjs");
+                let output_path = format!("{module_name}.{}", module_format.extension());
                 let module_alias = module_name.split('/').next_back().unwrap_or(&module_name);
                 let input_path = format!("{module_alias}.gleam",);
                 let mut source_map_builder = sourcemap::SourceMapBuilder::new(Some(&output_path));
@@ -148,9 +221,50 @@ impl<'a, 'doc> Generator<'a> {
                 None
             },
             stdlib_package,
+            reachable_definitions,
+            module_format,
+            exports: vec![],
         }
     }
 
+    /// The keyword a top level definition starts with. CommonJS modules and
+    /// global scripts have no `export` keyword, so there the name of the
+    /// definition is recorded to be exported at the end of the module instead.
+    ///
+    fn declaration_keyword(
+        &mut self,
+        declaration: Declaration,
+        exported: bool,
+        name: impl Into<EcoString>,
+    ) -> Document<'a, 'doc> {
+        let export_keyword = match self.module_format {
+            ModuleFormat::Esm => exported,
+            ModuleFormat::Cjs | ModuleFormat::Global => {
+                if exported {
+                    self.exports.push(name.into());
+                }
+                false
+            }
+        };
+
+        match (declaration, export_keyword) {
+            (Declaration::Const, true) => EXPORT_CONST_SPACE_DOCUMENT,
+            (Declaration::Const, false) => CONST_SPACE_DOCUMENT,
+            (Declaration::Class, true) => EXPORT_CLASS_SPACE_DOCUMENT,
+            (Declaration::Class, false) => CLASS_SPACE_DOCUMENT,
+            (Declaration::Function, true) => EXPORT_FUNCTION_SPACE_DOCUMENT,
+            (Declaration::Function, false) => FUNCTION_SPACE_DOCUMENT,
+        }
+    }
+
+    /// Whether a value is used by the program being compiled. This is always
+    /// the case unless only the reachable definitions are being generated.
+    ///
+    fn is_reachable(&self, module: &str, name: &str) -> bool {
+        self.reachable_definitions
+            .is_none_or(|reachable| reachable.contains(module, name))
+    }
+
     fn type_reference(&self, arena: &'doc DocumentArena<'a, 'doc>) -> Document<'a, 'doc> {
         if self.typescript == TypeScriptDeclarations::None {
             return EMPTY_DOCUMENT;
@@ -165,11 +279,17 @@ impl<'a, 'doc> Generator<'a> {
             .next_back()
             .expect("JavaScript generator could not identify imported module name.");
 
+        let extension = match self.module_format {
+            ModuleFormat::Esm => DOT_D_DOT_MTS_CLOSE_QUOTE_CLOSE_TAG_DOCUMENT,
+            ModuleFormat::Cjs => DOT_D_DOT_CTS_CLOSE_QUOTE_CLOSE_TAG_DOCUMENT,
+            ModuleFormat::Global => DOT_D_DOT_TS_CLOSE_QUOTE_CLOSE_TAG_DOCUMENT,
+        };
+
         docvec![
             arena,
             REFERENCE_TYPES_DOCUMENT,
             module,
-            DOT_D_DOT_MTS_CLOSE_QUOTE_CLOSE_TAG_DOCUMENT,
+            extension,
             LINE_DOCUMENT
         ]
     }
@@ -187,11 +307,17 @@ impl<'a, 'doc> Generator<'a> {
                     .next_back()
                     .expect("JavaScript generator could not identify imported module name.");
 
+                let extension = match self.module_format {
+                    ModuleFormat::Esm => DOT_MJS_DOT_MAP_DOCUMENT,
+                    ModuleFormat::Cjs => DOT_CJS_DOT_MAP_DOCUMENT,
+                    ModuleFormat::Global => DOT_JS_DOT_MAP_DOCUMENT,
+                };
+
                 docvec![
                     arena,
                     SOURCE_MAPPING_URL_EQUAL_DOCUMENT,
                     module,
-                    DOT_MJS_DOT_MAP_DOCUMENT,
+                    extension,
                     LINE_DOCUMENT
                 ]
             }
@@ -373,50 +499,119 @@ impl<'a, 'doc> Generator<'a> {
         let sourcemap_reference = self.sourcemap_reference(arena);
         let type_reference = self.type_reference(arena);
         let filepath_definition = self.filepath_definition(arena);
+        let exports = self.module_exports(arena);
 
         // Put it all together
 
-        if imports.is_empty() && no_statements {
-            docvec![
-                arena,
-                sourcemap_reference,
-                type_reference,
-                filepath_definition,
-                EXPORT_SPACE_OPEN_CLOSE_CURLY_DOCUMENT,
-                LINE_DOCUMENT,
-                echo_definition
-            ]
-        } else if imports.is_empty() {
-            docvec![
-                arena,
-                sourcemap_reference,
-                type_reference,
-                filepath_definition,
-                statements.append(arena, LINE_DOCUMENT),
-                echo_definition
-            ]
-        } else if no_statements {
-            docvec![
-                arena,
-                sourcemap_reference,
-                type_reference,
-                imports.into_doc(arena, JavaScriptCodegenTarget::JavaScript),
-                filepath_definition,
-                echo_definition,
-            ]
-        } else {
-            docvec![
+        let module =
+            if imports.is_empty() && no_statements && self.module_format != ModuleFormat::Esm {
+                docvec![arena, filepath_definition, exports, echo_definition]
+            } else if imports.is_empty() && no_statements {
+                docvec![
+                    arena,
+                    filepath_definition,
+                    EXPORT_SPACE_OPEN_CLOSE_CURLY_DOCUMENT,
+                    LINE_DOCUMENT,
+                    echo_definition
+                ]
+            } else if imports.is_empty() {
+                docvec![
+                    arena,
+                    filepath_definition,
+                    statements.append(arena, LINE_DOCUMENT),
+                    exports,
+                    echo_definition
+                ]
+            } else if no_statements {
+                docvec![
+                    arena,
+                    imports.into_doc(
+                        arena,
+                        JavaScriptCodegenTarget::JavaScript,
+                        self.module_format
+                    ),
+                    filepath_definition,
+                    exports,
+                    echo_definition,
+                ]
+            } else {
+                docvec![
+                    arena,
+                    imports.into_doc(
+                        arena,
+                        JavaScriptCodegenTarget::JavaScript,
+                        self.module_format
+                    ),
+                    LINE_DOCUMENT,
+                    filepath_definition,
+                    statements,
+                    LINE_DOCUMENT,
+                    exports,
+                    echo_definition
+                ]
+            };
+
+        // Global scripts are wrapped in a function so that their definitions
+        // don't leak into the global scope shared by all the scripts.
+        let module = match self.module_format {
+            ModuleFormat::Esm | ModuleFormat::Cjs => module,
+            ModuleFormat::Global => docvec![
                 arena,
-                sourcemap_reference,
-                type_reference,
-                imports.into_doc(arena, JavaScriptCodegenTarget::JavaScript),
-                LINE_DOCUMENT,
-                filepath_definition,
-                statements,
+                OPEN_PAREN_OPEN_CLOSE_PAREN_SPACE_ARROW_SPACE_OPEN_CURLY_DOCUMENT,
                 LINE_DOCUMENT,
-                echo_definition
-            ]
+                DOUBLE_QUOTE_USE_SPACE_STRICT_DOUBLE_QUOTE_SEMICOLON_DOCUMENT,
+                TWO_LINES_DOCUMENT,
+                module,
+                CLOSE_CURLY_CLOSE_PAREN_OPEN_CLOSE_PAREN_SEMICOLON_DOCUMENT,
+                LINE_DOCUMENT
+            ],
+        };
+
+        docvec![arena, sourcemap_reference, type_reference, module]
+    }
+
+    /// The assignment to `module.exports` ending a CommonJS module, or to the
+    /// module's key of `globalThis.Gleam` ending a global script. ES modules
+    /// export their values where they are defined instead.
+    ///
+    fn module_exports(&mut self, arena: &'doc DocumentArena<'a, 'doc>) -> Document<'a, 'doc> {
+        if self.exports.is_empty() {
+            return EMPTY_DOCUMENT;
         }
+
+        let names = arena.join(
+            std::mem::take(&mut self.exports)
+                .into_iter()
+                .map(|name| name.to_doc(arena)),
+            COMMA_BREAK_DOCUMENT,
+        );
+        let names = docvec![
+            arena,
+            docvec![arena, BREAKABLE_SPACE_DOCUMENT, names].nest(arena, INDENT),
+            TRAILING_COMMA_OR_SPACE_BREAK_DOCUMENT
+        ]
+        .group(arena);
+
+        let assignment = match self.module_format {
+            ModuleFormat::Esm | ModuleFormat::Cjs => {
+                MODULE_DOT_EXPORTS_SPACE_EQUAL_SPACE_OPEN_CURLY_DOCUMENT
+            }
+            ModuleFormat::Global => docvec![
+                arena,
+                OPEN_PAREN_GLOBAL_THIS_DOT_GLEAM_SPACE_QUESTION_QUESTION_EQUAL_SPACE_OPEN_CLOSE_CURLY_CLOSE_PAREN_OPEN_SQUARE_DOUBLE_QUOTE_DOCUMENT,
+                global::module_key(&self.module.type_info.package, &self.module.name),
+                DOUBLE_QUOTE_CLOSE_SQUARE_SPACE_EQUAL_SPACE_OPEN_CURLY_DOCUMENT
+            ],
+        };
+
+        docvec![
+            arena,
+            LINE_DOCUMENT,
+            assignment,
+            names,
+            CLOSE_CURLY_SEMICOLON_DOCUMENT,
+            LINE_DOCUMENT
+        ]
     }
 
     fn echo_definition(
@@ -484,8 +679,13 @@ impl<'a, 'doc> Generator<'a> {
         } = custom_type;
 
         // If there's no constructors then there's nothing to do here.
-        if constructors.is_empty() {
+        let Some(first_constructor) = constructors.first() else {
             return Some(vec![]);
+        };
+
+        // Either all the variants of a type are reachable, or none of them are.
+        if !self.is_reachable(&self.module.name, &first_constructor.name) {
+            return None;
         }
 
         self.tracker.custom_type_used = true;
@@ -546,7 +746,7 @@ impl<'a, 'doc> Generator<'a> {
     }
 
     fn variant_definition(
-        &self,
+        &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
         constructor: &'a TypedRecordConstructor,
         type_name: &'a str,
@@ -595,17 +795,17 @@ impl<'a, 'doc> Generator<'a> {
     /// allowing them to be compared more efficiently.
     ///
     fn variant_constructor_constant(
-        &self,
+        &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
         constructor: &'a TypedRecordConstructor,
         type_name: &'a str,
         publicity: Publicity,
     ) -> Document<'a, 'doc> {
-        let keyword = if publicity.is_importable() {
-            EXPORT_CONST_SPACE_DOCUMENT
-        } else {
-            CONST_SPACE_DOCUMENT
-        };
+        let keyword = self.declaration_keyword(
+            Declaration::Const,
+            publicity.is_importable(),
+            eco_format!("{type_name}${}$const", constructor.name),
+        );
         docvec![
             arena,
             self.source_map_tracker(arena, constructor.location.start),
@@ -628,17 +828,23 @@ impl<'a, 'doc> Generator<'a> {
     }
 
     fn variant_constructor_definition(
-        &self,
+        &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
         constructor: &'a TypedRecordConstructor,
         type_name: &'a str,
     ) -> Document<'a, 'doc> {
+        let keyword = self.declaration_keyword(
+            Declaration::Const,
+            true,
+            eco_format!("{type_name}${}", constructor.name),
+        );
+
         // If the constructor has no fields, return the singleton constant
         // instead.
         if constructor.arguments.is_empty() {
             return docvec![
                 arena,
-                EXPORT_CONST_SPACE_DOCUMENT,
+                keyword,
                 type_name,
                 DOLLAR_DOCUMENT,
                 constructor.name.as_str(),
@@ -682,7 +888,7 @@ impl<'a, 'doc> Generator<'a> {
         docvec![
             arena,
             self.source_map_tracker(arena, constructor.location.start),
-            EXPORT_CONST_SPACE_DOCUMENT,
+            keyword,
             type_name,
             DOLLAR_DOCUMENT,
             constructor.name.as_str(),
@@ -694,7 +900,7 @@ impl<'a, 'doc> Generator<'a> {
     }
 
     fn variant_check_definition(
-        &self,
+        &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
         constructor: &'a TypedRecordConstructor,
         type_name: &'a str,
@@ -708,10 +914,16 @@ impl<'a, 'doc> Generator<'a> {
         ]
         .group(arena);
 
+        let keyword = self.declaration_keyword(
+            Declaration::Const,
+            true,
+            eco_format!("{type_name}$is{}", constructor.name),
+        );
+
         docvec![
             arena,
             self.source_map_tracker(arena, constructor.location.start),
-            EXPORT_CONST_SPACE_DOCUMENT,
+            keyword,
             type_name,
             DOLLAR_IS_DOCUMENT,
             constructor.name.as_str(),
@@ -721,7 +933,7 @@ impl<'a, 'doc> Generator<'a> {
     }
 
     fn variant_fields_definition(
-        &self,
+        &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
         constructor: &'a TypedRecordConstructor,
         type_name: &'a str,
@@ -759,11 +971,12 @@ impl<'a, 'doc> Generator<'a> {
                 ]
                 .group(arena);
 
+                let keyword = self.declaration_keyword(Declaration::Const, true, &function_name);
                 functions.push(docvec![
                     arena,
                     LINE_DOCUMENT,
                     self.source_map_tracker(arena, constructor.location.start),
-                    EXPORT_CONST_SPACE_DOCUMENT,
+                    keyword,
                     function_name,
                     SPACE_EQUAL_SPACE_VALUE_SPACE_ARROW,
                     contents.nest(arena, INDENT),
@@ -779,11 +992,12 @@ impl<'a, 'doc> Generator<'a> {
                 .group(arena);
             }
 
+            let keyword = self.declaration_keyword(Declaration::Const, true, &function_name);
             functions.push(docvec![
                 arena,
                 LINE_DOCUMENT,
                 self.source_map_tracker(arena, constructor.location.start),
-                EXPORT_CONST_SPACE_DOCUMENT,
+                keyword,
                 function_name,
                 SPACE_EQUAL_SPACE_VALUE_SPACE_ARROW,
                 contents.nest(arena, INDENT),
@@ -794,7 +1008,7 @@ impl<'a, 'doc> Generator<'a> {
     }
 
     fn shared_custom_type_fields(
-        &self,
+        &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
         type_name: &'a str,
         shared_accessors: &HashMap<EcoString, RecordAccessor>,
@@ -811,9 +1025,10 @@ impl<'a, 'doc> Generator<'a> {
             ]
             .group(arena);
 
+            let keyword = self.declaration_keyword(Declaration::Const, true, &function_name);
             docvec![
                 arena,
-                EXPORT_CONST_SPACE_DOCUMENT,
+                keyword,
                 function_name,
                 SPACE_EQUAL_SPACE_VALUE_SPACE_ARROW,
                 contents.nest(arena, INDENT),
@@ -823,7 +1038,7 @@ impl<'a, 'doc> Generator<'a> {
     }
 
     fn variant_class_definition(
-        &self,
+        &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
         constructor: &'a TypedRecordConstructor,
         publicity: Publicity,
@@ -853,11 +1068,11 @@ impl<'a, 'doc> Generator<'a> {
             EMPTY_DOCUMENT
         };
 
-        let head = if publicity.is_public() {
-            EXPORT_CLASS_SPACE_DOCUMENT
-        } else {
-            CLASS_SPACE_DOCUMENT
-        };
+        let head = self.declaration_keyword(
+            Declaration::Class,
+            publicity.is_public(),
+            constructor.name.clone(),
+        );
 
         let head = docvec![
             arena,
@@ -944,10 +1159,33 @@ impl<'a, 'doc> Generator<'a> {
             }
         }
 
+        // Functions that call each other in tail position are compiled into a
+        // single loop, so they can recurse without growing the stack.
+        let groups = mutually_tail_recursive_groups(
+            &self.module.name,
+            self.module
+                .definitions
+                .functions
+                .iter()
+                .filter(|function| self.is_function_generated(function)),
+        );
+
         for function in &self.module.definitions.functions {
-            if let Some(definition) = self.module_function(arena, function) {
+            let group = function
+                .name
+                .as_ref()
+                .and_then(|(_, name)| groups.iter().find(|group| group.contains(name)));
+
+            if let Some(definition) = self.module_function(arena, function, group) {
                 definitions.push(definition);
             }
+
+            // The loop is placed right after the first function of its group.
+            if let Some(group) = group
+                && function.name.as_ref().map(|(_, name)| name) == group.first()
+            {
+                definitions.push(self.mutually_recursive_functions(arena, group));
+            }
         }
 
         definitions
@@ -977,6 +1215,7 @@ impl<'a, 'doc> Generator<'a> {
         for function in &self.module.definitions.functions {
             if let Some((_, name)) = &function.name
                 && let Some((module, external_function, _)) = &function.external_javascript
+                && self.is_reachable(&self.module.name, name)
             {
                 self.register_external_function(
                     arena,
@@ -993,21 +1232,36 @@ impl<'a, 'doc> Generator<'a> {
     }
 
     fn import_path(&self, package: &str, module: &str) -> EcoString {
+        // Global scripts don't import files, they read the values registered
+        // by other scripts. All the packages share the same prelude script.
+        if self.module_format == ModuleFormat::Global {
+            let package = if package.is_empty() {
+                &self.module.type_info.package
+            } else {
+                package
+            };
+            return match module {
+                PRELUDE_MODULE_NAME => global::PRELUDE_KEY.into(),
+                _ => global::module_key(package, module),
+            };
+        }
+
+        let extension = self.module_format.extension();
         // TODO: strip shared prefixed between current module and imported
         // module to avoid descending and climbing back out again
         if package == self.module.type_info.package || package.is_empty() {
             // Same package
             match self.current_module_name_segments_count {
-                1 => eco_format!("./{module}.mjs"),
+                1 => eco_format!("./{module}.{extension}"),
                 _ => {
                     let prefix = "../".repeat(self.current_module_name_segments_count - 1);
-                    eco_format!("{prefix}{module}.mjs")
+                    eco_format!("{prefix}{module}.{extension}")
                 }
             }
         } else {
             // Different package
             let prefix = "../".repeat(self.current_module_name_segments_count);
-            eco_format!("{prefix}{package}/{module}.mjs")
+            eco_format!("{prefix}{package}/{module}.{extension}")
         }
     }
 
@@ -1035,13 +1289,17 @@ impl<'a, 'doc> Generator<'a> {
 
         let module_name = eco_format!("${module_name}");
         let path = self.import_path(package, module);
-        let unqualified_imports = unqualified.iter().map(|i| {
+        // Values that are not generated can't be imported either.
+        let unqualified_imports = unqualified.iter().filter_map(|i| {
+            if !self.is_reachable(module, &i.name) {
+                return None;
+            }
             let alias = i.as_name.as_ref().map(|n| {
                 self.register_in_scope(n);
                 maybe_escape_identifier(n).to_doc(arena)
             });
             let name = maybe_escape_identifier(&i.name);
-            Member { name, alias }
+            Some(Member { name, alias })
         });
 
         let aliases = if discarded { vec![] } else { vec![module_name] };
@@ -1069,9 +1327,20 @@ impl<'a, 'doc> Generator<'a> {
             },
         };
         if publicity.is_importable() {
-            imports.register_export(maybe_escape_identifier_string(name));
+            match self.module_format {
+                ModuleFormat::Esm => imports.register_export(maybe_escape_identifier_string(name)),
+                ModuleFormat::Cjs | ModuleFormat::Global => {
+                    self.exports.push(maybe_escape_identifier_string(name))
+                }
+            }
         }
-        imports.register_module(EcoString::from(module), [], [member]);
+        let path = match self.module_format {
+            ModuleFormat::Esm | ModuleFormat::Cjs => EcoString::from(module),
+            ModuleFormat::Global => {
+                global::ffi_key(&self.module.type_info.package, &self.module.name, module)
+            }
+        };
+        imports.register_module(path, [], [member]);
     }
 
     fn module_constant(
@@ -1093,15 +1362,16 @@ impl<'a, 'doc> Generator<'a> {
             .module
             .unused_definition_positions
             .contains(&location.start)
+            || !self.is_reachable(&self.module.name, name)
         {
             return None;
         }
 
-        let head = if publicity.is_private() {
-            CONST_SPACE_DOCUMENT
-        } else {
-            EXPORT_CONST_SPACE_DOCUMENT
-        };
+        let head = self.declaration_keyword(
+            Declaration::Const,
+            !publicity.is_private(),
+            maybe_escape_identifier(name),
+        );
 
         let mut generator = expression::Generator::new(
             self.module.name.clone(),
@@ -1147,29 +1417,42 @@ impl<'a, 'doc> Generator<'a> {
         let _ = self.module_scope.insert(name.into(), 0);
     }
 
-    fn module_function(
-        &mut self,
-        arena: &'doc DocumentArena<'a, 'doc>,
-        function: &'a TypedFunction,
-    ) -> Option<Document<'a, 'doc>> {
+    fn is_function_generated(&self, function: &TypedFunction) -> bool {
         // We don't generate any code for unused functions.
         if self
             .module
             .unused_definition_positions
             .contains(&function.location.start)
+            || function
+                .name
+                .as_ref()
+                .is_some_and(|(_, name)| !self.is_reachable(&self.module.name, name))
         {
-            return None;
+            return false;
         }
 
         // If there's an external JavaScript implementation then it will be imported,
         // so we don't need to generate a function definition.
         if function.external_javascript.is_some() {
-            return None;
+            return false;
         }
 
         // If the function does not support JavaScript then we don't need to generate
         // a function definition.
-        if !function.implementations.supports(Target::JavaScript) {
+        function.implementations.supports(Target::JavaScript)
+    }
+
+    /// Generates a module function. If the function is part of a group of
+    /// mutually recursive functions its code is in the loop shared by the
+    /// group, so the function just starts that loop.
+    ///
+    fn module_function(
+        &mut self,
+        arena: &'doc DocumentArena<'a, 'doc>,
+        function: &'a TypedFunction,
+        mutually_recursive_functions: Option<&Vec<EcoString>>,
+    ) -> Option<Document<'a, 'doc>> {
+        if !self.is_function_generated(function) {
             return None;
         }
         let function_source_mapping = self.source_map_tracker(arena, function.location.start);
@@ -1199,28 +1482,50 @@ impl<'a, 'doc> Generator<'a> {
             }
         };
 
-        let mut generator = expression::Generator::new(
-            self.module.name.clone(),
-            self.src_path.clone(),
-            self.line_numbers,
-            name.clone(),
-            argument_names,
-            &mut self.tracker,
-            self.module_scope.clone(),
-            self.source_map_builder.clone(),
+        let head = self.declaration_keyword(
+            Declaration::Function,
+            !function.publicity.is_private(),
+            maybe_escape_identifier(name),
         );
 
-        let head = if function.publicity.is_private() {
-            FUNCTION_SPACE_DOCUMENT
-        } else {
-            EXPORT_FUNCTION_SPACE_DOCUMENT
-        };
+        let (body, tail_recursion_used) = match mutually_recursive_functions {
+            Some(group) => {
+                let index = group
+                    .iter()
+                    .position(|function| function == name)
+                    .expect("mutually recursive function index");
+                let arguments = std::iter::once(index.to_doc(arena)).chain(
+                    fun_argument_names(&function.arguments, false).map(|name| name.to_doc(arena)),
+                );
+                let body = docvec![
+                    arena,
+                    RETURN_SPACE_DOCUMENT,
+                    mutually_recursive_loop_name(group),
+                    wrap_arguments(arena, arguments),
+                    SEMICOLON_DOCUMENT
+                ];
+                (body, false)
+            }
 
-        let body = generator.function_body(
-            arena,
-            function.body.as_slice(),
-            function.arguments.as_slice(),
-        );
+            None => {
+                let mut generator = expression::Generator::new(
+                    self.module.name.clone(),
+                    self.src_path.clone(),
+                    self.line_numbers,
+                    name.clone(),
+                    argument_names,
+                    &mut self.tracker,
+                    self.module_scope.clone(),
+                    self.source_map_builder.clone(),
+                );
+                let body = generator.function_body(
+                    arena,
+                    function.body.as_slice(),
+                    function.arguments.as_slice(),
+                );
+                (body, generator.tail_recursion_used)
+            }
+        };
 
         Some(docvec![
             arena,
@@ -1228,11 +1533,7 @@ impl<'a, 'doc> Generator<'a> {
             function_source_mapping,
             head,
             maybe_escape_identifier(name.as_str()),
-            fun_arguments(
-                arena,
-                function.arguments.as_slice(),
-                generator.tail_recursion_used
-            ),
+            fun_arguments(arena, function.arguments.as_slice(), tail_recursion_used),
             SPACE_OPEN_CURLY_DOCUMENT,
             docvec![arena, LINE_DOCUMENT, body]
                 .nest(arena, INDENT)
@@ -1243,6 +1544,119 @@ impl<'a, 'doc> Generator<'a> {
         ])
     }
 
+    /// Generates the loop shared by a group of mutually recursive functions.
+    /// The function to run is picked by its index in the group, and the
+    /// arguments it's called with are stored in numbered slots, so a tail call
+    /// to any of the functions in the group is just a matter of updating these
+    /// and going round the loop again.
+    ///
+    fn mutually_recursive_functions(
+        &mut self,
+        arena: &'doc DocumentArena<'a, 'doc>,
+        group: &[EcoString],
+    ) -> Document<'a, 'doc> {
+        let functions = group
+            .iter()
+            .filter_map(|name| {
+                self.module.definitions.functions.iter().find(|function| {
+                    function
+                        .name
+                        .as_ref()
+                        .is_some_and(|(_, function_name)| function_name == name)
+                })
+            })
+            .collect_vec();
+
+        let slots = functions
+            .iter()
+            .map(|function| function.arguments.len())
+            .max()
+            .unwrap_or(0);
+        let arguments = std::iter::once(eco_format!("loop$function").to_doc(arena))
+            .chain((0..slots).map(|i| eco_format!("loop${i}").to_doc(arena)));
+
+        let count = functions.len();
+        let mut branches = Vec::with_capacity(count);
+        for (index, function) in functions.into_iter().enumerate() {
+            let (_, name) = function
+                .name
+                .as_ref()
+                .expect("A module's function must be named");
+            let argument_names = function
+                .arguments
+                .iter()
+                .map(|arg| arg.names.get_variable_name())
+                .collect();
+            let mut generator = expression::Generator::new(
+                self.module.name.clone(),
+                self.src_path.clone(),
+                self.line_numbers,
+                name.clone(),
+                argument_names,
+                &mut self.tracker,
+                self.module_scope.clone(),
+                self.source_map_builder.clone(),
+            );
+            generator.mutually_recursive_functions = group;
+            let body = generator.mutually_recursive_function_body(
+                arena,
+                function.body.as_slice(),
+                function.arguments.as_slice(),
+            );
+
+            let condition = if index == 0 {
+                docvec![
+                    arena,
+                    IF_SPACE_OPEN_PAREN_DOCUMENT,
+                    "loop$function === ",
+                    index,
+                    CLOSE_PAREN_SPACE_OPEN_CURLY_DOCUMENT
+                ]
+            } else if index + 1 == count {
+                CLOSE_CURLY_ELSE_OPEN_CURLY_DOCUMENT
+            } else {
+                docvec![
+                    arena,
+                    "} else if (loop$function === ",
+                    index,
+                    CLOSE_PAREN_SPACE_OPEN_CURLY_DOCUMENT
+                ]
+            };
+            branches.push(docvec![
+                arena,
+                condition,
+                self.source_map_tracker(arena, function.location.start),
+                docvec![arena, LINE_DOCUMENT, body].nest(arena, INDENT),
+                LINE_DOCUMENT
+            ]);
+        }
+
+        docvec![
+            arena,
+            FUNCTION_SPACE_DOCUMENT,
+            mutually_recursive_loop_name(group),
+            wrap_arguments(arena, arguments),
+            SPACE_OPEN_CURLY_DOCUMENT,
+            docvec![
+                arena,
+                LINE_DOCUMENT,
+                WHILE_TRUE_OPEN_PAREN_DOCUMENT,
+                docvec![
+                    arena,
+                    LINE_DOCUMENT,
+                    arena.concat(branches),
+                    CLOSE_CURLY_DOCUMENT
+                ]
+                .nest(arena, INDENT),
+                LINE_DOCUMENT,
+                CLOSE_CURLY_DOCUMENT
+            ]
+            .nest(arena, INDENT),
+            LINE_DOCUMENT,
+            CLOSE_CURLY_DOCUMENT,
+        ]
+    }
+
     fn register_module_definitions_in_scope(&mut self) {
         for constant in &self.module.definitions.constants {
             self.register_in_scope(&constant.name);
@@ -1342,6 +1756,8 @@ pub struct ModuleConfig<'a> {
     pub stdlib_package: StdlibPackage,
     pub path: &'a Utf8Path,
     pub project_root: &'a Utf8Path,
+    pub reachable_definitions: Option<&'a ReachableDefinitions>,
+    pub module_format: ModuleFormat,
 }
 
 pub fn module(config: ModuleConfig<'_>) -> (String, Option<SourceMap>) {
@@ -1365,9 +1781,9 @@ pub fn module(config: ModuleConfig<'_>) -> (String, Option<SourceMap>) {
     (output, source_map)
 }
 
-pub fn ts_declaration(module: &TypedModule) -> String {
+pub fn ts_declaration(module: &TypedModule, module_format: ModuleFormat) -> String {
     let arena = DocumentArena::new();
-    let document = typescript::TypeScriptGenerator::new(module).compile(&arena);
+    let document = typescript::TypeScriptGenerator::new(module, module_format).compile(&arena);
     document.to_pretty_string(80)
 }
 
@@ -1392,27 +1808,43 @@ fn fun_arguments<'a, 'doc>(
     arguments: &'a [TypedArg],
     tail_recursion_used: bool,
 ) -> Document<'a, 'doc> {
-    let mut discards = 0;
     wrap_arguments(
         arena,
-        arguments
-            .iter()
-            .map(|argument| match argument.get_variable_name() {
-                None => {
-                    let doc = if discards == 0 {
-                        UNDERSCORE_DOCUMENT
-                    } else {
-                        eco_format!("_{discards}").to_doc(arena)
-                    };
-                    discards += 1;
-                    doc
-                }
-                Some(name) if tail_recursion_used => eco_format!("loop${name}").to_doc(arena),
-                Some(name) => maybe_escape_identifier(name).to_doc(arena),
-            }),
+        fun_argument_names(arguments, tail_recursion_used).map(|name| name.to_doc(arena)),
     )
 }
 
+fn fun_argument_names(
+    arguments: &[TypedArg],
+    tail_recursion_used: bool,
+) -> impl Iterator<Item = EcoString> {
+    let mut discards = 0;
+    arguments
+        .iter()
+        .map(move |argument| match argument.get_variable_name() {
+            None => {
+                let name = if discards == 0 {
+                    "_".into()
+                } else {
+                    eco_format!("_{discards}")
+                };
+                discards += 1;
+                name
+            }
+            Some(name) if tail_recursion_used => eco_format!("loop${name}"),
+            Some(name) => maybe_escape_identifier(name),
+        })
+}
+
+/// The name of the function holding the loop shared by a group of mutually
+/// recursive functions. Gleam names can't contain a `$`, so this can't clash
+/// with any other definition.
+///
+fn mutually_recursive_loop_name(group: &[EcoString]) -> EcoString {
+    let first = group.first().expect("mutually recursive functions");
+    eco_format!("{}$loop", maybe_escape_identifier(first))
+}
+
 fn wrap_arguments<'a, 'doc>(
     arena: &'doc DocumentArena<'a, 'doc>,
     arguments: impl IntoIterator<Item = Document<'a, 'doc>>,
@@ -1543,7 +1975,9 @@ fn escape_identifier(word: &str) -> EcoString {
     eco_format!("{word}$")
 }
 
-fn maybe_escape_identifier(word: &str) -> EcoString {
+/// The name a Gleam value is exported with from a generated JavaScript module.
+///
+pub fn maybe_escape_identifier(word: &str) -> EcoString {
     if is_usable_js_identifier(word) {
         EcoString::from(word)
     } else {
@@ -244,6 +244,11 @@ pub(crate) struct Generator<'module, 'ast, 'doc> {
     // at the top level of the function to use in place of pushing new stack
     // frames.
     pub tail_recursion_used: bool,
+    /// The module functions compiled into the same loop as the current
+    /// function, because they call each other in tail position. Calling one of
+    /// them in tail position jumps to its code in the loop instead of pushing a
+    /// new stack frame.
+    pub mutually_recursive_functions: &'module [EcoString],
     /// Statements to be compiled when lifting blocks into statement scope.
     /// For example, when compiling the following code:
     /// ```gleam
@@ -309,6 +314,7 @@ impl<'module, 'a, 'doc> Generator<'module, 'a, 'doc> {
             function_name,
             function_arguments,
             tail_recursion_used: false,
+            mutually_recursive_functions: &[],
             current_scope,
             current_function,
             function_position: Position::Tail,
@@ -380,6 +386,34 @@ impl<'module, 'a, 'doc> Generator<'module, 'a, 'doc> {
         ]
     }
 
+    /// The body of a function compiled into the loop it shares with the
+    /// functions it is mutually recursive with. Its arguments are read from the
+    /// loop's numbered argument slots.
+    ///
+    pub fn mutually_recursive_function_body(
+        &mut self,
+        arena: &'doc DocumentArena<'a, 'doc>,
+        body: &'a [TypedStatement],
+        arguments: &'a [TypedArg],
+    ) -> Document<'a, 'doc> {
+        let assignments = arena.concat(arguments.iter().enumerate().flat_map(|(i, arg)| {
+            arg.get_variable_name().map(|name| {
+                docvec![
+                    arena,
+                    self.source_map_tracker(arena, arg.location.start),
+                    LET_SPACE_DOCUMENT,
+                    maybe_escape_identifier(name),
+                    SPACE_EQUAL_LOOP_DOLLAR_DOCUMENT,
+                    i,
+                    SEMICOLON_DOCUMENT,
+                    LINE_DOCUMENT
+                ]
+            })
+        }));
+        let body = self.statements(arena, body);
+        docvec![arena, assignments, body]
+    }
+
     fn statement(
         &mut self,
         arena: &'doc DocumentArena<'a, 'doc>,
@@ -1891,6 +1925,51 @@ impl<'module, 'a, 'doc> Generator<'module, 'a, 'doc> {
                 self.wrap_return(arena, construct_record(arena, None, name, arguments))
             }
 
+            // Mutual tail call optimisation. If we are calling a function that
+            // shares the current function's loop, and we are in tail position,
+            // we can jump to its code by setting the function to run and its
+            // arguments, and going round the loop again.
+            TypedExpr::Var {
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                        ..
+                    },
+                ..
+            } if *module == self.module_name
+                && !matches!(self.current_function, CurrentFunction::Anonymous)
+                && self.function_position.is_tail()
+                && self.mutually_recursive_functions.contains(name) =>
+            {
+                let index = self
+                    .mutually_recursive_functions
+                    .iter()
+                    .position(|function| function == name)
+                    .expect("mutually recursive function index");
+                let function = docvec![
+                    arena,
+                    LOOP_DOLLAR_DOCUMENT,
+                    "function",
+                    SPACE_EQUAL_SPACE_DOCUMENT,
+                    index,
+                    SEMICOLON_DOCUMENT
+                ];
+                // Every argument is rendered and assigned to its slot, even
+                // if the function discards it, as it may have side effects.
+                let arguments = arguments.into_iter().enumerate().map(|(i, argument)| {
+                    docvec![
+                        arena,
+                        LINE_DOCUMENT,
+                        LOOP_DOLLAR_DOCUMENT,
+                        i,
+                        SPACE_EQUAL_SPACE_DOCUMENT,
+                        argument,
+                        SEMICOLON_DOCUMENT
+                    ]
+                });
+                docvec![arena, function, arena.concat(arguments)]
+            }
+
             // Tail call optimisation. If we are calling the current function
             // and we are in tail position we can avoid creating a new stack
             // frame, enabling recursion with constant memory usage.
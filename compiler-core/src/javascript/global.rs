@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! The global module format, for browsers loading plain scripts.
+//!
+//! There is no module system for the scripts to import each other with, so
+//! each script registers its values on the `globalThis.Gleam` object, under the
+//! path of the script relative to the build directory without its extension,
+//! and reads the values of the scripts it depends on from there. The scripts
+//! must then be loaded in order, each one after the scripts it depends on.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use camino::Utf8Path;
+use ecow::{EcoString, eco_format};
+use regex::Regex;
+
+use crate::{Result, io::FileSystemReader};
+
+use super::tree_shaking::resolve_import;
+
+/// The key the prelude script registers its values under.
+///
+pub const PRELUDE_KEY: &str = "prelude";
+
+/// The key a compiled Gleam module registers its values under.
+///
+pub fn module_key(package: &str, module: &str) -> EcoString {
+    eco_format!("{package}/{module}")
+}
+
+/// The key an FFI script used by a Gleam module registers its values under.
+/// Bare specifiers don't refer to a file, so they are used as they are.
+///
+pub fn ffi_key(package: &str, module: &str, specifier: &str) -> EcoString {
+    let module_path = Utf8Path::new(package).join(module);
+    let directory = module_path.parent().unwrap_or(Utf8Path::new(package));
+    match resolve_import(directory, specifier) {
+        Some(path) => path
+            .with_extension("")
+            .components()
+            .map(|component| component.as_str())
+            .collect::<Vec<_>>()
+            .join("/")
+            .into(),
+        None => specifier.into(),
+    }
+}
+
+static REFERENCE_PATTERN: OnceLock<Regex> = OnceLock::new();
+
+/// Finds the keys of the scripts whose values some JavaScript code reads from
+/// `globalThis.Gleam`.
+///
+pub fn references(source: &str) -> Vec<EcoString> {
+    let pattern = REFERENCE_PATTERN.get_or_init(|| {
+        Regex::new(r#"\bglobalThis\.Gleam\[\s*["'](?P<key>[^"'\n]+)["']\s*\]"#)
+            .expect("Regex is correct")
+    });
+
+    pattern
+        .captures_iter(source)
+        .map(|captures| EcoString::from(&captures["key"]))
+        .collect()
+}
+
+/// The keys of the scripts that must be loaded to run the script with the
+/// given key, each one coming after the scripts it depends on. Keys with no
+/// script in the build directory are left out, as they are expected to be
+/// registered by some other code.
+///
+pub fn load_order(
+    io: &impl FileSystemReader,
+    build_directory: &Utf8Path,
+    key: &str,
+) -> Result<Vec<EcoString>> {
+    fn visit(
+        io: &impl FileSystemReader,
+        build_directory: &Utf8Path,
+        key: &str,
+        visited: &mut HashSet<EcoString>,
+        order: &mut Vec<EcoString>,
+    ) -> Result<()> {
+        if !visited.insert(key.into()) {
+            return Ok(());
+        }
+        let path = build_directory.join(format!("{key}.js"));
+        if !io.is_file(&path) {
+            return Ok(());
+        }
+        for reference in references(&io.read(&path)?) {
+            visit(io, build_directory, &reference, visited, order)?;
+        }
+        order.push(key.into());
+        Ok(())
+    }
+
+    let mut order = vec![];
+    visit(io, build_directory, key, &mut HashSet::new(), &mut order)?;
+    Ok(order)
+}
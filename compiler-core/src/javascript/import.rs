@@ -6,6 +6,7 @@ use std::collections::{HashMap, HashSet};
 use ecow::EcoString;
 use itertools::Itertools;
 
+use crate::build::ModuleFormat;
 use crate::javascript::{INDENT, JavaScriptCodegenTarget};
 use pretty_arena::*;
 
@@ -41,16 +42,32 @@ impl<'a, 'doc> Imports<'a, 'doc> {
         import.unqualified.extend(unqualified_imports);
     }
 
+    /// TypeScript declaration files always use `import` statements, while
+    /// CommonJS JavaScript modules use `require` instead, and global scripts
+    /// read the values registered on `globalThis.Gleam`.
+    ///
     pub fn into_doc(
         self,
         arena: &'doc DocumentArena<'a, 'doc>,
         codegen_target: JavaScriptCodegenTarget,
+        module_format: ModuleFormat,
     ) -> Document<'a, 'doc> {
         let imports = arena.concat(
             self.imports
                 .into_values()
                 .sorted_by(|a, b| a.path.cmp(&b.path))
-                .map(|import| import.into_doc(arena, codegen_target)),
+                .map(|import| match (codegen_target, module_format) {
+                    (JavaScriptCodegenTarget::JavaScript, ModuleFormat::Cjs) => {
+                        import.into_require_doc(arena)
+                    }
+                    (JavaScriptCodegenTarget::JavaScript, ModuleFormat::Global) => {
+                        import.into_global_doc(arena)
+                    }
+                    (JavaScriptCodegenTarget::JavaScript, ModuleFormat::Esm)
+                    | (JavaScriptCodegenTarget::TypeScriptDeclarations, _) => {
+                        import.into_doc(arena, codegen_target)
+                    }
+                }),
         );
 
         if self.exports.is_empty() {
@@ -181,6 +198,88 @@ impl<'a, 'doc> Import<'a, 'doc> {
             ]
         }
     }
+
+    fn into_require_doc(self, arena: &'doc DocumentArena<'a, 'doc>) -> Document<'a, 'doc> {
+        let path = self.path.to_doc(arena);
+        let alias_imports = arena.concat(self.aliases.into_iter().sorted().map(|alias| {
+            docvec![
+                arena,
+                CONST_SPACE_DOCUMENT,
+                alias,
+                SPACE_EQUAL_SPACE_REQUIRE_OPEN_PAREN_DOUBLE_QUOTE_DOCUMENT,
+                path,
+                DOUBLE_QUOTE_CLOSE_PAREN_SEMICOLON_DOCUMENT,
+                LINE_DOCUMENT
+            ]
+        }));
+        if self.unqualified.is_empty() {
+            alias_imports
+        } else {
+            let members = self
+                .unqualified
+                .into_iter()
+                .map(|member| member.into_destructuring_doc(arena));
+            let members = arena.join(members, COMMA_BREAK_DOCUMENT);
+            let members = docvec![
+                arena,
+                docvec![arena, BREAKABLE_SPACE_DOCUMENT, members].nest(arena, INDENT),
+                TRAILING_COMMA_OR_SPACE_BREAK_DOCUMENT
+            ]
+            .group(arena);
+            docvec![
+                arena,
+                alias_imports,
+                CONST_SPACE_DOCUMENT,
+                OPEN_CURLY_DOCUMENT,
+                members,
+                CLOSE_CURLY_SPACE_EQUAL_SPACE_REQUIRE_OPEN_PAREN_DOUBLE_QUOTE_DOCUMENT,
+                path,
+                DOUBLE_QUOTE_CLOSE_PAREN_SEMICOLON_DOCUMENT,
+                LINE_DOCUMENT
+            ]
+        }
+    }
+
+    fn into_global_doc(self, arena: &'doc DocumentArena<'a, 'doc>) -> Document<'a, 'doc> {
+        let key = self.path.to_doc(arena);
+        let alias_imports = arena.concat(self.aliases.into_iter().sorted().map(|alias| {
+            docvec![
+                arena,
+                CONST_SPACE_DOCUMENT,
+                alias,
+                SPACE_EQUAL_SPACE_GLOBAL_THIS_DOT_GLEAM_OPEN_SQUARE_DOUBLE_QUOTE_DOCUMENT,
+                key,
+                DOUBLE_QUOTE_CLOSE_SQUARE_SEMICOLON_DOCUMENT,
+                LINE_DOCUMENT
+            ]
+        }));
+        if self.unqualified.is_empty() {
+            alias_imports
+        } else {
+            let members = self
+                .unqualified
+                .into_iter()
+                .map(|member| member.into_destructuring_doc(arena));
+            let members = arena.join(members, COMMA_BREAK_DOCUMENT);
+            let members = docvec![
+                arena,
+                docvec![arena, BREAKABLE_SPACE_DOCUMENT, members].nest(arena, INDENT),
+                TRAILING_COMMA_OR_SPACE_BREAK_DOCUMENT
+            ]
+            .group(arena);
+            docvec![
+                arena,
+                alias_imports,
+                CONST_SPACE_DOCUMENT,
+                OPEN_CURLY_DOCUMENT,
+                members,
+                CLOSE_CURLY_SPACE_EQUAL_SPACE_GLOBAL_THIS_DOT_GLEAM_OPEN_SQUARE_DOUBLE_QUOTE_DOCUMENT,
+                key,
+                DOUBLE_QUOTE_CLOSE_SQUARE_SEMICOLON_DOCUMENT,
+                LINE_DOCUMENT
+            ]
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -196,6 +295,13 @@ impl<'a, 'doc> Member<'a, 'doc> {
             Some(alias) => docvec![arena, self.name, SPACE_AS_SPACE_DOCUMENT, alias],
         }
     }
+
+    fn into_destructuring_doc(self, arena: &'doc DocumentArena<'a, 'doc>) -> Document<'a, 'doc> {
+        match self.alias {
+            None => self.name.to_doc(arena),
+            Some(alias) => docvec![arena, self.name, COLON_SPACE_DOCUMENT, alias],
+        }
+    }
 }
 
 #[test]
@@ -271,7 +377,11 @@ fn into_doc() {
         LINE_DOCUMENT
             .append(
                 &arena,
-                imports.into_doc(&arena, JavaScriptCodegenTarget::JavaScript)
+                imports.into_doc(
+                    &arena,
+                    JavaScriptCodegenTarget::JavaScript,
+                    ModuleFormat::Esm
+                )
             )
             .to_pretty_string(40),
         r#"
@@ -291,3 +401,60 @@ import { one, two } from "./zzz";
         .to_string()
     );
 }
+
+#[test]
+fn into_require_doc() {
+    let arena = DocumentArena::new();
+    let mut imports = Imports::new();
+    imports.register_module("./gleam/empty".into(), [], []);
+    imports.register_module("./multiple/times".into(), ["wibble".into()], []);
+    imports.register_module(
+        "./multiple/times".into(),
+        [],
+        [Member {
+            name: "one".into(),
+            alias: None,
+        }],
+    );
+    imports.register_module(
+        "./other".into(),
+        [],
+        [
+            Member {
+                name: "one".into(),
+                alias: None,
+            },
+            Member {
+                name: "two".into(),
+                alias: Some("twoo".to_doc(&arena)),
+            },
+            Member {
+                name: "three".into(),
+                alias: None,
+            },
+        ],
+    );
+
+    assert_eq!(
+        LINE_DOCUMENT
+            .append(
+                &arena,
+                imports.into_doc(
+                    &arena,
+                    JavaScriptCodegenTarget::JavaScript,
+                    ModuleFormat::Cjs
+                )
+            )
+            .to_pretty_string(20),
+        r#"
+const wibble = require("./multiple/times");
+const { one } = require("./multiple/times");
+const {
+  one,
+  two: twoo,
+  three,
+} = require("./other");
+"#
+        .to_string()
+    );
+}
@@ -20,12 +20,15 @@ mod blocks;
 mod bools;
 mod case;
 mod case_clause_guards;
+mod commonjs;
+mod constant_folding;
 mod consts;
 mod custom_types;
 mod echo;
 mod externals;
 mod functions;
 mod generics;
+mod global;
 mod inlining;
 mod lists;
 mod modules;
@@ -38,6 +41,7 @@ mod results;
 mod sourcemaps;
 mod strings;
 mod todo;
+mod tree_shaking;
 mod tuples;
 mod type_alias;
 mod typescript;
@@ -153,6 +157,18 @@ macro_rules! assert_ts_def {
 }
 
 pub fn compile(src: &str, deps: Vec<(&str, &str, &str)>) -> TypedModule {
+    let (module, _dependencies) = compile_with_dependencies(src, deps);
+    module
+}
+
+/// Compiles a module along with the modules it depends on, returning the typed
+/// AST of all of them.
+///
+pub fn compile_with_dependencies(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+) -> (TypedModule, Vec<TypedModule>) {
+    let mut dependencies = vec![];
     let mut modules = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
     // DUPE: preludeinsertion
@@ -191,8 +207,9 @@ pub fn compile(src: &str, deps: Vec<(&str, &str, &str)>) -> TypedModule {
         }
         .infer_module(ast, line_numbers, "".into())
         .expect("should successfully infer");
-        let _ = modules.insert((*dep_name).into(), dep.type_info);
+        let _ = modules.insert((*dep_name).into(), dep.type_info.clone());
         let _ = direct_dependencies.insert((*dep_package).into(), ());
+        dependencies.push(dep);
     });
 
     let parsed =
@@ -218,15 +235,32 @@ pub fn compile(src: &str, deps: Vec<(&str, &str, &str)>) -> TypedModule {
     .infer_module(ast, line_numbers, "src/module.gleam".into())
     .expect("should successfully infer");
 
-    inline::module(module, &modules)
+    (inline::module(module, &modules), dependencies)
 }
 
 pub fn compile_js(src: &str, deps: Vec<(&str, &str, &str)>) -> String {
+    compile_js_with_module_format(src, deps, ModuleFormat::Esm)
+}
+
+pub fn compile_js_with_module_format(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    module_format: ModuleFormat,
+) -> String {
     let ast = compile(src, deps);
+    generate_js(&ast, src, module_format)
+}
+
+pub fn compile_js_with_constant_folding(src: &str, deps: Vec<(&str, &str, &str)>) -> String {
+    let ast = crate::constant_folding::module(compile(src, deps));
+    generate_js(&ast, src, ModuleFormat::Esm)
+}
+
+fn generate_js(ast: &TypedModule, src: &str, module_format: ModuleFormat) -> String {
     let line_numbers = LineNumbers::new(src);
     let stdlib_package = StdlibPackage::Present;
     let (output, _) = module(ModuleConfig {
-        module: &ast,
+        module: ast,
         line_numbers: &line_numbers,
         src: &"".into(),
         typescript: TypeScriptDeclarations::None,
@@ -234,6 +268,8 @@ pub fn compile_js(src: &str, deps: Vec<(&str, &str, &str)>) -> String {
         stdlib_package,
         path: Utf8Path::new("src/module.gleam"),
         project_root: "project/root".into(),
+        reachable_definitions: None,
+        module_format,
     });
 
     output.replace(
@@ -255,6 +291,8 @@ pub fn compile_js_with_source_map(src: &str, deps: Vec<(&str, &str, &str)>) -> (
         stdlib_package,
         path: Utf8Path::new("src/module.gleam"),
         project_root: "project/root".into(),
+        reachable_definitions: None,
+        module_format: ModuleFormat::Esm,
     });
     let source_map = source_map.expect("source map should always be present");
 
@@ -266,8 +304,16 @@ pub fn compile_js_with_source_map(src: &str, deps: Vec<(&str, &str, &str)>) -> (
 }
 
 pub fn compile_ts(src: &str, deps: Vec<(&str, &str, &str)>) -> String {
+    compile_ts_with_module_format(src, deps, ModuleFormat::Esm)
+}
+
+pub fn compile_ts_with_module_format(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    module_format: ModuleFormat,
+) -> String {
     let ast = compile(src, deps);
-    ts_declaration(&ast)
+    ts_declaration(&ast, module_format)
 }
 
 // Append zero indexed line numbers to the code for easier reading of source maps.
@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use crate::build::ModuleFormat;
+use crate::javascript::tests::{
+    CURRENT_PACKAGE, compile_js_with_module_format, compile_ts_with_module_format,
+};
+
+macro_rules! assert_cjs {
+    ($(($name:literal, $module_src:literal)),+, $src:literal $(,)?) => {
+        let compiled = compile_js_with_module_format(
+            $src,
+            vec![$((CURRENT_PACKAGE, $name, $module_src)),*],
+            ModuleFormat::Cjs,
+        );
+        let mut output = String::from("----- SOURCE CODE\n");
+        for (name, src) in [$(($name, $module_src)),*] {
+            output.push_str(&format!("-- {name}.gleam\n{src}\n\n"));
+        }
+        output.push_str(&format!("-- main.gleam\n{}\n\n----- COMPILED JAVASCRIPT\n", $src));
+        output.push_str(&compiled);
+
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+
+    ($src:literal $(,)?) => {
+        let compiled = compile_js_with_module_format($src, vec![], ModuleFormat::Cjs);
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED JAVASCRIPT\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+macro_rules! assert_cts_def {
+    ($(($name:literal, $module_src:literal)),+, $src:literal $(,)?) => {
+        let compiled = compile_ts_with_module_format(
+            $src,
+            vec![$((CURRENT_PACKAGE, $name, $module_src)),*],
+            ModuleFormat::Cjs,
+        );
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- TYPESCRIPT DEFINITIONS\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[test]
+fn public_definitions_are_assigned_to_module_exports() {
+    assert_cjs!(
+        r#"
+pub const wibble = 1
+
+const wobble = 2
+
+pub fn main() {
+  wibble + wobble + private()
+}
+
+fn private() {
+  3
+}
+"#
+    );
+}
+
+#[test]
+fn custom_type_definitions_are_assigned_to_module_exports() {
+    assert_cjs!(
+        r#"
+pub type Wibble {
+  Wibble(a: Int, b: Int)
+  Wobble(a: Int)
+  Wubble
+}
+"#
+    );
+}
+
+#[test]
+fn private_custom_type_definitions_are_not_exported() {
+    assert_cjs!(
+        r#"
+type Wibble {
+  Wibble(Int)
+  Wobble
+}
+
+pub fn main() {
+  case Wibble(1) {
+    Wibble(x) -> x
+    Wobble -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn imports_are_required() {
+    assert_cjs!(
+        (
+            "wibble",
+            r#"
+pub type Wibble {
+  Wibble
+  Wobble
+}
+
+pub fn wibble() { 1 }
+"#
+        ),
+        r#"
+import wibble.{Wobble, wibble as alias}
+
+pub fn main() {
+  #(wibble.wibble(), alias(), Wobble)
+}
+"#
+    );
+}
+
+#[test]
+fn prelude_values_are_required() {
+    assert_cjs!(
+        r#"
+pub fn main() {
+  #(Ok([1, 2]), Error(Nil))
+}
+"#
+    );
+}
+
+#[test]
+fn external_functions_are_required_and_exported() {
+    assert_cjs!(
+        r#"
+@external(javascript, "./wibble_ffi.cjs", "wibble")
+pub fn wibble() -> Int
+
+@external(javascript, "./wibble_ffi.cjs", "wobble")
+fn wobble() -> Int
+
+@external(javascript, "./wibble_ffi.cjs", "external")
+pub fn class() -> Int
+
+pub fn main() {
+  wibble() + wobble() + class()
+}
+"#
+    );
+}
+
+#[test]
+fn empty_module() {
+    assert_cjs!(
+        r#"
+type Wibble
+"#
+    );
+}
+
+#[test]
+fn typescript_declarations_import_cts_declarations() {
+    assert_cts_def!(
+        (
+            "wibble",
+            r#"
+pub type Wibble {
+  Wibble
+}
+"#
+        ),
+        r#"
+import wibble
+
+pub fn main() -> #(wibble.Wibble, Result(Int, Nil)) {
+  #(wibble.Wibble, Ok(1))
+}
+"#
+    );
+}
+
+#[test]
+fn prelude_exports_all_of_its_definitions() {
+    let prelude = crate::javascript::prelude(ModuleFormat::Cjs);
+    let (declarations, exports) = prelude
+        .rsplit_once("module.exports = { ")
+        .expect("module.exports assignment");
+
+    assert!(!declarations.contains("export "));
+    for name in [
+        "CustomType",
+        "List",
+        "toList",
+        "prepend",
+        "Result",
+        "isEqual",
+    ] {
+        assert!(
+            exports
+                .split(", ")
+                .any(|export| export.trim_end_matches(" };\n") == name),
+            "{name} is not exported"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+macro_rules! assert_folded_js {
+    ($src:expr $(,)?) => {{
+        let compiled = $crate::javascript::tests::compile_js_with_constant_folding($src, vec![]);
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED JAVASCRIPT\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+}
+
+#[test]
+fn int_operators_are_folded() {
+    assert_folded_js!(
+        "
+pub fn main() {
+  #(1 + 2 * 3 - 4 / 2, 7 % 3, -5 / 2, 10 / 0, 10 % 0)
+}
+"
+    );
+}
+
+#[test]
+fn ints_outside_of_the_safe_range_are_not_folded() {
+    assert_folded_js!(
+        "
+pub fn main() {
+  9_007_199_254_740_991 + 1
+}
+"
+    );
+}
+
+#[test]
+fn string_concatenation_is_folded() {
+    assert_folded_js!(
+        r#"
+pub fn main() {
+  "Hello, " <> "Joe" <> "!"
+}
+"#
+    );
+}
+
+#[test]
+fn strings_with_escapes_are_not_compared() {
+    assert_folded_js!(
+        r#"
+pub fn main() {
+  #("\u{61}" == "a", "a" == "a")
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_a_known_constructor_binds_its_fields() {
+    assert_folded_js!(
+        "
+pub fn main(x) {
+  case Ok(#(x, 1)) {
+    Ok(#(a, b)) -> a + b
+    Error(_) -> 0
+  }
+}
+"
+    );
+}
+
+#[test]
+fn case_on_a_list_literal() {
+    assert_folded_js!(
+        "
+pub fn main() {
+  case [1, 2, 3] {
+    [] -> []
+    [first, ..rest] -> [first * 2, ..rest]
+  }
+}
+"
+    );
+}
+
+#[test]
+fn case_on_a_string_prefix() {
+    assert_folded_js!(
+        r#"
+pub fn main() {
+  case "Hello, Joe" {
+    "Hello, " <> name -> name
+    _ -> "stranger"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_clauses_are_dropped_when_some_subjects_are_known() {
+    assert_folded_js!(
+        "
+pub fn main(x) {
+  case True, x {
+    False, _ -> 0
+    True, 1 -> 1
+    _, _ -> 2
+  }
+}
+"
+    );
+}
+
+#[test]
+fn case_on_a_subject_with_side_effects_is_not_folded() {
+    assert_folded_js!(
+        "
+pub fn main() {
+  case wibble() {
+    1 -> 1
+    _ -> 2
+  }
+}
+
+fn wibble() {
+  1
+}
+"
+    );
+}
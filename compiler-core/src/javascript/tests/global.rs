@@ -0,0 +1,288 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use camino::Utf8Path;
+
+use crate::build::ModuleFormat;
+use crate::io::{FileSystemWriter, memory::InMemoryFileSystem};
+use crate::javascript::global::{ffi_key, load_order, references};
+use crate::javascript::reachable_definitions;
+use crate::javascript::tests::{
+    CURRENT_PACKAGE, compile_js_with_module_format, compile_ts_with_module_format,
+    compile_with_dependencies,
+};
+
+macro_rules! assert_global {
+    ($(($name:literal, $module_src:literal)),+, $src:literal $(,)?) => {
+        let compiled = compile_js_with_module_format(
+            $src,
+            vec![$((CURRENT_PACKAGE, $name, $module_src)),*],
+            ModuleFormat::Global,
+        );
+        let mut output = String::from("----- SOURCE CODE\n");
+        for (name, src) in [$(($name, $module_src)),*] {
+            output.push_str(&format!("-- {name}.gleam\n{src}\n\n"));
+        }
+        output.push_str(&format!("-- main.gleam\n{}\n\n----- COMPILED JAVASCRIPT\n", $src));
+        output.push_str(&compiled);
+
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+
+    ($src:literal $(,)?) => {
+        let compiled = compile_js_with_module_format($src, vec![], ModuleFormat::Global);
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED JAVASCRIPT\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[test]
+fn public_definitions_are_registered_globally() {
+    assert_global!(
+        r#"
+pub const wibble = 1
+
+const wobble = 2
+
+pub type Wibble {
+  Wibble(a: Int)
+}
+
+pub fn main() {
+  wibble + wobble + private()
+}
+
+fn private() {
+  3
+}
+"#
+    );
+}
+
+#[test]
+fn imports_are_read_from_the_global_object() {
+    assert_global!(
+        (
+            "wibble",
+            r#"
+pub type Wibble {
+  Wibble
+  Wobble
+}
+
+pub fn wibble() { 1 }
+"#
+        ),
+        r#"
+import wibble.{Wobble, wibble as alias}
+
+pub fn main() {
+  #(wibble.wibble(), alias(), Wobble)
+}
+"#
+    );
+}
+
+#[test]
+fn prelude_values_are_read_from_the_global_object() {
+    assert_global!(
+        r#"
+pub fn main() {
+  #(Ok([1, 2]), Error(Nil))
+}
+"#
+    );
+}
+
+#[test]
+fn external_functions_are_read_from_the_global_object() {
+    assert_global!(
+        r#"
+@external(javascript, "./wibble_ffi.js", "wibble")
+pub fn wibble() -> Int
+
+@external(javascript, "../wobble_ffi.js", "wobble")
+fn wobble() -> Int
+
+@external(javascript, "some_library", "external")
+pub fn class() -> Int
+
+pub fn main() {
+  wibble() + wobble() + class()
+}
+"#
+    );
+}
+
+#[test]
+fn empty_module() {
+    assert_global!(
+        r#"
+type Wibble
+"#
+    );
+}
+
+#[test]
+fn typescript_declarations_import_ts_declarations() {
+    let src = r#"
+pub fn main() -> Result(Int, Nil) {
+  Ok(1)
+}
+"#;
+    let compiled = compile_ts_with_module_format(src, vec![], ModuleFormat::Global);
+    let output = format!("----- SOURCE CODE\n{src}\n\n----- TYPESCRIPT DEFINITIONS\n{compiled}");
+    insta::assert_snapshot!(insta::internals::AutoName, output, src);
+}
+
+#[test]
+fn prelude_registers_all_of_its_definitions() {
+    let prelude = crate::javascript::prelude(ModuleFormat::Global);
+    let (declarations, exports) = prelude
+        .rsplit_once("(globalThis.Gleam ??= {})[\"prelude\"] = { ")
+        .expect("globalThis.Gleam assignment");
+
+    assert!(prelude.starts_with("(() => {\n\"use strict\";\n"));
+    assert!(prelude.ends_with("})();\n"));
+    assert!(!declarations.contains("export "));
+    for name in ["CustomType", "List", "toList", "prepend", "isEqual"] {
+        assert!(
+            exports
+                .split(", ")
+                .any(|export| export.trim_end_matches(" };\n})();\n") == name),
+            "{name} is not registered"
+        );
+    }
+}
+
+#[test]
+fn ffi_keys_are_paths_relative_to_the_build_directory() {
+    assert_eq!(
+        ffi_key("wibble", "wobble", "./wobble_ffi.js"),
+        "wibble/wobble_ffi"
+    );
+    assert_eq!(
+        ffi_key("wibble", "wobble/wubble", "./wubble_ffi.js"),
+        "wibble/wobble/wubble_ffi"
+    );
+    assert_eq!(
+        ffi_key("wibble", "wobble/wubble", "../ffi/wubble.js"),
+        "wibble/ffi/wubble"
+    );
+    assert_eq!(ffi_key("wibble", "wobble", "some_library"), "some_library");
+}
+
+#[test]
+fn references_to_the_global_object() {
+    assert_eq!(
+        references(
+            r#"
+const { Ok } = globalThis.Gleam["prelude"];
+const $wibble = globalThis.Gleam['wibble/wibble'];
+(globalThis.Gleam ??= {})["wibble/wibble_ffi"] = { Ok };
+"#
+        ),
+        vec!["prelude", "wibble/wibble"]
+    );
+}
+
+#[test]
+fn scripts_are_loaded_after_the_scripts_they_depend_on() {
+    let fs = InMemoryFileSystem::new();
+    let build = Utf8Path::new("/build");
+    for (key, source) in [
+        ("prelude", ""),
+        (
+            "app/app",
+            r#"
+const $wibble = globalThis.Gleam["wibble/wibble"];
+const { Ok } = globalThis.Gleam["prelude"];
+"#,
+        ),
+        (
+            "wibble/wibble",
+            r#"
+const { wobble } = globalThis.Gleam["wibble/wibble_ffi"];
+const { CustomType } = globalThis.Gleam["prelude"];
+"#,
+        ),
+        (
+            "wibble/wibble_ffi",
+            r#"
+const { Ok } = globalThis.Gleam["prelude"];
+const library = globalThis.Gleam["some_library"];
+"#,
+        ),
+        ("unused/unused", ""),
+    ] {
+        fs.write(&build.join(format!("{key}.js")), source)
+            .expect("write script");
+    }
+
+    assert_eq!(
+        load_order(&fs, build, "app/app").expect("load order"),
+        vec!["prelude", "wibble/wibble_ffi", "wibble/wibble", "app/app"]
+    );
+}
+
+#[test]
+fn values_read_by_ffi_scripts_are_reachable() {
+    let (module, dependencies) = compile_with_dependencies(
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.external()
+}
+"#,
+        vec![(
+            CURRENT_PACKAGE,
+            "wibble",
+            r#"
+@external(javascript, "./wibble_ffi.js", "external")
+pub fn external() -> Int
+
+pub fn called_by_ffi() { 1 }
+
+pub fn unused() { 2 }
+"#,
+        )],
+    );
+
+    let fs = InMemoryFileSystem::new();
+    let build = Utf8Path::new("/build");
+    fs.write(
+        &build.join(CURRENT_PACKAGE).join("wibble_ffi.js"),
+        r#"
+(globalThis.Gleam ??= {})["thepackage/wibble_ffi"] = {
+  external: () => globalThis.Gleam["thepackage/other_ffi"].other(),
+};
+"#,
+    )
+    .expect("write FFI script");
+    fs.write(
+        &build.join(CURRENT_PACKAGE).join("other_ffi.js"),
+        r#"
+(globalThis.Gleam ??= {})["thepackage/other_ffi"] = {
+  other: () => globalThis.Gleam["thepackage/wibble"].called_by_ffi(),
+};
+"#,
+    )
+    .expect("write FFI script");
+
+    let reachable = reachable_definitions(
+        &fs,
+        build,
+        dependencies.iter().chain(std::iter::once(&module)),
+        ModuleFormat::Global,
+        &module.name,
+        "main",
+    )
+    .expect("reachable definitions");
+
+    assert!(reachable.contains("wibble", "external"));
+    assert!(reachable.contains("wibble", "called_by_ffi"));
+}
@@ -429,3 +429,64 @@ pub fn sum() {
 "
     );
 }
+
+const INLINE_MODULE: &str = "
+@inline
+pub fn unwrap(result: Result(a, e), or default: a) -> a {
+  case result {
+    Ok(value) -> value
+    Error(error) -> default
+  }
+}
+
+@inline
+pub fn wrap(value: a) -> Result(a, e) {
+  Ok(value)
+}
+
+pub fn triple(x: Int) -> Int {
+  x * 3
+}
+";
+
+#[test]
+fn inline_function_with_inline_attribute() {
+    assert_js!(
+        ("other_package", "other", INLINE_MODULE),
+        "
+import other
+
+pub fn main() {
+  other.unwrap(Ok(10), or: 0)
+}
+"
+    );
+}
+
+#[test]
+fn inline_nested_functions_with_inline_attribute() {
+    assert_js!(
+        ("other_package", "other", INLINE_MODULE),
+        "
+import other
+
+pub fn main(x) {
+  other.unwrap(other.wrap(x), or: 0)
+}
+"
+    );
+}
+
+#[test]
+fn do_not_inline_function_without_inline_attribute() {
+    assert_js!(
+        ("other_package", "other", INLINE_MODULE),
+        "
+import other
+
+pub fn main() {
+  other.triple(10)
+}
+"
+    );
+}
@@ -62,3 +62,111 @@ pub fn map(map) {
 "#
     );
 }
+
+#[test]
+fn mutual_tco() {
+    assert_js!(
+        r#"
+pub fn is_even(x) {
+  case x {
+    0 -> True
+    _ -> is_odd(x - 1)
+  }
+}
+
+pub fn is_odd(x) {
+  case x {
+    0 -> False
+    _ -> is_even(x - 1)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn mutual_tco_with_different_arities() {
+    assert_js!(
+        r#"
+pub fn count(list, total) {
+  case list {
+    [] -> total
+    [_, ..rest] -> skip(rest, total + 1, False)
+  }
+}
+
+fn skip(list, total, _flag) {
+  case list {
+    [] -> total
+    [first, ..rest] if first > 0 -> count(rest, total)
+    [_, ..rest] -> skip(rest, total, True)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn mutual_tco_through_blocks_and_pipes() {
+    assert_js!(
+        r#"
+pub fn ping(x) {
+  let y = x - 1
+  case y {
+    0 -> Nil
+    _ -> {
+      let z = y
+      z |> pong
+    }
+  }
+}
+
+pub fn pong(x) {
+  ping(x)
+}
+"#
+    );
+}
+
+#[test]
+fn not_mutual_tco_when_not_in_tail_position() {
+    assert_js!(
+        r#"
+pub fn is_even(x) {
+  case x {
+    0 -> True
+    _ -> !is_odd(x - 1)
+  }
+}
+
+pub fn is_odd(x) {
+  case x {
+    0 -> False
+    _ -> !is_even(x - 1)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn mutual_tco_group_calls_from_anonymous_functions_are_not_optimised() {
+    assert_js!(
+        r#"
+pub fn wibble(x) {
+  case x {
+    0 -> 0
+    1 -> wobble(0)
+    _ -> {
+      let f = fn() { wobble(x - 1) }
+      f()
+    }
+  }
+}
+
+pub fn wobble(x) {
+  wibble(x)
+}
+"#
+    );
+}
@@ -0,0 +1,367 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use crate::build::ModuleFormat;
+use crate::call_graph::ReachabilityAnalyser;
+use crate::io::{FileSystemWriter, memory::InMemoryFileSystem};
+use crate::javascript::tests::{CURRENT_PACKAGE, compile_with_dependencies};
+use crate::javascript::*;
+
+/// Compiles a program whose entrypoint is the `main` function of the `src`
+/// module, generating only the definitions reachable from it.
+///
+fn compile_tree_shaken_js(src: &str, deps: Vec<(&str, &str, &str)>) -> String {
+    let (module, dependencies) = compile_with_dependencies(src, deps);
+    let modules = dependencies.iter().chain(std::iter::once(&module));
+
+    let mut analyser = ReachabilityAnalyser::new(modules.clone());
+    analyser.reference(&module.name, "main");
+    let reachable = analyser.into_reachable_definitions();
+
+    let mut output = String::new();
+    for module in modules {
+        let line_numbers = LineNumbers::new("");
+        let (compiled, _) = crate::javascript::module(ModuleConfig {
+            module,
+            line_numbers: &line_numbers,
+            src: &"".into(),
+            typescript: TypeScriptDeclarations::None,
+            source_map: false,
+            stdlib_package: StdlibPackage::Present,
+            path: Utf8Path::new("src/module.gleam"),
+            project_root: "project/root".into(),
+            reachable_definitions: Some(&reachable),
+            module_format: ModuleFormat::Esm,
+        });
+        output.push_str("-- ");
+        output.push_str(&module.name);
+        output.push_str(".mjs\n");
+        output.push_str(&compiled);
+        output.push('\n');
+    }
+    output
+}
+
+macro_rules! assert_tree_shaken_js {
+    ($(($name:literal, $module_src:literal)),*, $src:literal $(,)?) => {
+        let compiled = compile_tree_shaken_js(
+            $src,
+            vec![$((CURRENT_PACKAGE, $name, $module_src)),*],
+        );
+        let mut output = String::from("----- SOURCE CODE\n");
+        for (name, src) in [$(($name, $module_src)),*] {
+            output.push_str(&format!("-- {name}.gleam\n{src}\n\n"));
+        }
+        output.push_str(&format!("-- main.gleam\n{}\n\n----- COMPILED JAVASCRIPT\n", $src));
+        output.push_str(&compiled);
+
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[test]
+fn unreachable_functions_are_not_generated() {
+    assert_tree_shaken_js!(
+        (
+            "wibble",
+            "
+pub fn used() { helper() }
+
+fn helper() { 1 }
+
+pub fn unused() { 2 }
+"
+        ),
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.used()
+}
+
+pub fn not_main() {
+  wibble.unused()
+}
+"#
+    );
+}
+
+#[test]
+fn functions_referenced_as_values_are_generated() {
+    assert_tree_shaken_js!(
+        (
+            "wibble",
+            "
+pub fn add(a, b) { a + b }
+
+pub fn subtract(a, b) { a - b }
+"
+        ),
+        r#"
+import wibble.{add}
+
+pub fn main() {
+  let f = add
+  f(1, 2)
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_constants_are_not_generated() {
+    assert_tree_shaken_js!(
+        (
+            "wibble",
+            "
+pub const used = #(1, other)
+
+const other = 2
+
+pub const unused = 3
+"
+        ),
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.used
+}
+"#
+    );
+}
+
+#[test]
+fn all_variants_of_a_reachable_custom_type_are_generated() {
+    assert_tree_shaken_js!(
+        (
+            "wibble",
+            "
+pub type Wibble {
+  Wibble(Int)
+  Wobble
+}
+
+pub type Unused {
+  Unused
+}
+"
+        ),
+        r#"
+import wibble
+
+pub fn main(x) {
+  case x {
+    wibble.Wobble -> 1
+    _ -> 2
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn records_used_in_constants_are_generated() {
+    assert_tree_shaken_js!(
+        (
+            "wibble",
+            "
+pub type Wibble {
+  Wibble(Int)
+}
+
+pub type Wobble {
+  Wobble(Int)
+}
+"
+        ),
+        r#"
+import wibble
+
+const value = wibble.Wibble(1)
+
+pub fn main() {
+  value
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_unqualified_imports_are_not_generated() {
+    assert_tree_shaken_js!(
+        (
+            "wibble",
+            "
+pub fn used() { 1 }
+
+pub fn unused() { 2 }
+"
+        ),
+        r#"
+import wibble.{unused, used}
+
+pub fn main() {
+  used()
+}
+
+pub fn not_main() {
+  unused()
+}
+"#
+    );
+}
+
+#[test]
+fn unreachable_external_functions_are_not_imported() {
+    assert_tree_shaken_js!(
+        (
+            "wibble",
+            r#"
+@external(javascript, "./wibble_ffi.mjs", "used")
+pub fn used() -> Int
+
+@external(javascript, "./wibble_ffi.mjs", "unused")
+pub fn unused() -> Int
+"#
+        ),
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.used()
+}
+"#
+    );
+}
+
+#[test]
+fn ffi_imports_of_named_values() {
+    assert_eq!(
+        ffi_imports(
+            r#"
+import { Ok, Error as GleamError } from "./gleam.mjs";
+import {
+  Some,
+  None,
+} from "../gleam/option.mjs";
+export { wibble } from "./wibble.mjs";
+"#
+        ),
+        vec![
+            FfiImport {
+                path: "./gleam.mjs".into(),
+                names: Some(vec!["Ok".into(), "Error".into()]),
+            },
+            FfiImport {
+                path: "../gleam/option.mjs".into(),
+                names: Some(vec!["Some".into(), "None".into()]),
+            },
+            FfiImport {
+                path: "./wibble.mjs".into(),
+                names: Some(vec!["wibble".into()]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn ffi_imports_of_whole_modules() {
+    assert_eq!(
+        ffi_imports(
+            r#"
+import * as $wibble from "./wibble.mjs";
+import wobble, { wubble } from "./wobble.mjs";
+export * from "./gleam.mjs";
+import "./side_effect.mjs";
+const lazy = await import("./lazy.mjs");
+const { wobble } = require("./required.cjs");
+"#
+        ),
+        vec![
+            FfiImport {
+                path: "./wibble.mjs".into(),
+                names: None,
+            },
+            FfiImport {
+                path: "./wobble.mjs".into(),
+                names: None,
+            },
+            FfiImport {
+                path: "./gleam.mjs".into(),
+                names: None,
+            },
+            FfiImport {
+                path: "./side_effect.mjs".into(),
+                names: None,
+            },
+            FfiImport {
+                path: "./lazy.mjs".into(),
+                names: None,
+            },
+            FfiImport {
+                path: "./required.cjs".into(),
+                names: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn values_imported_by_ffi_modules_are_reachable() {
+    let (module, dependencies) = compile_with_dependencies(
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.external()
+}
+"#,
+        vec![(
+            CURRENT_PACKAGE,
+            "wibble",
+            r#"
+@external(javascript, "./wibble_ffi.mjs", "external")
+pub fn external() -> Int
+
+pub fn called_by_ffi() { 1 }
+
+pub fn called_by_other_ffi() { 2 }
+
+pub fn unused() { 3 }
+"#,
+        )],
+    );
+
+    let fs = InMemoryFileSystem::new();
+    let build = Utf8Path::new("/build");
+    fs.write(
+        &build.join(CURRENT_PACKAGE).join("wibble_ffi.mjs"),
+        r#"
+import { called_by_ffi } from "./wibble.mjs";
+import { other } from "./other_ffi.mjs";
+import { readFileSync } from "node:fs";
+"#,
+    )
+    .expect("write FFI module");
+    fs.write(
+        &build.join(CURRENT_PACKAGE).join("other_ffi.mjs"),
+        r#"import { called_by_other_ffi } from "./wibble.mjs";"#,
+    )
+    .expect("write FFI module");
+
+    let reachable = reachable_definitions(
+        &fs,
+        build,
+        dependencies.iter().chain(std::iter::once(&module)),
+        ModuleFormat::Esm,
+        &module.name,
+        "main",
+    )
+    .expect("reachable definitions");
+
+    assert!(reachable.contains("wibble", "external"));
+    assert!(reachable.contains("wibble", "called_by_ffi"));
+    assert!(reachable.contains("wibble", "called_by_other_ffi"));
+    assert!(!reachable.contains("wibble", "unused"));
+}
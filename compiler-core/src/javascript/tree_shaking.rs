@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Finding the definitions of a JavaScript program that are reachable from its
+//! entrypoint, so that only those are generated.
+//!
+//! Most of the work is done by the call graph's reachability analysis on the
+//! Gleam code. What is specific to JavaScript is FFI code: an external function
+//! may be implemented in a JavaScript module that imports Gleam modules in
+//! turn, so the values it imports must be kept as well.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
+use regex::Regex;
+
+use crate::{
+    Result,
+    ast::TypedModule,
+    build::ModuleFormat,
+    call_graph::{ReachabilityAnalyser, ReachableDefinitions},
+    io::FileSystemReader,
+};
+
+use super::global;
+
+/// Finds the definitions of a program that can be reached from its entrypoint
+/// function, looking into the FFI modules of its reachable external functions.
+///
+/// The modules are expected to have been compiled to `build_directory`, each
+/// package in a directory of its own, along with their FFI modules.
+///
+pub fn reachable_definitions<'a, Modules>(
+    io: &impl FileSystemReader,
+    build_directory: &Utf8Path,
+    modules: Modules,
+    module_format: ModuleFormat,
+    entrypoint_module: &str,
+    entrypoint_function: &str,
+) -> Result<ReachableDefinitions>
+where
+    Modules: IntoIterator<Item = &'a TypedModule>,
+    Modules::IntoIter: Clone,
+{
+    let modules = modules.into_iter();
+    let module_paths: HashMap<Utf8PathBuf, &str> = modules
+        .clone()
+        .map(|module| {
+            let path = module_path(build_directory, module, module_format);
+            (path, module.name.as_str())
+        })
+        .collect();
+
+    let mut analyser = ReachabilityAnalyser::new(modules.clone());
+    analyser.reference(entrypoint_module, entrypoint_function);
+
+    // Each FFI module we look into can make more Gleam functions reachable,
+    // which in turn can be implemented by FFI modules we haven't seen yet. So
+    // we keep going until there's no new FFI module to look at.
+    let mut scanned = HashSet::new();
+    loop {
+        let mut to_scan = vec![];
+        for module in modules.clone() {
+            let directory = module_directory(build_directory, module, module_format);
+            let reachable = analyser.reachable_definitions();
+            to_scan.extend(
+                module
+                    .definitions
+                    .functions
+                    .iter()
+                    .filter(|function| {
+                        function
+                            .name
+                            .as_ref()
+                            .is_some_and(|(_, name)| reachable.contains(&module.name, name))
+                    })
+                    .filter_map(|function| function.external_javascript.as_ref())
+                    .filter_map(|(path, _, _)| resolve_import(&directory, path))
+                    .filter(|path| !scanned.contains(path)),
+            );
+        }
+
+        if to_scan.is_empty() {
+            break;
+        }
+
+        while let Some(path) = to_scan.pop() {
+            if !scanned.insert(path.clone()) || !io.is_file(&path) {
+                continue;
+            }
+
+            let directory = path.parent().unwrap_or(build_directory);
+            let source = io.read(&path)?;
+
+            // Scripts in the global module format read the values of other
+            // scripts from `globalThis.Gleam` rather than importing them.
+            if module_format == ModuleFormat::Global {
+                for key in global::references(&source) {
+                    let referenced_path = build_directory.join(format!("{key}.js"));
+                    match module_paths.get(&referenced_path) {
+                        Some(module) => analyser.reference_module(module),
+                        None => to_scan.push(referenced_path),
+                    }
+                }
+            }
+
+            for import in ffi_imports(&source) {
+                let Some(imported_path) = resolve_import(directory, &import.path) else {
+                    continue;
+                };
+
+                match (module_paths.get(&imported_path), import.names) {
+                    (Some(module), Some(names)) => {
+                        for name in names {
+                            analyser.reference(module, &name);
+                        }
+                    }
+                    (Some(module), None) => analyser.reference_module(module),
+                    // FFI modules can import other FFI modules.
+                    (None, _) => to_scan.push(imported_path),
+                }
+            }
+        }
+    }
+
+    Ok(analyser.into_reachable_definitions())
+}
+
+fn module_directory(
+    build_directory: &Utf8Path,
+    module: &TypedModule,
+    module_format: ModuleFormat,
+) -> Utf8PathBuf {
+    module_path(build_directory, module, module_format)
+        .parent()
+        .map(Utf8Path::to_path_buf)
+        .unwrap_or_else(|| build_directory.to_path_buf())
+}
+
+fn module_path(
+    build_directory: &Utf8Path,
+    module: &TypedModule,
+    module_format: ModuleFormat,
+) -> Utf8PathBuf {
+    build_directory
+        .join(module.type_info.package.as_str())
+        .join(format!("{}.{}", module.name, module_format.extension()))
+}
+
+/// Resolves a relative import specifier against the directory of the module
+/// importing it. Bare specifiers, such as `node:fs` or the name of a package
+/// installed with npm, can't refer to Gleam code so they are not resolved.
+///
+pub(crate) fn resolve_import(directory: &Utf8Path, specifier: &str) -> Option<Utf8PathBuf> {
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+
+    let mut path = directory.to_path_buf();
+    for component in Utf8Path::new(specifier).components() {
+        match component {
+            Utf8Component::CurDir => {}
+            Utf8Component::ParentDir => {
+                let _ = path.pop();
+            }
+            Utf8Component::Normal(name) => path.push(name),
+            Utf8Component::Prefix(_) | Utf8Component::RootDir => {}
+        }
+    }
+    Some(path)
+}
+
+/// A module imported, or re-exported, by some JavaScript code.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FfiImport {
+    pub path: EcoString,
+    /// The names of the values being imported, or `None` if the module is
+    /// used as a whole, for example with `import * as module from "..."`.
+    pub names: Option<Vec<EcoString>>,
+}
+
+static IMPORT_PATTERN: OnceLock<Regex> = OnceLock::new();
+
+/// Finds the modules imported by some JavaScript code. This doesn't fully
+/// parse JavaScript but recognises the static and dynamic imports, the
+/// re-exports, and the CommonJS `require` calls that are written in the usual
+/// way. Required modules are always considered to be used as a whole.
+///
+pub fn ffi_imports(source: &str) -> Vec<FfiImport> {
+    let pattern = IMPORT_PATTERN.get_or_init(|| {
+        Regex::new(
+            r#"(?:\b(?:import|export)\s*(?P<clause>(?:[\w$]+\s*,\s*)?(?:\{[^{}]*\}|\*(?:\s*as\s+[\w$]+)?|[\w$]+))\s*from\s*|\b(?:import\s*\(?|require\s*\()\s*)["'](?P<path>[^"'\n]+)["']"#,
+        )
+        .expect("Regex is correct")
+    });
+
+    pattern
+        .captures_iter(source)
+        .map(|captures| {
+            let path = EcoString::from(&captures["path"]);
+            let names = captures
+                .name("clause")
+                .map(|clause| clause.as_str().trim())
+                .and_then(|clause| clause.strip_prefix('{')?.strip_suffix('}'))
+                .map(|names| {
+                    names
+                        .split(',')
+                        .filter_map(|name| name.split_whitespace().next())
+                        .map(EcoString::from)
+                        .collect()
+                });
+            FfiImport { path, names }
+        })
+        .collect()
+}
@@ -17,6 +17,7 @@
 use crate::ast::{
     AssignName, Publicity, TypedCustomType, TypedFunction, TypedModuleConstant, TypedTypeAlias,
 };
+use crate::build::ModuleFormat;
 use crate::javascript::import::Member;
 use crate::type_::{PRELUDE_MODULE_NAME, RecordAccessor, is_prelude_module};
 use crate::{
@@ -35,6 +36,7 @@ use super::{INDENT, import::Imports, wrap_arguments};
 /// variables with the same id to end up with the same name in the generated
 /// TypeScript. This function converts a usize into base 26 A-Z for this purpose.
 fn id_to_type_var<'a, 'doc>(arena: &'doc DocumentArena<'a, 'doc>, id: u64) -> Document<'a, 'doc> {
+    let id = crate::uid::offset_in_range(id);
     if id < 26 {
         return std::iter::once(
             std::char::from_u32((id % 26 + 65) as u32).expect("id_to_type_var 0"),
@@ -189,16 +191,18 @@ pub struct TypeScriptGenerator<'a> {
     aliased_module_names: HashMap<&'a str, &'a str>,
     tracker: UsageTracker,
     current_module_name_segments_count: usize,
+    module_format: ModuleFormat,
 }
 
 impl<'a, 'doc> TypeScriptGenerator<'a> {
-    pub fn new(module: &'a TypedModule) -> Self {
+    pub fn new(module: &'a TypedModule, module_format: ModuleFormat) -> Self {
         let current_module_name_segments_count = module.name.split('/').count();
         Self {
             module,
             aliased_module_names: HashMap::new(),
             tracker: UsageTracker::default(),
             current_module_name_segments_count,
+            module_format,
         }
     }
 
@@ -223,11 +227,19 @@ impl<'a, 'doc> TypeScriptGenerator<'a> {
         } else if imports.is_empty() {
             statements.append(arena, LINE_DOCUMENT)
         } else if no_statements {
-            imports.into_doc(arena, JavaScriptCodegenTarget::TypeScriptDeclarations)
+            imports.into_doc(
+                arena,
+                JavaScriptCodegenTarget::TypeScriptDeclarations,
+                self.module_format,
+            )
         } else {
             docvec![
                 arena,
-                imports.into_doc(arena, JavaScriptCodegenTarget::TypeScriptDeclarations),
+                imports.into_doc(
+                    arena,
+                    JavaScriptCodegenTarget::TypeScriptDeclarations,
+                    self.module_format,
+                ),
                 LINE_DOCUMENT,
                 statements,
                 LINE_DOCUMENT
@@ -343,19 +355,20 @@ impl<'a, 'doc> TypeScriptGenerator<'a> {
         // DUPE: current_module_name_segments_count
         // TODO: strip shared prefixed between current module and imported
         // module to avoid descending and climbing back out again
+        let extension = self.module_format.declaration_extension();
         if package == self.module.type_info.package || package.is_empty() {
             // Same package
             match self.current_module_name_segments_count {
-                1 => eco_format!("./{module}.d.mts"),
+                1 => eco_format!("./{module}.{extension}"),
                 _ => {
                     let prefix = "../".repeat(self.current_module_name_segments_count - 1);
-                    eco_format!("{prefix}{module}.d.mts")
+                    eco_format!("{prefix}{module}.{extension}")
                 }
             }
         } else {
             // Different package
             let prefix = "../".repeat(self.current_module_name_segments_count);
-            eco_format!("{prefix}{package}/{module}.d.mts")
+            eco_format!("{prefix}{package}/{module}.{extension}")
         }
     }
 
@@ -70,17 +70,21 @@ pub mod analyse;
 pub mod ast;
 pub mod bit_array;
 pub mod build;
+pub mod call_graph;
 pub mod codegen;
 pub mod config;
+pub mod coverage;
 pub mod dependency;
 pub mod diagnostic;
 pub mod docs;
+pub mod doctest;
 pub mod encryption;
 pub mod erlang;
 pub mod error;
 pub mod hex;
 pub mod io;
 pub mod javascript;
+pub mod lint;
 pub mod manifest;
 pub mod metadata;
 pub mod package_interface;
@@ -92,9 +96,10 @@ pub mod type_;
 pub mod uid;
 pub mod version;
 pub mod warning;
+pub mod wasm;
 
 pub(crate) mod ast_folder;
-mod call_graph;
+pub(crate) mod constant_folding;
 mod dep_tree;
 pub(crate) mod derivation_tree;
 pub mod exhaustiveness;
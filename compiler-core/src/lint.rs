@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Style and correctness lints run over the typed AST of a module.
+//!
+//! Unlike warnings, which are found by the type checker while it analyses a
+//! module, lints are checked once a module has been successfully analysed.
+//! Each lint is implemented as a [`LintRule`], usually a visitor over the
+//! typed AST, and the rules are run by a [`Linter`].
+//!
+//! Lints are reported by the `gleam lint` command and by the language server,
+//! which also offers any fix they come with as a code action.
+//!
+
+mod rules;
+
+#[cfg(test)]
+mod tests;
+
+use ecow::EcoString;
+use src_span::SrcSpan;
+
+use crate::{ast::TypedModule, build, warning::Warning};
+
+pub use rules::{BoolGuard, ListLengthEmptyCheck, NestedResultTry, RedundantLet};
+
+/// A problem found by a lint rule.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint {
+    /// The name of the rule that found this problem.
+    pub rule: &'static str,
+    pub location: SrcSpan,
+    pub title: EcoString,
+    pub message: EcoString,
+    pub fix: Option<LintFix>,
+    /// Some lints only exist to offer a fix for a problem the type checker
+    /// already reports as a warning. Those are not reported a second time.
+    pub reported_by_compiler: bool,
+}
+
+/// A change to the code of a module that fixes a lint.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFix {
+    pub title: EcoString,
+    pub edits: Vec<LintEdit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintEdit {
+    pub location: SrcSpan,
+    pub replacement: EcoString,
+}
+
+impl LintFix {
+    /// Applies the fix to the code of the module it was found in.
+    ///
+    pub fn apply(&self, code: &str) -> String {
+        let mut code = code.to_string();
+        let mut edits = self.edits.iter().collect::<Vec<_>>();
+        edits.sort_by_key(|edit| std::cmp::Reverse(edit.location.start));
+        for edit in edits {
+            let range = edit.location.start as usize..edit.location.end as usize;
+            code.replace_range(range, &edit.replacement);
+        }
+        code
+    }
+}
+
+pub trait LintRule {
+    /// The name of the rule, in snake case.
+    fn name(&self) -> &'static str;
+
+    /// A short description of what the rule checks for.
+    fn description(&self) -> &'static str;
+
+    fn check(&self, module: &TypedModule, code: &str) -> Vec<Lint>;
+}
+
+/// Runs a set of lint rules over modules.
+///
+#[derive(Default)]
+pub struct Linter {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl Linter {
+    /// A linter with no rules. Rules can be added with [`Linter::add_rule`].
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A linter running all the rules that come with the compiler.
+    ///
+    pub fn with_builtin_rules() -> Self {
+        let mut linter = Self::new();
+        linter.add_rule(BoolGuard);
+        linter.add_rule(ListLengthEmptyCheck);
+        linter.add_rule(NestedResultTry);
+        linter.add_rule(RedundantLet);
+        linter
+    }
+
+    pub fn add_rule(&mut self, rule: impl LintRule + 'static) {
+        self.rules.push(Box::new(rule));
+    }
+
+    pub fn rules(&self) -> impl Iterator<Item = &dyn LintRule> {
+        self.rules.iter().map(|rule| rule.as_ref())
+    }
+
+    /// Returns all the lints found in a module, in the order they appear in
+    /// the code.
+    ///
+    pub fn lint(&self, module: &TypedModule, code: &str) -> Vec<Lint> {
+        let mut lints = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.check(module, code))
+            .collect::<Vec<_>>();
+        lints.sort_by_key(|lint| (lint.location.start, lint.rule));
+        lints
+    }
+
+    /// Returns the lints of a compiled module as warnings, leaving out the
+    /// ones the type checker has already reported.
+    ///
+    pub fn lint_module(&self, module: &build::Module) -> Vec<Warning> {
+        self.lint(&module.ast, &module.code)
+            .into_iter()
+            .filter(|lint| !lint.reported_by_compiler)
+            .map(|lint| Warning::Lint {
+                path: module.input_path.clone(),
+                src: module.code.clone(),
+                lint: Box::new(lint),
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Debug for Linter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.rules().map(|rule| rule.name()))
+            .finish()
+    }
+}
+
+/// Returns the code a span covers.
+///
+fn code_at(code: &str, location: SrcSpan) -> &str {
+    code.get(location.start as usize..location.end as usize)
+        .unwrap_or_default()
+}
@@ -0,0 +1,651 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! The lint rules that come with the compiler.
+
+use std::sync::Arc;
+
+use ecow::{EcoString, eco_format};
+use src_span::SrcSpan;
+use vec1::Vec1;
+
+use super::{Lint, LintEdit, LintFix, LintRule, code_at};
+use crate::{
+    ast::{
+        Assignment, AssignmentKind, BinOp, FunctionLiteralKind, Pattern, Statement, TypeAst,
+        TypedArg, TypedClause, TypedExpr, TypedFunction, TypedModule, TypedStatement,
+        visit::{self, Visit},
+    },
+    type_::{
+        self, Type, TypedCallArg, error::EmptyListCheckKind, expression::get_empty_list_check_kind,
+    },
+};
+
+/// Returns the name a module is imported with, if it is imported at all.
+///
+fn imported_module_name(module: &TypedModule, name: &str) -> Option<EcoString> {
+    module
+        .definitions
+        .imports
+        .iter()
+        .find(|import| import.module == name)
+        .and_then(|import| import.used_name())
+}
+
+/// Returns true if the expression is a reference to the given function of the
+/// given module, for example `list.length`.
+///
+fn is_module_function(expression: &TypedExpr, module: &str, function: &str) -> bool {
+    matches!(
+        expression,
+        TypedExpr::ModuleSelect { module_name, label, .. }
+            if module_name == module && label == function
+    )
+}
+
+/// Returns the whitespace a line starts with, if that whitespace is the only
+/// thing coming before the given position on its line.
+///
+fn indentation_before(code: &str, position: u32) -> Option<&str> {
+    let before = code.get(..position as usize)?;
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    let indentation = before.get(line_start..)?;
+    indentation
+        .chars()
+        .all(|char| char == ' ')
+        .then_some(indentation)
+}
+
+/// Removes up to `width` spaces from the start of every line of some code but
+/// the first one.
+///
+fn dedent(code: &str, width: usize) -> String {
+    code.split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if index == 0 {
+                return line;
+            }
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            line.get(spaces.min(width)..).unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A `case` expression on a `Bool` that returns a simple value in one branch
+/// and carries on with the rest of the function in the other can be written
+/// with `bool.guard`, avoiding one level of nesting:
+///
+/// ```gleam
+/// pub fn main(x) {
+///   case x {
+///     True -> Error(Nil)
+///     False -> {
+///       let y = wibble()
+///       Ok(y)
+///     }
+///   }
+/// }
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// pub fn main(x) {
+///   use <- bool.guard(when: x, return: Error(Nil))
+///   let y = wibble()
+///   Ok(y)
+/// }
+/// ```
+///
+#[derive(Debug, Clone, Copy)]
+pub struct BoolGuard;
+
+impl LintRule for BoolGuard {
+    fn name(&self) -> &'static str {
+        "bool_guard"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds `case` expressions on a `Bool` that could use `bool.guard`."
+    }
+
+    fn check(&self, module: &TypedModule, code: &str) -> Vec<Lint> {
+        let mut visitor = BoolGuardVisitor {
+            code,
+            bool_module: imported_module_name(module, "gleam/bool"),
+            lints: vec![],
+        };
+        visitor.visit_typed_module(module);
+        visitor.lints
+    }
+}
+
+struct BoolGuardVisitor<'a> {
+    code: &'a str,
+    /// The name `gleam/bool` is imported with. The fix is only offered if the
+    /// module is imported.
+    bool_module: Option<EcoString>,
+    lints: Vec<Lint>,
+}
+
+impl BoolGuardVisitor<'_> {
+    /// Only a `case` that is the last thing a function does can be replaced
+    /// with a `use`.
+    ///
+    fn check_final_statement(&mut self, statement: &TypedStatement) {
+        let Statement::Expression(TypedExpr::Case {
+            location,
+            subjects,
+            clauses,
+            ..
+        }) = statement
+        else {
+            return;
+        };
+
+        let ([subject], [first, second]) = (subjects.as_slice(), clauses.as_slice()) else {
+            return;
+        };
+        if !subject.type_().is_bool() {
+            return;
+        }
+
+        let (true_clause, false_clause) = match (bool_pattern(first), bool_pattern(second)) {
+            (Some(true), Some(false)) => (first, second),
+            (Some(false), Some(true)) => (second, first),
+            (Some(_) | None, Some(_) | None) => return,
+        };
+
+        // One branch has to return a simple value, while the other one has to
+        // be a block with the rest of the function.
+        let (returned_when, returned, rest) = match (&true_clause.then, &false_clause.then) {
+            (returned, TypedExpr::Block { statements, .. }) if is_simple_value(returned) => {
+                (true, returned, statements)
+            }
+            (TypedExpr::Block { statements, .. }, returned) if is_simple_value(returned) => {
+                (false, returned, statements)
+            }
+            (_, _) => return,
+        };
+
+        let subject_code = code_at(self.code, subject.location());
+        let value = if returned_when { "True" } else { "False" };
+        self.lints.push(Lint {
+            rule: BoolGuard.name(),
+            location: *location,
+            title: "Case could use `bool.guard`".into(),
+            message: eco_format!(
+                "This case expression returns early when `{subject_code}` is `{value}`. \
+It can be written with `use` and `bool.guard` to avoid nesting the rest of the function."
+            ),
+            fix: self.fix(*location, subject, returned_when, returned, rest),
+            reported_by_compiler: false,
+        });
+    }
+
+    fn fix(
+        &self,
+        location: SrcSpan,
+        subject: &TypedExpr,
+        returned_when: bool,
+        returned: &TypedExpr,
+        rest: &Vec1<TypedStatement>,
+    ) -> Option<LintFix> {
+        let bool_module = self.bool_module.as_ref()?;
+        let indentation = indentation_before(self.code, location.start)?;
+
+        let rest_start = rest.first().location().start;
+        let rest_indentation = indentation_before(self.code, rest_start)?;
+        let shift = rest_indentation.len().checked_sub(indentation.len())?;
+        let rest_location = SrcSpan::new(rest_start, rest.last().location().end);
+        let rest_code = dedent(code_at(self.code, rest_location), shift);
+
+        let subject_code = code_at(self.code, subject.location());
+        let condition = match (returned_when, subject) {
+            (true, _) => subject_code.to_string(),
+            (false, TypedExpr::BinOp { .. }) => format!("!{{ {subject_code} }}"),
+            (false, _) => format!("!{subject_code}"),
+        };
+        let returned_code = code_at(self.code, returned.location());
+
+        Some(LintFix {
+            title: eco_format!("Use `{bool_module}.guard`"),
+            edits: vec![LintEdit {
+                location,
+                replacement: eco_format!(
+                    "use <- {bool_module}.guard(when: {condition}, return: {returned_code})\n\
+{indentation}{rest_code}"
+                ),
+            }],
+        })
+    }
+}
+
+impl<'ast> Visit<'ast> for BoolGuardVisitor<'_> {
+    fn visit_typed_function(&mut self, fun: &'ast TypedFunction) {
+        if let Some(statement) = fun.body.last() {
+            self.check_final_statement(statement);
+        }
+        visit::visit_typed_function(self, fun);
+    }
+
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        kind: &'ast FunctionLiteralKind,
+        arguments: &'ast [TypedArg],
+        body: &'ast Vec1<TypedStatement>,
+        return_annotation: &'ast Option<TypeAst>,
+    ) {
+        self.check_final_statement(body.last());
+        visit::visit_typed_expr_fn(
+            self,
+            location,
+            type_,
+            kind,
+            arguments,
+            body,
+            return_annotation,
+        );
+    }
+}
+
+/// Returns the value a clause matches on, if its only pattern is a `True` or
+/// `False` literal and it has no guard.
+///
+fn bool_pattern(clause: &TypedClause) -> Option<bool> {
+    if clause.guard.is_some() || !clause.alternative_patterns.is_empty() {
+        return None;
+    }
+    let [
+        Pattern::Constructor {
+            name,
+            arguments,
+            type_,
+            ..
+        },
+    ] = clause.pattern.as_slice()
+    else {
+        return None;
+    };
+    if !arguments.is_empty() || !type_.is_bool() {
+        return None;
+    }
+    match name.as_str() {
+        "True" => Some(true),
+        "False" => Some(false),
+        _ => None,
+    }
+}
+
+/// `bool.guard` always evaluates the value it returns, so the lint is only
+/// reported when that value is cheap to build and has no side effects.
+///
+fn is_simple_value(expression: &TypedExpr) -> bool {
+    expression.is_literal() || expression.is_var()
+}
+
+/// Checking if a list is empty using `list.length` has to walk the entire
+/// list, while comparing it with the empty list only looks at its first
+/// element:
+///
+/// ```gleam
+/// list.length(items) == 0
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// items == []
+/// ```
+///
+/// The type checker already warns about this when using the standard library,
+/// so the lint is only reported for other `gleam/list` modules, but the fix is
+/// always offered.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct ListLengthEmptyCheck;
+
+impl LintRule for ListLengthEmptyCheck {
+    fn name(&self) -> &'static str {
+        "list_length_empty_check"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds uses of `list.length` to check if a list is empty."
+    }
+
+    fn check(&self, module: &TypedModule, code: &str) -> Vec<Lint> {
+        let reported_by_compiler = module
+            .type_info
+            .warnings
+            .iter()
+            .filter_map(|warning| {
+                if let type_::Warning::InefficientEmptyListCheck { location, .. } = warning {
+                    Some(*location)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut visitor = ListLengthEmptyCheckVisitor {
+            code,
+            reported_by_compiler,
+            lints: vec![],
+        };
+        visitor.visit_typed_module(module);
+        visitor.lints
+    }
+}
+
+struct ListLengthEmptyCheckVisitor<'a> {
+    code: &'a str,
+    reported_by_compiler: Vec<SrcSpan>,
+    lints: Vec<Lint>,
+}
+
+impl<'ast> Visit<'ast> for ListLengthEmptyCheckVisitor<'_> {
+    fn visit_typed_expr_bin_op(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        operator: &'ast BinOp,
+        operator_start: &'ast u32,
+        left: &'ast TypedExpr,
+        right: &'ast TypedExpr,
+    ) {
+        if let Some(lint) = self.check(*location, *operator, left, right) {
+            self.lints.push(lint);
+        }
+        visit::visit_typed_expr_bin_op(
+            self,
+            location,
+            type_,
+            operator,
+            operator_start,
+            left,
+            right,
+        );
+    }
+}
+
+impl ListLengthEmptyCheckVisitor<'_> {
+    fn check(
+        &self,
+        location: SrcSpan,
+        operator: BinOp,
+        left: &TypedExpr,
+        right: &TypedExpr,
+    ) -> Option<Lint> {
+        let call = if matches!(left, TypedExpr::Int { .. }) {
+            right
+        } else {
+            left
+        };
+        let TypedExpr::Call { fun, arguments, .. } = call else {
+            return None;
+        };
+        let [argument] = arguments.as_slice() else {
+            return None;
+        };
+        if !is_module_function(fun, "gleam/list", "length") {
+            return None;
+        }
+
+        let list = code_at(self.code, argument.value.location());
+        let replacement = match get_empty_list_check_kind(operator, left, right)? {
+            EmptyListCheckKind::Empty => eco_format!("{list} == []"),
+            EmptyListCheckKind::NonEmpty => eco_format!("{list} != []"),
+        };
+
+        Some(Lint {
+            rule: ListLengthEmptyCheck.name(),
+            location,
+            title: "Inefficient use of `list.length`".into(),
+            message: "The `list.length` function has to iterate across the whole list to \
+calculate its length. Comparing the list with `[]` is faster."
+                .into(),
+            fix: Some(LintFix {
+                title: eco_format!("Replace with `{replacement}`"),
+                edits: vec![LintEdit {
+                    location,
+                    replacement,
+                }],
+            }),
+            reported_by_compiler: self.reported_by_compiler.contains(&location),
+        })
+    }
+}
+
+/// A `result.try` whose callback does nothing but call `result.try` again
+/// nests deeper with each step, while `use` keeps all the steps at the same
+/// level:
+///
+/// ```gleam
+/// result.try(wibble(), fn(a) {
+///   result.try(wobble(a), fn(b) { Ok(a + b) })
+/// })
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// use a <- result.try(wibble())
+/// use b <- result.try(wobble(a))
+/// Ok(a + b)
+/// ```
+///
+#[derive(Debug, Clone, Copy)]
+pub struct NestedResultTry;
+
+impl LintRule for NestedResultTry {
+    fn name(&self) -> &'static str {
+        "nested_result_try"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds nested `result.try` calls that could use `use`."
+    }
+
+    fn check(&self, module: &TypedModule, _code: &str) -> Vec<Lint> {
+        let mut visitor = NestedResultTryVisitor {
+            nested: vec![],
+            lints: vec![],
+        };
+        visitor.visit_typed_module(module);
+        visitor.lints
+    }
+}
+
+struct NestedResultTryVisitor {
+    /// The calls that are nested inside another `result.try`: the lint is
+    /// reported once for the outermost call of a chain.
+    nested: Vec<SrcSpan>,
+    lints: Vec<Lint>,
+}
+
+impl<'ast> Visit<'ast> for NestedResultTryVisitor {
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        fun: &'ast TypedExpr,
+        arguments: &'ast [TypedCallArg],
+        open_parenthesis: &'ast Option<u32>,
+    ) {
+        if let Some(inner) = nested_result_try(fun, arguments) {
+            if !self.nested.contains(location) {
+                self.lints.push(Lint {
+                    rule: NestedResultTry.name(),
+                    location: *location,
+                    title: "Nested `result.try` calls".into(),
+                    message: "The callback of this `result.try` does nothing but call \
+`result.try` again. Each step can be written with `use` instead, so that they are not nested."
+                        .into(),
+                    fix: None,
+                    reported_by_compiler: false,
+                });
+            }
+            self.nested.push(inner);
+        }
+        visit::visit_typed_expr_call(self, location, type_, fun, arguments, open_parenthesis);
+    }
+}
+
+/// If this is a call to `result.try` with an anonymous function whose body is
+/// just another call to `result.try`, returns the location of that call.
+///
+fn nested_result_try(fun: &TypedExpr, arguments: &[TypedCallArg]) -> Option<SrcSpan> {
+    if !is_module_function(fun, "gleam/result", "try") {
+        return None;
+    }
+    let [_, callback] = arguments else {
+        return None;
+    };
+    let TypedExpr::Fn {
+        kind: FunctionLiteralKind::Anonymous { .. },
+        body,
+        ..
+    } = &callback.value
+    else {
+        return None;
+    };
+    let [Statement::Expression(TypedExpr::Call { location, fun, .. })] = body.as_slice() else {
+        return None;
+    };
+    is_module_function(fun, "gleam/result", "try").then_some(*location)
+}
+
+/// Assigning a value to a variable only to return that variable straight
+/// away is the same as returning the value:
+///
+/// ```gleam
+/// let x = wibble()
+/// x
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// wibble()
+/// ```
+///
+#[derive(Debug, Clone, Copy)]
+pub struct RedundantLet;
+
+impl LintRule for RedundantLet {
+    fn name(&self) -> &'static str {
+        "redundant_let"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds variables that are returned right after being assigned."
+    }
+
+    fn check(&self, module: &TypedModule, code: &str) -> Vec<Lint> {
+        let mut visitor = RedundantLetVisitor {
+            code,
+            lints: vec![],
+        };
+        visitor.visit_typed_module(module);
+        visitor.lints
+    }
+}
+
+struct RedundantLetVisitor<'a> {
+    code: &'a str,
+    lints: Vec<Lint>,
+}
+
+impl RedundantLetVisitor<'_> {
+    fn check_statements(&mut self, statements: &[TypedStatement]) {
+        let [
+            ..,
+            Statement::Assignment(assignment),
+            Statement::Expression(TypedExpr::Var {
+                location: variable_location,
+                constructor,
+                name,
+            }),
+        ] = statements
+        else {
+            return;
+        };
+        let Assignment {
+            location,
+            value,
+            pattern:
+                Pattern::Variable {
+                    name: assigned_name,
+                    ..
+                },
+            kind: AssignmentKind::Let,
+            annotation: None,
+            ..
+        } = assignment.as_ref()
+        else {
+            return;
+        };
+        if name != assigned_name || !constructor.is_local_variable() {
+            return;
+        }
+
+        self.lints.push(Lint {
+            rule: RedundantLet.name(),
+            location: *location,
+            title: "Redundant `let`".into(),
+            message: eco_format!(
+                "The variable `{name}` is returned right after being assigned, \
+so the value can be returned directly."
+            ),
+            fix: Some(LintFix {
+                title: "Return the value directly".into(),
+                edits: vec![LintEdit {
+                    location: SrcSpan::new(location.start, variable_location.end),
+                    replacement: code_at(self.code, value.location()).into(),
+                }],
+            }),
+            reported_by_compiler: false,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for RedundantLetVisitor<'_> {
+    fn visit_typed_function(&mut self, fun: &'ast TypedFunction) {
+        self.check_statements(&fun.body);
+        visit::visit_typed_function(self, fun);
+    }
+
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        kind: &'ast FunctionLiteralKind,
+        arguments: &'ast [TypedArg],
+        body: &'ast Vec1<TypedStatement>,
+        return_annotation: &'ast Option<TypeAst>,
+    ) {
+        self.check_statements(body.as_slice());
+        visit::visit_typed_expr_fn(
+            self,
+            location,
+            type_,
+            kind,
+            arguments,
+            body,
+            return_annotation,
+        );
+    }
+
+    fn visit_typed_expr_block(
+        &mut self,
+        location: &'ast SrcSpan,
+        statements: &'ast [TypedStatement],
+    ) {
+        self.check_statements(statements);
+        visit::visit_typed_expr_block(self, location, statements);
+    }
+}
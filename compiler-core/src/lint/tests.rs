@@ -0,0 +1,313 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use super::*;
+use crate::type_;
+
+const LIST_MODULE: &str = "pub fn length(of list: List(a)) -> Int { 0 }";
+
+const BOOL_MODULE: &str = "pub fn guard(
+  when requirement: Bool,
+  return consequence: a,
+  otherwise alternative: fn() -> a,
+) -> a {
+  case requirement {
+    True -> consequence
+    False -> alternative()
+  }
+}";
+
+const RESULT_MODULE: &str = "pub fn try(
+  result: Result(a, e),
+  apply fun: fn(a) -> Result(b, e),
+) -> Result(b, e) {
+  case result {
+    Ok(value) -> fun(value)
+    Error(error) -> Error(error)
+  }
+}";
+
+fn stdlib() -> Vec<(&'static str, &'static str, &'static str)> {
+    vec![
+        ("gleam_stdlib", "gleam/list", LIST_MODULE),
+        ("gleam_stdlib", "gleam/bool", BOOL_MODULE),
+        ("gleam_stdlib", "gleam/result", RESULT_MODULE),
+    ]
+}
+
+fn lint_with_dependencies(
+    src: &str,
+    dependencies: Vec<(&'static str, &'static str, &'static str)>,
+) -> Vec<Lint> {
+    let module = type_::tests::compile_module("test_module", src, None, dependencies)
+        .expect("module should compile");
+    Linter::with_builtin_rules().lint(&module, src)
+}
+
+fn lint(src: &str) -> Vec<Lint> {
+    lint_with_dependencies(src, stdlib())
+}
+
+fn print_lints(src: &str, lints: Vec<Lint>) -> String {
+    assert!(!lints.is_empty(), "expected some lints");
+    let mut output = format!("----- SOURCE CODE\n{src}");
+    for lint in lints {
+        output.push_str(&format!("\n----- LINT {}\n", lint.rule));
+        if lint.reported_by_compiler {
+            output.push_str("(also reported by the compiler)\n");
+        }
+        output.push_str(&format!(
+            "{}\n{}\n{}\n",
+            code_at(src, lint.location),
+            lint.title,
+            lint.message
+        ));
+        if let Some(fix) = &lint.fix {
+            output.push_str(&format!("\n----- FIX: {}\n{}", fix.title, fix.apply(src)));
+        }
+    }
+    output
+}
+
+#[test]
+fn bool_guard() {
+    let src = r#"import gleam/bool
+
+pub fn main(x: Bool) -> Result(Int, Nil) {
+  case x {
+    True -> Error(Nil)
+    False -> {
+      let y = 1
+      Ok(y)
+    }
+  }
+}
+"#;
+    insta::assert_snapshot!(print_lints(src, lint(src)));
+}
+
+#[test]
+fn bool_guard_returning_on_false() {
+    let src = r#"import gleam/bool
+
+pub fn main(x: Int) -> Int {
+  case x > 10 {
+    True -> {
+      let y = x * 2
+      y + 1
+    }
+    False -> 0
+  }
+}
+"#;
+    insta::assert_snapshot!(print_lints(src, lint(src)));
+}
+
+#[test]
+fn bool_guard_without_bool_import_has_no_fix() {
+    let src = r#"pub fn main(x: Bool) -> Result(Int, Nil) {
+  case x {
+    True -> Error(Nil)
+    False -> {
+      let y = 1
+      Ok(y)
+    }
+  }
+}
+"#;
+    insta::assert_snapshot!(print_lints(src, lint(src)));
+}
+
+#[test]
+fn bool_guard_is_not_reported_when_returning_a_call() {
+    let src = r#"pub fn main(x: Bool) -> Int {
+  case x {
+    True -> wibble()
+    False -> {
+      let y = 1
+      y + 1
+    }
+  }
+}
+
+fn wibble() -> Int {
+  1
+}
+"#;
+    assert_eq!(lint(src), vec![]);
+}
+
+#[test]
+fn bool_guard_is_not_reported_when_case_is_not_last() {
+    let src = r#"pub fn main(x: Bool) -> Int {
+  let z = case x {
+    True -> 0
+    False -> {
+      let y = 1
+      y + 1
+    }
+  }
+  z + 1
+}
+"#;
+    assert_eq!(lint(src), vec![]);
+}
+
+#[test]
+fn list_length_empty_check() {
+    let src = r#"import gleam/list
+
+pub fn main(items: List(Int)) -> Bool {
+  list.length(items) == 0
+}
+"#;
+    insta::assert_snapshot!(print_lints(src, lint(src)));
+}
+
+#[test]
+fn list_length_non_empty_check_outside_the_standard_library() {
+    let src = r#"import gleam/list
+
+pub fn main(items: List(Int)) -> Bool {
+  0 != list.length(items)
+}
+"#;
+    let lints = lint_with_dependencies(src, vec![("wibble", "gleam/list", LIST_MODULE)]);
+    insta::assert_snapshot!(print_lints(src, lints));
+}
+
+#[test]
+fn nested_result_try() {
+    let src = r#"import gleam/result
+
+pub fn main() -> Result(Int, Nil) {
+  result.try(Ok(1), fn(a) {
+    result.try(Ok(2), fn(b) {
+      result.try(Ok(3), fn(c) { Ok(a + b + c) })
+    })
+  })
+}
+"#;
+    insta::assert_snapshot!(print_lints(src, lint(src)));
+}
+
+#[test]
+fn result_try_with_use_is_not_reported() {
+    let src = r#"import gleam/result
+
+pub fn main() -> Result(Int, Nil) {
+  use a <- result.try(Ok(1))
+  use b <- result.try(Ok(2))
+  Ok(a + b)
+}
+"#;
+    assert_eq!(lint(src), vec![]);
+}
+
+#[test]
+fn redundant_let() {
+    let src = r#"pub fn main() -> Int {
+  let x = 1 + 2
+  x
+}
+"#;
+    insta::assert_snapshot!(print_lints(src, lint(src)));
+}
+
+#[test]
+fn redundant_let_in_anonymous_function() {
+    let src = r#"pub fn main() -> fn() -> Int {
+  fn() {
+    let x = 1
+    x
+  }
+}
+"#;
+    insta::assert_snapshot!(print_lints(src, lint(src)));
+}
+
+#[test]
+fn annotated_let_is_not_redundant() {
+    let src = r#"pub fn main() -> Int {
+  let x: Int = 1
+  x
+}
+"#;
+    assert_eq!(lint(src), vec![]);
+}
+
+#[test]
+fn let_returning_another_variable_is_not_redundant() {
+    let src = r#"pub fn main(y: Int) -> Int {
+  let x = 1
+  y
+}
+"#;
+    assert_eq!(lint(src), vec![]);
+}
+
+struct PublicFunctions;
+
+impl LintRule for PublicFunctions {
+    fn name(&self) -> &'static str {
+        "public_functions"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds all public functions."
+    }
+
+    fn check(&self, module: &TypedModule, _code: &str) -> Vec<Lint> {
+        module
+            .definitions
+            .functions
+            .iter()
+            .filter(|function| function.publicity.is_public())
+            .map(|function| Lint {
+                rule: self.name(),
+                location: function.location,
+                title: "Public function".into(),
+                message: "This function is public.".into(),
+                fix: None,
+                reported_by_compiler: false,
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn custom_rules_can_be_added() {
+    let src = r#"pub fn main() -> Int {
+  let x = 1
+  x
+}
+
+fn wibble() -> Int {
+  main()
+}
+"#;
+    let module = type_::tests::compile_module("test_module", src, None, vec![])
+        .expect("module should compile");
+    let mut linter = Linter::new();
+    linter.add_rule(PublicFunctions);
+
+    let rules = linter
+        .lint(&module, src)
+        .iter()
+        .map(|lint| lint.rule)
+        .collect::<Vec<_>>();
+    assert_eq!(rules, vec!["public_functions"]);
+}
+
+#[test]
+fn lints_of_all_rules_are_sorted_by_location() {
+    let src = r#"import gleam/list
+
+pub fn main(items: List(Int)) -> Bool {
+  let x = list.length(items) == 0
+  x
+}
+"#;
+    let rules = lint(src).iter().map(|lint| lint.rule).collect::<Vec<_>>();
+    assert_eq!(rules, vec!["redundant_let", "list_length_empty_check"]);
+}
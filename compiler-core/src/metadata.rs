@@ -4,9 +4,12 @@
 //! Seriaisation and deserialisation of Gleam compiler metadata into binary files
 //! using serde.
 
+mod hash;
 #[cfg(test)]
 mod tests;
 
+pub use hash::interface_hash;
+
 use std::{collections::HashMap, sync::Arc};
 
 use crate::{
@@ -247,6 +250,7 @@ impl RemapIds {
                 implementations,
                 external_erlang,
                 external_javascript,
+                external_wasm,
                 purity,
             } => ValueConstructorVariant::ModuleFn {
                 name,
@@ -258,6 +262,7 @@ impl RemapIds {
                 implementations,
                 external_erlang,
                 external_javascript,
+                external_wasm,
                 purity,
             },
             ValueConstructorVariant::Record {
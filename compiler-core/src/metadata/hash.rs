@@ -0,0 +1,543 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Hashing of the parts of a module interface other modules can depend on.
+//!
+//! The bytes produced by `metadata::encode` can't be compared directly to
+//! tell if a module's interface has changed: they include locations,
+//! documentation and warnings, which don't affect dependent modules, hash maps
+//! are encoded in whatever order they happen to be iterated in, and type
+//! variables are numbered differently each time a module is analysed.
+//!
+//! Instead the interface is walked in a stable order, skipping everything that
+//! only matters to the module itself, and with type variables numbered by
+//! their first appearance.
+
+use std::{collections::HashMap, sync::Arc};
+
+use ecow::EcoString;
+use itertools::Itertools;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::{
+    ast::{BitArrayOption, Constant, Publicity, TypedConstant},
+    type_::{
+        AccessorsMap, FieldMap, ModuleInterface, RecordAccessor, Type, TypeAliasConstructor,
+        TypeConstructor, TypeVar, TypeVariantConstructors, ValueConstructor,
+        ValueConstructorVariant,
+    },
+};
+
+pub fn interface_hash(module: &ModuleInterface) -> u64 {
+    let mut hasher = InterfaceHasher {
+        hasher: Xxh3::new(),
+        type_variables: HashMap::new(),
+    };
+    hasher.module(module);
+    hasher.hasher.digest()
+}
+
+struct InterfaceHasher {
+    hasher: Xxh3,
+    /// Type variables are numbered in the order they are first found in.
+    type_variables: HashMap<u64, u64>,
+}
+
+impl InterfaceHasher {
+    fn module(&mut self, module: &ModuleInterface) {
+        self.string(&module.name);
+        self.string(&module.package);
+        self.serialised(&module.origin);
+        self.bool(module.is_internal);
+        self.string(&module.minimum_required_version.to_string());
+
+        for (name, type_) in sorted_importable(&module.types, |type_| &type_.publicity) {
+            self.string(name);
+            self.type_constructor(type_);
+
+            match module.types_value_constructors.get(name) {
+                Some(constructors) => self.type_variant_constructors(constructors),
+                None => self.tag(0),
+            }
+            match module.accessors.get(name) {
+                Some(accessors) => self.accessors_map(accessors),
+                None => self.tag(0),
+            }
+        }
+
+        for (name, alias) in sorted_importable(&module.type_aliases, |alias| &alias.publicity) {
+            self.string(name);
+            self.type_alias(alias);
+        }
+
+        for (name, value) in sorted_importable(&module.values, |value| &value.publicity) {
+            self.string(name);
+            self.value_constructor(value);
+        }
+
+        // Inlinable functions don't hold any locations or type variables, so
+        // their encoded form is already stable.
+        for (name, function) in module
+            .inline_functions
+            .iter()
+            .sorted_by(|(one, _), (other, _)| one.cmp(other))
+        {
+            self.string(name);
+            self.serialised(function);
+        }
+    }
+
+    fn type_constructor(&mut self, type_: &TypeConstructor) {
+        let TypeConstructor {
+            publicity,
+            module,
+            parameters,
+            type_,
+            deprecation,
+            origin: _,
+            documentation: _,
+        } = type_;
+
+        self.publicity(publicity);
+        self.string(module);
+        self.types(parameters);
+        self.type_(type_);
+        self.serialised(deprecation);
+    }
+
+    fn type_variant_constructors(&mut self, constructors: &TypeVariantConstructors) {
+        let TypeVariantConstructors {
+            type_parameters_ids,
+            opaque,
+            variants,
+        } = constructors;
+
+        self.tag(1);
+        self.usize(type_parameters_ids.len());
+        for id in type_parameters_ids {
+            self.type_variable(*id);
+        }
+        self.serialised(opaque);
+        self.usize(variants.len());
+        for variant in variants {
+            self.string(&variant.name);
+            self.usize(variant.parameters.len());
+            for parameter in &variant.parameters {
+                self.optional_string(parameter.label.as_ref());
+                self.type_(&parameter.type_);
+            }
+        }
+    }
+
+    fn accessors_map(&mut self, accessors: &AccessorsMap) {
+        let AccessorsMap {
+            publicity,
+            type_,
+            shared_accessors,
+            variant_specific_accessors,
+            variant_positional_accessors,
+        } = accessors;
+
+        self.tag(1);
+        self.publicity(publicity);
+        self.type_(type_);
+        self.record_accessors(shared_accessors);
+        self.usize(variant_specific_accessors.len());
+        for accessors in variant_specific_accessors {
+            self.record_accessors(accessors);
+        }
+        self.usize(variant_positional_accessors.len());
+        for types in variant_positional_accessors {
+            self.types(types);
+        }
+    }
+
+    fn record_accessors(&mut self, accessors: &HashMap<EcoString, RecordAccessor>) {
+        self.usize(accessors.len());
+        for accessor in accessors
+            .values()
+            .sorted_by(|one, other| one.label.cmp(&other.label))
+        {
+            self.string(&accessor.label);
+            self.u64(accessor.index);
+            self.type_(&accessor.type_);
+        }
+    }
+
+    fn type_alias(&mut self, alias: &TypeAliasConstructor) {
+        let TypeAliasConstructor {
+            publicity,
+            module,
+            type_,
+            arity,
+            deprecation,
+            parameters,
+            documentation: _,
+            origin: _,
+        } = alias;
+
+        self.publicity(publicity);
+        self.string(module);
+        self.usize(*arity);
+        self.types(parameters);
+        self.type_(type_);
+        self.serialised(deprecation);
+    }
+
+    fn value_constructor(&mut self, value: &ValueConstructor) {
+        let ValueConstructor {
+            publicity,
+            deprecation,
+            variant,
+            type_,
+        } = value;
+
+        self.publicity(publicity);
+        self.serialised(deprecation);
+        self.type_(type_);
+
+        match variant {
+            ValueConstructorVariant::LocalVariable { .. } => self.tag(0),
+
+            ValueConstructorVariant::ModuleConstant {
+                module,
+                name,
+                literal,
+                implementations,
+                documentation: _,
+                location: _,
+            } => {
+                self.tag(1);
+                self.string(module);
+                self.string(name);
+                self.serialised(implementations);
+                // Constants are inlined where they're used, so their value is
+                // just as much part of the interface as their type.
+                self.constant(literal);
+            }
+
+            ValueConstructorVariant::ModuleFn {
+                name,
+                field_map,
+                module,
+                arity,
+                implementations,
+                external_erlang,
+                external_javascript,
+                external_wasm,
+                purity,
+                location: _,
+                documentation: _,
+            } => {
+                self.tag(2);
+                self.string(name);
+                self.field_map(field_map.as_ref());
+                self.string(module);
+                self.usize(*arity);
+                self.serialised(implementations);
+                self.serialised(external_erlang);
+                self.serialised(external_javascript);
+                self.serialised(external_wasm);
+                self.serialised(purity);
+            }
+
+            ValueConstructorVariant::Record {
+                name,
+                arity,
+                field_map,
+                module,
+                variants_count,
+                variant_index,
+                location: _,
+                documentation: _,
+            } => {
+                self.tag(3);
+                self.string(name);
+                self.u64((*arity).into());
+                self.field_map(field_map.as_ref());
+                self.string(module);
+                self.u64((*variants_count).into());
+                self.u64((*variant_index).into());
+            }
+        }
+    }
+
+    fn field_map(&mut self, field_map: Option<&FieldMap>) {
+        let Some(field_map) = field_map else {
+            return self.tag(0);
+        };
+
+        self.tag(1);
+        self.u64(field_map.arity.into());
+        self.usize(field_map.fields.len());
+        for (label, index) in field_map
+            .fields
+            .iter()
+            .sorted_by(|(one, _), (other, _)| one.cmp(other))
+        {
+            self.string(label);
+            self.u64((*index).into());
+        }
+    }
+
+    fn constant(&mut self, constant: &TypedConstant) {
+        match constant {
+            Constant::Int { value, .. } => {
+                self.tag(0);
+                self.string(value);
+            }
+
+            Constant::Float { value, .. } => {
+                self.tag(1);
+                self.string(value);
+            }
+
+            Constant::String { value, .. } => {
+                self.tag(2);
+                self.string(value);
+            }
+
+            Constant::Tuple { elements, .. } => {
+                self.tag(3);
+                self.constants(elements);
+            }
+
+            Constant::List { elements, tail, .. } => {
+                self.tag(4);
+                self.constants(elements);
+                match tail {
+                    Some(tail) => self.constant(tail),
+                    None => self.tag(0),
+                }
+            }
+
+            Constant::Record {
+                module,
+                name,
+                arguments,
+                ..
+            } => {
+                self.tag(5);
+                self.optional_string(module.as_ref().map(|(module, _)| module));
+                self.string(name);
+                let arguments = arguments.as_deref().unwrap_or_default();
+                self.usize(arguments.len());
+                for argument in arguments {
+                    self.optional_string(argument.label.as_ref());
+                    self.constant(&argument.value);
+                }
+            }
+
+            Constant::RecordUpdate {
+                module,
+                name,
+                record,
+                arguments,
+                ..
+            } => {
+                self.tag(6);
+                self.optional_string(module.as_ref().map(|(module, _)| module));
+                self.string(name);
+                self.constant(&record.base);
+                self.usize(arguments.len());
+                for argument in arguments {
+                    self.string(&argument.label);
+                    self.constant(&argument.value);
+                }
+            }
+
+            Constant::BitArray { segments, .. } => {
+                self.tag(7);
+                self.usize(segments.len());
+                for segment in segments {
+                    self.constant(&segment.value);
+                    self.usize(segment.options.len());
+                    for option in &segment.options {
+                        self.bit_array_option(option);
+                    }
+                }
+            }
+
+            Constant::Var { module, name, .. } => {
+                self.tag(8);
+                self.optional_string(module.as_ref().map(|(module, _)| module));
+                self.string(name);
+            }
+
+            Constant::BinaryOperator {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                self.tag(9);
+                self.string(operator.name());
+                self.constant(left);
+                self.constant(right);
+            }
+
+            Constant::Invalid { .. } => self.tag(10),
+
+            Constant::Todo { message, .. } => {
+                self.tag(11);
+                match message {
+                    Some(message) => self.constant(message),
+                    None => self.tag(0),
+                }
+            }
+        }
+    }
+
+    fn constants(&mut self, constants: &[TypedConstant]) {
+        self.usize(constants.len());
+        for constant in constants {
+            self.constant(constant);
+        }
+    }
+
+    fn bit_array_option(&mut self, option: &BitArrayOption<TypedConstant>) {
+        let tag = match option {
+            BitArrayOption::Bytes { .. } => 0,
+            BitArrayOption::Int { .. } => 1,
+            BitArrayOption::Float { .. } => 2,
+            BitArrayOption::Bits { .. } => 3,
+            BitArrayOption::Utf8 { .. } => 4,
+            BitArrayOption::Utf16 { .. } => 5,
+            BitArrayOption::Utf32 { .. } => 6,
+            BitArrayOption::Utf8Codepoint { .. } => 7,
+            BitArrayOption::Utf16Codepoint { .. } => 8,
+            BitArrayOption::Utf32Codepoint { .. } => 9,
+            BitArrayOption::Signed { .. } => 10,
+            BitArrayOption::Unsigned { .. } => 11,
+            BitArrayOption::Big { .. } => 12,
+            BitArrayOption::Little { .. } => 13,
+            BitArrayOption::Native { .. } => 14,
+            BitArrayOption::Size { value, .. } => {
+                self.tag(15);
+                return self.constant(value);
+            }
+            BitArrayOption::Unit { value, .. } => {
+                self.tag(16);
+                return self.u64((*value).into());
+            }
+        };
+        self.tag(tag);
+    }
+
+    fn types(&mut self, types: &[Arc<Type>]) {
+        self.usize(types.len());
+        for type_ in types {
+            self.type_(type_);
+        }
+    }
+
+    fn type_(&mut self, type_: &Type) {
+        match type_ {
+            Type::Named {
+                package,
+                module,
+                name,
+                arguments,
+                inferred_variant,
+                publicity: _,
+            } => {
+                self.tag(0);
+                self.string(package);
+                self.string(module);
+                self.string(name);
+                self.types(arguments);
+                self.serialised(inferred_variant);
+            }
+
+            Type::Fn { arguments, return_ } => {
+                self.tag(1);
+                self.types(arguments);
+                self.type_(return_);
+            }
+
+            Type::Var { type_ } => match &*type_.borrow() {
+                TypeVar::Link { type_ } => self.type_(type_),
+                TypeVar::Unbound { id } => {
+                    self.tag(2);
+                    self.type_variable(*id);
+                }
+                TypeVar::Generic { id } => {
+                    self.tag(3);
+                    self.type_variable(*id);
+                }
+            },
+
+            Type::Tuple { elements } => {
+                self.tag(4);
+                self.types(elements);
+            }
+        }
+    }
+
+    fn type_variable(&mut self, id: u64) {
+        let next = self.type_variables.len() as u64;
+        let id = *self.type_variables.entry(id).or_insert(next);
+        self.u64(id);
+    }
+
+    fn publicity(&mut self, publicity: &Publicity) {
+        self.tag(match publicity {
+            Publicity::Public => 0,
+            Publicity::Private => 1,
+            Publicity::Internal { .. } => 2,
+        });
+    }
+
+    fn serialised<T: serde::Serialize>(&mut self, value: &T) {
+        let bytes = bitcode::serialize(value).expect("Serialising interface for hashing");
+        self.bytes(&bytes);
+    }
+
+    fn optional_string(&mut self, string: Option<&EcoString>) {
+        match string {
+            Some(string) => {
+                self.tag(1);
+                self.string(string);
+            }
+            None => self.tag(0),
+        }
+    }
+
+    fn string(&mut self, string: &str) {
+        self.bytes(string.as_bytes());
+    }
+
+    /// Byte sequences are prefixed with their length so that two different
+    /// sequences of strings can never result in the same input to the hasher.
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.usize(bytes.len());
+        self.hasher.update(bytes);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.tag(value.into());
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.u64(value as u64);
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.hasher.update(&value.to_le_bytes());
+    }
+
+    fn tag(&mut self, tag: u8) {
+        self.hasher.update(&[tag]);
+    }
+}
+
+/// Returns the definitions other modules can import, sorted by name.
+fn sorted_importable<T>(
+    definitions: &HashMap<EcoString, T>,
+    publicity: impl Fn(&T) -> &Publicity,
+) -> impl Iterator<Item = (&EcoString, &T)> {
+    definitions
+        .iter()
+        .filter(move |(_, definition)| publicity(*definition).is_importable())
+        .sorted_by(|(one, _), (other, _)| one.cmp(other))
+}
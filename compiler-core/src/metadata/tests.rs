@@ -60,8 +60,10 @@ fn constant_module(constant: TypedConstant) -> ModuleInterface {
                         gleam: true,
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: true,
                     },
                     name: "one".into(),
                 },
@@ -538,12 +540,15 @@ fn module_fn_value() {
                     },
                     external_erlang: None,
                     external_javascript: None,
+                    external_wasm: None,
                     implementations: Implementations {
                         gleam: true,
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: true,
                     },
                     purity: Purity::Pure,
                 },
@@ -594,12 +599,15 @@ fn deprecated_module_fn_value() {
                     },
                     external_erlang: None,
                     external_javascript: None,
+                    external_wasm: None,
                     implementations: Implementations {
                         gleam: true,
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: true,
                     },
                     purity: Purity::Pure,
                 },
@@ -648,12 +656,15 @@ fn private_module_fn_value() {
                     },
                     external_erlang: None,
                     external_javascript: None,
+                    external_wasm: None,
                     implementations: Implementations {
                         gleam: true,
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: true,
                     },
                     purity: Purity::Pure,
                 },
@@ -704,12 +715,15 @@ fn module_fn_value_regression() {
                     },
                     external_erlang: None,
                     external_javascript: None,
+                    external_wasm: None,
                     implementations: Implementations {
                         gleam: true,
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: true,
                     },
                     purity: Purity::TrustedPure,
                 },
@@ -756,6 +770,7 @@ fn module_fn_value_with_field_map() {
                     }),
                     external_erlang: None,
                     external_javascript: None,
+                    external_wasm: None,
                     module: "a".into(),
                     arity: 5,
                     location: SrcSpan { start: 2, end: 11 },
@@ -763,8 +778,10 @@ fn module_fn_value_with_field_map() {
                         gleam: true,
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: true,
                     },
                     purity: Purity::Pure,
                 },
@@ -1229,8 +1246,10 @@ fn constant_var() {
                     gleam: true,
                     uses_erlang_externals: false,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: true,
                 },
                 name: "one_original".into(),
             },
@@ -1262,8 +1281,10 @@ fn constant_var() {
                             gleam: true,
                             uses_erlang_externals: false,
                             uses_javascript_externals: false,
+                            uses_wasm_externals: false,
                             can_run_on_erlang: true,
                             can_run_on_javascript: true,
+                            can_run_on_wasm: true,
                         },
                         name: "one".into(),
                     },
@@ -1284,8 +1305,10 @@ fn constant_var() {
                             gleam: true,
                             uses_erlang_externals: false,
                             uses_javascript_externals: false,
+                            uses_wasm_externals: false,
                             can_run_on_erlang: true,
                             can_run_on_javascript: true,
+                            can_run_on_wasm: true,
                         },
                         name: "one_original".into(),
                     },
@@ -1533,12 +1556,15 @@ fn module_fn_value_with_external_implementations() {
                     },
                     external_erlang: Some(("wibble".into(), "wobble".into())),
                     external_javascript: Some(("wobble".into(), "wibble".into())),
+                    external_wasm: None,
                     implementations: Implementations {
                         gleam: false,
                         uses_erlang_externals: true,
                         uses_javascript_externals: true,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: false,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: false,
                     },
                     purity: Purity::Impure,
                 },
@@ -1614,12 +1640,15 @@ fn internal_module_fn() {
                     },
                     external_erlang: Some(("wibble".into(), "wobble".into())),
                     external_javascript: Some(("wobble".into(), "wibble".into())),
+                    external_wasm: None,
                     implementations: Implementations {
                         gleam: false,
                         uses_erlang_externals: true,
                         uses_javascript_externals: true,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: false,
                     },
                     purity: Purity::Unknown,
                 },
@@ -1674,12 +1703,15 @@ fn internal_annotated_module_fn() {
                     },
                     external_erlang: Some(("wibble".into(), "wobble".into())),
                     external_javascript: Some(("wobble".into(), "wibble".into())),
+                    external_wasm: None,
                     implementations: Implementations {
                         gleam: false,
                         uses_erlang_externals: true,
                         uses_javascript_externals: true,
+                        uses_wasm_externals: false,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: false,
                     },
                     purity: Purity::Impure,
                 },
@@ -4,7 +4,7 @@
 use std::{collections::HashMap, ops::Deref};
 
 use ecow::EcoString;
-use serde::Serialize;
+use serde::{Deserialize, Serialize, de::IgnoredAny};
 
 #[cfg(test)]
 mod tests;
@@ -52,6 +52,42 @@ pub struct ModuleInterface {
     functions: HashMap<EcoString, FunctionInterface>,
 }
 
+/// The names of the public types and values of each module of a package, as
+/// read back from its serialised `PackageInterface`. This is all that's needed
+/// to compare the interfaces of different versions of a package.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct PackageInterfaceSummary {
+    #[serde(default)]
+    modules: HashMap<EcoString, ModuleInterfaceSummary>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "kebab-case", default)]
+struct ModuleInterfaceSummary {
+    type_aliases: HashMap<EcoString, IgnoredAny>,
+    types: HashMap<EcoString, IgnoredAny>,
+    constants: HashMap<EcoString, IgnoredAny>,
+    functions: HashMap<EcoString, IgnoredAny>,
+}
+
+impl PackageInterfaceSummary {
+    /// Whether the given module has a public type or type alias with the
+    /// given name.
+    pub fn has_type(&self, module: &str, name: &str) -> bool {
+        self.modules.get(module).is_some_and(|module| {
+            module.types.contains_key(name) || module.type_aliases.contains_key(name)
+        })
+    }
+
+    /// Whether the given module has a public constant or function with the
+    /// given name.
+    pub fn has_value(&self, module: &str, name: &str) -> bool {
+        self.modules.get(module).is_some_and(|module| {
+            module.constants.contains_key(name) || module.functions.contains_key(name)
+        })
+    }
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "kebab-case")]
 pub struct TypeDefinitionInterface {
@@ -265,6 +301,11 @@ impl ImplementationsInterface {
 
             can_run_on_erlang,
             can_run_on_javascript,
+
+            // The WebAssembly target is experimental, so it's not part of the
+            // package interface yet.
+            uses_wasm_externals: _,
+            can_run_on_wasm: _,
         } = implementations;
 
         ImplementationsInterface {
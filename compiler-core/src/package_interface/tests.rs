@@ -15,7 +15,7 @@ use crate::{
     config::{Docs, ErlangConfig, GleamVersion, JavaScriptConfig, PackageConfig},
     type_::PRELUDE_MODULE_NAME,
     uid::UniqueIdGenerator,
-    warning::{TypeWarningEmitter, WarningEmitter},
+    warning::{TypeWarningEmitter, WarningEmitter, WarningLevels},
 };
 
 use super::PackageInterface;
@@ -177,6 +177,7 @@ fn package_from_module(module: Module) -> Package {
                     .build()
                     .expect("internals glob"),
             ]),
+            warnings: WarningLevels::default(),
         },
         cached_module_names: Vec::new(),
         modules: vec![module],
@@ -350,3 +351,30 @@ pub fn type_alias_with_two_parameters() {
 pub fn type_alias_with_two_parameters_in_reverse() {
     assert_package_interface!("pub type Wibble(a, b) = Result(b, a)");
 }
+
+#[test]
+pub fn summary_is_read_back_from_interface() {
+    let json = compile_package(
+        None,
+        r#"
+pub type Wibble { Wibble }
+pub type Wobble = Int
+type Private { Private }
+pub const wabble = 1
+pub fn wubble() { Nil }
+fn private() { Nil }
+"#,
+        None,
+    );
+    let summary: super::PackageInterfaceSummary =
+        serde_json::from_str(&json).expect("summary from json");
+
+    assert!(summary.has_type("my/module", "Wibble"));
+    assert!(summary.has_type("my/module", "Wobble"));
+    assert!(!summary.has_type("my/module", "Private"));
+    assert!(!summary.has_type("my/other_module", "Wibble"));
+    assert!(summary.has_value("my/module", "wabble"));
+    assert!(summary.has_value("my/module", "wubble"));
+    assert!(!summary.has_value("my/module", "private"));
+    assert!(!summary.has_value("my/module", "Wibble"));
+}
@@ -79,7 +79,7 @@ use crate::type_::Deprecation;
 use crate::type_::error::{VariableDeclaration, VariableOrigin, VariableSyntax};
 use crate::type_::expression::{Implementations, Purity};
 use crate::type_::printer::Names;
-use crate::warning::{DeprecatedSyntaxWarning, WarningEmitter};
+use crate::warning::{DeprecatedSyntaxWarning, WarningEmitter, WarningName};
 use camino::Utf8PathBuf;
 use ecow::EcoString;
 use error::{LexicalError, ParseError, ParseErrorType};
@@ -131,18 +131,25 @@ struct Attributes {
     deprecated: Deprecation,
     external_erlang: Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: Option<(EcoString, EcoString, SrcSpan)>,
+    external_wasm: Option<(EcoString, EcoString, SrcSpan)>,
     internal: InternalAttribute,
+    inline: Option<SrcSpan>,
+    allowed_warnings: Vec<WarningName>,
 }
 
 impl Attributes {
     fn has_function_only(&self) -> bool {
-        self.external_erlang.is_some() || self.external_javascript.is_some()
+        self.external_erlang.is_some()
+            || self.external_javascript.is_some()
+            || self.external_wasm.is_some()
+            || self.inline.is_some()
     }
 
     fn has_external_for(&self, target: Target) -> bool {
         match target {
             Target::Erlang => self.external_erlang.is_some(),
             Target::JavaScript => self.external_javascript.is_some(),
+            Target::Wasm => self.external_wasm.is_some(),
         }
     }
 
@@ -150,6 +157,7 @@ impl Attributes {
         match target {
             Target::Erlang => self.external_erlang = ext,
             Target::JavaScript => self.external_javascript = ext,
+            Target::Wasm => self.external_wasm = ext,
         }
     }
 }
@@ -164,12 +172,54 @@ pub fn parse_module(
     path: Utf8PathBuf,
     src: &str,
     warnings: &WarningEmitter,
-) -> Result<Parsed, ParseError> {
+) -> Result<Parsed, Vec1<ParseError>> {
+    let (parsed, errors) = parse_module_with_recovery(path, src, warnings);
+    match Vec1::try_from_vec(errors) {
+        Ok(errors) => Err(errors),
+        Err(_) => Ok(parsed),
+    }
+}
+
+/// Parses a module, carrying on after a syntax error instead of stopping at
+/// the first one. The parser skips to the following statement or definition,
+/// so the returned module holds all the definitions that could be parsed,
+/// along with all the errors found in the source code.
+///
+/// The language server uses this to keep on providing information about a
+/// module while it's being edited.
+///
+pub fn parse_module_with_recovery(
+    path: Utf8PathBuf,
+    src: &str,
+    warnings: &WarningEmitter,
+) -> (Parsed, Vec<ParseError>) {
     let lex = lexer::make_tokenizer(src);
     let mut parser = Parser::new(lex);
-    let mut parsed = parser.parse_module()?;
+    let mut parsed = parser.parse_module();
     parsed.extra = parser.extra;
 
+    // Once the lexer has run into an error the parser doesn't get to see the
+    // following tokens, so any parse error that comes after it is not
+    // relevant.
+    let errors = if parser.lex_errors.is_empty() {
+        parser.errors
+    } else {
+        parser
+            .lex_errors
+            .into_iter()
+            .map(|error| ParseError {
+                error: ParseErrorType::LexError { error },
+                location: error.location,
+            })
+            .collect()
+    };
+
+    // Deprecations are not reported for code with syntax errors, those need
+    // to be fixed first.
+    if !errors.is_empty() {
+        return (parsed, errors);
+    }
+
     let src = EcoString::from(src);
     for warning in parser.warnings {
         warnings.emit(Warning::DeprecatedSyntax {
@@ -187,7 +237,7 @@ pub fn parse_module(
         });
     }
 
-    Ok(parsed)
+    (parsed, errors)
 }
 
 //
@@ -198,7 +248,7 @@ pub fn parse_statement_sequence(src: &str) -> Result<Vec1<UntypedStatement>, Par
     let lex = lexer::make_tokenizer(src);
     let mut parser = Parser::new(lex);
     let expr = parser.parse_statement_sequence();
-    let expr = parser.ensure_no_errors_or_remaining_input(expr)?;
+    let expr = finish_test_parse(&mut parser, expr)?;
     match expr {
         Some((e, _)) => Ok(e),
         _ => parse_error(ParseErrorType::ExpectedExpr, SrcSpan { start: 0, end: 0 }),
@@ -213,13 +263,71 @@ pub fn parse_const_value(src: &str) -> Result<UntypedConstant, ParseError> {
     let lex = lexer::make_tokenizer(src);
     let mut parser = Parser::new(lex);
     let expr = parser.parse_const_value();
-    let expr = parser.ensure_no_errors_or_remaining_input(expr)?;
+    let expr = finish_test_parse(&mut parser, expr)?;
     match expr {
         Some(e) => Ok(e),
         _ => parse_error(ParseErrorType::ExpectedExpr, SrcSpan { start: 0, end: 0 }),
     }
 }
 
+/// Surfaces the errors found when parsing something other than a whole module
+/// in the order: lexical errors, errors the parser has recovered from, the
+/// error returned by the parser, and finally any leftover input.
+///
+/// The parser carries on after an error, so it might run into a lexical error
+/// further down the source. That one is only reported if nothing before it has
+/// already failed to parse.
+///
+#[cfg(test)]
+fn finish_test_parse<T, A>(
+    parser: &mut Parser<T>,
+    parse_result: Result<A, ParseError>,
+) -> Result<A, ParseError>
+where
+    T: Iterator<Item = LexResult>,
+{
+    let first_parse_error = parser
+        .errors
+        .first()
+        .cloned()
+        .or_else(|| parse_result.as_ref().err().cloned());
+
+    if let Some(lex_error) = parser.lex_errors.first() {
+        match first_parse_error {
+            Some(error)
+                if error.error != ParseErrorType::UnexpectedEof
+                    && error.location.start < lex_error.location.start =>
+            {
+                return Err(error);
+            }
+            Some(_) | None => {
+                let location = lex_error.location;
+                let error = *lex_error;
+                return Err(ParseError {
+                    error: ParseErrorType::LexError { error },
+                    location,
+                });
+            }
+        }
+    }
+    if let Some(error) = first_parse_error {
+        return Err(error);
+    }
+    let parse_result = parse_result?;
+    if let Some((start, token, end)) = parser.next_token() {
+        let expected = vec!["An import, const, type, or function.".into()];
+        return parse_error(
+            ParseErrorType::UnexpectedToken {
+                token,
+                expected,
+                hint: None,
+            },
+            SrcSpan { start, end },
+        );
+    }
+    Ok(parse_result)
+}
+
 //
 // Parser
 //
@@ -228,8 +336,19 @@ pub struct Parser<T: Iterator<Item = LexResult>> {
     tokens: T,
     lex_errors: Vec<LexicalError>,
     warnings: Vec<DeprecatedSyntaxWarning>,
+    /// The errors the parser has recovered from, skipping to the following
+    /// statement or definition.
+    errors: Vec<ParseError>,
     token0: Option<Spanned>,
     token1: Option<Spanned>,
+    /// The number of braces that are still open before `token0` and `token1`.
+    /// This is used when recovering from an error, to know when the block the
+    /// error is in is over.
+    token0_depth: u32,
+    token1_depth: u32,
+    /// The number of braces that are still open after the latest token read
+    /// from the lexer.
+    brace_depth: u32,
     extra: ModuleExtra,
     doc_comments: VecDeque<(u32, EcoString)>,
     detached_doc_comments: Vec<SrcSpan>,
@@ -243,8 +362,12 @@ where
             tokens: input,
             lex_errors: vec![],
             warnings: vec![],
+            errors: vec![],
             token0: None,
             token1: None,
+            token0_depth: 0,
+            token1_depth: 0,
+            brace_depth: 0,
             extra: ModuleExtra::new(),
             doc_comments: VecDeque::new(),
             detached_doc_comments: Vec::new(),
@@ -254,9 +377,38 @@ where
         parser
     }
 
-    fn parse_module(&mut self) -> Result<Parsed, ParseError> {
-        let definitions = self.series_of(&Parser::parse_definition, None);
-        let definitions = self.ensure_no_errors_or_remaining_input(definitions)?;
+    fn parse_module(&mut self) -> Parsed {
+        let mut definitions = vec![];
+        loop {
+            let position = self.position();
+            match self.parse_definition() {
+                Ok(Some(definition)) => definitions.push(definition),
+                Ok(None) => match self.next_token() {
+                    None => break,
+                    Some((start, token, end)) => {
+                        self.errors.push(ParseError {
+                            error: ParseErrorType::UnexpectedToken {
+                                token,
+                                expected: vec!["An import, const, type, or function.".into()],
+                                hint: None,
+                            },
+                            location: SrcSpan { start, end },
+                        });
+                        self.skip_to_next_definition();
+                    }
+                },
+                Err(error) => {
+                    self.errors.push(error);
+                    // If the definition couldn't even be started the parser
+                    // needs to move past its first token.
+                    if self.position() == position {
+                        self.advance();
+                    }
+                    self.skip_to_next_definition();
+                }
+            }
+        }
+
         let module = Module {
             name: "".into(),
             documentation: vec![],
@@ -265,59 +417,75 @@ where
             names: Names::new(),
             unused_definition_positions: HashSet::new(),
         };
-        Ok(Parsed {
+        Parsed {
             module,
             extra: ModuleExtra::new(),
-        })
+        }
     }
 
-    // The way the parser is currently implemented, it cannot exit immediately while advancing
-    // the token stream upon seeing a LexError. That is to avoid having to put `?` all over the
-    // place and instead we collect LexErrors in `self.lex_errors` and attempt to continue parsing.
-    // Once parsing has returned we want to surface an error in the order:
-    // 1) LexError, 2) ParseError, 3) More Tokens Left
-    fn ensure_no_errors_or_remaining_input<A>(
-        &mut self,
-        parse_result: Result<A, ParseError>,
-    ) -> Result<A, ParseError> {
-        let parse_result = self.ensure_no_errors(parse_result)?;
-        if let Some((start, token, end)) = self.next_token() {
-            // there are still more tokens
-            let expected = vec!["An import, const, type, or function.".into()];
-            return parse_error(
-                ParseErrorType::UnexpectedToken {
-                    token,
-                    expected,
-                    hint: None,
-                },
-                SrcSpan { start, end },
-            );
+    /// Skips tokens until the start of the next top level definition, or the
+    /// end of the module.
+    fn skip_to_next_definition(&mut self) {
+        while !self.at_definition_start() && (self.token0.is_some() || self.token1.is_some()) {
+            self.advance();
         }
-        // no errors
-        Ok(parse_result)
     }
 
-    // The way the parser is currently implemented, it cannot exit immediately
-    // while advancing the token stream upon seeing a LexError. That is to avoid
-    // having to put `?` all over the place and instead we collect LexErrors in
-    // `self.lex_errors` and attempt to continue parsing.
-    // Once parsing has returned we want to surface an error in the order:
-    // 1) LexError, 2) ParseError
-    fn ensure_no_errors<A>(
-        &mut self,
-        parse_result: Result<A, ParseError>,
-    ) -> Result<A, ParseError> {
-        if let Some(error) = self.lex_errors.first() {
-            // Lex errors first
-            let location = error.location;
-            let error = *error;
-            parse_error(ParseErrorType::LexError { error }, location)
-        } else {
-            // Return any existing parse error
-            parse_result
+    /// Named functions, imports, types, constants, and attributes can only
+    /// appear at the top level of a module, so these are the tokens the parser
+    /// can safely start again from after an error.
+    fn at_definition_start(&self) -> bool {
+        match (&self.token0, &self.token1) {
+            (
+                Some((_, Token::Import | Token::Const | Token::Type | Token::Pub | Token::At, _)),
+                _,
+            )
+            | (Some((_, Token::Fn, _)), Some((_, Token::Name { .. }, _))) => true,
+            (_, _) => false,
         }
     }
 
+    /// Skips tokens until the start of the next statement of the block whose
+    /// first token had the given depth, or the end of the block. If the block
+    /// can't be recovered the error is returned, so the enclosing block or
+    /// definition can be skipped instead.
+    fn skip_to_next_statement(&mut self, depth: u32, error: ParseError) -> Result<(), ParseError> {
+        // There's nothing left to recover.
+        if error.error == ParseErrorType::UnexpectedEof {
+            return Err(error);
+        }
+
+        loop {
+            if self.at_definition_start() {
+                return Err(error);
+            }
+            match &self.token0 {
+                // The block is over, the error must be handled by the
+                // enclosing one.
+                Some(_) if self.token0_depth < depth => return Err(error),
+                Some((_, Token::RightBrace | Token::Let | Token::Use | Token::Assert, _))
+                    if self.token0_depth == depth =>
+                {
+                    break;
+                }
+                None if self.token1.is_none() => return Err(error),
+                Some(_) | None => self.advance(),
+            }
+        }
+
+        self.errors.push(error);
+        Ok(())
+    }
+
+    /// The position of the next token, used to tell if the parser has made any
+    /// progress.
+    fn position(&self) -> Option<u32> {
+        self.token0
+            .as_ref()
+            .or(self.token1.as_ref())
+            .map(|(start, _, _)| *start)
+    }
+
     fn parse_definition(&mut self) -> Result<Option<TargetedDefinition>, ParseError> {
         let mut attributes = Attributes::default();
         let location = self.parse_attributes(&mut attributes)?;
@@ -380,17 +548,25 @@ where
             }
         }?;
 
+        // Functions take the `@inline` attribute, so if it's still here it was
+        // used on some other kind of definition.
+        if let (Some(_), Some(inline_location)) = (&def, attributes.inline) {
+            return parse_error(ParseErrorType::ExpectedFunctionDefinition, inline_location);
+        }
+
         match (def, location) {
             (Some(definition), _) if definition.is_function() || definition.is_custom_type() => {
                 Ok(Some(TargetedDefinition {
                     definition,
                     target: attributes.target,
+                    allowed_warnings: attributes.allowed_warnings,
                 }))
             }
 
             (Some(definition), None) => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                allowed_warnings: attributes.allowed_warnings,
             })),
 
             (_, Some(location)) if attributes.has_function_only() => {
@@ -400,6 +576,7 @@ where
             (Some(definition), _) => Ok(Some(TargetedDefinition {
                 definition,
                 target: attributes.target,
+                allowed_warnings: attributes.allowed_warnings,
             })),
 
             (_, Some(location)) => parse_error(ParseErrorType::ExpectedDefinition, location),
@@ -553,6 +730,17 @@ where
                 }
             }
 
+            // A typed hole `_`, discards with a name are still not allowed
+            // in expressions.
+            Some((start, Token::DiscardName { name }, end)) if name == "_" => {
+                self.advance();
+                UntypedExpr::Todo {
+                    location: SrcSpan { start, end },
+                    kind: TodoKind::Hole,
+                    message: None,
+                }
+            }
+
             Some((start, Token::Todo, end)) => {
                 self.advance();
                 let message = self.maybe_parse_as_message()?;
@@ -1248,9 +1436,23 @@ where
         let mut statements = vec![];
         let mut start = None;
         let mut end = 0;
+        let depth = self.token0_depth;
 
-        // Try and parse as many expressions as possible
-        while let Some(statement) = self.parse_statement()? {
+        // Try and parse as many expressions as possible. If a statement can't
+        // be parsed it's left out, and parsing continues with the next one.
+        loop {
+            let position = self.position();
+            let statement = match self.parse_statement() {
+                Ok(Some(statement)) => statement,
+                Ok(None) => break,
+                Err(error) => {
+                    if self.position() == position && !self.at_definition_start() {
+                        self.advance();
+                    }
+                    self.skip_to_next_statement(depth, error)?;
+                    continue;
+                }
+            };
             if start.is_none() {
                 start = Some(statement.location().start);
             }
@@ -2323,14 +2525,18 @@ where
             return_type: (),
             return_annotation,
             deprecation: std::mem::take(&mut attributes.deprecated),
+            inline: attributes.inline.take(),
             external_erlang: attributes.external_erlang.take(),
             external_javascript: attributes.external_javascript.take(),
+            external_wasm: attributes.external_wasm.take(),
             implementations: Implementations {
                 gleam: true,
                 can_run_on_erlang: true,
                 can_run_on_javascript: true,
+                can_run_on_wasm: true,
                 uses_erlang_externals: false,
                 uses_javascript_externals: false,
+                uses_wasm_externals: false,
             },
             purity: Purity::Pure,
         })))
@@ -2512,7 +2718,22 @@ where
             }
         };
 
-        match self.parse_expression()? {
+        // A discard making up a whole argument is a function capture hole, and
+        // not a typed hole in an expression, so it's not parsed as one.
+        let is_capture_hole = matches!(
+            (&self.token0, &self.token1),
+            (
+                Some((_, Token::DiscardName { .. }, _)),
+                Some((_, Token::Comma | Token::RightParen, _))
+            )
+        );
+        let value = if is_capture_hole {
+            None
+        } else {
+            self.parse_expression()?
+        };
+
+        match value {
             Some(value) => {
                 let arg = match label {
                     Some((start, label, _)) => CallArg {
@@ -2682,6 +2903,7 @@ where
             deprecation: std::mem::take(&mut attributes.deprecated),
             external_erlang: std::mem::take(&mut attributes.external_erlang),
             external_javascript: std::mem::take(&mut attributes.external_javascript),
+            external_wasm: std::mem::take(&mut attributes.external_wasm),
         })))
     }
 
@@ -2694,8 +2916,11 @@ where
             // Expecting all but the deprecated atterbutes to be default
             if attributes.external_erlang.is_some()
                 || attributes.external_javascript.is_some()
+                || attributes.external_wasm.is_some()
                 || attributes.target.is_some()
                 || attributes.internal != InternalAttribute::Missing
+                || attributes.inline.is_some()
+                || !attributes.allowed_warnings.is_empty()
             {
                 return parse_error(ParseErrorType::UnknownAttributeRecordVariant, attr_span);
             }
@@ -3357,8 +3582,10 @@ where
                         gleam: true,
                         can_run_on_erlang: true,
                         can_run_on_javascript: true,
+                        can_run_on_wasm: true,
                         uses_erlang_externals: false,
                         uses_javascript_externals: false,
+                        uses_wasm_externals: false,
                     },
                 })))
             }
@@ -4458,6 +4685,7 @@ functions are declared separately from types.";
             match name.as_str() {
                 "javascript" => Ok(Target::JavaScript),
                 "erlang" => Ok(Target::Erlang),
+                "wasm" => Ok(Target::Wasm),
                 "js" => {
                     self.warnings
                         .push(DeprecatedSyntaxWarning::DeprecatedTargetShorthand {
@@ -4660,6 +4888,14 @@ functions are declared separately from types.";
         let mut previous_newline = None;
         let mut nxt;
         loop {
+            // The lexer can't move past an error, so once it has run into one
+            // there's nothing more to read: pulling more tokens would only
+            // report the same error again each time the parser recovers.
+            if !self.lex_errors.is_empty() {
+                nxt = None;
+                break;
+            }
+
             match self.tokens.next() {
                 // gather and skip extra
                 Some(Ok((start, Token::CommentNormal, end))) => {
@@ -4704,8 +4940,18 @@ functions are declared separately from types.";
                 }
             }
         }
+        let nxt_depth = self.brace_depth;
+        match &nxt {
+            Some((_, Token::LeftBrace, _)) => self.brace_depth += 1,
+            Some((_, Token::RightBrace, _)) => {
+                self.brace_depth = self.brace_depth.saturating_sub(1)
+            }
+            Some(_) | None => (),
+        }
         self.token0 = self.token1.take();
+        self.token0_depth = self.token1_depth;
         self.token1 = nxt.take();
+        self.token1_depth = nxt_depth;
         t
     }
 
@@ -4780,6 +5026,8 @@ functions are declared separately from types.";
             "target" => self.parse_target_attribute(start, end, attributes),
             "deprecated" => self.parse_deprecated_attribute(start, end, attributes),
             "internal" => self.parse_internal_attribute(start, end, attributes),
+            "inline" => self.parse_inline_attribute(start, end, attributes),
+            "allow" => self.parse_allow_attribute(start, attributes),
             _ => parse_error(ParseErrorType::UnknownAttribute, SrcSpan { start, end }),
         }?;
 
@@ -4816,6 +5064,7 @@ functions are declared separately from types.";
         let target = match target.as_str() {
             "erlang" => Target::Erlang,
             "javascript" => Target::JavaScript,
+            "wasm" => Target::Wasm,
             _ => return parse_error(ParseErrorType::UnknownTarget, SrcSpan::new(start, end)),
         };
 
@@ -4875,6 +5124,44 @@ functions are declared separately from types.";
             }
         }
     }
+
+    fn parse_inline_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if attributes.inline.is_some() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        attributes.inline = Some(SrcSpan::new(start, end));
+        Ok(end)
+    }
+
+    fn parse_allow_attribute(
+        &mut self,
+        start: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        let _ = self.expect_one(&Token::LeftParen)?;
+        // `todo` is a keyword, but it's also the name of a kind of warning.
+        let (name_start, name, name_end) = match self.maybe_one(&Token::Todo) {
+            Some((name_start, name_end)) => (name_start, "todo".into(), name_end),
+            None => self.expect_name(IncorrectNamePosition::Warning)?,
+        };
+        let Ok(name) = WarningName::from_str(&name) else {
+            return parse_error(
+                ParseErrorType::UnknownWarning,
+                SrcSpan::new(name_start, name_end),
+            );
+        };
+        let (_, end) = self.expect_one(&Token::RightParen)?;
+        if attributes.allowed_warnings.contains(&name) {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        attributes.allowed_warnings.push(name);
+        Ok(end)
+    }
 }
 
 fn concat_pattern_variable_left_hand_side_error<T>(start: u32, end: u32) -> Result<T, ParseError> {
@@ -81,6 +81,8 @@ pub enum IncorrectNamePosition {
     Constant,
     /// As a target name: `@external(_wibble, ...)`
     Target,
+    /// As the name of a warning: `@allow(_wibble)`
+    Warning,
     /// Used as a variable expression: `let a = _wibble`, `let a = _wibble(10)`
     Variable,
 }
@@ -124,6 +126,7 @@ pub enum ParseErrorType {
     DuplicateAttribute, // an attribute was used more than once
     UnknownAttribute, // an attribute was used that is not known
     UnknownTarget, // an unknown target was used
+    UnknownWarning, // an unknown kind of warning was given to `@allow`
     ListSpreadWithoutElements, // Pointless spread: `[..xs]`
     ListSpreadFollowedByElements, // trying to append something after the spread: `[..xs, x]`
     ListSpreadWithAnotherSpread {
@@ -287,7 +290,7 @@ impl ParseErrorType {
             },
 
             ParseErrorType::ExpectedTargetName => ParseErrorDetails {
-                text: "Try `erlang`, `javascript`.".into(),
+                text: "Try `erlang`, `javascript`, `wasm`.".into(),
                 hint: None,
                 label_text: "I was expecting a target name after this".into(),
                 extra_labels: vec![],
@@ -319,6 +322,7 @@ tuples are created with `#(` and `)`."
                     IncorrectNamePosition::Attribute => "An attribute name",
                     IncorrectNamePosition::Constant => "A constant name",
                     IncorrectNamePosition::Target => "A target name",
+                    IncorrectNamePosition::Warning => "A warning name",
                     IncorrectNamePosition::Variable => "A variable name",
                 };
                 ParseErrorDetails {
@@ -652,7 +656,9 @@ would have an unknown size."
 
             ParseErrorType::UnknownAttribute => ParseErrorDetails {
                 text: "".into(),
-                hint: Some("Try `deprecated`, `external` or `internal` instead.".into()),
+                hint: Some(
+                    "Try `allow`, `deprecated`, `external`, `inline` or `internal` instead.".into(),
+                ),
                 label_text: "I don't recognise this attribute".into(),
                 extra_labels: vec![],
             },
@@ -665,12 +671,22 @@ would have an unknown size."
             },
 
             ParseErrorType::UnknownTarget => ParseErrorDetails {
-                text: "Try `erlang`, `javascript`.".into(),
+                text: "Try `erlang`, `javascript`, `wasm`.".into(),
                 hint: None,
                 label_text: "I don't recognise this target".into(),
                 extra_labels: vec![],
             },
 
+            ParseErrorType::UnknownWarning => ParseErrorDetails {
+                text: wrap(
+                    "Warnings are named in snake case, like `unused_variable` or \
+`todo`. A group of warnings such as `unused` can be given too.",
+                ),
+                hint: None,
+                label_text: "I don't recognise this warning".into(),
+                extra_labels: vec![],
+            },
+
             ParseErrorType::ExpectedFunctionBody => ParseErrorDetails {
                 text: "".into(),
                 hint: None,
@@ -9,6 +9,7 @@ use crate::parse::token::Token;
 use crate::warning::WarningEmitter;
 use camino::Utf8PathBuf;
 use src_span::SrcSpan;
+use vec1::vec1;
 
 use ecow::EcoString;
 use itertools::Itertools;
@@ -60,7 +61,7 @@ pub fn expect_module_error(src: &str) -> String {
     let error = crate::error::Error::Parse {
         src: src.into(),
         path: Utf8PathBuf::from("/src/parse/error.gleam"),
-        error: Box::new(result),
+        errors: result,
     };
     error.pretty_string()
 }
@@ -70,7 +71,7 @@ pub fn expect_error(src: &str) -> String {
     let error = crate::error::Error::Parse {
         src: src.into(),
         path: Utf8PathBuf::from("/src/parse/error.gleam"),
-        error: Box::new(result),
+        errors: vec1![result],
     };
     error.pretty_string()
 }
@@ -948,6 +949,15 @@ pub fn main() { 1 }"#
     );
 }
 
+#[test]
+fn unknown_allowed_warning() {
+    assert_module_error!(
+        r#"
+@allow(unused_wibble)
+pub fn one() {}"#
+    );
+}
+
 #[test]
 fn incomplete_function() {
     assert_error!("fn()");
@@ -1017,6 +1027,41 @@ pub fn main() -> Nil {
     );
 }
 
+#[test]
+fn multiple_inline_attributes() {
+    assert_module_error!(
+        r#"
+@inline
+@inline
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn inline_attribute_on_constant() {
+    assert_module_error!(
+        r#"
+@inline
+pub const wibble = 1
+"#
+    );
+}
+
+#[test]
+fn inline_attribute_on_custom_type() {
+    assert_module_error!(
+        r#"
+@inline
+pub type Wibble {
+  Wibble
+}
+"#
+    );
+}
+
 #[test]
 fn attributes_with_no_definition() {
     assert_module_error!(
@@ -2086,6 +2131,18 @@ type Wibble {
     );
 }
 
+#[test]
+fn inline_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+type Wibble {
+    @inline
+    Wibble1
+}
+"#
+    );
+}
+
 #[test]
 fn multiple_unsupported_attributes_on_type_variant() {
     assert_module_error!(
@@ -2803,3 +2860,173 @@ pub fn main() {
 fn pipeline_in_constant_does_not_panic() {
     assert_module_error!("pub const wibble = 1 |> wobble");
 }
+
+fn parse_with_recovery(src: &str) -> (Vec<(EcoString, usize)>, Vec<ParseError>) {
+    let (parsed, errors) = crate::parse::parse_module_with_recovery(
+        Utf8PathBuf::from("test/path"),
+        src,
+        &WarningEmitter::null(),
+    );
+    let functions = parsed
+        .module
+        .definitions
+        .into_iter()
+        .filter_map(|definition| {
+            if let crate::ast::Definition::Function(function) = definition.definition {
+                function.name.map(|(_, name)| (name, function.body.len()))
+            } else {
+                None
+            }
+        })
+        .collect_vec();
+    (functions, errors)
+}
+
+#[test]
+fn recovery_skips_to_the_next_definition() {
+    let (functions, errors) = parse_with_recovery(
+        "
+pub fn wibble( {
+  1
+}
+
+pub fn wobble() {
+  2
+}
+",
+    );
+    assert_eq!(functions, vec![("wobble".into(), 1)]);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn recovery_skips_to_the_next_statement() {
+    let (functions, errors) = parse_with_recovery(
+        "
+pub fn main() {
+  let x = 1 +
+  let y = 2
+  y
+}
+",
+    );
+    assert_eq!(functions, vec![("main".into(), 2)]);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn recovery_skips_to_the_end_of_the_block() {
+    let (functions, errors) = parse_with_recovery(
+        "
+pub fn main() {
+  let x = {
+    1 +
+  }
+  x
+}
+
+pub fn wibble() {
+  1
+}
+",
+    );
+    assert_eq!(functions, vec![("main".into(), 2), ("wibble".into(), 1)]);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn recovery_from_unclosed_function_body() {
+    let (functions, errors) = parse_with_recovery(
+        "
+pub fn main() {
+  1 +
+
+pub fn wibble() {
+  1
+}
+",
+    );
+    assert_eq!(functions, vec![("wibble".into(), 1)]);
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn recovery_collects_errors_in_order() {
+    let (functions, errors) = parse_with_recovery(
+        "
+pub fn main() {
+  let = 1
+  let y = 2
+  y
+}
+
+const x =
+
+pub fn wibble() {
+  1
+}
+",
+    );
+    assert_eq!(functions, vec![("main".into(), 2), ("wibble".into(), 1)]);
+    let starts = errors
+        .iter()
+        .map(|error| error.location.start)
+        .collect_vec();
+    assert_eq!(starts.len(), 2);
+    assert!(starts.is_sorted());
+}
+
+#[test]
+fn recovered_errors_are_still_reported() {
+    // The first error is the one reported when parsing without recovering.
+    assert_eq!(
+        crate::parse::parse_module(
+            Utf8PathBuf::from("test/path"),
+            "pub fn main() {\n  let x = 1 +\n  let y = 2\n  y\n}\n",
+            &WarningEmitter::null(),
+        )
+        .map(|_| ()),
+        Err(vec1![ParseError {
+            error: ParseErrorType::OpNakedRight,
+            location: SrcSpan::new(28, 29),
+        }])
+    );
+}
+
+#[test]
+fn all_independent_errors_are_reported() {
+    let errors = crate::parse::parse_module(
+        Utf8PathBuf::from("test/path"),
+        "pub fn main() {
+  let x = 1 +
+  let y = 2
+  y
+}
+
+type Wobble {
+  Wobble(Int,
+}
+
+pub fn wabble() {
+  let = 1
+}
+",
+        &WarningEmitter::null(),
+    )
+    .map(|_| ())
+    .expect_err("should not parse");
+
+    let starts = errors
+        .iter()
+        .map(|error| error.location.start)
+        .collect_vec();
+    assert_eq!(starts.len(), 3);
+    assert!(starts.is_sorted());
+    assert_eq!(
+        errors.first(),
+        &ParseError {
+            error: ParseErrorType::OpNakedRight,
+            location: SrcSpan::new(28, 29),
+        }
+    );
+}
@@ -100,16 +100,33 @@ impl ProjectPaths {
         self.build_directory().join("erlang-shipment")
     }
 
+    pub fn javascript_shipment_directory(&self) -> Utf8PathBuf {
+        self.build_directory().join("javascript-shipment")
+    }
+
+    pub fn core_erlang_directory(&self) -> Utf8PathBuf {
+        self.build_directory().join("core-erlang")
+    }
+
+    /// The directory the coverage collected by `gleam test --coverage` is
+    /// written to, along with the report produced from it.
+    pub fn build_coverage_directory(&self) -> Utf8PathBuf {
+        self.build_directory_for_mode(Mode::Dev).join("coverage")
+    }
+
+    pub fn build_documentation_root_directory(&self) -> Utf8PathBuf {
+        self.build_directory_for_mode(Mode::Dev).join("docs")
+    }
+
     pub fn build_documentation_directory(&self, package: &str) -> Utf8PathBuf {
-        self.build_directory_for_mode(Mode::Dev)
-            .join("docs")
-            .join(package)
+        self.build_documentation_root_directory().join(package)
     }
 
     pub fn build_directory_for_target(&self, mode: Mode, target: Target) -> Utf8PathBuf {
         let target = match target {
             Target::Erlang => "erlang",
             Target::JavaScript => "javascript",
+            Target::Wasm => "wasm",
         };
         self.build_directory_for_mode(mode).join(target)
     }
@@ -5,6 +5,7 @@ pub(crate) mod environment;
 pub mod error;
 pub(crate) mod expression;
 pub(crate) mod fields;
+pub(crate) mod hole;
 pub(crate) mod hydrator;
 pub(crate) mod pattern;
 pub(crate) mod pipe;
@@ -42,10 +43,9 @@ use hydrator::Hydrator;
 use itertools::Itertools;
 use src_span::{LineNumbers, SrcSpan};
 use std::{
-    cell::RefCell,
     collections::{HashMap, HashSet},
     ops::Deref,
-    sync::Arc,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockResult},
 };
 
 pub trait HasType {
@@ -107,7 +107,7 @@ pub enum Type {
 
     /// A type variable. See the contained `TypeVar` enum for more information.
     ///
-    Var { type_: Arc<RefCell<TypeVar>> },
+    Var { type_: Arc<TypeVarCell> },
 
     /// A tuple is an ordered collection of 0 or more values, each of which
     /// can have a different type, so the `tuple` type is the sum of all the
@@ -745,6 +745,7 @@ pub enum ValueConstructorVariant {
         implementations: Implementations,
         external_erlang: Option<(EcoString, EcoString)>,
         external_javascript: Option<(EcoString, EcoString)>,
+        external_wasm: Option<(EcoString, EcoString)>,
         purity: Purity,
     },
 
@@ -804,6 +805,7 @@ impl ValueConstructorVariant {
                 module: module_name.clone(),
                 external_erlang: None,
                 external_javascript: None,
+                external_wasm: None,
                 documentation: None,
                 location: *location,
                 field_map: None,
@@ -818,6 +820,7 @@ impl ValueConstructorVariant {
                 field_map,
                 external_erlang,
                 external_javascript,
+                external_wasm,
                 purity,
                 ..
             } => ModuleValueConstructor::Fn {
@@ -826,6 +829,7 @@ impl ValueConstructorVariant {
                 documentation: documentation.clone(),
                 external_erlang: external_erlang.clone(),
                 external_javascript: external_javascript.clone(),
+                external_wasm: external_wasm.clone(),
                 location: *location,
                 field_map: field_map.clone(),
                 purity: *purity,
@@ -879,8 +883,10 @@ impl ValueConstructorVariant {
                 gleam: true,
                 can_run_on_erlang: true,
                 can_run_on_javascript: true,
+                can_run_on_wasm: true,
                 uses_javascript_externals: false,
                 uses_erlang_externals: false,
+                uses_wasm_externals: false,
             },
 
             ValueConstructorVariant::ModuleFn {
@@ -944,6 +950,7 @@ pub enum ModuleValueConstructor {
         ///
         external_erlang: Option<(EcoString, EcoString)>,
         external_javascript: Option<(EcoString, EcoString)>,
+        external_wasm: Option<(EcoString, EcoString)>,
         field_map: Option<FieldMap>,
         documentation: Option<EcoString>,
         purity: Purity,
@@ -1205,15 +1212,27 @@ impl ModuleInterface {
     }
 
     pub fn get_main_function(&self, target: Target) -> Result<ModuleFunction, crate::Error> {
-        // Module must have a value with the name "main"
-        let Some(value) = self.values.get(&EcoString::from("main")) else {
+        self.get_entrypoint_function("main", target)
+    }
+
+    /// Gets the function a program starts by running, which is usually the
+    /// `main` function of the module.
+    ///
+    pub fn get_entrypoint_function(
+        &self,
+        function: &str,
+        target: Target,
+    ) -> Result<ModuleFunction, crate::Error> {
+        // Module must have a value with the name of the function
+        let Some(value) = self.values.get(function) else {
             return Err(crate::Error::ModuleDoesNotHaveMainFunction {
                 module: self.name.clone(),
+                function: function.into(),
                 origin: self.origin,
             });
         };
 
-        assert_suitable_main_function(value, &self.name, self.origin, target)?;
+        assert_suitable_main_function(value, &self.name, function, self.origin, target)?;
 
         Ok(ModuleFunction {
             package: self.package.clone(),
@@ -1289,6 +1308,58 @@ pub enum TypeVar {
     Generic { id: u64 },
 }
 
+/// The mutable cell holding a type variable, which works like a `RefCell`.
+///
+/// Modules are analysed on different threads, each of them reading the types
+/// of the modules it imports, so a lock is used to make types shareable. A
+/// type variable is only ever updated by the analysis it was created in, so
+/// the lock is never contended.
+///
+#[derive(Debug)]
+pub struct TypeVarCell(RwLock<TypeVar>);
+
+impl TypeVarCell {
+    pub fn new(type_: TypeVar) -> Self {
+        Self(RwLock::new(type_))
+    }
+
+    pub fn borrow(&self) -> RwLockReadGuard<'_, TypeVar> {
+        self.0.read().expect("Type variable lock poisoned")
+    }
+
+    pub fn borrow_mut(&self) -> RwLockWriteGuard<'_, TypeVar> {
+        self.0.write().expect("Type variable lock poisoned")
+    }
+
+    pub fn try_borrow(&self) -> TryLockResult<RwLockReadGuard<'_, TypeVar>> {
+        self.0.try_read()
+    }
+
+    pub fn try_borrow_mut(&self) -> TryLockResult<RwLockWriteGuard<'_, TypeVar>> {
+        self.0.try_write()
+    }
+}
+
+impl PartialEq for TypeVarCell {
+    fn eq(&self, other: &Self) -> bool {
+        *self.borrow() == *other.borrow()
+    }
+}
+
+impl Eq for TypeVarCell {}
+
+impl serde::Serialize for TypeVarCell {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.borrow().serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TypeVarCell {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        TypeVar::deserialize(deserializer).map(Self::new)
+    }
+}
+
 impl TypeVar {
     pub fn is_unbound(&self) -> bool {
         match self {
@@ -1805,6 +1876,7 @@ pub enum ValueUsage {
 fn assert_suitable_main_function(
     value: &ValueConstructor,
     module_name: &EcoString,
+    function: &str,
     origin: Origin,
     target: Target,
 ) -> Result<(), crate::Error> {
@@ -1817,6 +1889,7 @@ fn assert_suitable_main_function(
     else {
         return Err(crate::Error::ModuleDoesNotHaveMainFunction {
             module: module_name.clone(),
+            function: function.into(),
             origin,
         });
     };
@@ -1825,6 +1898,7 @@ fn assert_suitable_main_function(
     if !implementations.supports(target) {
         return Err(crate::Error::MainFunctionDoesNotSupportTarget {
             module: module_name.clone(),
+            function: function.into(),
             target,
         });
     }
@@ -1833,6 +1907,7 @@ fn assert_suitable_main_function(
     if *arity != 0 {
         return Err(crate::Error::MainFunctionHasWrongArity {
             module: module_name.clone(),
+            function: function.into(),
             arity: *arity,
         });
     }
@@ -1842,6 +1917,7 @@ fn assert_suitable_main_function(
     if !value.publicity.is_importable() {
         return Err(crate::Error::MainFunctionIsPrivate {
             module: module_name.clone(),
+            function: function.into(),
         });
     }
 
@@ -13,7 +13,7 @@ use crate::{
 };
 
 use super::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
 pub struct EnvironmentArguments<'a> {
@@ -115,6 +115,30 @@ pub struct Environment<'a> {
     pub references: ReferenceTracker,
 
     pub dev_dependencies: &'a HashSet<EcoString>,
+
+    /// The fields of the private custom types defined in the current module
+    /// that haven't been read so far, either by accessing them or by pattern
+    /// matching on them. Any field still in here once the whole module has
+    /// been analysed is unused.
+    pub unread_fields: HashMap<FieldId, UnreadField>,
+}
+
+/// Identifies a field of one of the variants of a custom type defined in the
+/// current module.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldId {
+    pub type_name: EcoString,
+    pub variant_index: u16,
+    pub index: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnreadField {
+    /// The location of the field's definition, including its label.
+    pub location: SrcSpan,
+    /// The name of the variant the field belongs to.
+    pub constructor: EcoString,
 }
 
 #[derive(Debug)]
@@ -171,6 +195,7 @@ impl<'a> Environment<'a> {
             echo_found: false,
             references: ReferenceTracker::new(),
             dev_dependencies,
+            unread_fields: HashMap::new(),
         }
     }
 
@@ -658,6 +683,77 @@ impl Environment<'_> {
         let _ = self.accessors.insert(type_name, accessors);
     }
 
+    /// Registers a field of a private custom type, so that it can be reported
+    /// if it's never read.
+    ///
+    pub fn insert_unread_field(&mut self, id: FieldId, field: UnreadField) {
+        let _ = self.unread_fields.insert(id, field);
+    }
+
+    /// Marks a field of a custom type defined in the current module as read.
+    /// If the variant is not known, the field is read from whichever variant
+    /// the record is: this can only happen with fields shared by all variants,
+    /// which are in the same position in each of them.
+    ///
+    pub fn register_field_read(&mut self, type_name: &EcoString, variant: Option<u16>, index: u64) {
+        self.unread_fields.retain(|field, _| {
+            field.type_name != *type_name
+                || field.index != index
+                || variant.is_some_and(|variant| variant != field.variant_index)
+        });
+    }
+
+    /// Marks as read every field of the custom types defined in the current
+    /// module that a value of the given type can hold. Comparing a value with
+    /// `==` or printing it with `echo` or `string.inspect` reads all of its
+    /// fields, including the ones of the records nested inside it.
+    ///
+    pub fn register_all_fields_read(&mut self, type_: Arc<Type>) {
+        let mut types = vec![type_];
+        let mut visited = HashSet::new();
+
+        while let Some(type_) = types.pop() {
+            if self.unread_fields.is_empty() {
+                return;
+            }
+
+            match collapse_links(type_).as_ref() {
+                Type::Named {
+                    module,
+                    name,
+                    arguments,
+                    ..
+                } => {
+                    types.extend(arguments.iter().cloned());
+                    if *module != self.current_module || !visited.insert(name.clone()) {
+                        continue;
+                    }
+
+                    self.unread_fields
+                        .retain(|field, _| field.type_name != *name);
+                    if let Some(constructors) = self.module_types_constructors.get(name) {
+                        types.extend(
+                            constructors
+                                .variants
+                                .iter()
+                                .flat_map(|variant| &variant.parameters)
+                                .map(|parameter| parameter.type_.clone()),
+                        );
+                    }
+                }
+                Type::Tuple { elements } => types.extend(elements.iter().cloned()),
+                // Functions are compared by reference, but to avoid reporting
+                // false positives the types they work with are still treated
+                // as being read, just like those of any other value.
+                Type::Fn { arguments, return_ } => {
+                    types.extend(arguments.iter().cloned());
+                    types.push(return_.clone());
+                }
+                Type::Var { .. } => {}
+            }
+        }
+    }
+
     /// Instantiate converts generic variables into unbound ones.
     ///
     pub fn instantiate(
@@ -838,6 +934,7 @@ impl Environment<'_> {
         // only if the module they come from is not also unused.
         let mut unused_modules = HashSet::new();
         let mut unused_imported_items = vec![];
+        let mut unused_constructors = HashSet::new();
 
         for (entity, info) in self.references.unused() {
             let name = entity.name;
@@ -852,11 +949,14 @@ impl Environment<'_> {
                     let _ = unused_positions.insert(location.start);
                     Warning::UnusedPrivateModuleConstant { location, name }
                 }
-                EntityKind::Constructor => Warning::UnusedConstructor {
-                    location,
-                    name,
-                    imported: false,
-                },
+                EntityKind::Constructor => {
+                    let _ = unused_constructors.insert(name.clone());
+                    Warning::UnusedConstructor {
+                        location,
+                        name,
+                        imported: false,
+                    }
+                }
                 EntityKind::Type => {
                     let _ = unused_positions.insert(location.start);
                     Warning::UnusedType {
@@ -916,6 +1016,24 @@ impl Environment<'_> {
             .filter(|(module, _)| !unused_modules.contains(module))
             .for_each(|(_, warning)| problems.warning(warning));
 
+        // The fields of an unused constructor are not reported, as removing
+        // the constructor would remove those as well.
+        for (
+            _,
+            UnreadField {
+                location,
+                constructor,
+            },
+        ) in std::mem::take(&mut self.unread_fields)
+        {
+            if !unused_constructors.contains(&constructor) {
+                problems.warning(Warning::UnusedRecordField {
+                    location,
+                    constructor,
+                });
+            }
+        }
+
         unused_positions
     }
 
@@ -1094,7 +1212,7 @@ pub fn unify(t1: Arc<Type>, t2: Arc<Type>) -> Result<(), UnifyError> {
                 // unify may replace t's inner value with another link
                 // (See the Action::Link branch just above)
                 // This can cause the compiler to build up an ever-growing chain of links.
-                // Therefore, we try to collapse the links. However, the RefCell in type_
+                // Therefore, we try to collapse the links. However, the cell in type_
                 // may already be borrowed by collapsing the links in t2 at the start
                 // of the function, in which case accept the extra link.
                 if let Ok(mut type_) = type_.try_borrow_mut() {
@@ -1194,9 +1312,9 @@ pub fn unify(t1: Arc<Type>, t2: Arc<Type>) -> Result<(), UnifyError> {
 
 #[cfg(test)]
 mod unify_tests {
-    use std::{cell::RefCell, ops::Deref, sync::Arc};
+    use std::{ops::Deref, sync::Arc};
 
-    use crate::type_::{Type, TypeVar, unify};
+    use crate::type_::{Type, TypeVar, TypeVarCell, unify};
 
     // Repeated unification used to add a link to t1 for each branch
     // See https://github.com/gleam-lang/gleam/issues/4805
@@ -1238,7 +1356,7 @@ mod unify_tests {
 
     fn unbound(id: u64) -> Arc<Type> {
         Arc::new(Type::Var {
-            type_: Arc::new(RefCell::new(TypeVar::Unbound { id })),
+            type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id })),
         })
     }
 }
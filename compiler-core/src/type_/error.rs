@@ -11,6 +11,7 @@ use crate::{
     exhaustiveness::ImpossibleBitArraySegmentPattern,
     parse::LiteralFloatValue,
     type_::{Type, expression::ComparisonOutcome, printer::Names},
+    warning::WarningKind,
 };
 
 use ecow::EcoString;
@@ -710,6 +711,13 @@ pub enum Error {
     RecordUpdateVariantWithNoFields {
         location: SrcSpan,
     },
+    /// A typed hole `_` has been used in place of an expression, but typed
+    /// holes have not been enabled in `gleam.toml`.
+    ///
+    TypedHoleNotEnabled {
+        location: SrcSpan,
+    },
+
     /// When a constant contains a todo.
     /// Unlike todo _expressions_, todo constants are a compile time error: we
     /// want the developer to take care of them before they can run their code.
@@ -903,6 +911,19 @@ pub enum Warning {
         names: Names,
     },
 
+    /// A `_` has been used in place of an expression.
+    ///
+    TypedHole {
+        location: SrcSpan,
+        type_: Arc<Type>,
+        /// The local variables in scope where the hole is, sorted by name.
+        local_variables: Vec<(EcoString, Arc<Type>)>,
+        /// The imported functions returning a value that could fill the hole,
+        /// best candidates first.
+        candidates: Vec<(EcoString, Arc<Type>)>,
+        names: Names,
+    },
+
     ImplicitlyDiscardedResult {
         location: SrcSpan,
     },
@@ -947,6 +968,14 @@ pub enum Warning {
         name: EcoString,
     },
 
+    /// A field of a private custom type that is never read: it is never
+    /// accessed and all patterns matching on its variant discard it.
+    ///
+    UnusedRecordField {
+        location: SrcSpan,
+        constructor: EcoString,
+    },
+
     UnusedImportedValue {
         location: SrcSpan,
         name: EcoString,
@@ -1073,6 +1102,21 @@ pub enum Warning {
         reason: AssertImpossiblePattern,
     },
 
+    /// A `let assert` whose pattern doesn't match all the possible values, so
+    /// it can crash at runtime. The missing patterns are the values it will
+    /// crash on.
+    ///
+    /// ```gleam
+    /// let assert Ok(x) = result
+    /// ```
+    ///
+    /// This warning is allowed by default.
+    ///
+    InexhaustiveLetAssert {
+        location: SrcSpan,
+        missing: Vec<EcoString>,
+    },
+
     /// When a `todo` or `panic` is used as a function instead of providing the
     /// error message with the `as` syntax.
     ///
@@ -1104,6 +1148,22 @@ pub enum Warning {
         location: SrcSpan,
     },
 
+    /// When a function marked with the `@inline` attribute can't be inlined.
+    /// This could be because it's an external function, or because its body
+    /// uses something that can't be inlined, like a value from another module.
+    ///
+    /// ```gleam
+    /// @inline
+    /// pub fn wibble() {
+    ///   let x = 1
+    ///   x + 1
+    /// }
+    /// ```
+    ///
+    UninlinableFunction {
+        location: SrcSpan,
+    },
+
     /// When the `gleam` range specified in the package's `gleam.toml` is too
     /// low and would include a version that's too low to support this feature.
     ///
@@ -1328,6 +1388,12 @@ pub enum UnreachablePatternReason {
     /// of the custom type that we are matching on, and this matches
     /// against one of the variants we know it isn't.
     ImpossibleVariant,
+    /// The clause is unreachable because it is matching on an `Int` value that
+    /// we could tell the matched value is never going to have.
+    ImpossibleValue,
+    /// The clause is unreachable because its guard is never going to be true
+    /// when it is checked.
+    GuardNeverTrue,
     /// The clause is unreachable because it is matching on a pattern segment
     /// that we could tell is never going to match
     ImpossibleSegments(Vec<ImpossibleBitArraySegmentPattern>),
@@ -1414,6 +1480,7 @@ impl Error {
             | Error::RecordUpdateVariantWithNoFields { location }
             | Error::QualifiedTypeMissingName { location }
             | Error::TodoConstant { location }
+            | Error::TypedHoleNotEnabled { location }
             | Error::LowercaseBoolPattern { location } => location.start,
             Error::UnknownLabels { unknown, .. } => {
                 unknown.iter().map(|(_, s)| s.start).min().unwrap_or(0)
@@ -1441,6 +1508,7 @@ impl Warning {
     pub(crate) fn location(&self) -> SrcSpan {
         match self {
             Warning::Todo { location, .. }
+            | Warning::TypedHole { location, .. }
             | Warning::ImplicitlyDiscardedResult { location, .. }
             | Warning::UnusedLiteral { location, .. }
             | Warning::UnusedValue { location, .. }
@@ -1448,6 +1516,7 @@ impl Warning {
             | Warning::AllFieldsRecordUpdate { location, .. }
             | Warning::UnusedType { location, .. }
             | Warning::UnusedConstructor { location, .. }
+            | Warning::UnusedRecordField { location, .. }
             | Warning::UnusedImportedValue { location, .. }
             | Warning::UnusedImportedModule { location, .. }
             | Warning::UnusedImportedModuleAlias { location, .. }
@@ -1464,10 +1533,12 @@ impl Warning {
             | Warning::CaseMatchOnLiteralValue { location, .. }
             | Warning::OpaqueExternalType { location, .. }
             | Warning::RedundantAssertAssignment { location, .. }
+            | Warning::InexhaustiveLetAssert { location, .. }
             | Warning::AssertAssignmentOnImpossiblePattern { location, .. }
             | Warning::TodoOrPanicUsedAsFunction { location, .. }
             | Warning::UnreachableCodeAfterPanic { location, .. }
             | Warning::RedundantPipeFunctionCapture { location, .. }
+            | Warning::UninlinableFunction { location, .. }
             | Warning::FeatureRequiresHigherGleamVersion { location, .. }
             | Warning::JavaScriptIntUnsafe { location, .. }
             | Warning::AssertLiteralBool { location, .. }
@@ -1484,8 +1555,63 @@ impl Warning {
         }
     }
 
+    pub fn kind(&self) -> WarningKind {
+        match self {
+            Warning::Todo { .. } => WarningKind::Todo,
+            Warning::TypedHole { .. } => WarningKind::TypedHole,
+            Warning::ImplicitlyDiscardedResult { .. } => WarningKind::UnusedResult,
+            Warning::UnusedLiteral { .. } | Warning::UnusedValue { .. } => WarningKind::UnusedValue,
+            Warning::NoFieldsRecordUpdate { .. } | Warning::AllFieldsRecordUpdate { .. } => {
+                WarningKind::RedundantRecordUpdate
+            }
+            Warning::UnusedType { .. } | Warning::UnusedConstructor { .. } => {
+                WarningKind::UnusedType
+            }
+            Warning::UnusedRecordField { .. } => WarningKind::UnusedField,
+            Warning::UnusedImportedValue { .. }
+            | Warning::UnusedImportedModule { .. }
+            | Warning::UnusedImportedModuleAlias { .. } => WarningKind::UnusedImport,
+            Warning::UnusedPrivateModuleConstant { .. } => WarningKind::UnusedConstant,
+            Warning::UnusedPrivateFunction { .. } => WarningKind::UnusedFunction,
+            Warning::UnusedVariable { origin, .. } if origin.is_function_parameter() => {
+                WarningKind::UnusedArgument
+            }
+            Warning::UnusedVariable { .. } | Warning::UnusedDiscardPattern { .. } => {
+                WarningKind::UnusedVariable
+            }
+            Warning::UnusedRecursiveArgument { .. } => WarningKind::UnusedArgument,
+            Warning::UnnecessaryDoubleIntNegation { .. }
+            | Warning::UnnecessaryDoubleBoolNegation { .. } => WarningKind::RedundantNegation,
+            Warning::InefficientEmptyListCheck { .. } => WarningKind::InefficientEmptyListCheck,
+            Warning::TransitiveDependencyImported { .. } => WarningKind::TransitiveDependency,
+            Warning::DeprecatedItem { .. } => WarningKind::Deprecated,
+            Warning::UnreachableCasePattern { .. } => WarningKind::UnreachableCaseClause,
+            Warning::CaseMatchOnLiteralCollection { .. }
+            | Warning::CaseMatchOnLiteralValue { .. } => WarningKind::RedundantCase,
+            Warning::OpaqueExternalType { .. } => WarningKind::OpaqueExternalType,
+            Warning::RedundantAssertAssignment { .. } | Warning::AssertLiteralBool { .. } => {
+                WarningKind::RedundantAssert
+            }
+            Warning::AssertAssignmentOnImpossiblePattern { .. } => WarningKind::ImpossiblePattern,
+            Warning::InexhaustiveLetAssert { .. } => WarningKind::InexhaustiveLetAssert,
+            Warning::TodoOrPanicUsedAsFunction { .. } => WarningKind::TodoOrPanicAsFunction,
+            Warning::UnreachableCodeAfterPanic { .. } => WarningKind::UnreachableCode,
+            Warning::RedundantPipeFunctionCapture { .. } => WarningKind::RedundantFunctionCapture,
+            Warning::UninlinableFunction { .. } => WarningKind::UninlinableFunction,
+            Warning::FeatureRequiresHigherGleamVersion { .. } => WarningKind::GleamVersion,
+            Warning::JavaScriptIntUnsafe { .. } | Warning::JavaScriptBitArrayUnsafeInt { .. } => {
+                WarningKind::JavascriptUnsafeInt
+            }
+            Warning::BitArraySegmentTruncatedValue { .. } => WarningKind::TruncatedValue,
+            Warning::ModuleImportedTwice { .. } => WarningKind::DuplicateImport,
+            Warning::TopLevelDefinitionShadowsImport { .. } => WarningKind::ShadowedImport,
+            Warning::RedundantComparison { .. } => WarningKind::RedundantComparison,
+            Warning::PipeIntoCallWhichReturnsFunction { .. } => WarningKind::PipeIntoFunction,
+        }
+    }
+
     pub(crate) fn is_todo(&self) -> bool {
-        matches!(self, Self::Todo { .. })
+        matches!(self, Self::Todo { .. } | Self::TypedHole { .. })
     }
 }
 
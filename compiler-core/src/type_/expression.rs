@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-FileCopyrightText: 2020 The Gleam contributors
 
-use super::{pipe::PipeTyper, *};
+use super::{hole::PendingHole, pipe::PipeTyper, *};
 use crate::{
     STDLIB_PACKAGE_NAME,
     analyse::{Inferred, infer_bit_array_option, name::check_argument_names},
@@ -17,7 +17,7 @@ use crate::{
         UntypedUseAssignment, Use, UseAssignment,
     },
     build::Target,
-    exhaustiveness::{self, CompileCaseResult, CompiledCase, Reachability},
+    exhaustiveness::{self, CompileCaseResult, CompiledCase, IntRange, Reachability},
     parse::{LiteralFloatValue, PatternPosition},
     reference::{LabelSyntax, ReferenceKind},
 };
@@ -50,12 +50,16 @@ pub struct Implementations {
     pub gleam: bool,
     pub can_run_on_erlang: bool,
     pub can_run_on_javascript: bool,
+    pub can_run_on_wasm: bool,
     /// Whether the function has an implementation that uses external erlang
     /// code.
     pub uses_erlang_externals: bool,
     /// Whether the function has an implementation that uses external javascript
     /// code.
     pub uses_javascript_externals: bool,
+    /// Whether the function has an implementation that uses external wasm
+    /// code.
+    pub uses_wasm_externals: bool,
 }
 
 impl Implementations {
@@ -64,8 +68,10 @@ impl Implementations {
             gleam: true,
             can_run_on_erlang: true,
             can_run_on_javascript: true,
+            can_run_on_wasm: true,
             uses_javascript_externals: false,
             uses_erlang_externals: false,
+            uses_wasm_externals: false,
         }
     }
 }
@@ -162,6 +168,8 @@ pub struct FunctionDefinition {
     pub has_erlang_external: bool,
     /// The function has @external(JavaScript, "...", "...")
     pub has_javascript_external: bool,
+    /// The function has @external(wasm, "...", "...")
+    pub has_wasm_external: bool,
 }
 
 impl FunctionDefinition {
@@ -169,6 +177,7 @@ impl FunctionDefinition {
         match target {
             Target::Erlang => self.has_erlang_external,
             Target::JavaScript => self.has_javascript_external,
+            Target::Wasm => self.has_wasm_external,
         }
     }
 }
@@ -188,13 +197,16 @@ impl Implementations {
             gleam,
             uses_erlang_externals: other_uses_erlang_externals,
             uses_javascript_externals: other_uses_javascript_externals,
+            uses_wasm_externals: other_uses_wasm_externals,
             can_run_on_erlang: other_can_run_on_erlang,
             can_run_on_javascript: other_can_run_on_javascript,
+            can_run_on_wasm: other_can_run_on_wasm,
         } = implementations;
         let FunctionDefinition {
             has_body: _,
             has_erlang_external,
             has_javascript_external,
+            has_wasm_external,
         } = current_function_definition;
 
         // If a pure-Gleam function uses a function that doesn't have a pure
@@ -207,6 +219,8 @@ impl Implementations {
             || (self.can_run_on_erlang && (*gleam || *other_can_run_on_erlang));
         self.can_run_on_javascript = *has_javascript_external
             || (self.can_run_on_javascript && (*gleam || *other_can_run_on_javascript));
+        self.can_run_on_wasm =
+            *has_wasm_external || (self.can_run_on_wasm && (*gleam || *other_can_run_on_wasm));
 
         // If a function uses a function that relies on external code (be it
         // javascript or erlang) then it's considered as using external code as
@@ -229,6 +243,7 @@ impl Implementations {
         self.uses_erlang_externals = self.uses_erlang_externals || *other_uses_erlang_externals;
         self.uses_javascript_externals =
             self.uses_javascript_externals || *other_uses_javascript_externals;
+        self.uses_wasm_externals = self.uses_wasm_externals || *other_uses_wasm_externals;
     }
 
     /// Returns true if the current target is supported by the given
@@ -240,6 +255,7 @@ impl Implementations {
             || match target {
                 Target::Erlang => self.can_run_on_erlang,
                 Target::JavaScript => self.can_run_on_javascript,
+                Target::Wasm => self.can_run_on_wasm,
             }
     }
 }
@@ -299,6 +315,16 @@ pub(crate) struct ExprTyper<'a, 'b> {
     // Type hydrator for creating types from annotations
     pub(crate) hydrator: Hydrator,
 
+    // The typed holes found so far, they are reported with `report_holes`
+    // once the enclosing function has been inferred.
+    pub(crate) holes: Vec<PendingHole>,
+
+    // The range of values of the `Int` variables assigned so far, when it is
+    // known to be limited. The key is the location where the variable is
+    // defined. These are used to check the exhaustiveness of case expressions
+    // matching on those variables.
+    pub(crate) int_ranges: HashMap<SrcSpan, IntRange>,
+
     // Accumulated errors and warnings found while typing the expression
     pub(crate) problems: &'a mut Problems,
 }
@@ -318,13 +344,16 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             gleam: definition.has_body,
             can_run_on_erlang: definition.has_body || definition.has_erlang_external,
             can_run_on_javascript: definition.has_body || definition.has_javascript_external,
+            can_run_on_wasm: definition.has_body || definition.has_wasm_external,
             uses_erlang_externals: definition.has_erlang_external,
             uses_javascript_externals: definition.has_javascript_external,
+            uses_wasm_externals: definition.has_wasm_external,
         };
 
         let uses_externals = match environment.target {
             Target::Erlang => implementations.uses_erlang_externals,
             Target::JavaScript => implementations.uses_javascript_externals,
+            Target::Wasm => implementations.uses_wasm_externals,
         };
 
         let purity = if is_trusted_pure_module(environment) {
@@ -347,10 +376,31 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             purity,
             current_function_definition: definition,
             minimum_required_version: Version::new(0, 1, 0),
+            holes: vec![],
+            int_ranges: HashMap::new(),
             problems,
         }
     }
 
+    /// Emits a warning for each of the typed holes found so far. This is
+    /// called once the enclosing function has been inferred so that the types
+    /// of the holes are known.
+    ///
+    /// Typed holes are opt-in, if they are not enabled each hole is reported
+    /// as an error instead.
+    ///
+    pub fn report_holes(&mut self, enabled: bool) {
+        for hole in std::mem::take(&mut self.holes) {
+            if enabled {
+                self.problems.warning(hole.into_warning(self.environment));
+            } else {
+                self.problems.error(Error::TypedHoleNotEnabled {
+                    location: hole.location,
+                });
+            }
+        }
+    }
+
     fn in_new_scope<T, E>(
         &mut self,
         process_scope: impl FnOnce(&mut Self) -> Result<T, E>,
@@ -574,17 +624,29 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         // Type the todo as whatever it would need to be to type check.
         let type_ = self.new_unbound_var();
 
-        // Emit a warning that there is a todo in the code.
+        // Emit a warning that there is a todo in the code. Holes are only
+        // reported once the function they're in has been inferred, as that's
+        // when we know what their type is.
         let warning_location = match kind {
-            TodoKind::Keyword | TodoKind::IncompleteUse | TodoKind::EmptyBlock => location,
+            TodoKind::Keyword | TodoKind::IncompleteUse | TodoKind::EmptyBlock | TodoKind::Hole => {
+                location
+            }
             TodoKind::EmptyFunction { function_location } => function_location,
         };
-        self.problems.warning(Warning::Todo {
-            kind,
-            location: warning_location,
-            type_: type_.clone(),
-            names: self.environment.names.clone(),
-        });
+        if kind == TodoKind::Hole {
+            self.holes.push(PendingHole {
+                location,
+                type_: type_.clone(),
+                scope: self.environment.scope.clone(),
+            });
+        } else {
+            self.problems.warning(Warning::Todo {
+                kind,
+                location: warning_location,
+                type_: type_.clone(),
+                names: self.environment.names.clone(),
+            });
+        }
 
         self.purity = Purity::Impure;
 
@@ -625,6 +687,9 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             if self.previous_panics {
                 self.warn_for_unreachable_code(location, PanicPosition::EchoExpression);
             }
+            // Printing a value reads all of its fields.
+            self.environment
+                .register_all_fields_read(expression.type_());
             expression
         } else {
             let location = SrcSpan {
@@ -1891,6 +1956,9 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
                 self.check_for_inefficient_empty_list_check(operator, &left, &right, location);
 
+                // Comparing two values reads all of their fields.
+                self.environment.register_all_fields_read(left.type_());
+
                 return TypedExpr::BinOp {
                     location,
                     operator,
@@ -2197,6 +2265,12 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             };
         }
 
+        if let (AssignmentKind::Let, Pattern::Variable { location, .. }) = (&kind, &pattern)
+            && let Some(range) = self.limited_int_range(&value)
+        {
+            let _ = self.int_ranges.insert(*location, range);
+        }
+
         let (output, not_exhaustive_error) =
             self.check_let_exhaustiveness(location, value.type_(), &pattern);
 
@@ -2224,10 +2298,18 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             }),
 
             // Otherwise, if the pattern is never reachable (through variant inference),
-            // we can warn the user about this.
-            (AssignmentKind::Assert { .. }, Err(_)) => {
+            // we can warn the user about this. If it is reachable the assertion
+            // can still crash, so we report the values it would crash on.
+            (AssignmentKind::Assert { .. }, Err(error)) => {
                 // There is only one pattern to match, so it is index 0
                 match output.is_reachable(0, 0) {
+                    Reachability::Reachable => {
+                        if let Error::InexhaustiveLetAssignment { missing, .. } = error {
+                            self.problems
+                                .warning(Warning::InexhaustiveLetAssert { location, missing });
+                        }
+                    }
+
                     Reachability::Unreachable(UnreachablePatternReason::ImpossibleVariant) => self
                         .problems
                         .warning(Warning::AssertAssignmentOnImpossiblePattern {
@@ -2243,9 +2325,14 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                             location: pattern.location(),
                             reason: AssertImpossiblePattern::ImpossibleSegments { segments },
                         }),
-                    // A duplicate pattern warning should not happen, since there is only one pattern.
-                    Reachability::Reachable
-                    | Reachability::Unreachable(UnreachablePatternReason::DuplicatePattern) => {}
+                    // A duplicate pattern warning should not happen, since there is only one
+                    // pattern. And a `let assert` has neither guards nor a subject with a
+                    // known range of values.
+                    Reachability::Unreachable(
+                        UnreachablePatternReason::DuplicatePattern
+                        | UnreachablePatternReason::ImpossibleValue
+                        | UnreachablePatternReason::GuardNeverTrue,
+                    ) => {}
                 }
             }
         }
@@ -2397,7 +2484,12 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         // if they are invalid, it will crash. Therefore, if any errors were found
         // when type checking the pattern, we don't perform the exhaustiveness check.
         let compiled_case = if patterns_typechecked_successfully {
-            self.check_case_exhaustiveness(location, &subject_types, &typed_clauses)
+            self.check_case_exhaustiveness(
+                location,
+                &typed_subjects,
+                &subject_types,
+                &typed_clauses,
+            )
         } else {
             CompiledCase::failure()
         };
@@ -2682,6 +2774,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                             self.problems
                                 .error(convert_unify_error(error, right.location()));
                         }
+                        self.environment.register_all_fields_read(left.type_());
                     }
 
                     BinOp::GtInt
@@ -3106,6 +3199,21 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             }
         }
 
+        // Accessing a field of a type defined in this module counts as reading
+        // it, unless it's only being copied over by a record update.
+        if let Type::Named {
+            module,
+            name,
+            inferred_variant,
+            ..
+        } = collapse_links(record_type.clone()).as_ref()
+            && module == &self.environment.current_module
+            && usage != FieldAccessUsage::RecordUpdate
+        {
+            self.environment
+                .register_field_read(name, *inferred_variant, index);
+        }
+
         let mut type_vars = hashmap![];
         let accessor_record_type = self.instantiate(accessor_record_type, &mut type_vars);
         let type_ = self.instantiate(type_, &mut type_vars);
@@ -5178,9 +5286,49 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             self.warn_for_unreachable_code(fun.location(), PanicPosition::LastFunctionArgument);
         }
 
+        // Printing a value with `string.inspect` reads all of its fields.
+        if self.is_stdlib_inspect(&fun) {
+            for argument in &typed_arguments {
+                self.environment
+                    .register_all_fields_read(argument.value.type_());
+            }
+        }
+
         (fun, typed_arguments, return_type)
     }
 
+    /// Checks if a function is the standard library's `string.inspect`.
+    ///
+    pub(crate) fn is_stdlib_inspect(&self, fun: &TypedExpr) -> bool {
+        let (module, name) = if let TypedExpr::ModuleSelect {
+            constructor: ModuleValueConstructor::Fn { module, name, .. },
+            ..
+        } = fun
+        {
+            (module, name)
+        } else if let TypedExpr::Var {
+            constructor:
+                ValueConstructor {
+                    variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                    ..
+                },
+            ..
+        } = fun
+        {
+            (module, name)
+        } else {
+            return false;
+        };
+
+        module == "gleam/string"
+            && name == "inspect"
+            && self
+                .environment
+                .importable_modules
+                .get(module)
+                .is_some_and(|module| module.package == STDLIB_PACKAGE_NAME)
+    }
+
     fn infer_call_argument(
         &mut self,
         called_function: &TypedExpr,
@@ -5463,10 +5611,16 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
     fn check_case_exhaustiveness(
         &mut self,
         location: SrcSpan,
+        subjects: &[TypedExpr],
         subject_types: &[Arc<Type>],
         clauses: &[TypedClause],
     ) -> CompiledCase {
         let mut case = exhaustiveness::CaseToCompile::new(subject_types);
+        for (index, subject) in subjects.iter().enumerate() {
+            if let Some(range) = self.limited_int_range(subject) {
+                case.set_subject_range(index, range);
+            }
+        }
         clauses.iter().for_each(|clause| case.add_clause(clause));
         let result = case.compile(self.environment);
 
@@ -5506,6 +5660,104 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         result.compiled_case
     }
 
+    /// The range of values an `Int` expression can have, if it's limited to
+    /// more than a single value. For example `x % 3` is always between -2 and
+    /// 2.
+    ///
+    /// Constant values are left out, so that matching on those is not going
+    /// to result in warnings.
+    ///
+    fn limited_int_range(&self, expression: &TypedExpr) -> Option<IntRange> {
+        let range = self.int_range(expression)?;
+        let is_limited =
+            !range.is_unbounded() && range.size().is_none_or(|size| size > BigInt::from(1));
+        is_limited.then_some(range)
+    }
+
+    /// The range of values an `Int` expression can have, if anything is known
+    /// about it.
+    ///
+    fn int_range(&self, expression: &TypedExpr) -> Option<IntRange> {
+        match expression {
+            TypedExpr::Int { int_value, .. } => Some(IntRange::single(int_value.clone())),
+
+            TypedExpr::NegateInt { value, .. } => Some(self.int_range(value)?.negate()),
+
+            TypedExpr::BinOp {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let left = self.int_range(left).unwrap_or_else(IntRange::unbounded);
+                let right = self.int_range(right).unwrap_or_else(IntRange::unbounded);
+                let range = match operator {
+                    BinOp::AddInt => left.add(&right),
+                    BinOp::SubInt => left.subtract(&right),
+                    BinOp::RemainderInt => left.remainder(&right),
+                    BinOp::And
+                    | BinOp::Or
+                    | BinOp::Eq
+                    | BinOp::NotEq
+                    | BinOp::LtInt
+                    | BinOp::LtEqInt
+                    | BinOp::LtFloat
+                    | BinOp::LtEqFloat
+                    | BinOp::GtEqInt
+                    | BinOp::GtInt
+                    | BinOp::GtEqFloat
+                    | BinOp::GtFloat
+                    | BinOp::AddFloat
+                    | BinOp::SubFloat
+                    | BinOp::MultInt
+                    | BinOp::MultFloat
+                    | BinOp::DivInt
+                    | BinOp::DivFloat
+                    | BinOp::Concatenate => return None,
+                };
+                (!range.is_unbounded()).then_some(range)
+            }
+
+            TypedExpr::Block { statements, .. } if statements.len() == 1 => {
+                match statements.first() {
+                    Statement::Expression(expression) => self.int_range(expression),
+                    Statement::Assignment(_) | Statement::Use(_) | Statement::Assert(_) => None,
+                }
+            }
+
+            TypedExpr::Var {
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::LocalVariable { location, .. },
+                        ..
+                    },
+                ..
+            } => self.int_ranges.get(location).cloned(),
+
+            TypedExpr::Block { .. }
+            | TypedExpr::Var { .. }
+            | TypedExpr::Float { .. }
+            | TypedExpr::String { .. }
+            | TypedExpr::Pipeline { .. }
+            | TypedExpr::Fn { .. }
+            | TypedExpr::List { .. }
+            | TypedExpr::Call { .. }
+            | TypedExpr::Case { .. }
+            | TypedExpr::RecordAccess { .. }
+            | TypedExpr::PositionalAccess { .. }
+            | TypedExpr::ModuleSelect { .. }
+            | TypedExpr::Tuple { .. }
+            | TypedExpr::TupleIndex { .. }
+            | TypedExpr::Todo { .. }
+            | TypedExpr::Panic { .. }
+            | TypedExpr::Echo { .. }
+            | TypedExpr::BitArray { .. }
+            | TypedExpr::RecordUpdate { .. }
+            | TypedExpr::NegateBool { .. }
+            | TypedExpr::Invalid { .. } => None,
+        }
+    }
+
     fn track_feature_usage(&mut self, feature_kind: FeatureKind, location: SrcSpan) {
         let minimum_required_version = feature_kind.required_version();
 
@@ -6059,7 +6311,7 @@ fn check_subject_for_redundant_match(
 /// can categorize an empty list check in one of two ways:
 ///   - Checking for the empty list
 ///   - Checking for a non-empty list
-fn get_empty_list_check_kind<'a>(
+pub(crate) fn get_empty_list_check_kind<'a>(
     binop: BinOp,
     left: &'a TypedExpr,
     right: &'a TypedExpr,
@@ -0,0 +1,210 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Typed holes are `_`s used in place of an expression:
+//!
+//! ```gleam
+//! pub fn main() {
+//!   let name = "Lucy"
+//!   let size: Int = _
+//!   //              ^ This is a hole
+//! }
+//! ```
+//!
+//! A hole behaves like a `todo`, but instead of a plain warning the compiler
+//! reports the type the hole needs to have, the local variables that are in
+//! scope, and the imported functions that could be called to fill it.
+//!
+//! The type of a hole is usually only known once the whole function it appears
+//! in has been inferred, so holes are collected while typing the function and
+//! only reported once it's done.
+
+use super::*;
+use crate::type_::environment::Environment;
+use ecow::eco_format;
+
+/// The maximum number of functions suggested to fill a hole.
+const MAX_CANDIDATES: usize = 5;
+
+#[derive(Debug)]
+pub(crate) struct PendingHole {
+    pub location: SrcSpan,
+    pub type_: Arc<Type>,
+    /// The values that were in scope where the hole is.
+    pub scope: im::HashMap<EcoString, ValueConstructor>,
+}
+
+impl PendingHole {
+    /// Turns the hole into a warning. This must only be called after the
+    /// function the hole appears in has been inferred, so that its type is as
+    /// precise as possible.
+    ///
+    pub fn into_warning(self, environment: &Environment<'_>) -> Warning {
+        let local_variables = self
+            .scope
+            .iter()
+            .filter(|(_, value)| match &value.variant {
+                ValueConstructorVariant::LocalVariable { origin, .. } => {
+                    origin.declaration != VariableDeclaration::Generated
+                }
+                ValueConstructorVariant::ModuleConstant { .. }
+                | ValueConstructorVariant::ModuleFn { .. }
+                | ValueConstructorVariant::Record { .. } => false,
+            })
+            .map(|(name, value)| (name.clone(), value.type_.clone()))
+            .sorted_by(|(one, _), (other, _)| one.cmp(other))
+            .collect_vec();
+
+        Warning::TypedHole {
+            location: self.location,
+            candidates: candidates(environment, &self.type_),
+            type_: self.type_,
+            local_variables,
+            names: environment.names.clone(),
+        }
+    }
+}
+
+/// How well a function's return type fits the type of a hole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Fit {
+    /// The function returns exactly the type of the hole.
+    Exact,
+    /// The function is generic and returns the type of the hole once its type
+    /// variables have been instantiated.
+    Generic,
+}
+
+/// Finds the functions from imported modules returning a value that could be
+/// used to fill a hole of the given type, best candidates first.
+///
+/// If the type of the hole is still unbound any function would do, so there's
+/// no point in suggesting any.
+///
+fn candidates(environment: &Environment<'_>, type_: &Arc<Type>) -> Vec<(EcoString, Arc<Type>)> {
+    if type_.is_unbound() {
+        return vec![];
+    }
+
+    environment
+        .imported_modules
+        .iter()
+        .flat_map(|(module_name, (_, module))| {
+            let is_same_package = module.package == environment.current_package;
+            module
+                .values
+                .iter()
+                .filter(move |(_, value)| {
+                    if is_same_package {
+                        value.publicity.is_importable()
+                    } else {
+                        value.publicity.is_public()
+                    }
+                })
+                .map(move |(name, value)| (module_name, name, value))
+        })
+        .filter(|(_, _, value)| !value.deprecation.is_deprecated())
+        .filter_map(|(module_name, name, value)| {
+            let (arguments, return_) = value.type_.fn_types()?;
+            let fit = if return_.same_as(type_) {
+                Fit::Exact
+            } else if fits(type_, &return_, &mut HashMap::new()) {
+                Fit::Generic
+            } else {
+                return None;
+            };
+            let name = eco_format!("{module_name}.{name}");
+            Some((
+                (fit, arguments.len(), name.clone()),
+                (name, value.type_.clone()),
+            ))
+        })
+        .sorted_by(|(one, _), (other, _)| one.cmp(other))
+        .take(MAX_CANDIDATES)
+        .map(|(_, candidate)| candidate)
+        .collect_vec()
+}
+
+/// Checks if a value of the `candidate` type can be used where the `expected`
+/// type is needed, instantiating the candidate's generic type variables as
+/// needed. Unlike unification this never changes either of the types.
+///
+fn fits(expected: &Type, candidate: &Type, generics: &mut HashMap<u64, Arc<Type>>) -> bool {
+    if let Type::Var { type_ } = candidate {
+        match &*type_.borrow() {
+            TypeVar::Link { type_ } => return fits(expected, type_, generics),
+            TypeVar::Unbound { .. } => return true,
+            TypeVar::Generic { id } => {
+                return match generics.get(id) {
+                    Some(bound) => bound.same_as(expected),
+                    None => {
+                        let _ = generics.insert(*id, Arc::new(expected.clone()));
+                        true
+                    }
+                };
+            }
+        }
+    }
+
+    match expected {
+        Type::Var { type_ } => match &*type_.borrow() {
+            TypeVar::Link { type_ } => fits(type_, candidate, generics),
+            TypeVar::Unbound { .. } => true,
+            // A generic type in the hole can only be filled by a value that is
+            // generic itself, and that case is already dealt with above.
+            TypeVar::Generic { .. } => false,
+        },
+
+        Type::Named {
+            package,
+            module,
+            name,
+            arguments,
+            ..
+        } => match candidate {
+            Type::Named {
+                package: candidate_package,
+                module: candidate_module,
+                name: candidate_name,
+                arguments: candidate_arguments,
+                ..
+            } => {
+                package == candidate_package
+                    && module == candidate_module
+                    && name == candidate_name
+                    && all_fit(arguments, candidate_arguments, generics)
+            }
+            Type::Fn { .. } | Type::Var { .. } | Type::Tuple { .. } => false,
+        },
+
+        Type::Fn { arguments, return_ } => match candidate {
+            Type::Fn {
+                arguments: candidate_arguments,
+                return_: candidate_return,
+            } => {
+                all_fit(arguments, candidate_arguments, generics)
+                    && fits(return_, candidate_return, generics)
+            }
+            Type::Named { .. } | Type::Var { .. } | Type::Tuple { .. } => false,
+        },
+
+        Type::Tuple { elements } => match candidate {
+            Type::Tuple {
+                elements: candidate_elements,
+            } => all_fit(elements, candidate_elements, generics),
+            Type::Named { .. } | Type::Fn { .. } | Type::Var { .. } => false,
+        },
+    }
+}
+
+fn all_fit(
+    expected: &[Arc<Type>],
+    candidates: &[Arc<Type>],
+    generics: &mut HashMap<u64, Arc<Type>>,
+) -> bool {
+    expected.len() == candidates.len()
+        && expected
+            .iter()
+            .zip(candidates)
+            .all(|(expected, candidate)| fits(expected, candidate, generics))
+}
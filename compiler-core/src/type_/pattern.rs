@@ -1242,6 +1242,22 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                             constructor_field_map,
                         );
 
+                        if let Some((module, type_name)) = return_.named_type_name()
+                            && module == self.environment.current_module
+                        {
+                            // Matching a field against anything other than a
+                            // discard pattern counts as reading it.
+                            for (index, argument) in pattern_arguments.iter().enumerate() {
+                                if !matches!(argument.value, Pattern::Discard { .. }) {
+                                    self.environment.register_field_read(
+                                        &type_name,
+                                        Some(pattern_constructor.constructor_index),
+                                        index as u64,
+                                    );
+                                }
+                            }
+                        }
+
                         if let Some(type_name) = return_.named_type_name() {
                             for argument in &pattern_arguments {
                                 if let Some(label) = &argument.label
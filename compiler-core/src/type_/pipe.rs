@@ -178,6 +178,9 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
                 } => {
                     self.expr_typer.environment.echo_found = true;
                     self.expr_typer.purity = Purity::Impure;
+                    self.expr_typer
+                        .environment
+                        .register_all_fields_read(self.argument_type.clone());
                     // An echo that is not followed by an expression that is
                     // used as a pipeline's step is just like the identity
                     // function.
@@ -392,6 +395,13 @@ impl<'a, 'b, 'c> PipeTyper<'a, 'b, 'c> {
             }
         }
 
+        // Printing a value with `string.inspect` reads all of its fields.
+        if self.expr_typer.is_stdlib_inspect(&function) {
+            self.expr_typer
+                .environment
+                .register_all_fields_read(self.argument_type.clone());
+        }
+
         TypedExpr::Call {
             location: function_location,
             type_: return_type,
@@ -8,12 +8,12 @@ use crate::{ast::Publicity, build::Origin, uid::UniqueIdGenerator};
 
 use super::{
     ModuleInterface, Opaque, References, Type, TypeConstructor, TypeValueConstructor,
-    TypeValueConstructorField, TypeVar, TypeVariantConstructors, ValueConstructor,
+    TypeValueConstructorField, TypeVar, TypeVarCell, TypeVariantConstructors, ValueConstructor,
     ValueConstructorVariant,
 };
 use crate::type_::Deprecation::NotDeprecated;
 use src_span::{LineNumbers, SrcSpan};
-use std::{cell::RefCell, collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 const BIT_ARRAY: &str = "BitArray";
 const BOOL: &str = "Bool";
@@ -201,20 +201,20 @@ pub fn utf_codepoint() -> Arc<Type> {
 
 pub fn generic_var(id: u64) -> Arc<Type> {
     Arc::new(Type::Var {
-        type_: Arc::new(RefCell::new(TypeVar::Generic { id })),
+        type_: Arc::new(TypeVarCell::new(TypeVar::Generic { id })),
     })
 }
 
 pub fn unbound_var(id: u64) -> Arc<Type> {
     Arc::new(Type::Var {
-        type_: Arc::new(RefCell::new(TypeVar::Unbound { id })),
+        type_: Arc::new(TypeVarCell::new(TypeVar::Unbound { id })),
     })
 }
 
 #[cfg(test)]
 pub fn link(type_: Arc<Type>) -> Arc<Type> {
     Arc::new(Type::Var {
-        type_: Arc::new(RefCell::new(TypeVar::Link { type_ })),
+        type_: Arc::new(TypeVarCell::new(TypeVar::Link { type_ })),
     })
 }
 
@@ -662,7 +662,7 @@ fn test_generic_type_annotation() {
     let mut printer = Printer::new(&names);
 
     let type_ = Type::Var {
-        type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id: 0 })),
+        type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Generic { id: 0 })),
     };
 
     assert_eq!(printer.print_type(&type_), "one");
@@ -674,11 +674,11 @@ fn test_generic_type_var() {
     let mut printer = Printer::new(&names);
 
     let type_ = Type::Var {
-        type_: Arc::new(std::cell::RefCell::new(TypeVar::Unbound { id: 0 })),
+        type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Unbound { id: 0 })),
     };
 
     let typ2 = Type::Var {
-        type_: Arc::new(std::cell::RefCell::new(TypeVar::Unbound { id: 1 })),
+        type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Unbound { id: 1 })),
     };
 
     assert_eq!(printer.print_type(&type_), "a");
@@ -790,7 +790,7 @@ fn test_type_alias_and_generics() {
     let type_ = Type::Named {
         name: "Tiger".into(),
         arguments: vec![Arc::new(Type::Var {
-            type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id: 0 })),
+            type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Generic { id: 0 })),
         })],
         module: "mod".into(),
         publicity: crate::ast::Publicity::Public,
@@ -814,7 +814,7 @@ fn test_unqualified_import_and_generic() {
     let type_ = Type::Named {
         name: "Cat".into(),
         arguments: vec![Arc::new(Type::Var {
-            type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id: 0 })),
+            type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Generic { id: 0 })),
         })],
         module: "mod".into(),
         publicity: crate::ast::Publicity::Public,
@@ -920,10 +920,10 @@ fn test_multiple_generic_annotations() {
         name: "Tiger".into(),
         arguments: vec![
             Arc::new(Type::Var {
-                type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id: 0 })),
+                type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Generic { id: 0 })),
             }),
             Arc::new(Type::Var {
-                type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id: 1 })),
+                type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Generic { id: 1 })),
             }),
         ],
         module: "tigermodule".into(),
@@ -933,7 +933,7 @@ fn test_multiple_generic_annotations() {
     };
 
     let typ1 = Type::Var {
-        type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id: 2 })),
+        type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Generic { id: 2 })),
     };
 
     assert_eq!(printer.print_type(&type_), "tigermodule.Tiger(one, two)");
@@ -950,7 +950,7 @@ fn test_variable_name_already_in_scope() {
     let mut printer = Printer::new(&names);
 
     let type_ = |id| Type::Var {
-        type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id })),
+        type_: Arc::new(crate::type_::TypeVarCell::new(TypeVar::Generic { id })),
     };
 
     assert_eq!(printer.print_type(&type_(0)), "c");
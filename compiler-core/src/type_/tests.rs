@@ -189,6 +189,7 @@ macro_rules! assert_error {
                     errors: error,
                 },
             )]),
+            unparsed_modules: vec![],
         };
         let error_string = error.pretty_string();
         let output = format!(
@@ -227,6 +228,31 @@ pub(crate) fn get_printed_warnings(
     print_warnings(get_warnings(src, deps, target, gleam_version))
 }
 
+/// Returns the printed warnings of a module compiled with typed holes enabled
+/// in `gleam.toml`.
+///
+pub(crate) fn get_printed_typed_hole_warnings(
+    src: &str,
+    deps: Vec<DependencyModule<'_>>,
+) -> String {
+    let warnings = VectorWarningEmitterIO::default();
+    let levels = crate::warning::WarningLevels::new([(
+        crate::warning::WarningName::Kind(crate::warning::WarningKind::TypedHole),
+        crate::warning::WarningLevel::Warn,
+    )]);
+    let emitter = WarningEmitter::new(Rc::new(warnings.clone())).with_levels(levels);
+    _ = compile_module_with_emitter(
+        "test_module",
+        src,
+        emitter,
+        deps,
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        None,
+    );
+    print_warnings(warnings.take())
+}
+
 fn print_warnings(warnings: Vec<crate::warning::Warning>) -> String {
     let mut nocolor = termcolor::Buffer::no_color();
     for warning in warnings {
@@ -425,6 +451,7 @@ fn compile_statement_sequence(
             has_body: true,
             has_erlang_external: false,
             has_javascript_external: false,
+            has_wasm_external: false,
         },
         &mut problems,
     )
@@ -514,11 +541,30 @@ pub fn compile_module_with_opts(
     target_support: TargetSupport,
     gleam_version: Option<Range<Version>>,
 ) -> Outcome<TypedModule, Vec1<super::Error>> {
-    let ids = UniqueIdGenerator::new();
-    let mut modules = im::HashMap::new();
-
     let emitter =
         WarningEmitter::new(warnings.unwrap_or_else(|| Rc::new(VectorWarningEmitterIO::default())));
+    compile_module_with_emitter(
+        module_name,
+        src,
+        emitter,
+        dep,
+        target,
+        target_support,
+        gleam_version,
+    )
+}
+
+fn compile_module_with_emitter(
+    module_name: &str,
+    src: &str,
+    emitter: WarningEmitter,
+    dep: Vec<DependencyModule<'_>>,
+    target: Target,
+    target_support: TargetSupport,
+    gleam_version: Option<Range<Version>>,
+) -> Outcome<TypedModule, Vec1<super::Error>> {
+    let ids = UniqueIdGenerator::new();
+    let mut modules = im::HashMap::new();
 
     // DUPE: preludeinsertion
     // TODO: Currently we do this here and also in the tests. It would be better
@@ -615,6 +661,7 @@ pub fn module_error_with_target(
                 errors: Vec1::try_from_vec(error).expect("should have at least one error"),
             },
         )]),
+        unparsed_modules: vec![],
     };
     error.pretty_string()
 }
@@ -655,6 +702,7 @@ pub fn internal_module_error_with_target(
                 errors: Vec1::try_from_vec(error).expect("should have at least one error"),
             },
         )]),
+        unparsed_modules: vec![],
     };
     error.pretty_string()
 }
@@ -666,7 +714,7 @@ pub fn syntax_error(src: &str) -> String {
     let error = Error::Parse {
         src: src.into(),
         path: Utf8PathBuf::from("/src/one/two.gleam"),
-        error: Box::new(error),
+        errors: error,
     };
     error.pretty_string()
 }
@@ -2983,15 +3031,23 @@ fn assert_suitable_main_function_not_module_function() {
                 gleam: true,
                 uses_erlang_externals: false,
                 uses_javascript_externals: false,
+                uses_wasm_externals: false,
                 can_run_on_erlang: true,
                 can_run_on_javascript: true,
+                can_run_on_wasm: true,
             },
             name: "main".into(),
         },
     };
     assert!(
-        assert_suitable_main_function(&value, &"module".into(), Origin::Src, Target::Erlang)
-            .is_err(),
+        assert_suitable_main_function(
+            &value,
+            &"module".into(),
+            "main",
+            Origin::Src,
+            Target::Erlang
+        )
+        .is_err(),
     );
 }
 
@@ -3010,19 +3066,28 @@ fn assert_suitable_main_function_wrong_arity() {
             module: "module".into(),
             external_erlang: None,
             external_javascript: None,
+            external_wasm: None,
             implementations: Implementations {
                 gleam: true,
                 uses_erlang_externals: false,
                 uses_javascript_externals: false,
+                uses_wasm_externals: false,
                 can_run_on_erlang: true,
                 can_run_on_javascript: true,
+                can_run_on_wasm: true,
             },
             purity: Purity::Pure,
         },
     };
     assert!(
-        assert_suitable_main_function(&value, &"module".into(), Origin::Src, Target::Erlang)
-            .is_err(),
+        assert_suitable_main_function(
+            &value,
+            &"module".into(),
+            "main",
+            Origin::Src,
+            Target::Erlang
+        )
+        .is_err(),
     );
 }
 
@@ -3041,19 +3106,28 @@ fn assert_suitable_main_function_ok() {
             module: "module".into(),
             external_erlang: None,
             external_javascript: None,
+            external_wasm: None,
             implementations: Implementations {
                 gleam: true,
                 uses_erlang_externals: false,
                 uses_javascript_externals: false,
+                uses_wasm_externals: false,
                 can_run_on_erlang: true,
                 can_run_on_javascript: true,
+                can_run_on_wasm: true,
             },
             purity: Purity::Pure,
         },
     };
     assert!(
-        assert_suitable_main_function(&value, &"module".into(), Origin::Src, Target::Erlang)
-            .is_ok(),
+        assert_suitable_main_function(
+            &value,
+            &"module".into(),
+            "main",
+            Origin::Src,
+            Target::Erlang
+        )
+        .is_ok(),
     );
 }
 
@@ -3072,19 +3146,28 @@ fn assert_suitable_main_function_erlang_not_supported() {
             module: "module".into(),
             external_erlang: Some(("wibble".into(), "wobble".into())),
             external_javascript: Some(("wobble".into(), "wibble".into())),
+            external_wasm: None,
             implementations: Implementations {
                 gleam: false,
                 uses_erlang_externals: true,
                 uses_javascript_externals: true,
+                uses_wasm_externals: false,
                 can_run_on_erlang: false,
                 can_run_on_javascript: true,
+                can_run_on_wasm: false,
             },
             purity: Purity::Impure,
         },
     };
     assert!(
-        assert_suitable_main_function(&value, &"module".into(), Origin::Src, Target::Erlang)
-            .is_err(),
+        assert_suitable_main_function(
+            &value,
+            &"module".into(),
+            "main",
+            Origin::Src,
+            Target::Erlang
+        )
+        .is_err(),
     );
 }
 
@@ -3103,19 +3186,28 @@ fn assert_suitable_main_function_javascript_not_supported() {
             module: "module".into(),
             external_erlang: Some(("wibble".into(), "wobble".into())),
             external_javascript: Some(("wobble".into(), "wibble".into())),
+            external_wasm: None,
             implementations: Implementations {
                 gleam: false,
                 uses_erlang_externals: true,
                 uses_javascript_externals: true,
+                uses_wasm_externals: false,
                 can_run_on_erlang: true,
                 can_run_on_javascript: false,
+                can_run_on_wasm: false,
             },
             purity: Purity::Impure,
         },
     };
     assert!(
-        assert_suitable_main_function(&value, &"module".into(), Origin::Src, Target::JavaScript)
-            .is_err(),
+        assert_suitable_main_function(
+            &value,
+            &"module".into(),
+            "main",
+            Origin::Src,
+            Target::JavaScript
+        )
+        .is_err(),
     );
 }
 
@@ -598,3 +598,231 @@ pub fn main() -> Wibble {
 "
     );
 }
+
+#[test]
+fn unused_record_field() {
+    assert_warning!(
+        "
+type Wibble {
+  Wibble(used: Int, unused: Int)
+}
+
+pub fn main() {
+  Wibble(1, 2).used
+}
+"
+    );
+}
+
+#[test]
+fn no_unused_record_field_warning_for_opaque_type() {
+    assert_no_warnings!(
+        "
+pub opaque type Wibble {
+  Wibble(used: Int, unused: Int)
+}
+
+pub fn new() -> Wibble {
+  Wibble(1, 2)
+}
+
+pub fn used(wibble: Wibble) -> Int {
+  wibble.used
+}
+"
+    );
+}
+
+#[test]
+fn unused_record_field_only_read_in_other_variant() {
+    assert_warning!(
+        "
+type Wibble {
+  Wibble(a: Int)
+  Wobble(a: Int)
+}
+
+pub fn main() {
+  wibble(Wibble(1)) + wibble(Wobble(2))
+}
+
+fn wibble(wibble: Wibble) -> Int {
+  case wibble {
+    Wibble(a:) -> a
+    Wobble(_) -> 0
+  }
+}
+"
+    );
+}
+
+#[test]
+fn fields_of_unused_constructor_are_not_reported() {
+    assert_warning!(
+        "
+type Wibble {
+  Wibble
+  Wobble(a: Int)
+}
+
+pub fn main() {
+  Wibble
+}
+"
+    );
+}
+
+#[test]
+fn record_fields_read_by_access_pattern_or_guard() {
+    assert_no_warnings!(
+        "
+type Wibble {
+  Wibble(a: Int, b: Int, c: Int)
+}
+
+pub fn main() {
+  let wibble = Wibble(1, 2, 3)
+  let Wibble(b:, ..) = wibble
+  case wibble {
+    _ if wibble.c > 0 -> wibble.a + b
+    _ -> 0
+  }
+}
+"
+    );
+}
+
+#[test]
+fn record_fields_read_by_equality() {
+    assert_no_warnings!(
+        "
+type Wibble {
+  Wibble(a: Int, wobble: Wobble)
+}
+
+type Wobble {
+  Wobble(b: Int)
+}
+
+pub fn main() {
+  wibble(1) == wibble(2)
+}
+
+fn wibble(a: Int) -> Wibble {
+  Wibble(a, Wobble(a))
+}
+"
+    );
+}
+
+#[test]
+fn record_fields_read_by_inequality_in_guard() {
+    assert_no_warnings!(
+        "
+type Wibble {
+  Wibble(a: Int)
+}
+
+pub fn main(a: Int) {
+  let wibble = Wibble(a)
+  case Nil {
+    _ if wibble != Wibble(1) -> 1
+    _ -> 0
+  }
+}
+"
+    );
+}
+
+#[test]
+fn record_fields_read_by_echo() {
+    assert_no_warnings!(
+        "
+type Wibble {
+  Wibble(a: Int)
+}
+
+pub fn main() {
+  echo [Wibble(1)]
+}
+"
+    );
+}
+
+#[test]
+fn record_fields_read_by_piped_echo() {
+    assert_no_warnings!(
+        "
+type Wibble {
+  Wibble(a: Int)
+}
+
+pub fn main() {
+  #(1, Wibble(1)) |> echo
+}
+"
+    );
+}
+
+#[test]
+fn record_fields_read_by_string_inspect() {
+    assert_no_warnings!(
+        (
+            "gleam_stdlib",
+            "gleam/string",
+            "pub fn inspect(term: anything) -> String { todo }"
+        ),
+        "
+import gleam/string
+
+type Wibble {
+  Wibble(a: Int)
+}
+
+type Wobble {
+  Wobble(b: Int)
+}
+
+pub fn main() {
+  #(Wibble(1) |> string.inspect, string.inspect(Wobble(2)))
+}
+"
+    );
+}
+
+#[test]
+fn record_fields_not_read_by_other_functions() {
+    assert_warning!(
+        (
+            "gleam_stdlib",
+            "gleam/string",
+            "pub fn identity(term: anything) -> anything { term }"
+        ),
+        "
+import gleam/string
+
+type Wibble {
+  Wibble(a: Int)
+}
+
+pub fn main() {
+  string.identity(Wibble(1))
+}
+"
+    );
+}
+
+#[test]
+fn unused_record_field_of_public_type() {
+    assert_no_warnings!(
+        "
+pub type Wibble {
+  Wibble(a: Int)
+}
+
+pub fn main() {
+  Wibble(1)
+}
+"
+    );
+}
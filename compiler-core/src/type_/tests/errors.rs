@@ -3603,6 +3603,15 @@ fn todo_in_a_constant_produces_an_error() {
     assert_module_error!("pub const wibble = todo");
 }
 
+#[test]
+fn typed_hole_is_an_error_when_not_enabled() {
+    assert_module_error!(
+        "pub fn main() -> Int {
+  1 + _
+}"
+    );
+}
+
 #[test]
 fn todo_constant_does_not_stop_analysis() {
     assert_module_error!(
@@ -1960,3 +1960,98 @@ pub fn main(x) {
 "#
     );
 }
+
+#[test]
+fn int_remainder_matching_on_all_possible_values() {
+    assert_no_warnings!(
+        r#"
+pub fn main(x) {
+  let y = x % 3
+  case y {
+    -2 -> "-2"
+    -1 -> "-1"
+    0 -> "0"
+    1 -> "1"
+    2 -> "2"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn catch_all_after_matching_on_all_possible_int_values() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case { x % 3 + 3 } % 3 {
+    0 -> "zero"
+    1 -> "one"
+    2 -> "two"
+    _ -> "other"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn missing_int_values_of_remainder() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x % 3 {
+    0 -> "zero"
+    1 -> "one"
+    2 -> "two"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn int_pattern_outside_of_possible_values() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x % 2 {
+    0 -> "even"
+    3 -> "three"
+    _ -> "odd"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn catch_all_after_guards_covering_all_values() {
+    assert_warning!(
+        r#"
+pub fn main(x) {
+  case x {
+    n if n > 0 -> "positive"
+    n if n <= 0 -> "not positive"
+    _ -> "other"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn guard_that_is_never_true() {
+    assert_warning!(
+        r#"
+pub fn main(x: Int, flag: Bool) {
+  case x {
+    _ if flag -> 1
+    0 -> 2
+    _ if flag -> 3
+    _ -> 4
+  }
+}
+"#
+    );
+}
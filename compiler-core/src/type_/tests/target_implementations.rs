@@ -54,8 +54,10 @@ pub fn pure_gleam_2() { pure_gleam_1() * 2 }
                     gleam: true,
                     uses_erlang_externals: false,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: true,
                 }
             ),
             (
@@ -64,8 +66,10 @@ pub fn pure_gleam_2() { pure_gleam_1() * 2 }
                     gleam: true,
                     uses_erlang_externals: false,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: true,
                 }
             )
         ],
@@ -88,8 +92,10 @@ pub fn erlang_only_2() { erlang_only_1() * 2 }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: false,
+                    can_run_on_wasm: false,
                 }
             ),
             (
@@ -98,8 +104,10 @@ pub fn erlang_only_2() { erlang_only_1() * 2 }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: false,
+                    can_run_on_wasm: false,
                 }
             )
         ],
@@ -123,8 +131,10 @@ pub fn all_externals_2() { all_externals_1() * 2 }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: false,
                 }
             ),
             (
@@ -133,8 +143,10 @@ pub fn all_externals_2() { all_externals_1() * 2 }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: false,
                 }
             )
         ],
@@ -162,8 +174,10 @@ pub fn pure_gleam() {
                     gleam: true,
                     uses_erlang_externals: true,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: true,
                 }
             ),
             (
@@ -172,8 +186,10 @@ pub fn pure_gleam() {
                     gleam: true,
                     uses_erlang_externals: false,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: true,
                 }
             ),
             (
@@ -182,8 +198,10 @@ pub fn pure_gleam() {
                     gleam: true,
                     uses_erlang_externals: true,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: true,
                 }
             )
         ],
@@ -209,8 +227,10 @@ pub fn all_externals() -> Int { erlang_external_and_javascript_body() }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: false,
                 }
             ),
             (
@@ -219,8 +239,10 @@ pub fn all_externals() -> Int { erlang_external_and_javascript_body() }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: false,
                 }
             ),
             (
@@ -229,8 +251,10 @@ pub fn all_externals() -> Int { erlang_external_and_javascript_body() }
                     gleam: false,
                     uses_erlang_externals: false,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: false,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: false,
                 }
             )
         ],
@@ -256,8 +280,10 @@ pub fn all_externals() -> Int { javascript_external_and_erlang_body() }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: false,
                 }
             ),
             (
@@ -266,8 +292,10 @@ pub fn all_externals() -> Int { javascript_external_and_erlang_body() }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: false,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: false,
+                    can_run_on_wasm: false,
                 }
             ),
             (
@@ -276,8 +304,10 @@ pub fn all_externals() -> Int { javascript_external_and_erlang_body() }
                     gleam: false,
                     uses_erlang_externals: true,
                     uses_javascript_externals: true,
+                    uses_wasm_externals: false,
                     can_run_on_erlang: true,
                     can_run_on_javascript: true,
+                    can_run_on_wasm: false,
                 }
             )
         ],
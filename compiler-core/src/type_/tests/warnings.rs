@@ -81,6 +81,93 @@ pub fn wibble() { }
     );
 }
 
+macro_rules! assert_typed_hole_warning {
+    ($src:expr) => {
+        let warning = $crate::type_::tests::get_printed_typed_hole_warnings($src, vec![]);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+
+    (($name:expr, $module_src:literal), $src:literal $(,)?) => {
+        let warning = $crate::type_::tests::get_printed_typed_hole_warnings(
+            $src,
+            vec![("thepackage", $name, $module_src)],
+        );
+        assert!(!warning.is_empty());
+        let output = format!(
+            "----- SOURCE CODE\n-- {}.gleam\n{}\n\n-- main.gleam\n{}\n\n----- WARNING\n{}",
+            $name, $module_src, $src, warning
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[test]
+fn typed_hole_with_unknown_type() {
+    assert_typed_hole_warning!(
+        "pub fn main() {
+  _
+}"
+    );
+}
+
+#[test]
+fn typed_hole_reports_local_variables() {
+    assert_typed_hole_warning!(
+        r#"pub fn wibble(name: String, size: Int) -> Int {
+  case name {
+    "" -> size
+    _ -> _
+  }
+}"#
+    );
+}
+
+#[test]
+fn typed_hole_suggests_imported_functions() {
+    assert_typed_hole_warning!(
+        (
+            "wibble",
+            "
+pub fn length(_string: String) -> Int { 1 }
+pub fn parse(_string: String) -> Result(Int, Nil) { Ok(1) }
+pub fn identity(x: a) -> a { x }
+pub fn zero() -> Int { 0 }
+fn private() -> Int { 0 }
+"
+        ),
+        "
+import wibble
+pub fn main() -> Int {
+  wibble.zero() + _
+}"
+    );
+}
+
+#[test]
+fn typed_hole_is_reported_next_to_a_type_error() {
+    assert_typed_hole_warning!(
+        r#"pub fn main(name: String) -> Int {
+  let size: Int = _
+  size + name
+}"#
+    );
+}
+
+#[test]
+fn capture_hole_is_not_a_typed_hole() {
+    assert_no_warnings!(
+        "
+fn add(x: Int, y: Int) -> Int { x + y }
+
+pub fn main() {
+  add(_, 1)
+}
+"
+    );
+}
+
 #[test]
 fn warning_variable_never_used_test() {
     assert_warning!(
@@ -1781,6 +1868,59 @@ pub fn main() {
     );
 }
 
+fn inexhaustive_let_assert_warnings(src: &str) -> String {
+    let module = compile_module("test_module", src, None, vec![]).expect("module should compile");
+    let warnings = module
+        .type_info
+        .warnings
+        .into_iter()
+        .map(|warning| crate::warning::Warning::Type {
+            path: "/src/warning/wrn.gleam".into(),
+            src: src.into(),
+            warning: Box::new(warning),
+        })
+        .collect_vec();
+    print_warnings(warnings)
+}
+
+#[test]
+fn inexhaustive_let_assert() {
+    let src = "
+pub fn main(x: Result(Int, Nil)) {
+  let assert Ok(y) = x
+  y
+}
+";
+    let warning = inexhaustive_let_assert_warnings(src);
+    let output = format!("----- SOURCE CODE\n{src}\n\n----- WARNING\n{warning}");
+    insta::assert_snapshot!(insta::internals::AutoName, output, src);
+}
+
+#[test]
+fn inexhaustive_let_assert_with_many_missing_patterns() {
+    let src = "
+pub fn main(x: List(Int)) {
+  let assert [first, second] = x
+  first + second
+}
+";
+    let warning = inexhaustive_let_assert_warnings(src);
+    let output = format!("----- SOURCE CODE\n{src}\n\n----- WARNING\n{warning}");
+    insta::assert_snapshot!(insta::internals::AutoName, output, src);
+}
+
+#[test]
+fn inexhaustive_let_assert_is_allowed_by_default() {
+    assert_no_warnings!(
+        "
+pub fn main(x: Result(Int, Nil)) {
+  let assert Ok(y) = x
+  y
+}
+"
+    );
+}
+
 #[test]
 fn panic_used_as_function() {
     assert_warning!(
@@ -5239,3 +5379,143 @@ pub fn main() {
 "
     );
 }
+
+#[test]
+fn uninlinable_function_with_inline_attribute() {
+    assert_warning!(
+        "
+@inline
+pub fn wibble(x) {
+  let y = x
+  y
+}
+"
+    );
+}
+
+#[test]
+fn external_function_with_inline_attribute() {
+    assert_warning!(
+        "
+@inline
+@external(erlang, \"wibble\", \"wobble\")
+@external(javascript, \"./wibble.mjs\", \"wobble\")
+pub fn wibble(x: Int) -> Int
+"
+    );
+}
+
+#[test]
+fn inlinable_function_with_inline_attribute() {
+    assert_no_warnings!(
+        "
+@inline
+pub fn wibble(x) {
+  Ok(x)
+}
+"
+    );
+}
+
+#[test]
+fn allow_attribute_silences_warning() {
+    assert_no_warnings!(
+        "
+@allow(unused_variable)
+pub fn main() {
+  let x = 1
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn allow_attribute_with_warning_group() {
+    assert_no_warnings!(
+        "
+@allow(unused)
+pub fn main() {
+  let x = 1
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn allow_attribute_with_todo() {
+    assert_no_warnings!(
+        "
+@allow(todo)
+pub fn main() {
+  todo
+}
+"
+    );
+}
+
+#[test]
+fn unused_arguments_have_their_own_warning_kind() {
+    let warnings = get_warnings(
+        "
+pub fn main(x) {
+  let y = 1
+  fn(z) { Nil }
+}
+",
+        vec![],
+        Target::Erlang,
+        None,
+    );
+    let kinds = warnings.iter().map(|warning| warning.kind()).collect_vec();
+    assert_eq!(
+        kinds,
+        vec![
+            crate::warning::WarningKind::UnusedArgument,
+            crate::warning::WarningKind::UnusedVariable,
+            crate::warning::WarningKind::UnusedArgument,
+        ]
+    );
+}
+
+#[test]
+fn allow_attribute_silences_unused_argument() {
+    assert_no_warnings!(
+        "
+@allow(unused_argument)
+pub fn main(x) {
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn allow_attribute_only_applies_to_its_definition() {
+    let warnings = get_warnings(
+        "
+@allow(unused_variable)
+pub fn main() {
+  let x = 1
+  todo
+}
+
+pub fn other() {
+  let y = 1
+  Nil
+}
+",
+        vec![],
+        Target::Erlang,
+        None,
+    );
+    let kinds = warnings.iter().map(|warning| warning.kind()).collect_vec();
+    assert_eq!(
+        kinds,
+        vec![
+            crate::warning::WarningKind::Todo,
+            crate::warning::WarningKind::UnusedVariable,
+        ]
+    );
+}
@@ -21,6 +21,29 @@ impl UniqueIdGenerator {
     pub fn next(&self) -> u64 {
         self.id.fetch_add(1, Ordering::Relaxed)
     }
+
+    /// Reserves a range of ids, returning a new generator that hands out the
+    /// ids in that range. Modules analysed at the same time on different
+    /// threads each get their own range, so the ids they use don't depend on
+    /// the order the threads happen to run in.
+    pub fn reserve_range(&self) -> Self {
+        let start = self.id.fetch_add(RANGE_SIZE, Ordering::Relaxed);
+        Self {
+            id: Arc::new(AtomicU64::new(start)),
+        }
+    }
+}
+
+/// The number of ids in a range reserved with `reserve_range`. No single module
+/// could ever need this many.
+const RANGE_SIZE: u64 = 1 << 32;
+
+/// The position of an id within the range it was reserved from. The ids used
+/// while analysing a module are all taken from the same range, so they stay
+/// distinct, while being small enough to be turned into readable names in the
+/// generated code.
+pub fn offset_in_range(id: u64) -> u64 {
+    id % RANGE_SIZE
 }
 
 #[test]
@@ -41,3 +64,31 @@ fn id_geneation() {
     assert_eq!(ids.next(), 6);
     assert_eq!(ids.next(), 7);
 }
+
+#[test]
+fn reserved_ranges() {
+    let ids = UniqueIdGenerator::new();
+    assert_eq!(ids.next(), 0);
+
+    let range = ids.reserve_range();
+    let other_range = ids.reserve_range();
+    assert_eq!(other_range.next(), 1 + RANGE_SIZE);
+    assert_eq!(range.next(), 1);
+    assert_eq!(range.next(), 2);
+
+    // Ids of the original generator come after the reserved ranges
+    assert_eq!(ids.next(), 1 + 2 * RANGE_SIZE);
+}
+
+#[test]
+fn offsets_in_reserved_ranges() {
+    let ids = UniqueIdGenerator::new();
+    assert_eq!(ids.next(), 0);
+
+    let range = ids.reserve_range();
+    let other_range = ids.reserve_range();
+    assert_eq!(offset_in_range(range.next()), 1);
+    assert_eq!(offset_in_range(other_range.next()), 1);
+    assert_eq!(offset_in_range(other_range.next()), 2);
+    assert_eq!(offset_in_range(ids.next()), 1);
+}
@@ -7,6 +7,7 @@ use crate::{
     diagnostic::{self, Diagnostic, ExtraLabel, Location},
     error::wrap,
     exhaustiveness::ImpossibleBitArraySegmentPattern,
+    lint::Lint,
     type_::{
         self,
         error::{
@@ -21,12 +22,17 @@ use camino::Utf8PathBuf;
 use debug_ignore::DebugIgnore;
 use ecow::EcoString;
 use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use src_span::SrcSpan;
 use std::{
+    collections::BTreeMap,
+    fmt,
     io::Write,
+    str::FromStr,
     sync::{Arc, atomic::Ordering},
 };
 use std::{rc::Rc, sync::atomic::AtomicUsize};
+use strum::{EnumIter, EnumString, IntoEnumIterator, IntoStaticStr};
 use termcolor::Buffer;
 
 macro_rules! wrap_format {
@@ -90,6 +96,12 @@ pub struct WarningEmitter {
     /// package only, the count is reset back to zero after the dependencies are
     /// compiled.
     count: Arc<AtomicUsize>,
+    /// The number of emitted warnings that were denied, and so have to make
+    /// the build fail. This is reset along with the count.
+    denied_count: Arc<AtomicUsize>,
+    /// The levels configured for each kind of warning. Allowed warnings are
+    /// not emitted at all.
+    levels: Arc<WarningLevels>,
     emitter: DebugIgnore<Rc<dyn WarningEmitterIO>>,
 }
 
@@ -97,6 +109,8 @@ impl WarningEmitter {
     pub fn new(emitter: Rc<dyn WarningEmitterIO>) -> Self {
         Self {
             count: Arc::new(AtomicUsize::new(0)),
+            denied_count: Arc::new(AtomicUsize::new(0)),
+            levels: Arc::new(WarningLevels::default()),
             emitter: DebugIgnore(emitter),
         }
     }
@@ -105,15 +119,44 @@ impl WarningEmitter {
         Self::new(Rc::new(NullWarningEmitterIO))
     }
 
+    /// Returns an emitter reporting the warnings to the same place, and
+    /// counting them along with this one, but using the given levels instead.
+    ///
+    pub fn with_levels(&self, levels: WarningLevels) -> Self {
+        Self {
+            levels: Arc::new(levels),
+            ..self.clone()
+        }
+    }
+
+    pub fn level(&self, kind: WarningKind) -> WarningLevel {
+        self.levels.level(kind)
+    }
+
     pub fn reset_count(&self) {
         self.count.store(0, Ordering::Relaxed);
+        self.denied_count.store(0, Ordering::Relaxed);
     }
 
     pub fn count(&self) -> usize {
         self.count.load(Ordering::Relaxed)
     }
 
+    pub fn denied_count(&self) -> usize {
+        self.denied_count.load(Ordering::Relaxed)
+    }
+
     pub fn emit(&self, warning: Warning) {
+        let warning = match self.levels.level(warning.kind()) {
+            WarningLevel::Allow => return,
+            WarningLevel::Warn => warning,
+            WarningLevel::Deny => {
+                _ = self.denied_count.fetch_add(1, Ordering::Relaxed);
+                Warning::Denied {
+                    warning: Box::new(warning),
+                }
+            }
+        };
         _ = self.count.fetch_add(1, Ordering::Relaxed);
         self.emitter.emit_warning(warning);
     }
@@ -149,6 +192,10 @@ impl TypeWarningEmitter {
         }
     }
 
+    pub fn level(&self, kind: WarningKind) -> WarningLevel {
+        self.emitter.level(kind)
+    }
+
     pub fn emit(&self, warning: type_::Warning) {
         self.emitter.emit(Warning::Type {
             path: self.module_path.clone(),
@@ -158,6 +205,301 @@ impl TypeWarningEmitter {
     }
 }
 
+/// How a kind of warning is reported. This can be set for each kind of
+/// warning, or for a whole group of them, in the `[warnings]` section of
+/// `gleam.toml`:
+///
+/// ```toml
+/// [warnings]
+/// unused = "deny"
+/// todo = "allow"
+/// ```
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WarningLevel {
+    /// The warning is not reported at all.
+    Allow,
+    #[default]
+    Warn,
+    /// The warning is reported as an error, and the build fails.
+    Deny,
+}
+
+/// The kinds of warning the compiler can emit, named the way they are
+/// written in `gleam.toml` and in the `@allow` attribute.
+///
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString, IntoStaticStr, EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum WarningKind {
+    Todo,
+    TypedHole,
+    UnusedResult,
+    UnusedValue,
+    UnusedType,
+    UnusedField,
+    UnusedImport,
+    UnusedConstant,
+    UnusedFunction,
+    UnusedVariable,
+    UnusedArgument,
+    DuplicateImport,
+    RedundantRecordUpdate,
+    RedundantNegation,
+    RedundantCase,
+    RedundantAssert,
+    RedundantFunctionCapture,
+    RedundantComparison,
+    InefficientEmptyListCheck,
+    TransitiveDependency,
+    Deprecated,
+    UnreachableCaseClause,
+    UnreachableCode,
+    ImpossiblePattern,
+    InexhaustiveLetAssert,
+    OpaqueExternalType,
+    TodoOrPanicAsFunction,
+    UninlinableFunction,
+    GleamVersion,
+    JavascriptUnsafeInt,
+    TruncatedValue,
+    ShadowedImport,
+    PipeIntoFunction,
+    InvalidModuleName,
+    EmptyModule,
+    DetachedDocComment,
+    Lint,
+}
+
+impl WarningKind {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+
+    /// The level of this kind of warning when none is set in `gleam.toml`.
+    /// Kinds that are allowed by default are opt-in: they can only be turned
+    /// on by setting their own level, and not the level of a group.
+    ///
+    pub fn default_level(self) -> WarningLevel {
+        match self {
+            WarningKind::InexhaustiveLetAssert | WarningKind::TypedHole => WarningLevel::Allow,
+            WarningKind::Todo
+            | WarningKind::UnusedResult
+            | WarningKind::UnusedValue
+            | WarningKind::UnusedType
+            | WarningKind::UnusedField
+            | WarningKind::UnusedImport
+            | WarningKind::UnusedConstant
+            | WarningKind::UnusedFunction
+            | WarningKind::UnusedVariable
+            | WarningKind::UnusedArgument
+            | WarningKind::DuplicateImport
+            | WarningKind::RedundantRecordUpdate
+            | WarningKind::RedundantNegation
+            | WarningKind::RedundantCase
+            | WarningKind::RedundantAssert
+            | WarningKind::RedundantFunctionCapture
+            | WarningKind::RedundantComparison
+            | WarningKind::InefficientEmptyListCheck
+            | WarningKind::TransitiveDependency
+            | WarningKind::Deprecated
+            | WarningKind::UnreachableCaseClause
+            | WarningKind::UnreachableCode
+            | WarningKind::ImpossiblePattern
+            | WarningKind::OpaqueExternalType
+            | WarningKind::TodoOrPanicAsFunction
+            | WarningKind::UninlinableFunction
+            | WarningKind::GleamVersion
+            | WarningKind::JavascriptUnsafeInt
+            | WarningKind::TruncatedValue
+            | WarningKind::ShadowedImport
+            | WarningKind::PipeIntoFunction
+            | WarningKind::InvalidModuleName
+            | WarningKind::EmptyModule
+            | WarningKind::DetachedDocComment
+            | WarningKind::Lint => WarningLevel::Warn,
+        }
+    }
+}
+
+/// Groups of related kinds of warning, so that they can be configured all at
+/// once.
+///
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString, IntoStaticStr, EnumIter,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum WarningGroup {
+    /// Every kind of warning.
+    All,
+    /// Things that are defined or imported but never used.
+    Unused,
+    /// Code that has no effect and can be removed or simplified.
+    Redundant,
+    /// Code that can never run.
+    Unreachable,
+    /// Code that might not behave as expected on the JavaScript target.
+    Javascript,
+}
+
+impl WarningGroup {
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+
+    pub fn contains(self, kind: WarningKind) -> bool {
+        match self {
+            WarningGroup::All => true,
+
+            WarningGroup::Unused => matches!(
+                kind,
+                WarningKind::UnusedResult
+                    | WarningKind::UnusedValue
+                    | WarningKind::UnusedType
+                    | WarningKind::UnusedField
+                    | WarningKind::UnusedImport
+                    | WarningKind::UnusedConstant
+                    | WarningKind::UnusedFunction
+                    | WarningKind::UnusedVariable
+                    | WarningKind::UnusedArgument
+            ),
+
+            WarningGroup::Redundant => matches!(
+                kind,
+                WarningKind::DuplicateImport
+                    | WarningKind::RedundantRecordUpdate
+                    | WarningKind::RedundantNegation
+                    | WarningKind::RedundantCase
+                    | WarningKind::RedundantAssert
+                    | WarningKind::RedundantFunctionCapture
+                    | WarningKind::RedundantComparison
+                    | WarningKind::InefficientEmptyListCheck
+                    | WarningKind::OpaqueExternalType
+            ),
+
+            WarningGroup::Unreachable => matches!(
+                kind,
+                WarningKind::UnreachableCaseClause
+                    | WarningKind::UnreachableCode
+                    | WarningKind::ImpossiblePattern
+            ),
+
+            WarningGroup::Javascript => matches!(kind, WarningKind::JavascriptUnsafeInt),
+        }
+    }
+}
+
+/// The name of either a single kind of warning or of a group of them.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WarningName {
+    Kind(WarningKind),
+    Group(WarningGroup),
+}
+
+impl WarningName {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WarningName::Kind(kind) => kind.as_str(),
+            WarningName::Group(group) => group.as_str(),
+        }
+    }
+
+    pub fn includes(self, kind: WarningKind) -> bool {
+        match self {
+            WarningName::Kind(name) => name == kind,
+            WarningName::Group(group) => group.contains(kind),
+        }
+    }
+}
+
+impl FromStr for WarningName {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        if let Ok(kind) = WarningKind::from_str(name) {
+            Ok(WarningName::Kind(kind))
+        } else if let Ok(group) = WarningGroup::from_str(name) {
+            Ok(WarningName::Group(group))
+        } else {
+            Err(format!("`{name}` is not a known kind or group of warnings"))
+        }
+    }
+}
+
+impl fmt::Display for WarningName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for WarningName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for WarningName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = EcoString::deserialize(deserializer)?;
+        WarningName::from_str(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The levels set for kinds and groups of warnings in the `[warnings]`
+/// section of `gleam.toml`.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct WarningLevels {
+    levels: BTreeMap<WarningName, WarningLevel>,
+}
+
+impl WarningLevels {
+    pub fn new(levels: impl IntoIterator<Item = (WarningName, WarningLevel)>) -> Self {
+        Self {
+            levels: levels.into_iter().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// The level of a kind of warning. A level set for the kind itself takes
+    /// precedence over the ones set for its groups, and the `all` group comes
+    /// last. If the kind is in more than one group with a level, the strictest
+    /// level is used. Kinds that are allowed by default ignore the levels of
+    /// their groups.
+    ///
+    pub fn level(&self, kind: WarningKind) -> WarningLevel {
+        if let Some(level) = self.levels.get(&WarningName::Kind(kind)) {
+            return *level;
+        }
+
+        let default = kind.default_level();
+        if default == WarningLevel::Allow {
+            return default;
+        }
+
+        WarningGroup::iter()
+            .filter(|group| *group != WarningGroup::All && group.contains(kind))
+            .filter_map(|group| self.levels.get(&WarningName::Group(group)))
+            .max()
+            .or_else(|| self.levels.get(&WarningName::Group(WarningGroup::All)))
+            .copied()
+            .unwrap_or(default)
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum Warning {
     Type {
@@ -186,6 +528,19 @@ pub enum Warning {
         src: EcoString,
         location: SrcSpan,
     },
+
+    /// A problem found by a lint rule rather than by the type checker.
+    Lint {
+        path: Utf8PathBuf,
+        src: EcoString,
+        lint: Box<Lint>,
+    },
+
+    /// A warning of a kind that has been denied in `gleam.toml`, so it is
+    /// reported as an error.
+    Denied {
+        warning: Box<Warning>,
+    },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -268,8 +623,46 @@ pub enum DeprecatedSyntaxWarning {
 }
 
 impl Warning {
+    pub fn kind(&self) -> WarningKind {
+        match self {
+            Warning::Type { warning, .. } => warning.kind(),
+            Warning::InvalidSource { .. } => WarningKind::InvalidModuleName,
+            Warning::DeprecatedSyntax { .. } => WarningKind::Deprecated,
+            Warning::EmptyModule { .. } => WarningKind::EmptyModule,
+            Warning::DetachedDocComment { .. } => WarningKind::DetachedDocComment,
+            Warning::Lint { .. } => WarningKind::Lint,
+            Warning::Denied { warning } => warning.kind(),
+        }
+    }
+
     pub fn to_diagnostic(&self) -> Diagnostic {
         match self {
+            Warning::Denied { warning } => {
+                let mut diagnostic = warning.to_diagnostic();
+                diagnostic.level = diagnostic::Level::Error;
+                diagnostic
+            }
+
+            Warning::Lint { path, src, lint } => Diagnostic {
+                title: lint.title.to_string(),
+                text: wrap_format!(
+                    "{}\nThis is reported by the `{}` lint.",
+                    lint.message,
+                    lint.rule
+                ),
+                level: diagnostic::Level::Warning,
+                location: Some(Location {
+                    label: diagnostic::Label {
+                        text: None,
+                        span: lint.location,
+                    },
+                    path: path.clone(),
+                    src: src.clone(),
+                    extra_labels: vec![],
+                }),
+                hint: lint.fix.as_ref().map(|fix| format!("{}.", fix.title)),
+            },
+
             Warning::InvalidSource { path } => Diagnostic {
                 title: "Invalid module name".into(),
                 text: wrap(
@@ -429,6 +822,7 @@ To have a clause without a guard, remove this.",
                 let full_name = match target {
                     Target::Erlang => "erlang",
                     Target::JavaScript => "javascript",
+                    Target::Wasm => "wasm",
                 };
 
                 Diagnostic {
@@ -482,7 +876,7 @@ comment so it is not attached to any definition.",
                     names,
                 } => {
                     let (title, text) = match kind {
-                        TodoKind::Keyword => {
+                        TodoKind::Keyword | TodoKind::Hole => {
                             let text = "\
 This code will crash if it is run. Be sure to finish it before
 running your program.";
@@ -545,6 +939,64 @@ running your program.";
                     }
                 }
 
+                type_::Warning::TypedHole {
+                    location,
+                    type_,
+                    local_variables,
+                    candidates,
+                    names,
+                } => {
+                    let mut printer = Printer::new(names);
+                    let label = if type_.is_unbound() {
+                        "This code is incomplete".into()
+                    } else {
+                        format!("This hole has type `{}`", printer.print_type(type_))
+                    };
+
+                    let mut sections = vec![
+                        "\
+This code will crash if it is run. Replace the hole with a value
+of the expected type before running your program."
+                            .to_string(),
+                    ];
+                    if !local_variables.is_empty() {
+                        let variables = local_variables
+                            .iter()
+                            .map(|(name, type_)| {
+                                format!("    {name}: {}", printer.print_type(type_))
+                            })
+                            .join("\n");
+                        sections.push(format!("Local variables in scope:\n\n{variables}"));
+                    }
+                    if !candidates.is_empty() {
+                        let functions = candidates
+                            .iter()
+                            .map(|(name, type_)| {
+                                format!("    {name}: {}", printer.print_type(type_))
+                            })
+                            .join("\n");
+                        sections.push(format!(
+                            "Imported functions that could fill the hole:\n\n{functions}"
+                        ));
+                    }
+
+                    Diagnostic {
+                        title: "Typed hole".into(),
+                        text: sections.join("\n\n"),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some(label),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                        hint: None,
+                    }
+                }
+
                 type_::Warning::ImplicitlyDiscardedResult { location } => Diagnostic {
                     title: "Unused result value".into(),
                     text: "".into(),
@@ -673,6 +1125,22 @@ running your program.";
                     }
                 }
 
+                type_::Warning::UnusedRecordField { location, .. } => Diagnostic {
+                    title: "Unused record field".into(),
+                    text: "".into(),
+                    hint: Some("You can safely remove it.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        src: src.clone(),
+                        path: path.to_path_buf(),
+                        label: diagnostic::Label {
+                            text: Some("This field is never read".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
                 type_::Warning::UnusedImportedModule { location, .. } => Diagnostic {
                     title: "Unused imported module".into(),
                     text: "".into(),
@@ -947,6 +1415,14 @@ pattern matches the same values.",
                         UnreachablePatternReason::ImpossibleVariant => wrap(
                             "This pattern cannot be reached as it matches on \
 a variant of a type which is never present.",
+                        ),
+                        UnreachablePatternReason::ImpossibleValue => wrap(
+                            "This pattern cannot be reached as the matched \
+value is never equal to it.",
+                        ),
+                        UnreachablePatternReason::GuardNeverTrue => wrap(
+                            "This pattern cannot be reached as its guard \
+is never true.",
                         ),
                         UnreachablePatternReason::ImpossibleSegments(_) => wrap(
                             "This pattern cannot be reached as it contains \
@@ -956,7 +1432,9 @@ segments that will never match.",
 
                     let extra_labels = match reason {
                         UnreachablePatternReason::DuplicatePattern
-                        | UnreachablePatternReason::ImpossibleVariant => vec![],
+                        | UnreachablePatternReason::ImpossibleVariant
+                        | UnreachablePatternReason::ImpossibleValue
+                        | UnreachablePatternReason::GuardNeverTrue => vec![],
                         UnreachablePatternReason::ImpossibleSegments(segments) => segments
                             .iter()
                             .map(|segment| ExtraLabel {
@@ -1133,6 +1611,35 @@ Either change the pattern or use `panic` to unconditionally fail.",
                     }
                 }
 
+                type_::Warning::InexhaustiveLetAssert { location, missing } => {
+                    let mut text = wrap(
+                        "This assertion uses a pattern that does not match all possible \
+values. If one of the other values is used then the assertion will crash.
+
+The values it will crash on are:\n",
+                    );
+                    for missing in missing {
+                        text.push_str("\n    ");
+                        text.push_str(missing);
+                    }
+
+                    Diagnostic {
+                        title: "Inexhaustive assertion".into(),
+                        text,
+                        hint: Some("Use a `case` expression to handle these values.".into()),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            label: diagnostic::Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 type_::Warning::TodoOrPanicUsedAsFunction {
                     kind,
                     location,
@@ -1253,6 +1760,26 @@ See: https://tour.gleam.run/functions/pipelines/",
                         extra_labels: vec![],
                     }),
                 },
+                type_::Warning::UninlinableFunction { location } => Diagnostic {
+                    title: "Function cannot be inlined".into(),
+                    text: wrap(
+                        "This function is marked as inlinable but it can't be \
+inlined, so the attribute has no effect. Only functions with a Gleam \
+implementation can be inlined, and their body can only refer to their own \
+arguments and to the records of the prelude.",
+                    ),
+                    hint: Some("You can remove this attribute.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        label: diagnostic::Label {
+                            text: Some("This has no effect".into()),
+                            span: *location,
+                        },
+                        path: path.clone(),
+                        src: src.clone(),
+                        extra_labels: vec![],
+                    }),
+                },
                 type_::Warning::FeatureRequiresHigherGleamVersion {
                     location,
                     minimum_required_version,
@@ -0,0 +1,1594 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! Code generation for the experimental WebAssembly target.
+//!
+//! Each Gleam module is compiled to a WebAssembly module written in the text
+//! format, making use of the garbage collection proposal. All Gleam values are
+//! represented as `eqref`s:
+//!
+//! - `Int`s are boxed 64 bit integers. Unlike the other targets they don't
+//!   have arbitrary precision: arithmetic wraps on overflow and integer
+//!   literals that don't fit in 64 bits are truncated.
+//! - `Float`s are boxed 64 bit floats.
+//! - `Bool`s are `i31ref`s holding `0` or `1`, and `Nil` is the `i31ref` `0`.
+//! - `String`s are structs holding an array of UTF-8 bytes.
+//! - Tuples are structs holding an array of values.
+//! - Custom types are structs holding the index of their variant and an array
+//!   with their fields.
+//! - Lists are linked lists of structs holding a head and a tail, and the
+//!   empty list is the null reference.
+//! - Functions used as values are closures: structs holding a reference to a
+//!   function and an array with the values it captures.
+//!
+//! Each module imports the functions it uses from other modules, and the
+//! runtime functions it needs from the prelude, which is instantiated with the
+//! name `gleam`.
+
+#[cfg(test)]
+mod tests;
+mod wat;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
+use std::sync::Arc;
+
+use ecow::{EcoString, eco_format};
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use src_span::SrcSpan;
+use vec1::Vec1;
+
+use crate::analyse::Inferred;
+use crate::ast::{
+    AssignName, AssignmentKind, BinOp, CallArg, ClauseGuard, Constant, Pattern, Statement,
+    TypedArg, TypedAssignment, TypedClause, TypedClauseGuard, TypedConstant, TypedExpr,
+    TypedFunction, TypedModule, TypedPattern, TypedPipelineAssignment, TypedStatement,
+    visit::Visit,
+};
+use crate::build::Target;
+use crate::type_::error::VariableOrigin;
+use crate::type_::{ModuleValueConstructor, Type, ValueConstructor, ValueConstructorVariant};
+
+use self::wat::Wat;
+
+pub const PRELUDE: &str = include_str!("../templates/prelude.wat");
+
+/// The types shared by all the generated modules and the prelude. Type
+/// definitions are compared structurally, so modules can pass values to each
+/// other as long as they define these in the same way.
+///
+const TYPES: [(&str, &str); 9] = [
+    ("$Int", "(struct (field i64))"),
+    ("$Float", "(struct (field f64))"),
+    ("$Bytes", "(array (mut i8))"),
+    ("$String", "(struct (field (ref $Bytes)))"),
+    ("$Values", "(array (mut eqref))"),
+    ("$Tuple", "(struct (field (ref $Values)))"),
+    ("$Record", "(struct (field i32) (field (ref $Values)))"),
+    ("$List", "(struct (field eqref) (field eqref))"),
+    ("$Closure", "(struct (field funcref) (field (ref $Values)))"),
+];
+
+const EQREF: &str = "eqref";
+const CLOSURE: &str = "(ref null $Closure)";
+
+/// A feature of the language that can't be compiled to WebAssembly yet.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    pub location: SrcSpan,
+    pub feature: EcoString,
+}
+
+/// Generates the WebAssembly text format module for the given Gleam module.
+///
+pub fn module(module: &TypedModule) -> Result<String, Unsupported> {
+    let mut generator = Generator::new(module);
+    let functions: Vec<_> = module
+        .definitions
+        .functions
+        .iter()
+        .filter_map(|function| generator.module_function(function))
+        .collect();
+
+    match generator.unsupported.take() {
+        Some(unsupported) => Err(unsupported),
+        None => {
+            let mut output = generator.into_module(functions).to_pretty_string();
+            output.push('\n');
+            Ok(output)
+        }
+    }
+}
+
+/// The functions of the prelude a module can import.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Runtime {
+    Equal,
+    StringConcat,
+    StringStartsWith,
+    StringDropStart,
+    DivideInt,
+    RemainderInt,
+    DivideFloat,
+}
+
+impl Runtime {
+    fn name(self) -> &'static str {
+        match self {
+            Runtime::Equal => "equal",
+            Runtime::StringConcat => "string_concat",
+            Runtime::StringStartsWith => "string_starts_with",
+            Runtime::StringDropStart => "string_drop_start",
+            Runtime::DivideInt => "divide_int",
+            Runtime::RemainderInt => "remainder_int",
+            Runtime::DivideFloat => "divide_float",
+        }
+    }
+
+    fn signature(self) -> (&'static [&'static str], &'static str) {
+        match self {
+            Runtime::Equal | Runtime::StringStartsWith => (&[EQREF, EQREF], "i32"),
+            Runtime::StringConcat => (&[EQREF, EQREF], EQREF),
+            Runtime::StringDropStart => (&[EQREF, "i32"], EQREF),
+            Runtime::DivideInt | Runtime::RemainderInt => (&["i64", "i64"], "i64"),
+            Runtime::DivideFloat => (&["f64", "f64"], "f64"),
+        }
+    }
+}
+
+/// The state shared by all the functions of a module: what it needs to import
+/// and export, and the helper functions generated along the way.
+///
+struct Generator<'module> {
+    module: &'module TypedModule,
+    /// The arities of the closures created or called in this module.
+    closure_arities: BTreeSet<usize>,
+    runtime: BTreeSet<Runtime>,
+    /// The Gleam functions imported from other modules, with their arity.
+    function_imports: BTreeMap<(EcoString, EcoString), usize>,
+    external_imports: Vec<Wat>,
+    /// Lifted anonymous functions and the wrappers used to turn module
+    /// functions and record constructors into closures.
+    helpers: Vec<Wat>,
+    /// The functions that are referenced with `ref.func`, they must be
+    /// declared in an element segment.
+    referenced_functions: BTreeSet<EcoString>,
+    exports: Vec<EcoString>,
+    data: Vec<Wat>,
+    anonymous_functions: usize,
+    unsupported: Option<Unsupported>,
+}
+
+impl<'module> Generator<'module> {
+    fn new(module: &'module TypedModule) -> Self {
+        Self {
+            module,
+            closure_arities: BTreeSet::new(),
+            runtime: BTreeSet::new(),
+            function_imports: BTreeMap::new(),
+            external_imports: vec![],
+            helpers: vec![],
+            referenced_functions: BTreeSet::new(),
+            exports: vec![],
+            data: vec![],
+            anonymous_functions: 0,
+            unsupported: None,
+        }
+    }
+
+    fn module_function(&mut self, function: &TypedFunction) -> Option<Wat> {
+        let (_, name) = function.name.as_ref()?;
+        if !function.implementations.supports(Target::Wasm) {
+            return None;
+        }
+
+        if function.publicity.is_importable() {
+            self.exports.push(name.clone());
+        }
+
+        if let Some((module, external_name, _)) = &function.external_wasm {
+            let mut signature = vec![Wat::atom("func"), Wat::atom(eco_format!("${name}"))];
+            signature.extend(function.arguments.iter().map(|_| param(EQREF)));
+            signature.push(result(EQREF));
+            self.external_imports.push(Wat::list([
+                Wat::atom("import"),
+                Wat::atom(wat::string(module.as_bytes())),
+                Wat::atom(wat::string(external_name.as_bytes())),
+                Wat::List(signature),
+            ]));
+            return None;
+        }
+
+        if function.body.is_empty() {
+            return None;
+        }
+
+        let mut generator = FunctionGenerator::new(self);
+        let parameters = function
+            .arguments
+            .iter()
+            .map(|argument| generator.parameter(argument))
+            .collect();
+        let body = generator.statements(&function.body);
+        Some(generator.finish(eco_format!("${name}"), None, parameters, body))
+    }
+
+    /// The name to use to call a Gleam function, importing it if it's defined
+    /// in another module.
+    ///
+    fn function_name(&mut self, module: &EcoString, name: &EcoString, arity: usize) -> EcoString {
+        if *module == self.module.name {
+            eco_format!("${name}")
+        } else {
+            let _ = self
+                .function_imports
+                .insert((module.clone(), name.clone()), arity);
+            eco_format!("${module}.{name}")
+        }
+    }
+
+    fn runtime(&mut self, function: Runtime, arguments: impl IntoIterator<Item = Wat>) -> Wat {
+        let _ = self.runtime.insert(function);
+        let name = eco_format!("$gleam.{}", function.name());
+        Wat::call("call", std::iter::once(Wat::atom(name)).chain(arguments))
+    }
+
+    fn string(&mut self, value: &EcoString) -> Wat {
+        let value = crate::strings::convert_string_escape_chars(value);
+        let segment = eco_format!("$string.{}", self.data.len());
+        self.data.push(Wat::list([
+            Wat::atom("data"),
+            Wat::atom(segment.clone()),
+            Wat::atom(wat::string(value.as_bytes())),
+        ]));
+
+        Wat::call(
+            "struct.new",
+            [
+                Wat::atom("$String"),
+                Wat::call(
+                    "array.new_data",
+                    [
+                        Wat::atom("$Bytes"),
+                        Wat::atom(segment),
+                        i32_const(0),
+                        i32_const(value.len()),
+                    ],
+                ),
+            ],
+        )
+    }
+
+    fn closure(&mut self, function: EcoString, captured: Vec<Wat>) -> Wat {
+        let _ = self.referenced_functions.insert(function.clone());
+        let length = captured.len();
+        let mut values = vec![Wat::atom("$Values"), Wat::atom(eco_format!("{length}"))];
+        values.extend(captured);
+
+        Wat::call(
+            "struct.new",
+            [
+                Wat::atom("$Closure"),
+                Wat::instruction(["ref.func", function.as_str()]),
+                Wat::call("array.new_fixed", values),
+            ],
+        )
+    }
+
+    /// A module function used as a value, wrapped in a closure that captures
+    /// nothing.
+    ///
+    fn function_value(&mut self, module: &EcoString, name: &EcoString, arity: usize) -> Wat {
+        let function = self.function_name(module, name, arity);
+        let wrapper = eco_format!("{function}.closure");
+        if !self.referenced_functions.contains(&wrapper) {
+            let call = Wat::call(
+                "call",
+                std::iter::once(Wat::atom(function)).chain(arguments(arity)),
+            );
+            self.helpers.push(wrapper_function(&wrapper, arity, call));
+            let _ = self.closure_arities.insert(arity);
+        }
+        self.closure(wrapper, vec![])
+    }
+
+    /// A record constructor used as a value, wrapped in a closure that
+    /// captures nothing.
+    ///
+    fn constructor_value(
+        &mut self,
+        module: &EcoString,
+        name: &EcoString,
+        tag: u16,
+        arity: usize,
+    ) -> Wat {
+        let wrapper = eco_format!("${module}.{name}.constructor");
+        if !self.referenced_functions.contains(&wrapper) {
+            let record = record(tag, arguments(arity).collect());
+            self.helpers.push(wrapper_function(&wrapper, arity, record));
+            let _ = self.closure_arities.insert(arity);
+        }
+        self.closure(wrapper, vec![])
+    }
+
+    fn record_value(
+        &mut self,
+        type_: &Type,
+        module: &EcoString,
+        name: &EcoString,
+        tag: u16,
+        arity: u16,
+    ) -> Wat {
+        if type_.is_bool() {
+            bool_value(name == "True")
+        } else if type_.is_nil() {
+            nil()
+        } else if arity == 0 {
+            record(tag, vec![])
+        } else {
+            self.constructor_value(module, name, tag, arity.into())
+        }
+    }
+
+    fn mark_unsupported(&mut self, location: SrcSpan, feature: &str) -> Wat {
+        if self.unsupported.is_none() {
+            self.unsupported = Some(Unsupported {
+                location,
+                feature: feature.into(),
+            });
+        }
+        Wat::instruction(["unreachable"])
+    }
+
+    fn into_module(self, functions: Vec<Wat>) -> Wat {
+        let mut fields = vec![
+            Wat::atom("module"),
+            Wat::atom(eco_format!("${}", self.module.name)),
+        ];
+        fields.extend(
+            TYPES
+                .iter()
+                .map(|&(name, definition)| Wat::instruction(["type", name, definition])),
+        );
+        fields.extend(
+            self.closure_arities
+                .iter()
+                .map(|arity| closure_type(*arity)),
+        );
+
+        for function in &self.runtime {
+            let (parameters, result_type) = function.signature();
+            let mut signature = vec![
+                Wat::atom("func"),
+                Wat::atom(eco_format!("$gleam.{}", function.name())),
+            ];
+            signature.extend(parameters.iter().map(|type_| param(type_)));
+            signature.push(result(result_type));
+            fields.push(Wat::list([
+                Wat::atom("import"),
+                Wat::atom(wat::string(b"gleam")),
+                Wat::atom(wat::string(function.name().as_bytes())),
+                Wat::List(signature),
+            ]));
+        }
+
+        for ((module, name), arity) in &self.function_imports {
+            let mut signature = vec![
+                Wat::atom("func"),
+                Wat::atom(eco_format!("${module}.{name}")),
+            ];
+            signature.extend((0..*arity).map(|_| param(EQREF)));
+            signature.push(result(EQREF));
+            fields.push(Wat::list([
+                Wat::atom("import"),
+                Wat::atom(wat::string(module.as_bytes())),
+                Wat::atom(wat::string(name.as_bytes())),
+                Wat::List(signature),
+            ]));
+        }
+
+        fields.extend(self.external_imports);
+        fields.extend(functions);
+        fields.extend(self.helpers);
+        fields.extend(self.exports.iter().map(|name| {
+            Wat::list([
+                Wat::atom("export"),
+                Wat::atom(wat::string(name.as_bytes())),
+                Wat::instruction(["func", eco_format!("${name}").as_str()]),
+            ])
+        }));
+
+        if !self.referenced_functions.is_empty() {
+            let mut declaration = vec![Wat::atom("elem"), Wat::atom("declare"), Wat::atom("func")];
+            declaration.extend(self.referenced_functions.into_iter().map(Wat::atom));
+            fields.push(Wat::List(declaration));
+        }
+
+        fields.extend(self.data);
+        Wat::List(fields)
+    }
+}
+
+/// The state used while generating the body of a single function: its locals
+/// and the variables in scope.
+///
+struct FunctionGenerator<'generator, 'module> {
+    generator: &'generator mut Generator<'module>,
+    /// The local holding the current value of each Gleam variable in scope.
+    scope: HashMap<EcoString, EcoString>,
+    /// How many times each name has been used for a parameter, local or
+    /// label, so that each gets a unique name.
+    names: HashMap<EcoString, usize>,
+    locals: Vec<(EcoString, &'static str)>,
+    /// When compiling the alternatives of a clause the variables bound by each
+    /// alternative must be stored in the same locals.
+    reuse_bindings: bool,
+}
+
+/// A step needed to match a pattern: either a condition that must hold for
+/// the pattern to match, or the binding of a variable.
+///
+enum Step {
+    Check(Wat),
+    Bind(Wat),
+}
+
+impl<'generator, 'module> FunctionGenerator<'generator, 'module> {
+    fn new(generator: &'generator mut Generator<'module>) -> Self {
+        Self {
+            generator,
+            scope: HashMap::new(),
+            names: HashMap::new(),
+            locals: vec![],
+            reuse_bindings: false,
+        }
+    }
+
+    fn finish(
+        self,
+        name: EcoString,
+        closure_arity: Option<usize>,
+        parameters: Vec<Wat>,
+        body: Vec<Wat>,
+    ) -> Wat {
+        let mut function = vec![Wat::atom("func"), Wat::atom(name)];
+        if let Some(arity) = closure_arity {
+            function.push(Wat::instruction([
+                "type",
+                eco_format!("$Fn{arity}").as_str(),
+            ]));
+        }
+        function.extend(parameters);
+        function.push(result(EQREF));
+        function.extend(
+            self.locals
+                .into_iter()
+                .map(|(name, type_)| Wat::instruction(["local", name.as_str(), type_])),
+        );
+        function.extend(body);
+        Wat::List(function)
+    }
+
+    fn unique_name(&mut self, name: &str) -> EcoString {
+        let count = self.names.entry(name.into()).or_insert(0);
+        let unique = if *count == 0 {
+            eco_format!("${name}")
+        } else {
+            eco_format!("${name}.{count}")
+        };
+        *count += 1;
+        unique
+    }
+
+    fn local(&mut self, name: &str, type_: &'static str) -> EcoString {
+        let local = self.unique_name(name);
+        self.locals.push((local.clone(), type_));
+        local
+    }
+
+    fn bind_variable(&mut self, name: &EcoString) -> EcoString {
+        if self.reuse_bindings
+            && let Some(local) = self.scope.get(name)
+        {
+            return local.clone();
+        }
+        let local = self.local(name, EQREF);
+        let _ = self.scope.insert(name.clone(), local.clone());
+        local
+    }
+
+    fn parameter(&mut self, argument: &TypedArg) -> Wat {
+        match argument.get_variable_name() {
+            Some(name) => {
+                let local = self.unique_name(name);
+                let _ = self.scope.insert(name.clone(), local.clone());
+                Wat::instruction(["param", local.as_str(), EQREF])
+            }
+            None => param(EQREF),
+        }
+    }
+
+    /// Compiles a sequence of statements, leaving the value of the last one
+    /// on the stack.
+    ///
+    fn statements(&mut self, statements: &[TypedStatement]) -> Vec<Wat> {
+        let mut output = vec![];
+        let last = statements.len().saturating_sub(1);
+        for (index, statement) in statements.iter().enumerate() {
+            let value = match statement {
+                Statement::Expression(expression) => Some(self.expression(expression)),
+                Statement::Use(use_) => Some(self.expression(&use_.call)),
+                Statement::Assignment(assignment) => {
+                    let local = self.assignment(assignment, &mut output);
+                    (index == last).then(|| local_get(&local))
+                }
+                Statement::Assert(assert) => {
+                    let condition = bool_to_i32(self.expression(&assert.value));
+                    let mut failure = vec![];
+                    if let Some(message) = &assert.message {
+                        failure.push(drop(self.expression(message)));
+                    }
+                    failure.push(Wat::instruction(["unreachable"]));
+                    output.push(Wat::call(
+                        "if",
+                        [
+                            Wat::call("i32.eqz", [condition]),
+                            Wat::call("then", failure),
+                        ],
+                    ));
+                    (index == last).then(nil)
+                }
+            };
+
+            match value {
+                Some(value) if index == last => output.push(value),
+                Some(value) => output.push(drop(value)),
+                None => (),
+            }
+        }
+        output
+    }
+
+    /// Compiles an assignment, returning the local holding the assigned value.
+    ///
+    fn assignment(&mut self, assignment: &TypedAssignment, output: &mut Vec<Wat>) -> EcoString {
+        let value = self.expression(&assignment.value);
+        if let Pattern::Variable { name, .. } = &assignment.pattern {
+            let local = self.bind_variable(name);
+            output.push(local_set(&local, value));
+            return local;
+        }
+
+        let subject = self.local("subject", EQREF);
+        output.push(local_set(&subject, value));
+        let mut steps = vec![];
+        self.pattern(&assignment.pattern, local_get(&subject), &mut steps);
+
+        if steps.iter().all(|step| matches!(step, Step::Bind(_))) {
+            output.extend(checked(steps, ""));
+            return subject;
+        }
+
+        let label = self.unique_name("assignment");
+        let failed = eco_format!("{label}.failed");
+        let mut matched = checked(steps, &failed);
+        matched.push(Wat::instruction(["br", label.as_str()]));
+
+        let mut body = vec![block(&failed, matched)];
+        if let AssignmentKind::Assert {
+            message: Some(message),
+            ..
+        } = &assignment.kind
+        {
+            body.push(drop(self.expression(message)));
+        }
+        body.push(Wat::instruction(["unreachable"]));
+        output.push(block(&label, body));
+        subject
+    }
+
+    fn expression(&mut self, expression: &TypedExpr) -> Wat {
+        match expression {
+            TypedExpr::Int { int_value, .. } => box_int(i64_const(int_value)),
+            TypedExpr::Float { float_value, .. } => box_float(f64_const(float_value.value())),
+            TypedExpr::String { value, .. } => self.generator.string(value),
+
+            TypedExpr::Block { statements, .. } => self.block(statements),
+
+            TypedExpr::Pipeline {
+                first_value,
+                assignments,
+                finally,
+                ..
+            } => self.pipeline(first_value, assignments.iter().map(|(a, _)| a), finally),
+
+            TypedExpr::Var {
+                constructor, name, ..
+            } => self.variable(name, constructor),
+
+            TypedExpr::Fn {
+                arguments, body, ..
+            } => self.anonymous_function(arguments, body),
+
+            TypedExpr::List { elements, tail, .. } => {
+                let tail = match tail {
+                    Some(tail) => self.expression(tail),
+                    None => empty_list(),
+                };
+                let elements: Vec<_> = elements.iter().map(|e| self.expression(e)).collect();
+                list(elements, tail)
+            }
+
+            TypedExpr::Call { fun, arguments, .. } => self.call(fun, arguments),
+
+            TypedExpr::BinOp {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let left = self.expression(left);
+                let right = self.expression(right);
+                self.binary_operator(*operator, left, right)
+            }
+
+            TypedExpr::Case {
+                subjects, clauses, ..
+            } => self.case(subjects, clauses),
+
+            TypedExpr::RecordAccess { index, record, .. }
+            | TypedExpr::PositionalAccess { index, record, .. } => {
+                let record = self.expression(record);
+                record_field(record, *index)
+            }
+
+            TypedExpr::ModuleSelect {
+                type_,
+                module_name,
+                constructor,
+                ..
+            } => match constructor {
+                ModuleValueConstructor::Fn { module, name, .. } => {
+                    let arity = type_.fn_arity().unwrap_or_default();
+                    self.generator.function_value(module, name, arity)
+                }
+                ModuleValueConstructor::Record {
+                    name,
+                    variant_index,
+                    arity,
+                    type_,
+                    ..
+                } => self
+                    .generator
+                    .record_value(type_, module_name, name, *variant_index, *arity),
+                ModuleValueConstructor::Constant { literal, .. } => self.constant(literal),
+            },
+
+            TypedExpr::Tuple { elements, .. } => {
+                let elements = elements.iter().map(|e| self.expression(e)).collect();
+                tuple(elements)
+            }
+
+            TypedExpr::TupleIndex { index, tuple, .. } => {
+                let tuple = self.expression(tuple);
+                tuple_element(tuple, *index)
+            }
+
+            TypedExpr::Todo { message, .. } | TypedExpr::Panic { message, .. } => {
+                self.crash(message.as_deref())
+            }
+
+            TypedExpr::Echo { location, .. } => {
+                self.generator.mark_unsupported(*location, "`echo`")
+            }
+
+            TypedExpr::BitArray { location, .. } => {
+                self.generator.mark_unsupported(*location, "bit arrays")
+            }
+
+            TypedExpr::RecordUpdate {
+                updated_record,
+                updated_record_assigned_name,
+                constructor,
+                arguments,
+                ..
+            } => {
+                let mut body = vec![];
+                if let Some(name) = updated_record_assigned_name {
+                    let record = self.expression(updated_record);
+                    let local = self.bind_variable(name);
+                    body.push(local_set(&local, record));
+                }
+                body.push(self.call(constructor, arguments));
+                value_block(None, body)
+            }
+
+            TypedExpr::NegateBool { value, .. } => {
+                let value = bool_to_i32(self.expression(value));
+                i32_to_bool(Wat::call("i32.eqz", [value]))
+            }
+
+            TypedExpr::NegateInt { value, .. } => {
+                let value = unbox_int(self.expression(value));
+                box_int(Wat::call(
+                    "i64.sub",
+                    [Wat::instruction(["i64.const", "0"]), value],
+                ))
+            }
+
+            TypedExpr::Invalid { .. } => {
+                panic!("invalid expressions should not reach code generation")
+            }
+        }
+    }
+
+    fn block(&mut self, statements: &Vec1<TypedStatement>) -> Wat {
+        let scope = self.scope.clone();
+        let body = self.statements(statements);
+        self.scope = scope;
+        value_block(None, body)
+    }
+
+    fn pipeline<'a>(
+        &mut self,
+        first_value: &'a TypedPipelineAssignment,
+        assignments: impl Iterator<Item = &'a TypedPipelineAssignment>,
+        finally: &TypedExpr,
+    ) -> Wat {
+        let scope = self.scope.clone();
+        let mut body = vec![];
+        for assignment in std::iter::once(first_value).chain(assignments) {
+            let value = self.expression(&assignment.value);
+            let local = self.bind_variable(&assignment.name);
+            body.push(local_set(&local, value));
+        }
+        body.push(self.expression(finally));
+        self.scope = scope;
+        value_block(None, body)
+    }
+
+    fn variable(&mut self, name: &EcoString, constructor: &ValueConstructor) -> Wat {
+        match &constructor.variant {
+            ValueConstructorVariant::LocalVariable { .. } => match self.scope.get(name) {
+                Some(local) => local_get(local),
+                None => panic!("variable `{name}` is not in scope"),
+            },
+            ValueConstructorVariant::ModuleConstant { literal, .. } => self.constant(literal),
+            ValueConstructorVariant::ModuleFn {
+                module,
+                name,
+                arity,
+                ..
+            } => self.generator.function_value(module, name, *arity),
+            ValueConstructorVariant::Record {
+                name,
+                module,
+                variant_index,
+                arity,
+                ..
+            } => self.generator.record_value(
+                &constructor.type_,
+                module,
+                name,
+                *variant_index,
+                *arity,
+            ),
+        }
+    }
+
+    fn call(&mut self, fun: &TypedExpr, arguments: &[CallArg<TypedExpr>]) -> Wat {
+        // Functions and constructors that are called directly don't need to be
+        // turned into closures first.
+        let direct = if let TypedExpr::Var { constructor, .. } = fun {
+            match &constructor.variant {
+                ValueConstructorVariant::ModuleFn { module, name, .. } => {
+                    Some(Callee::Function(module, name))
+                }
+                ValueConstructorVariant::Record { variant_index, .. } => {
+                    Some(Callee::Record(*variant_index))
+                }
+                ValueConstructorVariant::LocalVariable { .. }
+                | ValueConstructorVariant::ModuleConstant { .. } => None,
+            }
+        } else if let TypedExpr::ModuleSelect { constructor, .. } = fun {
+            match constructor {
+                ModuleValueConstructor::Fn { module, name, .. } => {
+                    Some(Callee::Function(module, name))
+                }
+                ModuleValueConstructor::Record { variant_index, .. } => {
+                    Some(Callee::Record(*variant_index))
+                }
+                ModuleValueConstructor::Constant { .. } => None,
+            }
+        } else {
+            None
+        };
+
+        match direct {
+            Some(Callee::Function(module, name)) => {
+                let arguments: Vec<_> = arguments
+                    .iter()
+                    .map(|a| self.expression(&a.value))
+                    .collect();
+                let function = self.generator.function_name(module, name, arguments.len());
+                Wat::call(
+                    "call",
+                    std::iter::once(Wat::atom(function)).chain(arguments),
+                )
+            }
+            Some(Callee::Record(tag)) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|a| self.expression(&a.value))
+                    .collect();
+                record(tag, arguments)
+            }
+            None => {
+                let fun = self.expression(fun);
+                let arguments = arguments
+                    .iter()
+                    .map(|a| self.expression(&a.value))
+                    .collect();
+                self.closure_call(fun, arguments)
+            }
+        }
+    }
+
+    fn closure_call(&mut self, fun: Wat, arguments: Vec<Wat>) -> Wat {
+        let arity = arguments.len();
+        let _ = self.generator.closure_arities.insert(arity);
+        let closure = self.local("closure", CLOSURE);
+        let function_type = eco_format!("$Fn{arity}");
+
+        let mut operands = vec![
+            Wat::atom(function_type.clone()),
+            Wat::call(
+                "local.tee",
+                [
+                    Wat::atom(closure.clone()),
+                    Wat::call("ref.cast", [Wat::atom("(ref $Closure)"), fun]),
+                ],
+            ),
+        ];
+        operands.extend(arguments);
+        operands.push(Wat::call(
+            "ref.cast",
+            [
+                Wat::atom(eco_format!("(ref {function_type})")),
+                Wat::call(
+                    "struct.get",
+                    [Wat::atom("$Closure"), Wat::atom("0"), local_get(&closure)],
+                ),
+            ],
+        ));
+        Wat::call("call_ref", operands)
+    }
+
+    /// Anonymous functions are lifted to module functions, and the variables
+    /// they use from the enclosing scope are stored in their closure.
+    ///
+    fn anonymous_function(&mut self, arguments: &[TypedArg], body: &[TypedStatement]) -> Wat {
+        let mut captured = CapturedVariables {
+            scope: &self.scope,
+            names: vec![],
+        };
+        for statement in body {
+            captured.visit_typed_statement(statement);
+        }
+        let mut captured = captured.names;
+        captured.retain(|name| {
+            !arguments
+                .iter()
+                .any(|argument| argument.get_variable_name() == Some(name))
+        });
+        let captured_values = captured
+            .iter()
+            .filter_map(|name| self.scope.get(name).map(|local| local_get(local)))
+            .collect();
+
+        self.generator.anonymous_functions += 1;
+        let name = eco_format!("$anonymous.{}", self.generator.anonymous_functions);
+        let arity = arguments.len();
+
+        let mut generator = FunctionGenerator::new(self.generator);
+        let closure = generator.unique_name("closure");
+        let mut parameters = vec![Wat::instruction(["param", closure.as_str(), CLOSURE])];
+        parameters.extend(
+            arguments
+                .iter()
+                .map(|argument| generator.parameter(argument)),
+        );
+
+        let mut function_body = vec![];
+        for (index, variable) in captured.iter().enumerate() {
+            let local = generator.bind_variable(variable);
+            let environment = Wat::call(
+                "struct.get",
+                [Wat::atom("$Closure"), Wat::atom("1"), local_get(&closure)],
+            );
+            function_body.push(local_set(
+                &local,
+                Wat::call(
+                    "array.get",
+                    [Wat::atom("$Values"), environment, i32_const(index)],
+                ),
+            ));
+        }
+        function_body.extend(generator.statements(body));
+        let function = generator.finish(name.clone(), Some(arity), parameters, function_body);
+
+        self.generator.helpers.push(function);
+        let _ = self.generator.closure_arities.insert(arity);
+        self.generator.closure(name, captured_values)
+    }
+
+    fn crash(&mut self, message: Option<&TypedExpr>) -> Wat {
+        let mut body = vec![];
+        if let Some(message) = message {
+            body.push(drop(self.expression(message)));
+        }
+        body.push(Wat::instruction(["unreachable"]));
+        value_block(None, body)
+    }
+
+    fn binary_operator(&mut self, operator: BinOp, left: Wat, right: Wat) -> Wat {
+        match operator {
+            BinOp::And => if_else(bool_to_i32(left), right, bool_value(false)),
+            BinOp::Or => if_else(bool_to_i32(left), bool_value(true), right),
+
+            BinOp::Eq => i32_to_bool(self.generator.runtime(Runtime::Equal, [left, right])),
+            BinOp::NotEq => {
+                let equal = self.generator.runtime(Runtime::Equal, [left, right]);
+                i32_to_bool(Wat::call("i32.eqz", [equal]))
+            }
+
+            BinOp::LtInt => i32_to_bool(int_operation("i64.lt_s", left, right)),
+            BinOp::LtEqInt => i32_to_bool(int_operation("i64.le_s", left, right)),
+            BinOp::GtInt => i32_to_bool(int_operation("i64.gt_s", left, right)),
+            BinOp::GtEqInt => i32_to_bool(int_operation("i64.ge_s", left, right)),
+            BinOp::LtFloat => i32_to_bool(float_operation("f64.lt", left, right)),
+            BinOp::LtEqFloat => i32_to_bool(float_operation("f64.le", left, right)),
+            BinOp::GtFloat => i32_to_bool(float_operation("f64.gt", left, right)),
+            BinOp::GtEqFloat => i32_to_bool(float_operation("f64.ge", left, right)),
+
+            BinOp::AddInt => box_int(int_operation("i64.add", left, right)),
+            BinOp::SubInt => box_int(int_operation("i64.sub", left, right)),
+            BinOp::MultInt => box_int(int_operation("i64.mul", left, right)),
+            BinOp::DivInt => {
+                let operands = [unbox_int(left), unbox_int(right)];
+                box_int(self.generator.runtime(Runtime::DivideInt, operands))
+            }
+            BinOp::RemainderInt => {
+                let operands = [unbox_int(left), unbox_int(right)];
+                box_int(self.generator.runtime(Runtime::RemainderInt, operands))
+            }
+
+            BinOp::AddFloat => box_float(float_operation("f64.add", left, right)),
+            BinOp::SubFloat => box_float(float_operation("f64.sub", left, right)),
+            BinOp::MultFloat => box_float(float_operation("f64.mul", left, right)),
+            BinOp::DivFloat => {
+                let operands = [unbox_float(left), unbox_float(right)];
+                box_float(self.generator.runtime(Runtime::DivideFloat, operands))
+            }
+
+            BinOp::Concatenate => self.generator.runtime(Runtime::StringConcat, [left, right]),
+        }
+    }
+
+    fn constant(&mut self, constant: &TypedConstant) -> Wat {
+        match constant {
+            Constant::Int { int_value, .. } => box_int(i64_const(int_value)),
+            Constant::Float { float_value, .. } => box_float(f64_const(float_value.value())),
+            Constant::String { value, .. } => self.generator.string(value),
+
+            Constant::Tuple { elements, .. } => {
+                let elements = elements.iter().map(|e| self.constant(e)).collect();
+                tuple(elements)
+            }
+
+            Constant::List { elements, tail, .. } => {
+                let tail = match tail {
+                    Some(tail) => self.constant(tail),
+                    None => empty_list(),
+                };
+                let elements: Vec<_> = elements.iter().map(|e| self.constant(e)).collect();
+                list(elements, tail)
+            }
+
+            Constant::Record {
+                name,
+                arguments,
+                type_,
+                record_constructor,
+                ..
+            } => {
+                let Some(ValueConstructor {
+                    variant:
+                        ValueConstructorVariant::Record {
+                            module,
+                            variant_index,
+                            arity,
+                            ..
+                        },
+                    ..
+                }) = record_constructor.as_deref()
+                else {
+                    panic!("record constant without a record constructor")
+                };
+
+                match arguments {
+                    Some(arguments) if !type_.is_bool() && !type_.is_nil() => {
+                        let arguments = arguments.iter().map(|a| self.constant(&a.value)).collect();
+                        record(*variant_index, arguments)
+                    }
+                    Some(_) | None => {
+                        let arity = if arguments.is_some() { 0 } else { *arity };
+                        self.generator
+                            .record_value(type_, module, name, *variant_index, arity)
+                    }
+                }
+            }
+
+            Constant::Var {
+                name, constructor, ..
+            } => match constructor {
+                Some(constructor) => self.variable(name, constructor),
+                None => panic!("constant variable `{name}` without a constructor"),
+            },
+
+            Constant::BinaryOperator {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let left = self.constant(left);
+                let right = self.constant(right);
+                self.binary_operator(*operator, left, right)
+            }
+
+            Constant::BitArray { location, .. } => {
+                self.generator.mark_unsupported(*location, "bit arrays")
+            }
+
+            Constant::RecordUpdate { .. } => {
+                panic!("record updates should not reach code generation")
+            }
+            Constant::Todo { .. } => {
+                panic!("todo constants should not reach code generation")
+            }
+            Constant::Invalid { .. } => {
+                panic!("invalid constants should not reach code generation")
+            }
+        }
+    }
+
+    /// Compiles a `case` expression, checking each clause in turn.
+    ///
+    fn case(&mut self, subjects: &[TypedExpr], clauses: &[TypedClause]) -> Wat {
+        let mut body = vec![];
+        let subjects: Vec<_> = subjects
+            .iter()
+            .map(|subject| {
+                let value = self.expression(subject);
+                let local = self.local("subject", EQREF);
+                body.push(local_set(&local, value));
+                local
+            })
+            .collect();
+
+        let label = self.unique_name("case");
+        for clause in clauses {
+            let scope = self.scope.clone();
+            let clause_label = self.unique_name("clause");
+            let patterns: Vec<_> = clause.patterns().collect();
+
+            let mut clause_body = if let [pattern] = patterns.as_slice() {
+                let steps = self.multi_pattern(pattern, &subjects);
+                checked(steps, &clause_label)
+            } else {
+                let matched = eco_format!("{clause_label}.matched");
+                let mut alternatives = vec![];
+                for (index, pattern) in patterns.iter().enumerate() {
+                    self.reuse_bindings = index > 0;
+                    let alternative = eco_format!("{clause_label}.{index}");
+                    let steps = self.multi_pattern(pattern, &subjects);
+                    let mut matching = checked(steps, &alternative);
+                    matching.push(Wat::instruction(["br", matched.as_str()]));
+                    alternatives.push(block(&alternative, matching));
+                }
+                self.reuse_bindings = false;
+                alternatives.push(Wat::instruction(["br", clause_label.as_str()]));
+                vec![block(&matched, alternatives)]
+            };
+
+            if let Some(guard) = &clause.guard {
+                let condition = bool_to_i32(self.guard(guard));
+                clause_body.push(fail_unless(&clause_label, condition));
+            }
+
+            let then = self.expression(&clause.then);
+            clause_body.push(Wat::call("br", [Wat::atom(label.clone()), then]));
+            body.push(block(&clause_label, clause_body));
+            self.scope = scope;
+        }
+
+        body.push(Wat::instruction(["unreachable"]));
+        value_block(Some(&label), body)
+    }
+
+    fn multi_pattern(&mut self, patterns: &[TypedPattern], subjects: &[EcoString]) -> Vec<Step> {
+        let mut steps = vec![];
+        for (pattern, subject) in patterns.iter().zip(subjects) {
+            self.pattern(pattern, local_get(subject), &mut steps);
+        }
+        steps
+    }
+
+    /// Adds the steps needed to match the given value against a pattern. The
+    /// subject is an expression without side effects, so it can be used
+    /// multiple times.
+    ///
+    fn pattern(&mut self, pattern: &TypedPattern, subject: Wat, steps: &mut Vec<Step>) {
+        match pattern {
+            Pattern::Int { int_value, .. } => {
+                let value = i64_const(int_value);
+                steps.push(Step::Check(Wat::call(
+                    "i64.eq",
+                    [unbox_int(subject), value],
+                )));
+            }
+
+            Pattern::Float { float_value, .. } => {
+                let value = f64_const(float_value.value());
+                steps.push(Step::Check(Wat::call(
+                    "f64.eq",
+                    [unbox_float(subject), value],
+                )));
+            }
+
+            Pattern::String { value, .. } => {
+                let value = self.generator.string(value);
+                let equal = self.generator.runtime(Runtime::Equal, [subject, value]);
+                steps.push(Step::Check(equal));
+            }
+
+            Pattern::Variable { name, .. } => {
+                let local = self.bind_variable(name);
+                steps.push(Step::Bind(local_set(&local, subject)));
+            }
+
+            Pattern::Assign { name, pattern, .. } => {
+                let local = self.bind_variable(name);
+                steps.push(Step::Bind(local_set(&local, subject.clone())));
+                self.pattern(pattern, subject, steps);
+            }
+
+            Pattern::Discard { .. } => (),
+
+            Pattern::List { elements, tail, .. } => {
+                let mut list = subject;
+                for element in elements {
+                    let is_empty = Wat::call("ref.is_null", [list.clone()]);
+                    steps.push(Step::Check(Wat::call("i32.eqz", [is_empty])));
+                    self.pattern(element, list_field(list.clone(), 0), steps);
+                    list = list_field(list, 1);
+                }
+                match tail {
+                    Some(tail) => self.pattern(&tail.pattern, list, steps),
+                    None => steps.push(Step::Check(Wat::call("ref.is_null", [list]))),
+                }
+            }
+
+            Pattern::Constructor {
+                arguments,
+                constructor,
+                type_,
+                ..
+            } => {
+                let Inferred::Known(constructor) = constructor else {
+                    panic!("unknown pattern constructors should not reach code generation")
+                };
+                if type_.is_bool() {
+                    let value = bool_to_i32(subject);
+                    steps.push(Step::Check(if constructor.name == "True" {
+                        value
+                    } else {
+                        Wat::call("i32.eqz", [value])
+                    }));
+                } else if !type_.is_nil() {
+                    let tag = Wat::call(
+                        "struct.get",
+                        [
+                            Wat::atom("$Record"),
+                            Wat::atom("0"),
+                            cast("$Record", subject.clone()),
+                        ],
+                    );
+                    let expected = i32_const(constructor.constructor_index);
+                    steps.push(Step::Check(Wat::call("i32.eq", [tag, expected])));
+                    for (index, argument) in arguments.iter().enumerate() {
+                        let field = record_field(subject.clone(), index as u64);
+                        self.pattern(&argument.value, field, steps);
+                    }
+                }
+            }
+
+            Pattern::Tuple { elements, .. } => {
+                for (index, element) in elements.iter().enumerate() {
+                    let value = tuple_element(subject.clone(), index as u64);
+                    self.pattern(element, value, steps);
+                }
+            }
+
+            Pattern::StringPrefix {
+                left_side_string,
+                left_side_assignment,
+                right_side_assignment,
+                ..
+            } => {
+                let prefix = self.generator.string(left_side_string);
+                let starts_with = self
+                    .generator
+                    .runtime(Runtime::StringStartsWith, [subject.clone(), prefix.clone()]);
+                steps.push(Step::Check(starts_with));
+
+                if let Some((name, _)) = left_side_assignment {
+                    let local = self.bind_variable(name);
+                    steps.push(Step::Bind(local_set(&local, prefix)));
+                }
+                if let AssignName::Variable(name) = right_side_assignment {
+                    let length =
+                        crate::strings::convert_string_escape_chars(left_side_string).len();
+                    let rest = self
+                        .generator
+                        .runtime(Runtime::StringDropStart, [subject, i32_const(length)]);
+                    let local = self.bind_variable(name);
+                    steps.push(Step::Bind(local_set(&local, rest)));
+                }
+            }
+
+            Pattern::BitArray { location, .. } => {
+                let _ = self.generator.mark_unsupported(*location, "bit arrays");
+            }
+            Pattern::BitArraySize(size) => {
+                let _ = self
+                    .generator
+                    .mark_unsupported(size.location(), "bit arrays");
+            }
+
+            Pattern::Invalid { .. } => {
+                panic!("invalid patterns should not reach code generation")
+            }
+        }
+    }
+
+    fn guard(&mut self, guard: &TypedClauseGuard) -> Wat {
+        match guard {
+            ClauseGuard::Block { value, .. } => self.guard(value),
+            ClauseGuard::BinaryOperator {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let left = self.guard(left);
+                let right = self.guard(right);
+                self.binary_operator(*operator, left, right)
+            }
+            ClauseGuard::Not { expression, .. } => {
+                let value = bool_to_i32(self.guard(expression));
+                i32_to_bool(Wat::call("i32.eqz", [value]))
+            }
+            ClauseGuard::Var { name, .. } => match self.scope.get(name) {
+                Some(local) => local_get(local),
+                None => panic!("variable `{name}` is not in scope"),
+            },
+            ClauseGuard::TupleIndex { index, tuple, .. } => {
+                let tuple = self.guard(tuple);
+                tuple_element(tuple, *index)
+            }
+            ClauseGuard::FieldAccess {
+                index, container, ..
+            } => {
+                let container = self.guard(container);
+                record_field(container, index.expect("field index should be known"))
+            }
+            ClauseGuard::ModuleSelect { literal, .. } | ClauseGuard::Constant(literal) => {
+                self.constant(literal)
+            }
+            ClauseGuard::Invalid { .. } => {
+                panic!("invalid guards should not reach code generation")
+            }
+        }
+    }
+}
+
+enum Callee<'a> {
+    Function(&'a EcoString, &'a EcoString),
+    Record(u16),
+}
+
+/// Finds the variables of the enclosing scope used by an anonymous function.
+///
+struct CapturedVariables<'a> {
+    scope: &'a HashMap<EcoString, EcoString>,
+    names: Vec<EcoString>,
+}
+
+impl CapturedVariables<'_> {
+    fn capture(&mut self, name: &EcoString) {
+        if self.scope.contains_key(name) && !self.names.contains(name) {
+            self.names.push(name.clone());
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for CapturedVariables<'_> {
+    fn visit_typed_expr_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        name: &'ast EcoString,
+    ) {
+        if constructor.is_local_variable() {
+            self.capture(name);
+        }
+    }
+
+    fn visit_typed_clause_guard_var(
+        &mut self,
+        _location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        _type_: &'ast Arc<Type>,
+        _definition_location: &'ast SrcSpan,
+        _origin: &'ast VariableOrigin,
+    ) {
+        self.capture(name);
+    }
+}
+
+/// Turns the steps of a pattern into instructions, branching to the given
+/// label if any of the checks fails.
+///
+fn checked(steps: Vec<Step>, fail: &str) -> Vec<Wat> {
+    steps
+        .into_iter()
+        .map(|step| match step {
+            Step::Check(condition) => fail_unless(fail, condition),
+            Step::Bind(binding) => binding,
+        })
+        .collect()
+}
+
+fn fail_unless(label: &str, condition: Wat) -> Wat {
+    let failed = match condition {
+        // Rather than negating a negated condition we can use it as it is.
+        Wat::List(mut elements)
+            if elements.len() == 2 && elements.first() == Some(&Wat::atom("i32.eqz")) =>
+        {
+            elements.remove(1)
+        }
+        condition @ (Wat::Atom(_) | Wat::List(_)) => Wat::call("i32.eqz", [condition]),
+    };
+    Wat::call("br_if", [Wat::atom(label), failed])
+}
+
+/// A function that can be stored in a closure, passing its arguments on to
+/// the given body.
+///
+fn wrapper_function(name: &str, arity: usize, body: Wat) -> Wat {
+    let mut function = vec![
+        Wat::atom("func"),
+        Wat::atom(name),
+        Wat::instruction(["type", eco_format!("$Fn{arity}").as_str()]),
+        param(CLOSURE),
+    ];
+    function.extend((0..arity).map(|_| param(EQREF)));
+    function.push(result(EQREF));
+    function.push(body);
+    Wat::List(function)
+}
+
+/// The arguments of a wrapper function, which come after its closure.
+///
+fn arguments(arity: usize) -> impl Iterator<Item = Wat> {
+    (1..=arity).map(|index| Wat::instruction(["local.get", eco_format!("{index}").as_str()]))
+}
+
+fn closure_type(arity: usize) -> Wat {
+    let mut function = vec![Wat::atom("func"), param(CLOSURE)];
+    function.extend((0..arity).map(|_| param(EQREF)));
+    function.push(result(EQREF));
+    Wat::list([
+        Wat::atom("type"),
+        Wat::atom(eco_format!("$Fn{arity}")),
+        Wat::List(function),
+    ])
+}
+
+fn param(type_: &str) -> Wat {
+    Wat::instruction(["param", type_])
+}
+
+fn result(type_: &str) -> Wat {
+    Wat::instruction(["result", type_])
+}
+
+fn block(label: &str, body: Vec<Wat>) -> Wat {
+    Wat::call("block", std::iter::once(Wat::atom(label)).chain(body))
+}
+
+fn value_block(label: Option<&str>, body: Vec<Wat>) -> Wat {
+    let mut elements: Vec<_> = label.map(Wat::atom).into_iter().collect();
+    elements.push(result(EQREF));
+    elements.extend(body);
+    Wat::call("block", elements)
+}
+
+fn if_else(condition: Wat, then: Wat, else_: Wat) -> Wat {
+    Wat::call(
+        "if",
+        [
+            result(EQREF),
+            condition,
+            Wat::call("then", [then]),
+            Wat::call("else", [else_]),
+        ],
+    )
+}
+
+fn local_get(name: &str) -> Wat {
+    Wat::instruction(["local.get", name])
+}
+
+fn local_set(name: &str, value: Wat) -> Wat {
+    Wat::call("local.set", [Wat::atom(name), value])
+}
+
+fn drop(value: Wat) -> Wat {
+    Wat::call("drop", [value])
+}
+
+fn cast(type_: &str, value: Wat) -> Wat {
+    Wat::call("ref.cast", [Wat::atom(eco_format!("(ref {type_})")), value])
+}
+
+fn i32_const(value: impl Display) -> Wat {
+    Wat::instruction(["i32.const", eco_format!("{value}").as_str()])
+}
+
+/// Int literals are truncated to 64 bits, wrapping around like arithmetic
+/// does.
+///
+fn i64_const(value: &BigInt) -> Wat {
+    let value = (value & BigInt::from(u64::MAX))
+        .to_u64()
+        .expect("value should fit in 64 bits") as i64;
+    Wat::instruction(["i64.const", eco_format!("{value}").as_str()])
+}
+
+fn f64_const(value: f64) -> Wat {
+    Wat::instruction(["f64.const", eco_format!("{value:?}").as_str()])
+}
+
+/// Applies an instruction to the unboxed values of two Ints.
+///
+fn int_operation(instruction: &str, left: Wat, right: Wat) -> Wat {
+    Wat::call(instruction, [unbox_int(left), unbox_int(right)])
+}
+
+/// Applies an instruction to the unboxed values of two Floats.
+///
+fn float_operation(instruction: &str, left: Wat, right: Wat) -> Wat {
+    Wat::call(instruction, [unbox_float(left), unbox_float(right)])
+}
+
+fn box_int(value: Wat) -> Wat {
+    Wat::call("struct.new", [Wat::atom("$Int"), value])
+}
+
+fn unbox_int(value: Wat) -> Wat {
+    Wat::call(
+        "struct.get",
+        [Wat::atom("$Int"), Wat::atom("0"), cast("$Int", value)],
+    )
+}
+
+fn box_float(value: Wat) -> Wat {
+    Wat::call("struct.new", [Wat::atom("$Float"), value])
+}
+
+fn unbox_float(value: Wat) -> Wat {
+    Wat::call(
+        "struct.get",
+        [Wat::atom("$Float"), Wat::atom("0"), cast("$Float", value)],
+    )
+}
+
+fn bool_value(value: bool) -> Wat {
+    i32_to_bool(i32_const(u8::from(value)))
+}
+
+fn i32_to_bool(value: Wat) -> Wat {
+    Wat::call("ref.i31", [value])
+}
+
+fn bool_to_i32(value: Wat) -> Wat {
+    Wat::call("i31.get_u", [cast("i31", value)])
+}
+
+fn nil() -> Wat {
+    bool_value(false)
+}
+
+fn tuple(elements: Vec<Wat>) -> Wat {
+    Wat::call("struct.new", [Wat::atom("$Tuple"), values(elements)])
+}
+
+fn tuple_element(tuple: Wat, index: u64) -> Wat {
+    let elements = Wat::call(
+        "struct.get",
+        [Wat::atom("$Tuple"), Wat::atom("0"), cast("$Tuple", tuple)],
+    );
+    Wat::call(
+        "array.get",
+        [Wat::atom("$Values"), elements, i32_const(index)],
+    )
+}
+
+fn record(tag: u16, fields: Vec<Wat>) -> Wat {
+    Wat::call(
+        "struct.new",
+        [Wat::atom("$Record"), i32_const(tag), values(fields)],
+    )
+}
+
+fn record_field(record: Wat, index: u64) -> Wat {
+    let fields = Wat::call(
+        "struct.get",
+        [
+            Wat::atom("$Record"),
+            Wat::atom("1"),
+            cast("$Record", record),
+        ],
+    );
+    Wat::call(
+        "array.get",
+        [Wat::atom("$Values"), fields, i32_const(index)],
+    )
+}
+
+fn values(values: Vec<Wat>) -> Wat {
+    let length = values.len();
+    Wat::call(
+        "array.new_fixed",
+        [Wat::atom("$Values"), Wat::atom(eco_format!("{length}"))]
+            .into_iter()
+            .chain(values),
+    )
+}
+
+fn empty_list() -> Wat {
+    Wat::instruction(["ref.null", "eq"])
+}
+
+fn list(elements: Vec<Wat>, tail: Wat) -> Wat {
+    elements.into_iter().rev().fold(tail, |tail, element| {
+        Wat::call("struct.new", [Wat::atom("$List"), element, tail])
+    })
+}
+
+fn list_field(list: Wat, index: u8) -> Wat {
+    Wat::call(
+        "struct.get",
+        [
+            Wat::atom("$List"),
+            Wat::atom(eco_format!("{index}")),
+            cast("$List", list),
+        ],
+    )
+}
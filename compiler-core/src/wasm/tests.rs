@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use std::collections::{HashMap, HashSet};
+
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+use src_span::LineNumbers;
+
+use crate::{
+    analyse::TargetSupport,
+    ast::TypedModule,
+    build::{Origin, Target},
+    config::PackageConfig,
+    inline,
+    type_::PRELUDE_MODULE_NAME,
+    uid::UniqueIdGenerator,
+    warning::{TypeWarningEmitter, WarningEmitter},
+};
+
+#[macro_export]
+macro_rules! assert_wasm {
+    ($(($name:literal, $module_src:literal)),+, $src:literal $(,)?) => {
+        let compiled =
+            $crate::wasm::tests::compile_wasm($src, vec![$(($name, $module_src)),*]);
+        let mut output = String::from("----- SOURCE CODE\n");
+        for (name, src) in [$(($name, $module_src)),*] {
+            output.push_str("-- ");
+            output.push_str(name);
+            output.push_str(".gleam\n");
+            output.push_str(src);
+            output.push_str("\n\n");
+        }
+        output.push_str("-- main.gleam\n");
+        output.push_str($src);
+        output.push_str("\n\n----- COMPILED WEBASSEMBLY\n");
+        output.push_str(&compiled);
+
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+
+    ($src:expr $(,)?) => {{
+        let compiled = $crate::wasm::tests::compile_wasm($src, vec![]);
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED WEBASSEMBLY\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+}
+
+pub fn compile(src: &str, deps: Vec<(&str, &str)>) -> TypedModule {
+    let mut modules = im::HashMap::new();
+    let ids = UniqueIdGenerator::new();
+    let _ = modules.insert(
+        PRELUDE_MODULE_NAME.into(),
+        crate::type_::build_prelude(&ids),
+    );
+    let mut config = PackageConfig::default();
+    config.name = "thepackage".into();
+
+    for (name, src) in deps {
+        let parsed = crate::parse::parse_module(
+            Utf8PathBuf::from("test/path"),
+            src,
+            &WarningEmitter::null(),
+        )
+        .expect("dep syntax error");
+        let mut ast = parsed.module;
+        ast.name = name.into();
+
+        let dep = crate::analyse::ModuleAnalyzerConstructor::<()> {
+            target: Target::Wasm,
+            ids: &ids,
+            origin: Origin::Src,
+            importable_modules: &modules,
+            warnings: &TypeWarningEmitter::null(),
+            direct_dependencies: &HashMap::new(),
+            dev_dependencies: &HashSet::new(),
+            target_support: TargetSupport::Enforced,
+            package_config: &config,
+        }
+        .infer_module(ast, LineNumbers::new(src), "".into())
+        .expect("should successfully infer");
+        let _ = modules.insert(name.into(), dep.type_info);
+    }
+
+    let parsed =
+        crate::parse::parse_module(Utf8PathBuf::from("test/path"), src, &WarningEmitter::null())
+            .expect("syntax error");
+    let mut ast = parsed.module;
+    ast.name = "my/mod".into();
+
+    let module = crate::analyse::ModuleAnalyzerConstructor::<()> {
+        target: Target::Wasm,
+        ids: &ids,
+        origin: Origin::Src,
+        importable_modules: &modules,
+        warnings: &TypeWarningEmitter::null(),
+        direct_dependencies: &HashMap::new(),
+        dev_dependencies: &HashSet::new(),
+        target_support: TargetSupport::Enforced,
+        package_config: &config,
+    }
+    .infer_module(ast, LineNumbers::new(src), "src/module.gleam".into())
+    .expect("should successfully infer");
+
+    inline::module(module, &modules)
+}
+
+pub fn compile_wasm(src: &str, deps: Vec<(&str, &str)>) -> String {
+    super::module(&compile(src, deps)).expect("should be supported")
+}
+
+/// Checks that the WebAssembly text is well formed and that the module it
+/// describes passes validation.
+///
+#[track_caller]
+fn assert_valid(wat: &str) {
+    let binary = wat::parse_str(wat).expect("should be well formed");
+    let _ = wasmparser::Validator::new()
+        .validate_all(&binary)
+        .expect("should be valid");
+}
+
+#[test]
+fn prelude_is_valid() {
+    assert_valid(super::PRELUDE);
+}
+
+#[test]
+fn generated_modules_are_valid() {
+    let programs = [
+        "pub fn add(a, b) { a + b }",
+        r#"
+@external(wasm, "env", "log")
+pub fn log(message: String) -> Nil
+
+pub fn main() {
+  log("Hello")
+}
+"#,
+        r#"
+pub fn add(x) {
+  fn(y) { x + y }
+}
+"#,
+        r#"
+pub fn first(list) {
+  case list {
+    [x, ..] -> x
+    [] -> 0
+  }
+}
+"#,
+        r#"
+pub type Pet {
+  Cat(name: String)
+  Dog(name: String)
+}
+
+pub fn new() {
+  Dog("Fido")
+}
+
+pub fn name(pet: Pet) {
+  pet.name
+}
+"#,
+        r#"
+pub fn compare(a, b) {
+  #(a == b, a <. b, [a] == [b])
+}
+
+pub fn greet(name) {
+  "Hello, " <> name
+}
+"#,
+    ];
+
+    for program in programs {
+        assert_valid(&compile_wasm(program, vec![]));
+    }
+    assert_valid(&compile_wasm(
+        "import wibble pub fn main() { wibble.wobble() }",
+        vec![("wibble", "pub fn wobble() { 1 }")],
+    ));
+}
+
+#[test]
+fn prelude_defines_the_shared_types() {
+    for (name, definition) in super::TYPES {
+        let type_ = format!("(type {name} {definition})");
+        assert!(super::PRELUDE.contains(&type_), "missing {type_}");
+    }
+}
+
+#[test]
+fn int_arithmetic() {
+    assert_wasm!(
+        r#"
+pub fn add(a, b) {
+  a + b
+}
+"#
+    );
+}
+
+#[test]
+fn external_function() {
+    assert_wasm!(
+        r#"
+@external(wasm, "env", "log")
+pub fn log(message: String) -> Nil
+
+pub fn main() {
+  log("Hello")
+}
+"#
+    );
+}
+
+#[test]
+fn imported_function() {
+    assert_wasm!(
+        ("wibble", "pub fn wobble() { 1 }"),
+        r#"
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+"#
+    );
+}
+
+#[test]
+fn anonymous_function_capturing_a_variable() {
+    assert_wasm!(
+        r#"
+pub fn add(x) {
+  fn(y) { x + y }
+}
+"#
+    );
+}
+
+#[test]
+fn case_on_a_list() {
+    assert_wasm!(
+        r#"
+pub fn first(list) {
+  case list {
+    [x, ..] -> x
+    [] -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn custom_type() {
+    assert_wasm!(
+        r#"
+pub type Pet {
+  Cat(name: String)
+  Dog(name: String)
+}
+
+pub fn new() {
+  Dog("Fido")
+}
+
+pub fn name(pet: Pet) {
+  pet.name
+}
+"#
+    );
+}
+
+#[test]
+fn bit_arrays_are_not_supported() {
+    let module = compile("pub fn main() { <<1>> }", vec![]);
+    let result = super::module(&module).map_err(|unsupported| unsupported.feature);
+    assert_eq!(result, Err(EcoString::from("bit arrays")));
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! A tiny representation of the WebAssembly text format, used to build the
+//! generated modules and print them with a readable layout.
+
+use std::fmt::Write;
+
+use ecow::EcoString;
+
+const MAX_WIDTH: usize = 80;
+const INDENT: usize = 2;
+
+/// An S-expression of the WebAssembly text format. Instructions are always
+/// written in their folded form, so an expression is either an atom such as
+/// `i64.add`, `$wibble` or `"string"`, or a parenthesised list of expressions.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Wat {
+    Atom(EcoString),
+    List(Vec<Wat>),
+}
+
+impl Wat {
+    pub fn atom(atom: impl Into<EcoString>) -> Self {
+        Wat::Atom(atom.into())
+    }
+
+    pub fn list(elements: impl IntoIterator<Item = Wat>) -> Self {
+        Wat::List(elements.into_iter().collect())
+    }
+
+    /// A list starting with the given atoms, for example `(local.get $x)`.
+    ///
+    pub fn instruction<'a>(atoms: impl IntoIterator<Item = &'a str>) -> Self {
+        Wat::List(atoms.into_iter().map(Wat::atom).collect())
+    }
+
+    /// A list starting with an instruction name and followed by the given
+    /// operands, for example `(i64.add (local.get $x) (local.get $y))`.
+    ///
+    pub fn call(name: &str, operands: impl IntoIterator<Item = Wat>) -> Self {
+        let mut elements = vec![Wat::atom(name)];
+        elements.extend(operands);
+        Wat::List(elements)
+    }
+
+    pub fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        self.print(0, &mut output);
+        output
+    }
+
+    fn print(&self, indent: usize, output: &mut String) {
+        let elements = match self {
+            Wat::Atom(atom) => {
+                output.push_str(atom);
+                return;
+            }
+            Wat::List(elements) => elements,
+        };
+
+        let flat = self.flat();
+        if indent + flat.len() <= MAX_WIDTH || elements.iter().all(|e| matches!(e, Wat::Atom(_))) {
+            output.push_str(&flat);
+            return;
+        }
+
+        // The leading atoms stay on the first line, all the nested lists are
+        // printed on their own lines:
+        //
+        //     (func $wibble
+        //       (param $x eqref)
+        //       (result eqref)
+        //       ...)
+        //
+        output.push('(');
+        let mut first = true;
+        let mut breaking = false;
+        for element in elements {
+            if matches!(element, Wat::List(_)) {
+                breaking = true;
+            }
+            if breaking {
+                output.push('\n');
+                output.push_str(&" ".repeat(indent + INDENT));
+                element.print(indent + INDENT, output);
+            } else {
+                if !first {
+                    output.push(' ');
+                }
+                element.print(indent, output);
+            }
+            first = false;
+        }
+        output.push(')');
+    }
+
+    fn flat(&self) -> String {
+        match self {
+            Wat::Atom(atom) => atom.to_string(),
+            Wat::List(elements) => {
+                let elements: Vec<_> = elements.iter().map(Wat::flat).collect();
+                format!("({})", elements.join(" "))
+            }
+        }
+    }
+}
+
+/// Escapes a sequence of bytes so it can be used in a string of the text
+/// format.
+///
+pub fn string(bytes: &[u8]) -> EcoString {
+    let mut escaped = String::with_capacity(bytes.len() + 2);
+    escaped.push('"');
+    for byte in bytes {
+        match byte {
+            b'"' => escaped.push_str("\\\""),
+            b'\\' => escaped.push_str("\\\\"),
+            0x20..=0x7e => escaped.push(char::from(*byte)),
+            _ => {
+                let _ = write!(escaped, "\\{byte:02x}");
+            }
+        }
+    }
+    escaped.push('"');
+    escaped.into()
+}
@@ -1,5 +1,6 @@
 -module('{{ application }}@@main').
--export([run/1, main/1]).
+-export([run/1, run_with_coverage/3, main/1, halt/0, halt/1, halt/2]).
+-compile({no_auto_import, [halt/0, halt/1, halt/2]}).
 
 -define(red, "\e[31;1m").
 -define(grey, "\e[90m").
@@ -8,33 +9,132 @@
 
 %%% Used by `gleam run`
 run(Module) ->
+    run(Module, fun init:stop/1).
+
+%%% Used by `gleam test --coverage`
+%%% The given modules are instrumented by `cover` before running the program,
+%%% and once it's over the number of times each of their lines has been
+%%% executed is written to the output file.
+run_with_coverage(Module, CoveredModules, Output) ->
+    {ok, _} = cover:start(),
+    _ = cover:compile_beam(CoveredModules),
+    persistent_term:put({?MODULE, coverage}, {CoveredModules, Output}),
+    redirect_halts(CoveredModules),
+    run(Module, fun halt/1).
+
+run(Module, Stop) ->
     io:setopts(standard_io, [binary, {encoding, utf8}]),
     io:setopts(standard_error, [{encoding, utf8}]),
     process_flag(trap_exit, true),
-    Pid = spawn_link(fun() -> run_module(Module) end),
+    Pid = spawn_link(fun() -> run_module(Module, Stop) end),
     receive
         {'EXIT', Pid, {Reason, [First|_] = StackTrace}} when is_tuple(First) ->
             print_error_with_stacktrace(exit, Reason, StackTrace),
-            init:stop(1);
+            Stop(1);
         {'EXIT', Pid, Reason} when Reason =/= normal ->
             print_error(exit, Reason),
-            init:stop(1)
+            Stop(1)
     end.
 
 %%% Used by escripts
 main(_) ->
     run('{{ application }}').
 
-run_module(Module) ->
+run_module(Module, Stop) ->
     try
         {ok, _} = application:ensure_all_started('{{ application }}'),
         erlang:process_flag(trap_exit, false),
         Module:main(),
-        init:stop(0)
+        Stop(0)
     catch
         Class:Reason:StackTrace ->
             print_error_with_stacktrace(Class, Reason, StackTrace),
-            init:stop(1)
+            Stop(1)
+    end.
+
+%%% Test runners usually halt the virtual machine once they're done, which
+%%% would lose the coverage collected so far. So the modules calling
+%%% `erlang:halt` are recompiled to call the `halt` functions of this module
+%%% instead, writing the coverage before halting.
+redirect_halts(CoveredModules) ->
+    Root = code:root_dir(),
+    Directories = [D || D <- code:get_path(), not lists:prefix(Root, D)],
+    Beams = lists:append([filelib:wildcard(filename:join(D, "*.beam")) || D <- Directories]),
+    lists:foreach(
+        fun(Beam) ->
+            Module = list_to_atom(filename:basename(Beam, ".beam")),
+            case Module =:= ?MODULE orelse lists:member(Module, CoveredModules) of
+                true -> ok;
+                false -> redirect_halts_in(Beam)
+            end
+        end,
+        Beams
+    ).
+
+redirect_halts_in(Beam) ->
+    case beam_lib:chunks(Beam, [imports]) of
+        {ok, {Module, [{imports, Imports}]}} ->
+            case lists:keymember(halt, 2, [I || {erlang, _, _} = I <- Imports]) of
+                true -> recompile_without_halts(Module, Beam);
+                false -> ok
+            end;
+        _ ->
+            ok
+    end.
+
+recompile_without_halts(Module, Beam) ->
+    case beam_lib:chunks(Beam, [abstract_code]) of
+        {ok, {Module, [{abstract_code, {raw_abstract_v1, Forms}}]}} ->
+            % Local calls to `halt` are only calls to the BIF if the module
+            % doesn't define a function with the same name.
+            Defined = [A || {function, _, halt, A, _} <- Forms],
+            Redirected = redirect_halt_calls(Forms, Defined),
+            case compile:forms(Redirected, [binary, return_errors]) of
+                {ok, Module, Binary} -> code:load_binary(Module, Beam, Binary);
+                _ -> ok
+            end;
+        _ ->
+            ok
+    end.
+
+redirect_halt_calls({remote, Anno, {atom, _, erlang}, {atom, _, halt} = Name}, _) ->
+    {remote, Anno, {atom, Anno, ?MODULE}, Name};
+redirect_halt_calls({function, {atom, Anno, erlang}, {atom, _, halt} = Name, Arity}, _) ->
+    {function, {atom, Anno, ?MODULE}, Name, Arity};
+redirect_halt_calls({call, Anno, {atom, _, halt} = Name, Arguments}, Defined) ->
+    Called = case lists:member(length(Arguments), Defined) of
+        true -> Name;
+        false -> {remote, Anno, {atom, Anno, ?MODULE}, Name}
+    end,
+    {call, Anno, Called, redirect_halt_calls(Arguments, Defined)};
+redirect_halt_calls(Tuple, Defined) when is_tuple(Tuple) ->
+    list_to_tuple(redirect_halt_calls(tuple_to_list(Tuple), Defined));
+redirect_halt_calls([Head | Tail], Defined) ->
+    [redirect_halt_calls(Head, Defined) | redirect_halt_calls(Tail, Defined)];
+redirect_halt_calls(Other, _) ->
+    Other.
+
+halt() ->
+    halt(0).
+
+halt(Status) ->
+    halt(Status, []).
+
+halt(Status, Options) ->
+    write_coverage(),
+    erlang:halt(Status, Options).
+
+write_coverage() ->
+    case persistent_term:get({?MODULE, coverage}, none) of
+        none ->
+            ok;
+        {CoveredModules, Output} ->
+            {result, Lines, _} = cover:analyse(CoveredModules, calls, line),
+            Analysis = [
+                io_lib:format("~s ~B ~B~n", [M, L, N])
+                || { {M, L}, N} <- Lines
+            ],
+            ok = file:write_file(Output, Analysis)
     end.
 
 print_error_with_stacktrace(Class, Error, Stacktrace) ->
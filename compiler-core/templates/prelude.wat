@@ -0,0 +1,173 @@
+;; The runtime used by Gleam modules compiled to WebAssembly. Every Gleam
+;; module imports the functions it needs from this module, which is
+;; instantiated with the name "gleam".
+;;
+;; The type definitions must be the same as the ones in the generated modules,
+;; so that values can be passed from one module to another.
+(module $gleam
+  (type $Int (struct (field i64)))
+  (type $Float (struct (field f64)))
+  (type $Bytes (array (mut i8)))
+  (type $String (struct (field (ref $Bytes))))
+  (type $Values (array (mut eqref)))
+  (type $Tuple (struct (field (ref $Values))))
+  (type $Record (struct (field i32) (field (ref $Values))))
+  (type $List (struct (field eqref) (field eqref)))
+  (type $Closure (struct (field funcref) (field (ref $Values))))
+
+  ;; Structural equality of two values of the same type.
+  (func $equal (export "equal") (param $a eqref) (param $b eqref) (result i32)
+    (loop $next
+      ;; Identical references, which includes `Nil`, `Bool`s and empty lists.
+      (if (ref.eq (local.get $a) (local.get $b))
+        (then (return (i32.const 1))))
+      (if (i32.or (ref.is_null (local.get $a)) (ref.is_null (local.get $b)))
+        (then (return (i32.const 0))))
+
+      ;; Lists are compared one element at a time, without recursing on their
+      ;; tail so long lists don't overflow the stack.
+      (if (ref.test (ref $List) (local.get $a))
+        (then
+          (if (i32.eqz
+                (call $equal
+                  (struct.get $List 0 (ref.cast (ref $List) (local.get $a)))
+                  (struct.get $List 0 (ref.cast (ref $List) (local.get $b)))))
+            (then (return (i32.const 0))))
+          (local.set $a (struct.get $List 1 (ref.cast (ref $List) (local.get $a))))
+          (local.set $b (struct.get $List 1 (ref.cast (ref $List) (local.get $b))))
+          (br $next))))
+
+    (if (ref.test (ref $Int) (local.get $a))
+      (then
+        (return
+          (i64.eq
+            (struct.get $Int 0 (ref.cast (ref $Int) (local.get $a)))
+            (struct.get $Int 0 (ref.cast (ref $Int) (local.get $b)))))))
+    (if (ref.test (ref $Float) (local.get $a))
+      (then
+        (return
+          (f64.eq
+            (struct.get $Float 0 (ref.cast (ref $Float) (local.get $a)))
+            (struct.get $Float 0 (ref.cast (ref $Float) (local.get $b)))))))
+    (if (ref.test (ref $String) (local.get $a))
+      (then
+        (return
+          (call $bytes_equal
+            (struct.get $String 0 (ref.cast (ref $String) (local.get $a)))
+            (struct.get $String 0 (ref.cast (ref $String) (local.get $b)))))))
+    (if (ref.test (ref $Tuple) (local.get $a))
+      (then
+        (return
+          (call $values_equal
+            (struct.get $Tuple 0 (ref.cast (ref $Tuple) (local.get $a)))
+            (struct.get $Tuple 0 (ref.cast (ref $Tuple) (local.get $b)))))))
+    (if (ref.test (ref $Record) (local.get $a))
+      (then
+        (if (i32.ne
+              (struct.get $Record 0 (ref.cast (ref $Record) (local.get $a)))
+              (struct.get $Record 0 (ref.cast (ref $Record) (local.get $b))))
+          (then (return (i32.const 0))))
+        (return
+          (call $values_equal
+            (struct.get $Record 1 (ref.cast (ref $Record) (local.get $a)))
+            (struct.get $Record 1 (ref.cast (ref $Record) (local.get $b)))))))
+
+    ;; Functions are only equal to themselves.
+    (i32.const 0))
+
+  (func $values_equal (param $a (ref $Values)) (param $b (ref $Values)) (result i32)
+    (local $index i32)
+    (if (i32.ne (array.len (local.get $a)) (array.len (local.get $b)))
+      (then (return (i32.const 0))))
+    (block $done
+      (loop $next
+        (br_if $done (i32.ge_u (local.get $index) (array.len (local.get $a))))
+        (if (i32.eqz
+              (call $equal
+                (array.get $Values (local.get $a) (local.get $index))
+                (array.get $Values (local.get $b) (local.get $index))))
+          (then (return (i32.const 0))))
+        (local.set $index (i32.add (local.get $index) (i32.const 1)))
+        (br $next)))
+    (i32.const 1))
+
+  (func $bytes_equal (param $a (ref $Bytes)) (param $b (ref $Bytes)) (result i32)
+    (if (i32.ne (array.len (local.get $a)) (array.len (local.get $b)))
+      (then (return (i32.const 0))))
+    (call $bytes_match (local.get $a) (local.get $b) (array.len (local.get $b))))
+
+  ;; Whether the first `length` bytes of the two arrays are the same.
+  (func $bytes_match (param $a (ref $Bytes)) (param $b (ref $Bytes)) (param $length i32) (result i32)
+    (local $index i32)
+    (block $done
+      (loop $next
+        (br_if $done (i32.ge_u (local.get $index) (local.get $length)))
+        (if (i32.ne
+              (array.get_u $Bytes (local.get $a) (local.get $index))
+              (array.get_u $Bytes (local.get $b) (local.get $index)))
+          (then (return (i32.const 0))))
+        (local.set $index (i32.add (local.get $index) (i32.const 1)))
+        (br $next)))
+    (i32.const 1))
+
+  (func $string_concat (export "string_concat") (param $a eqref) (param $b eqref) (result eqref)
+    (local $left (ref null $Bytes))
+    (local $right (ref null $Bytes))
+    (local $result (ref null $Bytes))
+    (local.set $left (struct.get $String 0 (ref.cast (ref $String) (local.get $a))))
+    (local.set $right (struct.get $String 0 (ref.cast (ref $String) (local.get $b))))
+    (local.set $result
+      (array.new_default $Bytes
+        (i32.add (array.len (local.get $left)) (array.len (local.get $right)))))
+    (array.copy $Bytes $Bytes
+      (local.get $result) (i32.const 0)
+      (local.get $left) (i32.const 0) (array.len (local.get $left)))
+    (array.copy $Bytes $Bytes
+      (local.get $result) (array.len (local.get $left))
+      (local.get $right) (i32.const 0) (array.len (local.get $right)))
+    (struct.new $String (ref.as_non_null (local.get $result))))
+
+  (func $string_starts_with (export "string_starts_with") (param $string eqref) (param $prefix eqref) (result i32)
+    (local $bytes (ref null $Bytes))
+    (local $prefix_bytes (ref null $Bytes))
+    (local.set $bytes (struct.get $String 0 (ref.cast (ref $String) (local.get $string))))
+    (local.set $prefix_bytes (struct.get $String 0 (ref.cast (ref $String) (local.get $prefix))))
+    (if (i32.lt_u (array.len (local.get $bytes)) (array.len (local.get $prefix_bytes)))
+      (then (return (i32.const 0))))
+    (call $bytes_match
+      (ref.as_non_null (local.get $bytes))
+      (ref.as_non_null (local.get $prefix_bytes))
+      (array.len (local.get $prefix_bytes))))
+
+  ;; The string without its first `count` bytes.
+  (func $string_drop_start (export "string_drop_start") (param $string eqref) (param $count i32) (result eqref)
+    (local $bytes (ref null $Bytes))
+    (local $result (ref null $Bytes))
+    (local.set $bytes (struct.get $String 0 (ref.cast (ref $String) (local.get $string))))
+    (local.set $result
+      (array.new_default $Bytes (i32.sub (array.len (local.get $bytes)) (local.get $count))))
+    (array.copy $Bytes $Bytes
+      (local.get $result) (i32.const 0)
+      (local.get $bytes) (local.get $count) (array.len (local.get $result)))
+    (struct.new $String (ref.as_non_null (local.get $result))))
+
+  ;; Ints are 64 bit integers that wrap on overflow. Dividing by zero returns
+  ;; zero, like on the other targets.
+  (func $divide_int (export "divide_int") (param $a i64) (param $b i64) (result i64)
+    (if (i64.eqz (local.get $b))
+      (then (return (i64.const 0))))
+    ;; `i64.div_s` traps when the result overflows, which only happens when
+    ;; dividing the smallest Int by -1.
+    (if (i64.eq (local.get $b) (i64.const -1))
+      (then (return (i64.sub (i64.const 0) (local.get $a)))))
+    (i64.div_s (local.get $a) (local.get $b)))
+
+  (func $remainder_int (export "remainder_int") (param $a i64) (param $b i64) (result i64)
+    (if (i64.eqz (local.get $b))
+      (then (return (i64.const 0))))
+    (i64.rem_s (local.get $a) (local.get $b)))
+
+  (func $divide_float (export "divide_float") (param $a f64) (param $b f64) (result f64)
+    (if (f64.eq (local.get $b) (f64.const 0))
+      (then (return (f64.const 0))))
+    (f64.div (local.get $a) (local.get $b))))
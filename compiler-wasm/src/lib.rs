@@ -10,7 +10,8 @@ use gleam_core::{
     Error,
     analyse::TargetSupport,
     build::{
-        Mode, NullTelemetry, PackageCompiler, StaleTracker, Target, TargetCodegenConfiguration,
+        Mode, ModuleFormat, NullTelemetry, PackageCompiler, StaleTracker, Target,
+        TargetCodegenConfiguration,
     },
     config::PackageConfig,
     io::{FileSystemReader, FileSystemWriter},
@@ -119,8 +120,10 @@ pub fn compile_package(project_id: usize, target: &str) -> Result<(), String> {
     let target = match target.to_lowercase().as_str() {
         "erl" | "erlang" => Target::Erlang,
         "js" | "javascript" => Target::JavaScript,
+        "wasm" => Target::Wasm,
         _ => {
-            let msg = format!("Unknown target `{target}`, expected `erlang` or `javascript`");
+            let msg =
+                format!("Unknown target `{target}`, expected `erlang`, `javascript` or `wasm`");
             return Err(msg);
         }
     };
@@ -195,8 +198,10 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
         Target::JavaScript => TargetCodegenConfiguration::JavaScript {
             emit_typescript_definitions: false,
             emit_source_maps: false,
+            module_format: ModuleFormat::Esm,
             prelude_location: Utf8PathBuf::from("./gleam_prelude.mjs"),
         },
+        Target::Wasm => TargetCodegenConfiguration::Wasm,
     };
 
     tracing::info!("Compiling package");
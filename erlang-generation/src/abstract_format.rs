@@ -0,0 +1,1047 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! An `ErlangBuilder` producing the Erlang Abstract Format representation of a
+//! module, encoded in the Erlang Term Format.
+//!
+//! The resulting binary can be decoded with `erlang:binary_to_term/1` and the
+//! list of forms it holds can be compiled directly with `compile:forms/2`.
+//! This skips producing Erlang source code only to have the Erlang compiler
+//! parse it back again.
+//!
+//! Since the Erlang Term Format is a prefix encoding, each term being
+//! immediately followed by its items, this maps quite nicely to the way the
+//! `ErlangBuilder` API is designed: most of the time we can write the beginning
+//! of a term and let the following calls generate its items.
+//! The only thing we need to keep track of is the number of items in lists,
+//! which is only written once the list is over.
+//!
+//! Each form is described here:
+//! https://www.erlang.org/doc/apps/erts/absform.html
+//!
+
+#[cfg(test)]
+mod tests;
+
+use ecow::EcoString;
+use erlang_term_format::{ListEnder, TermBuilder};
+use num_bigint::BigInt;
+use src_span::{LineNumbers, SrcSpan};
+
+use crate::{BitArraySegmentSpecifier, DocContent, ErlangBuilder, ErlangModuleName};
+
+/// A structure that implements the `ErlangBuilder` trait and produces the
+/// binary representation of a module's forms, ready to be compiled by
+/// `compile:forms/2`.
+#[derive(Debug)]
+pub struct ErlangAbstractFormatBuilder {
+    etf: TermBuilder,
+    /// This keeps track of the terms that are still open and waiting for more
+    /// items to be generated.
+    open_terms: Vec<OpenTerm>,
+    /// The line used to annotate the generated forms. This is updated by the
+    /// latest `-file` attribute, and by every expression or pattern that has a
+    /// location in the Gleam source file. Items with no location of their own
+    /// are annotated with the line of the item that comes before them.
+    line: u32,
+    /// The line numbers of the Gleam source file, used to turn the location of
+    /// each expression into the line it's annotated with.
+    line_numbers: Option<LineNumbers>,
+}
+
+/// A term that has been started, and is waiting for more items to be
+/// generated before it is over.
+#[derive(Debug)]
+enum OpenTerm {
+    /// A list of items whose length is not known in advance. Each item
+    /// generated inside it increases its count, and it's closed explicitly by
+    /// one of the `end_*` functions.
+    List { list: ListEnder, items: u32 },
+
+    /// A term that is closed explicitly by one of the `end_*` functions.
+    /// Any item generated inside it doesn't need to be counted: for example
+    /// the subject of a case expression.
+    Term,
+
+    /// A term that is automatically closed once a given number of items have
+    /// been generated. For example a binary operator is over once its
+    /// left-hand side and right-hand side are generated.
+    Operands { remaining: u8, kind: OperandsKind },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperandsKind {
+    /// A bit array segment: the value of the segment, its size and its
+    /// specifiers.
+    BitArraySegment,
+    /// Any other term that has a fixed number of items.
+    Other,
+}
+
+impl ErlangBuilder<Vec<u8>> for ErlangAbstractFormatBuilder {
+    type BitArray = ();
+    type BitArrayPattern = ();
+    type Block = ();
+    type CalledExpression = ();
+    type Call = ();
+    type Case = ();
+    type Guard = ();
+    type CaseSubject = ();
+    type ClauseBody = ();
+    type ClauseGuards = ();
+    type ClausePattern = ();
+    type Function = ();
+    type FunctionSpec = ();
+    type FunctionType = ();
+    type FunctionTypeArguments = ();
+    type Map = ();
+    type NamedType = ();
+    type RemoteNamedType = ();
+    type RecordAttribute = ();
+    type Tuple = ();
+    type TuplePattern = ();
+    type TupleType = ();
+    type UnionType = ();
+    /// The type parameters are the last item of a type attribute, so they can
+    /// only be generated once the type definition is over.
+    type TypeSpec = Vec<EcoString>;
+
+    fn new(module_name: Option<ErlangModuleName>) -> Self {
+        let mut etf = TermBuilder::new();
+        // All the forms of the module are collected in a single list.
+        let list = etf.start_list();
+        let mut builder = Self {
+            etf,
+            open_terms: vec![OpenTerm::List { list, items: 0 }],
+            line: 1,
+            line_numbers: None,
+        };
+
+        if let Some(module_name) = module_name {
+            // {attribute, Line, module, Name}
+            builder.attribute("module");
+            builder.etf.atom(&module_name.0);
+            builder.item_generated();
+        }
+
+        builder
+    }
+
+    fn into_output(mut self) -> Vec<u8> {
+        // {eof, Line}
+        self.etf.small_tuple(2);
+        self.etf.atom("eof");
+        self.annotation();
+        self.item_generated();
+
+        self.end_list();
+        if !self.open_terms.is_empty() {
+            panic!("module forms are over but some terms are still open");
+        }
+        self.etf.into_vec()
+    }
+
+    fn export_attribute<Name: AsRef<str>>(
+        &mut self,
+        exported: impl IntoIterator<Item = (Name, usize)>,
+    ) {
+        // If there's no item in the iterator we don't add the attribute at all.
+        let mut exported = exported.into_iter().peekable();
+        if exported.peek().is_none() {
+            return;
+        }
+
+        // {attribute, Line, export, [{Name, Arity}]}
+        self.attribute("export");
+        self.function_names(exported);
+        self.item_generated();
+    }
+
+    fn export_type_attribute<Name: AsRef<str>>(
+        &mut self,
+        exported: impl IntoIterator<Item = (Name, usize)>,
+    ) {
+        let mut exported = exported.into_iter().peekable();
+        if exported.peek().is_none() {
+            return;
+        }
+
+        // {attribute, Line, export_type, [{Name, Arity}]}
+        self.attribute("export_type");
+        self.function_names(exported);
+        self.item_generated();
+    }
+
+    fn doc_attribute(&mut self, content: DocContent<'_>) {
+        // {attribute, Line, doc, Content}
+        self.attribute("doc");
+        self.doc_content(content);
+        self.item_generated();
+    }
+
+    fn moduledoc_attribute(&mut self, content: DocContent<'_>) {
+        // {attribute, Line, moduledoc, Content}
+        self.attribute("moduledoc");
+        self.doc_content(content);
+        self.item_generated();
+    }
+
+    fn compile_attribute<'a>(&mut self, arguments: impl IntoIterator<Item = &'a str>) {
+        // {attribute, Line, compile, [Option]}
+        self.attribute("compile");
+        let list = self.etf.start_list();
+        let mut length = 0;
+        for argument in arguments {
+            self.etf.atom(argument);
+            length += 1;
+        }
+        self.etf.end_list(list, length);
+        self.item_generated();
+    }
+
+    fn file_attribute(&mut self, file: &str, line: u32) {
+        self.line = line;
+        // {attribute, Line, file, {File, Line}}
+        self.attribute("file");
+        self.etf.small_tuple(2);
+        self.etf.charlist(&unescape_string_content(file));
+        self.etf.usize(line as usize);
+        self.item_generated();
+    }
+
+    fn source_line_numbers(&mut self, line_numbers: LineNumbers) {
+        self.line_numbers = Some(line_numbers);
+    }
+
+    fn start_record_attribute(&mut self, record_name: &str) -> Self::RecordAttribute {
+        // {attribute, Line, record, {Name, [Field]}}
+        self.attribute("record");
+        self.etf.small_tuple(2);
+        self.etf.atom(record_name);
+        self.start_list();
+    }
+
+    fn end_record_attribute(&mut self, _record: Self::RecordAttribute) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn record_field(&mut self) {
+        // {typed_record_field, {record_field, Line, Name}, Type}
+        self.etf.small_tuple(3);
+        self.etf.atom("typed_record_field");
+        self.tagged_tuple("record_field", 3);
+        self.operands(2);
+    }
+
+    fn start_function_spec(&mut self, name: &str, arity: usize) -> Self::FunctionSpec {
+        // {attribute, Line, spec, {{Name, Arity}, [FunctionType]}}
+        self.attribute("spec");
+        self.etf.small_tuple(2);
+        self.etf.small_tuple(2);
+        self.etf.atom(name);
+        self.etf.usize(arity);
+        self.start_list();
+    }
+
+    fn end_function_spec(&mut self, _function_spec: Self::FunctionSpec) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_type_spec<Name: AsRef<str>>(
+        &mut self,
+        opaque: bool,
+        name: &str,
+        type_parameters: impl IntoIterator<Item = Name>,
+    ) -> Self::TypeSpec {
+        // {attribute, Line, type, {Name, Type, [Variable]}}
+        self.attribute(if opaque { "opaque" } else { "type" });
+        self.etf.small_tuple(3);
+        self.etf.atom(name);
+        self.open_terms.push(OpenTerm::Term);
+        type_parameters
+            .into_iter()
+            .map(|name| name.as_ref().into())
+            .collect()
+    }
+
+    fn end_type_spec(&mut self, type_parameters: Self::TypeSpec) {
+        self.end_term();
+        let list = self.etf.start_list();
+        for type_parameter in &type_parameters {
+            self.tagged_tuple("var", 3);
+            self.etf.atom(type_parameter);
+        }
+        self.etf.end_list(list, type_parameters.len() as u32);
+        self.item_generated();
+    }
+
+    fn start_function_type(&mut self) -> Self::FunctionTypeArguments {
+        // {type, Line, 'fun', [{type, Line, product, [Argument]}, Return]}
+        self.tagged_tuple("type", 4);
+        self.etf.atom("fun");
+        self.start_list();
+        self.tagged_tuple("type", 4);
+        self.etf.atom("product");
+        self.start_list();
+    }
+
+    fn end_function_type_arguments(
+        &mut self,
+        _function_type: Self::FunctionTypeArguments,
+    ) -> Self::FunctionType {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn end_function_type(&mut self, _function_type: Self::FunctionType) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_named_type(&mut self, name: &str) -> Self::NamedType {
+        // {type, Line, Name, [Argument]} or {user_type, Line, Name, [Argument]}
+        if is_builtin_type(name) {
+            self.tagged_tuple("type", 4);
+        } else {
+            self.tagged_tuple("user_type", 4);
+        }
+        self.etf.atom(name);
+        self.start_list();
+    }
+
+    fn end_named_type(&mut self, _named_type: Self::NamedType) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_remote_named_type(
+        &mut self,
+        module: ErlangModuleName,
+        name: &str,
+    ) -> Self::RemoteNamedType {
+        // {remote_type, Line, [{atom, Line, Module}, {atom, Line, Name}, [Argument]]}
+        self.tagged_tuple("remote_type", 3);
+        let list = self.etf.start_list();
+        self.tagged_tuple("atom", 3);
+        self.etf.atom(&module.0);
+        self.tagged_tuple("atom", 3);
+        self.etf.atom(name);
+        self.open_terms.push(OpenTerm::List { list, items: 2 });
+        self.start_list();
+    }
+
+    fn end_remote_named_type(&mut self, _named_type: Self::RemoteNamedType) {
+        // We close the list of arguments and then the list holding the module,
+        // name, and arguments.
+        self.end_list();
+        self.item_generated();
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_tuple_type(&mut self) -> Self::TupleType {
+        // {type, Line, tuple, [Item]}
+        self.tagged_tuple("type", 4);
+        self.etf.atom("tuple");
+        self.start_list();
+    }
+
+    fn end_tuple_type(&mut self, _tuple: Self::TupleType) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_union_type(&mut self) -> Self::UnionType {
+        // {type, Line, union, [Alternative]}
+        self.tagged_tuple("type", 4);
+        self.etf.atom("union");
+        self.start_list();
+    }
+
+    fn end_union_type(&mut self, _union_type: Self::UnionType) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn type_variable(&mut self, name: &str) {
+        // {var, Line, Name}
+        self.tagged_tuple("var", 3);
+        self.etf.atom(name);
+        self.item_generated();
+    }
+
+    fn literal_atom_type(&mut self, name: &str) {
+        // {atom, Line, Name}
+        self.tagged_tuple("atom", 3);
+        self.etf.atom(name);
+        self.item_generated();
+    }
+
+    fn start_function<Name: AsRef<str>>(
+        &mut self,
+        location: SrcSpan,
+        name: &str,
+        arity: usize,
+        arguments_names: impl IntoIterator<Item = (SrcSpan, Name)>,
+    ) -> Self::Function {
+        self.locate(location);
+        // {function, Line, Name, Arity, [Clause]}
+        self.tagged_tuple("function", 5);
+        self.etf.atom(name);
+        self.etf.usize(arity);
+        self.start_list();
+        self.function_clause(arguments_names);
+    }
+
+    fn start_anonymous_function<Name: AsRef<str>>(
+        &mut self,
+        location: SrcSpan,
+        arguments_names: impl IntoIterator<Item = (SrcSpan, Name)>,
+    ) -> Self::Function {
+        self.locate(location);
+        // {'fun', Line, {clauses, [Clause]}}
+        self.tagged_tuple("fun", 3);
+        self.etf.small_tuple(2);
+        self.etf.atom("clauses");
+        self.start_list();
+        self.function_clause(arguments_names);
+    }
+
+    fn end_function(&mut self, _function: Self::Function) {
+        // We first close the body of the function's only clause, and then the
+        // list of clauses itself.
+        self.end_list();
+        self.item_generated();
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_block(&mut self, location: SrcSpan) -> Self::Block {
+        self.locate(location);
+        // {block, Line, [Expression]}
+        self.tagged_tuple("block", 3);
+        self.start_list();
+    }
+
+    fn end_block(&mut self, _block: Self::Block) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_remote_call(
+        &mut self,
+        location: SrcSpan,
+        module: ErlangModuleName,
+        function: &str,
+    ) -> Self::Call {
+        self.locate(location);
+        // {call, Line, {remote, Line, {atom, Line, Module}, {atom, Line, Name}}, [Argument]}
+        self.tagged_tuple("call", 4);
+        self.tagged_tuple("remote", 4);
+        self.tagged_tuple("atom", 3);
+        self.etf.atom(&module.0);
+        self.tagged_tuple("atom", 3);
+        self.etf.atom(function);
+        self.start_list();
+    }
+
+    fn start_call(&mut self, location: SrcSpan) -> Self::CalledExpression {
+        self.locate(location);
+        // {call, Line, Function, [Argument]}
+        self.tagged_tuple("call", 4);
+        self.open_terms.push(OpenTerm::Term);
+    }
+
+    fn end_called_expression(&mut self, _called: Self::CalledExpression) -> Self::Call {
+        self.end_term();
+        self.start_list();
+    }
+
+    fn end_call(&mut self, _call: Self::Call) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_tuple(&mut self, location: SrcSpan) -> Self::Tuple {
+        self.locate(location);
+        // {tuple, Line, [Item]}
+        self.tagged_tuple("tuple", 3);
+        self.start_list();
+    }
+
+    fn end_tuple(&mut self, _tuple: Self::Tuple) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_map(&mut self, location: SrcSpan) -> Self::Map {
+        self.locate(location);
+        // {map, Line, [Field]}
+        self.tagged_tuple("map", 3);
+        self.start_list();
+    }
+
+    fn end_map(&mut self, _map: Self::Map) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn map_field(&mut self, location: SrcSpan) {
+        self.locate(location);
+        // {map_field_assoc, Line, Key, Value}
+        self.tagged_tuple("map_field_assoc", 4);
+        self.operands(2);
+    }
+
+    fn start_bit_array(&mut self, location: SrcSpan) -> Self::BitArray {
+        self.locate(location);
+        // {bin, Line, [Segment]}
+        self.tagged_tuple("bin", 3);
+        self.start_list();
+    }
+
+    fn end_bit_array(&mut self, _bit_array: Self::BitArray) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn bit_array_segment(&mut self, location: SrcSpan) {
+        self.locate(location);
+        // {bin_element, Line, Value, Size, Specifiers}
+        self.tagged_tuple("bin_element", 5);
+        self.open_terms.push(OpenTerm::Operands {
+            remaining: 3,
+            kind: OperandsKind::BitArraySegment,
+        });
+    }
+
+    fn bit_array_segment_default_size(&mut self) {
+        self.etf.atom("default");
+        self.item_generated();
+    }
+
+    fn bit_array_segment_specifiers(
+        &mut self,
+        specifiers: impl IntoIterator<Item = BitArraySegmentSpecifier>,
+    ) {
+        let mut specifiers = specifiers.into_iter().peekable();
+        if specifiers.peek().is_none() {
+            self.etf.atom("default");
+            self.item_generated();
+            return;
+        }
+
+        let list = self.etf.start_list();
+        let mut length = 0;
+        for specifier in specifiers {
+            length += 1;
+            let atom = match specifier {
+                BitArraySegmentSpecifier::Utf8 => "utf8",
+                BitArraySegmentSpecifier::Utf16 => "utf16",
+                BitArraySegmentSpecifier::Utf32 => "utf32",
+                BitArraySegmentSpecifier::Integer => "integer",
+                BitArraySegmentSpecifier::Float => "float",
+                BitArraySegmentSpecifier::Binary => "binary",
+                BitArraySegmentSpecifier::Bitstring => "bitstring",
+                BitArraySegmentSpecifier::Signed => "signed",
+                BitArraySegmentSpecifier::Unsigned => "unsigned",
+                BitArraySegmentSpecifier::Little => "little",
+                BitArraySegmentSpecifier::Big => "big",
+                BitArraySegmentSpecifier::Native => "native",
+                BitArraySegmentSpecifier::Unit(unit) => {
+                    // {unit, Unit}
+                    self.etf.small_tuple(2);
+                    self.etf.atom("unit");
+                    self.etf.usize(unit as usize);
+                    continue;
+                }
+            };
+            self.etf.atom(atom);
+        }
+        self.etf.end_list(list, length);
+        self.item_generated();
+    }
+
+    fn cons_list(&mut self, location: SrcSpan) {
+        self.locate(location);
+        // {cons, Line, Head, Tail}
+        self.tagged_tuple("cons", 4);
+        self.operands(2);
+    }
+
+    fn empty_list(&mut self, location: SrcSpan) {
+        self.locate(location);
+        // {nil, Line}
+        self.tagged_tuple("nil", 2);
+        self.item_generated();
+    }
+
+    fn start_case(&mut self, location: SrcSpan) -> Self::CaseSubject {
+        self.locate(location);
+        // {'case', Line, Subject, [Clause]}
+        self.tagged_tuple("case", 4);
+        self.open_terms.push(OpenTerm::Term);
+    }
+
+    fn end_case_subject(&mut self, _case: Self::CaseSubject) -> Self::Case {
+        self.end_term();
+        self.start_list();
+    }
+
+    fn end_case(&mut self, _case: Self::Case) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn start_case_clause(&mut self, location: SrcSpan) -> Self::ClausePattern {
+        self.locate(location);
+        // {clause, Line, [Pattern], [[Guard]], [Expression]}
+        self.tagged_tuple("clause", 5);
+        self.start_list();
+    }
+
+    fn end_clause_pattern(&mut self, _clause_pattern: Self::ClausePattern) -> Self::ClauseGuards {
+        self.end_list();
+        self.start_list();
+    }
+
+    fn start_clause_guard(&mut self) -> Self::Guard {
+        self.start_list();
+    }
+
+    fn end_clause_guard(&mut self, _clause_guard: Self::Guard) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn end_clause_guards(&mut self, _clause_guards: Self::ClauseGuards) -> Self::ClauseBody {
+        self.end_list();
+        self.start_list();
+    }
+
+    fn end_clause_body(&mut self, _clause_body: Self::ClauseBody) {
+        self.end_list();
+        self.item_generated();
+    }
+
+    fn variable(&mut self, location: SrcSpan, name: &str) {
+        self.locate(location);
+        // {var, Line, Name}
+        self.tagged_tuple("var", 3);
+        self.etf.atom(name);
+        self.item_generated();
+    }
+
+    fn unary_operator(&mut self, location: SrcSpan, operator: &str) {
+        self.locate(location);
+        // {op, Line, Operator, Expression}
+        self.tagged_tuple("op", 4);
+        self.etf.atom(operator);
+        self.operands(1);
+    }
+
+    fn binary_operator(&mut self, location: SrcSpan, operator: &'static str) {
+        self.locate(location);
+        // {op, Line, Operator, Left, Right}
+        self.tagged_tuple("op", 5);
+        self.etf.atom(operator);
+        self.operands(2);
+    }
+
+    fn function_reference(
+        &mut self,
+        location: SrcSpan,
+        module: Option<ErlangModuleName>,
+        name: &str,
+        arity: usize,
+    ) {
+        self.locate(location);
+        self.tagged_tuple("fun", 3);
+        match module {
+            // {'fun', Line, {function, Module, Name, Arity}}
+            Some(module) => {
+                self.etf.small_tuple(4);
+                self.etf.atom("function");
+                self.tagged_tuple("atom", 3);
+                self.etf.atom(&module.0);
+                self.tagged_tuple("atom", 3);
+                self.etf.atom(name);
+                self.tagged_tuple("integer", 3);
+                self.etf.usize(arity);
+            }
+            // {'fun', Line, {function, Name, Arity}}
+            None => {
+                self.etf.small_tuple(3);
+                self.etf.atom("function");
+                self.etf.atom(name);
+                self.etf.usize(arity);
+            }
+        }
+        self.item_generated();
+    }
+
+    fn match_operator(&mut self, location: SrcSpan) {
+        self.locate(location);
+        // {match, Line, Pattern, Expression}
+        self.tagged_tuple("match", 4);
+        self.operands(2);
+    }
+
+    fn match_pattern(&mut self, location: SrcSpan) {
+        self.locate(location);
+        // {match, Line, Pattern, Pattern}
+        self.tagged_tuple("match", 4);
+        self.operands(2);
+    }
+
+    fn variable_pattern(&mut self, location: SrcSpan, name: &str) {
+        self.variable(location, name);
+    }
+
+    fn discard_pattern(&mut self, location: SrcSpan) {
+        self.variable(location, "_");
+    }
+
+    fn int_pattern(&mut self, location: SrcSpan, number: BigInt) {
+        self.int(location, number);
+    }
+
+    fn float_pattern(&mut self, location: SrcSpan, number: f64) {
+        self.float(location, number);
+    }
+
+    fn string_pattern(&mut self, location: SrcSpan, content: &str) {
+        self.string(location, content);
+    }
+
+    fn atom_pattern(&mut self, location: SrcSpan, name: &str) {
+        self.atom(location, name);
+    }
+
+    fn start_tuple_pattern(&mut self, location: SrcSpan) -> Self::TuplePattern {
+        self.start_tuple(location)
+    }
+
+    fn end_tuple_pattern(&mut self, tuple: Self::TuplePattern) {
+        self.end_tuple(tuple)
+    }
+
+    fn start_bit_array_pattern(&mut self, location: SrcSpan) -> Self::BitArrayPattern {
+        self.start_bit_array(location)
+    }
+
+    fn end_bit_array_pattern(&mut self, bit_array: Self::BitArrayPattern) {
+        self.end_bit_array(bit_array)
+    }
+
+    fn cons_list_pattern(&mut self, location: SrcSpan) {
+        self.cons_list(location)
+    }
+
+    fn empty_list_pattern(&mut self, location: SrcSpan) {
+        self.empty_list(location)
+    }
+
+    fn string(&mut self, location: SrcSpan, content: &str) {
+        self.locate(location);
+        let content = unescape_string_content(content);
+
+        // If the string is the value of a bit array segment then it's a plain
+        // string, its specifiers are generated along with the segment.
+        // {string, Line, Characters}
+        if self.is_bit_array_segment_value() {
+            self.tagged_tuple("string", 3);
+            self.etf.charlist(&content);
+            self.item_generated();
+            return;
+        }
+
+        // Otherwise it's a bit array with a single utf8 segment:
+        // {bin, Line, [{bin_element, Line, {string, Line, Characters}, default, [utf8]}]}
+        self.tagged_tuple("bin", 3);
+        let segments = self.etf.start_list();
+        self.tagged_tuple("bin_element", 5);
+        self.tagged_tuple("string", 3);
+        self.etf.charlist(&content);
+        self.etf.atom("default");
+        let specifiers = self.etf.start_list();
+        self.etf.atom("utf8");
+        self.etf.end_list(specifiers, 1);
+        self.etf.end_list(segments, 1);
+        self.item_generated();
+    }
+
+    fn int(&mut self, location: SrcSpan, value: BigInt) {
+        self.locate(location);
+        // {integer, Line, Value}
+        self.tagged_tuple("integer", 3);
+        self.etf.bigint(value);
+        self.item_generated();
+    }
+
+    fn float(&mut self, location: SrcSpan, value: f64) {
+        self.locate(location);
+        // {float, Line, Value}
+        self.tagged_tuple("float", 3);
+        self.etf.new_float(value);
+        self.item_generated();
+    }
+
+    fn atom(&mut self, location: SrcSpan, name: &str) {
+        self.locate(location);
+        // {atom, Line, Name}
+        self.tagged_tuple("atom", 3);
+        self.etf.atom(name);
+        self.item_generated();
+    }
+}
+
+impl ErlangAbstractFormatBuilder {
+    /// This has to be called every time a complete item has been generated.
+    /// It updates the terms that are still open: counting the items of the
+    /// innermost list, or closing the terms that now have all their operands.
+    fn item_generated(&mut self) {
+        loop {
+            match self.open_terms.last_mut() {
+                Some(OpenTerm::List { items, .. }) => {
+                    *items += 1;
+                    return;
+                }
+                Some(OpenTerm::Term) => return,
+                Some(OpenTerm::Operands { remaining, .. }) => {
+                    *remaining -= 1;
+                    if *remaining > 0 {
+                        return;
+                    }
+                    // All the operands have been generated, so the term is
+                    // over and counts as a complete item itself.
+                    let _ = self.open_terms.pop();
+                }
+                None => panic!("item generated outside of the module's forms"),
+            }
+        }
+    }
+
+    /// Starts a list of items whose number is not known in advance.
+    fn start_list(&mut self) {
+        let list = self.etf.start_list();
+        self.open_terms.push(OpenTerm::List { list, items: 0 });
+    }
+
+    /// Closes the innermost open list.
+    fn end_list(&mut self) {
+        let Some(OpenTerm::List { list, items }) = self.open_terms.pop() else {
+            panic!("tried closing a list, but the innermost open term is not a list");
+        };
+        self.etf.end_list(list, items);
+    }
+
+    /// Closes the innermost open term that was waiting to be closed explicitly.
+    fn end_term(&mut self) {
+        let Some(OpenTerm::Term) = self.open_terms.pop() else {
+            panic!("tried closing a term, but the innermost open term is a different one");
+        };
+    }
+
+    /// Starts a term that is over once the given number of items have been
+    /// generated.
+    fn operands(&mut self, remaining: u8) {
+        self.open_terms.push(OpenTerm::Operands {
+            remaining,
+            kind: OperandsKind::Other,
+        });
+    }
+
+    fn is_bit_array_segment_value(&self) -> bool {
+        matches!(
+            self.open_terms.last(),
+            Some(OpenTerm::Operands {
+                remaining: 3,
+                kind: OperandsKind::BitArraySegment,
+            })
+        )
+    }
+
+    /// Updates the line used to annotate the following items with the one the
+    /// given location is on.
+    /// Generated code that doesn't come from the source file has a blank
+    /// location, in that case we keep the line of the enclosing item.
+    fn locate(&mut self, location: SrcSpan) {
+        if location == SrcSpan::default() {
+            return;
+        }
+        if let Some(line_numbers) = &self.line_numbers {
+            self.line = line_numbers.line_number(location.start);
+        }
+    }
+
+    /// The annotation of a form. This is just its line number.
+    fn annotation(&mut self) {
+        self.etf.usize(self.line as usize);
+    }
+
+    /// Writes the beginning of a tuple of the given size, tagged with an atom
+    /// and followed by the annotation: `{Tag, Line, ...}`.
+    fn tagged_tuple(&mut self, tag: &str, arity: u8) {
+        self.etf.small_tuple(arity);
+        self.etf.atom(tag);
+        self.annotation();
+    }
+
+    /// Writes the beginning of an attribute form: `{attribute, Line, Name, ...}`.
+    /// The value of the attribute must be generated right after this.
+    fn attribute(&mut self, name: &str) {
+        self.tagged_tuple("attribute", 4);
+        self.etf.atom(name);
+    }
+
+    /// Writes a list of `{Name, Arity}` tuples, like the ones used by the
+    /// export attributes.
+    fn function_names<Name: AsRef<str>>(&mut self, names: impl IntoIterator<Item = (Name, usize)>) {
+        let list = self.etf.start_list();
+        let mut length = 0;
+        for (name, arity) in names {
+            self.etf.small_tuple(2);
+            self.etf.atom(name.as_ref());
+            self.etf.usize(arity);
+            length += 1;
+        }
+        self.etf.end_list(list, length);
+    }
+
+    /// Starts the single clause of a function, with a variable pattern for
+    /// each of its arguments and no guards. After this the statements of the
+    /// function's body can be generated.
+    fn function_clause<Name: AsRef<str>>(
+        &mut self,
+        arguments_names: impl IntoIterator<Item = (SrcSpan, Name)>,
+    ) {
+        // {clause, Line, [Pattern], [], [Expression]}
+        self.tagged_tuple("clause", 5);
+        let arguments = self.etf.start_list();
+        let mut length = 0;
+        for (argument_location, argument) in arguments_names {
+            self.locate(argument_location);
+            self.tagged_tuple("var", 3);
+            self.etf.atom(argument.as_ref());
+            length += 1;
+        }
+        self.etf.end_list(arguments, length);
+        self.etf.empty_list();
+        self.start_list();
+    }
+
+    fn doc_content(&mut self, content: DocContent<'_>) {
+        match content {
+            DocContent::False => self.etf.atom("false"),
+            // Doc attributes hold the documentation itself and not an
+            // expression producing it, so this is a plain binary.
+            DocContent::String(content) => self.etf.binary(content.len() as u32, content.bytes()),
+        }
+    }
+}
+
+/// Types that are built into Erlang are represented differently from the ones
+/// that are defined in a module, so we need to tell the two apart.
+/// This is the same list used by the Erlang compiler:
+/// https://github.com/erlang/otp/blob/master/lib/stdlib/src/erl_internal.erl
+///
+pub(crate) fn is_builtin_type(name: &str) -> bool {
+    matches!(
+        name,
+        "any"
+            | "arity"
+            | "atom"
+            | "binary"
+            | "bitstring"
+            | "bool"
+            | "boolean"
+            | "byte"
+            | "char"
+            | "dynamic"
+            | "float"
+            | "function"
+            | "identifier"
+            | "integer"
+            | "iodata"
+            | "iolist"
+            | "list"
+            | "map"
+            | "maybe_improper_list"
+            | "mfa"
+            | "module"
+            | "neg_integer"
+            | "nil"
+            | "no_return"
+            | "node"
+            | "non_neg_integer"
+            | "none"
+            | "nonempty_improper_list"
+            | "nonempty_list"
+            | "nonempty_maybe_improper_list"
+            | "nonempty_string"
+            | "number"
+            | "pid"
+            | "port"
+            | "pos_integer"
+            | "reference"
+            | "string"
+            | "term"
+            | "timeout"
+            | "tuple"
+    )
+}
+
+/// The strings given to the builder are the content of Gleam string literals,
+/// so they might contain escape sequences that need to be turned into the
+/// characters they stand for.
+///
+pub(crate) fn unescape_string_content(content: &str) -> String {
+    let mut unescaped = String::with_capacity(content.len());
+    let mut characters = content.chars();
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+
+        match characters.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some('f') => unescaped.push('\u{000C}'),
+            // This is not a valid Gleam escape sequence, but it's used by the
+            // compiler itself when generating code for `echo`.
+            Some('e') => unescaped.push('\u{001B}'),
+            Some('"') => unescaped.push('"'),
+            Some('\\') => unescaped.push('\\'),
+            Some('u') => {
+                // A unicode escape sequence looks like this: `\u{1F600}`.
+                let rest = characters.as_str();
+                let codepoint = rest
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.split_once('}'))
+                    .and_then(|(hex, _)| u32::from_str_radix(hex, 16).ok().map(|n| (hex, n)))
+                    .and_then(|(hex, codepoint)| Some((hex, char::from_u32(codepoint)?)));
+
+                match codepoint {
+                    Some((hex, codepoint)) => {
+                        unescaped.push(codepoint);
+                        characters = rest[hex.len() + 2..].chars();
+                    }
+                    None => unescaped.push_str("\\u"),
+                }
+            }
+            // Gleam doesn't allow any other escape sequence, so this can't
+            // really happen. We keep the characters as they are.
+            Some(other) => {
+                unescaped.push('\\');
+                unescaped.push(other);
+            }
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
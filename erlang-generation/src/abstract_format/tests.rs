@@ -0,0 +1,437 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+// Reading raw bytes is not really practical, so in these tests the binary
+// produced by the builder is decoded and pretty printed using Erlang's
+// syntax. This way the expected output can be compared to what
+// `erl_parse` and `epp` would produce for the equivalent Erlang code.
+//
+// For example, to check what an expression should look like one can run
+// this bit of Erlang:
+//
+// ```erl
+// {ok, Tokens, _} = erl_scan:string("{ok, X}."),
+// {ok, [Expression]} = erl_parse:parse_exprs(Tokens),
+// io:format("~w~n", [Expression]).
+// ```
+//
+
+// The builder's tokens are all unit values, but we still want to pass them
+// around to show how the builder is meant to be used.
+#![allow(clippy::let_unit_value)]
+
+use super::*;
+use crate::BitArraySegmentSpecifier;
+use std::fmt::Write;
+
+fn forms(builder: ErlangAbstractFormatBuilder) -> String {
+    let bytes = builder.into_output();
+    let mut decoder = Decoder {
+        bytes: &bytes,
+        position: 0,
+    };
+    assert_eq!(decoder.byte(), 131, "missing version number");
+
+    let mut output = String::new();
+    // All the forms are in a single top level list, we print each of them on
+    // its own line to make the tests a bit easier to read.
+    assert_eq!(decoder.byte(), 108, "forms should be a list");
+    let length = decoder.u32();
+    for _ in 0..length {
+        decoder.term(&mut output);
+        output.push('\n');
+    }
+    assert_eq!(decoder.byte(), 106, "forms should be a proper list");
+    assert_eq!(decoder.position, bytes.len(), "unexpected trailing bytes");
+    output
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl Decoder<'_> {
+    fn byte(&mut self) -> u8 {
+        let byte = self.bytes[self.position];
+        self.position += 1;
+        byte
+    }
+
+    fn take(&mut self, count: usize) -> &[u8] {
+        let bytes = &self.bytes[self.position..self.position + count];
+        self.position += count;
+        bytes
+    }
+
+    fn u16(&mut self) -> usize {
+        u16::from_be_bytes([self.byte(), self.byte()]) as usize
+    }
+
+    fn u32(&mut self) -> usize {
+        u32::from_be_bytes([self.byte(), self.byte(), self.byte(), self.byte()]) as usize
+    }
+
+    fn term(&mut self, output: &mut String) {
+        match self.byte() {
+            // Small integer
+            97 => write!(output, "{}", self.byte()).unwrap(),
+            // Integer
+            98 => {
+                let bytes = self.take(4).try_into().unwrap();
+                write!(output, "{}", i32::from_be_bytes(bytes)).unwrap()
+            }
+            // Small big
+            110 => {
+                let length = self.byte() as usize;
+                let sign = self.byte();
+                let number = BigInt::from_bytes_le(num_bigint::Sign::Plus, self.take(length));
+                let number = if sign == 1 { -number } else { number };
+                write!(output, "{number}").unwrap()
+            }
+            // New float
+            70 => {
+                let bytes = self.take(8).try_into().unwrap();
+                write!(output, "{:?}", f64::from_be_bytes(bytes)).unwrap()
+            }
+            // Small atom utf8
+            119 => {
+                let length = self.byte() as usize;
+                let atom = std::str::from_utf8(self.take(length)).unwrap();
+                output.push_str(atom)
+            }
+            // Small tuple
+            104 => {
+                let arity = self.byte();
+                output.push('{');
+                for index in 0..arity {
+                    if index != 0 {
+                        output.push(',');
+                    }
+                    self.term(output);
+                }
+                output.push('}');
+            }
+            // Nil
+            106 => output.push_str("[]"),
+            // String
+            107 => {
+                let length = self.u16();
+                let string = std::str::from_utf8(self.take(length)).unwrap();
+                write!(output, "{string:?}").unwrap()
+            }
+            // List
+            108 => {
+                let length = self.u32();
+                output.push('[');
+                for index in 0..length {
+                    if index != 0 {
+                        output.push(',');
+                    }
+                    self.term(output);
+                }
+                assert_eq!(self.byte(), 106, "improper list");
+                output.push(']');
+            }
+            // Binary
+            109 => {
+                let length = self.u32();
+                let string = std::str::from_utf8(self.take(length)).unwrap();
+                write!(output, "<<{string:?}>>").unwrap()
+            }
+            tag => panic!("unexpected tag {tag}"),
+        }
+    }
+}
+
+#[test]
+fn empty_module() {
+    let builder = ErlangAbstractFormatBuilder::new(Some(ErlangModuleName::new("wibble/wobble")));
+    assert_eq!(
+        forms(builder),
+        "{attribute,1,module,wibble@wobble}
+{eof,1}
+"
+    );
+}
+
+#[test]
+fn module_attributes() {
+    let mut builder = ErlangAbstractFormatBuilder::new(Some(ErlangModuleName::new("wibble")));
+    builder.compile_attribute(["no_auto_import", "nowarn_unused_vars"]);
+    builder.export_attribute([("main", 0), ("wobble", 2)]);
+    builder.export_type_attribute::<&str>([]);
+    builder.moduledoc_attribute(DocContent::String(" Some \"docs\"\n"));
+    builder.doc_attribute(DocContent::False);
+
+    assert_eq!(
+        forms(builder),
+        r#"{attribute,1,module,wibble}
+{attribute,1,compile,[no_auto_import,nowarn_unused_vars]}
+{attribute,1,export,[{main,0},{wobble,2}]}
+{attribute,1,moduledoc,<<" Some \"docs\"\n">>}
+{attribute,1,doc,false}
+{eof,1}
+"#
+    );
+}
+
+#[test]
+fn file_attribute_updates_line_annotations() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangAbstractFormatBuilder::new(None);
+    builder.file_attribute("src/wibble.gleam", 12);
+    let function = builder.start_function(location, "main", 1, [(location, "X")]);
+    builder.variable(location, "X");
+    builder.end_function(function);
+
+    assert_eq!(
+        forms(builder),
+        r#"{attribute,12,file,{"src/wibble.gleam",12}}
+{function,12,main,1,[{clause,12,[{var,12,X}],[],[{var,12,X}]}]}
+{eof,12}
+"#
+    );
+}
+
+#[test]
+fn expressions_are_annotated_with_their_source_line() {
+    let src = "pub fn main(x) {
+  let y =
+    x + 1
+  case y {
+    _ -> y
+  }
+}
+";
+    let location_of = |code: &str| {
+        let start = src.find(code).expect("code in source") as u32;
+        SrcSpan::new(start, start + code.len() as u32)
+    };
+
+    let mut builder = ErlangAbstractFormatBuilder::new(None);
+    builder.source_line_numbers(LineNumbers::new(src));
+    builder.file_attribute("src/wibble.gleam", 1);
+    let function =
+        builder.start_function(location_of("main"), "main", 1, [(location_of("x)"), "X")]);
+
+    // Y = X + 1,
+    builder.match_operator(location_of("let y"));
+    builder.variable_pattern(location_of("y ="), "Y");
+    builder.binary_operator(location_of("x + 1"), "+");
+    builder.variable(location_of("x +"), "X");
+    builder.int(location_of("1"), 1.into());
+
+    // case Y of _ -> Y end
+    let case = builder.start_case(location_of("case"));
+    builder.variable(location_of("y {"), "Y");
+    let case = builder.end_case_subject(case);
+    let pattern = builder.start_case_clause(location_of("_ ->"));
+    builder.discard_pattern(location_of("_ ->"));
+    let guards = builder.end_clause_pattern(pattern);
+    let body = builder.end_clause_guards(guards);
+    // Generated code that doesn't come from the source keeps the line of the
+    // item before it.
+    builder.variable(SrcSpan::default(), "Y");
+    builder.end_clause_body(body);
+    builder.end_case(case);
+
+    builder.end_function(function);
+
+    assert_eq!(
+        forms(builder),
+        r#"{attribute,1,file,{"src/wibble.gleam",1}}
+{function,1,main,1,[{clause,1,[{var,1,X}],[],[{match,2,{var,2,Y},{op,3,+,{var,3,X},{integer,3,1}}},{case,4,{var,4,Y},[{clause,5,[{var,5,_}],[],[{var,5,Y}]}]}]}]}
+{eof,5}
+"#
+    );
+}
+
+#[test]
+fn function_with_calls_and_operators() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangAbstractFormatBuilder::new(None);
+    let function = builder.start_function(location, "main", 2, [(location, "A"), (location, "B")]);
+
+    // X = A + B,
+    builder.match_operator(location);
+    builder.variable_pattern(location, "X");
+    builder.binary_operator(location, "+");
+    builder.variable(location, "A");
+    builder.variable(location, "B");
+
+    // lists:reverse([X]),
+    let call = builder.start_remote_call(location, ErlangModuleName::lists(), "reverse");
+    builder.cons_list(location);
+    builder.variable(location, "X");
+    builder.empty_list(location);
+    builder.end_call(call);
+
+    // (fun wobble/1)(-X)
+    let called = builder.start_call(location);
+    builder.function_reference(location, None, "wobble", 1);
+    let call = builder.end_called_expression(called);
+    builder.unary_operator(location, "-");
+    builder.variable(location, "X");
+    builder.end_call(call);
+
+    builder.end_function(function);
+
+    assert_eq!(
+        forms(builder),
+        "{function,1,main,2,[{clause,1,[{var,1,A},{var,1,B}],[],[\
+{match,1,{var,1,X},{op,1,+,{var,1,A},{var,1,B}}},\
+{call,1,{remote,1,{atom,1,lists},{atom,1,reverse}},[{cons,1,{var,1,X},{nil,1}}]},\
+{call,1,{fun,1,{function,wobble,1}},[{op,1,-,{var,1,X}}]}\
+]}]}
+{eof,1}
+"
+    );
+}
+
+#[test]
+fn case_expression_with_guards() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangAbstractFormatBuilder::new(None);
+    let function = builder.start_function(location, "main", 1, [(location, "X")]);
+
+    let case = builder.start_case(location);
+    builder.variable(location, "X");
+    let case = builder.end_case_subject(case);
+
+    // {ok, Y} when Y > 1 -> Y;
+    let clause = builder.start_case_clause(location);
+    let tuple = builder.start_tuple_pattern(location);
+    builder.atom_pattern(location, "ok");
+    builder.variable_pattern(location, "Y");
+    builder.end_tuple_pattern(tuple);
+    let guards = builder.end_clause_pattern(clause);
+    let guard = builder.start_clause_guard();
+    builder.binary_operator(location, ">");
+    builder.variable(location, "Y");
+    builder.int(location, BigInt::from(1));
+    builder.end_clause_guard(guard);
+    let body = builder.end_clause_guards(guards);
+    builder.variable(location, "Y");
+    builder.end_clause_body(body);
+
+    // _ -> 18446744073709551616
+    let clause = builder.start_case_clause(location);
+    builder.discard_pattern(location);
+    let guards = builder.end_clause_pattern(clause);
+    let body = builder.end_clause_guards(guards);
+    builder.int(location, BigInt::from(u64::MAX) + 1);
+    builder.end_clause_body(body);
+
+    builder.end_case(case);
+    builder.end_function(function);
+
+    assert_eq!(
+        forms(builder),
+        "{function,1,main,1,[{clause,1,[{var,1,X}],[],[{case,1,{var,1,X},[\
+{clause,1,[{tuple,1,[{atom,1,ok},{var,1,Y}]}],[[{op,1,>,{var,1,Y},{integer,1,1}}]],[{var,1,Y}]},\
+{clause,1,[{var,1,_}],[],[{integer,1,18446744073709551616}]}\
+]}]}]}
+{eof,1}
+"
+    );
+}
+
+#[test]
+fn strings_are_unescaped() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangAbstractFormatBuilder::new(None);
+    let function = builder.start_function::<&str>(location, "main", 0, []);
+    builder.string(location, r#"\"wibble\"\n\u{1F600}"#);
+    builder.end_function(function);
+
+    assert_eq!(
+        forms(builder),
+        r#"{function,1,main,0,[{clause,1,[],[],[{bin,1,[{bin_element,1,{string,1,[34,119,105,98,98,108,101,34,10,128512]},default,[utf8]}]}]}]}
+{eof,1}
+"#
+    );
+}
+
+#[test]
+fn bit_array_segments() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangAbstractFormatBuilder::new(None);
+    let function = builder.start_function::<&str>(location, "main", 0, []);
+
+    // <<"wibble"/utf16, 1:16/little-unit:2, 1.0>>
+    let bit_array = builder.start_bit_array(location);
+    builder.bit_array_segment(location);
+    builder.string(location, "wibble");
+    builder.bit_array_segment_default_size();
+    builder.bit_array_segment_specifiers([BitArraySegmentSpecifier::Utf16]);
+
+    builder.bit_array_segment(location);
+    builder.int(location, BigInt::from(1));
+    builder.int(location, BigInt::from(16));
+    builder.bit_array_segment_specifiers([
+        BitArraySegmentSpecifier::Little,
+        BitArraySegmentSpecifier::Unit(2),
+    ]);
+
+    builder.bit_array_segment(location);
+    builder.float(location, 1.0);
+    builder.bit_array_segment_default_size();
+    builder.bit_array_segment_specifiers([]);
+    builder.end_bit_array(bit_array);
+
+    builder.end_function(function);
+
+    assert_eq!(
+        forms(builder),
+        r#"{function,1,main,0,[{clause,1,[],[],[{bin,1,[{bin_element,1,{string,1,"wibble"},default,[utf16]},{bin_element,1,{integer,1,1},{integer,1,16},[little,{unit,2}]},{bin_element,1,{float,1,1.0},default,default}]}]}]}
+{eof,1}
+"#
+    );
+}
+
+#[test]
+fn type_attributes() {
+    let mut builder = ErlangAbstractFormatBuilder::new(None);
+
+    // -opaque wibble(A) :: {wibble, A} | gleam@dict:dict(integer(), wobble()).
+    let type_spec = builder.start_type_spec(true, "wibble", ["A"]);
+    let union = builder.start_union_type();
+    let tuple = builder.start_tuple_type();
+    builder.literal_atom_type("wibble");
+    builder.type_variable("A");
+    builder.end_tuple_type(tuple);
+    let remote = builder.start_remote_named_type(ErlangModuleName::new("gleam/dict"), "dict");
+    let integer = builder.start_named_type("integer");
+    builder.end_named_type(integer);
+    let wobble = builder.start_named_type("wobble");
+    builder.end_named_type(wobble);
+    builder.end_remote_named_type(remote);
+    builder.end_union_type(union);
+    builder.end_type_spec(type_spec);
+
+    // -spec main(fun(() -> nil)) -> nil.
+    let spec = builder.start_function_spec("main", 1);
+    let arguments = builder.start_function_type();
+    let function_type = builder.start_function_type();
+    let function_type = builder.end_function_type_arguments(function_type);
+    builder.literal_atom_type("nil");
+    builder.end_function_type(function_type);
+    let function_type = builder.end_function_type_arguments(arguments);
+    builder.literal_atom_type("nil");
+    builder.end_function_type(function_type);
+    builder.end_function_spec(spec);
+
+    assert_eq!(
+        forms(builder),
+        "{attribute,1,opaque,{wibble,{type,1,union,[\
+{type,1,tuple,[{atom,1,wibble},{var,1,A}]},\
+{remote_type,1,[{atom,1,gleam@dict},{atom,1,dict},[{type,1,integer,[]},{user_type,1,wobble,[]}]]}\
+]},[{var,1,A}]}}
+{attribute,1,spec,{{main,1},[{type,1,fun,[{type,1,product,[\
+{type,1,fun,[{type,1,product,[]},{atom,1,nil}]}\
+]},{atom,1,nil}]}]}}
+{eof,1}
+"
+    );
+}
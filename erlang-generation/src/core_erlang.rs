@@ -0,0 +1,1824 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+//! An `ErlangBuilder` producing the Core Erlang representation of a module.
+//!
+//! Core Erlang is the small functional language the Erlang compiler turns
+//! Erlang code into before optimising it, and it's what tools like Dialyzer
+//! reason about. It has no operators, no guard sequences, and pattern matching
+//! can only happen in `case` expressions, so these are all translated to
+//! their Core Erlang equivalent: an operator becomes a call to the function
+//! of the `erlang` module it stands for, and a match becomes a `let` or a
+//! `case` expression wrapping all the statements that follow it.
+//!
+//! Since the translation of a statement depends on what comes after it, this
+//! builder can't write its output as it goes like the other ones do. It first
+//! collects a tree of all the items of the module, which is printed once the
+//! module is over.
+//!
+//! The language is described here:
+//! https://www.it.uu.se/research/group/hipe/cerl/doc/core_erlang-1.0.3.pdf
+//!
+
+#[cfg(test)]
+mod tests;
+
+use ecow::{EcoString, eco_format};
+use itertools::Itertools;
+use num_bigint::BigInt;
+use src_span::{LineNumbers, SrcSpan};
+
+use crate::{
+    BitArraySegmentSpecifier, DocContent, ErlangBuilder, ErlangModuleName,
+    abstract_format::{is_builtin_type, unescape_string_content},
+    format_float,
+};
+
+/// A structure that implements the `ErlangBuilder` trait and produces the
+/// Core Erlang code of a module, as it would be printed by `erlc +to_core`.
+#[derive(Debug)]
+pub struct ErlangCoreBuilder {
+    module_name: Option<EcoString>,
+    exports: Vec<(EcoString, usize)>,
+    attributes: Vec<Attribute>,
+    functions: Vec<Function>,
+    /// This keeps track of the items that are still open and waiting for more
+    /// items to be generated.
+    open_items: Vec<OpenItem>,
+    /// The line of the Gleam code the latest item with a location comes from.
+    /// This is used to annotate the function definitions and the types in the
+    /// module attributes.
+    line: u32,
+    line_numbers: Option<LineNumbers>,
+}
+
+#[derive(Debug)]
+struct Function {
+    line: u32,
+    name: EcoString,
+    arguments: Vec<EcoString>,
+    body: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Attribute {
+    File {
+        file: String,
+        line: u32,
+    },
+    ExportType(Vec<(EcoString, usize)>),
+    Spec {
+        line: u32,
+        name: EcoString,
+        arity: usize,
+        type_: Node,
+    },
+    Type {
+        line: u32,
+        opaque: bool,
+        name: EcoString,
+        parameters: Vec<EcoString>,
+        type_: Node,
+    },
+}
+
+/// An item that has been started, along with the items that have been
+/// generated inside it so far.
+#[derive(Debug)]
+struct OpenItem {
+    kind: OpenKind,
+    items: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum OpenKind {
+    Function {
+        line: u32,
+        name: Option<EcoString>,
+        arguments: Vec<EcoString>,
+    },
+    Block,
+    Tuple,
+    Map,
+    MapField,
+    BitArray,
+    BitArraySegment,
+    Cons,
+    UnaryOperator(EcoString),
+    BinaryOperator(&'static str),
+    Match,
+    RemoteCall {
+        module: EcoString,
+        function: EcoString,
+    },
+    CalledExpression,
+    Call,
+    CaseSubject,
+    Case,
+    ClausePattern,
+    ClauseGuards,
+    Guard,
+    ClauseBody {
+        pattern: Node,
+        guards: Vec<Vec<Node>>,
+    },
+    RecordAttribute,
+    RecordField,
+    FunctionSpec {
+        line: u32,
+        name: EcoString,
+        arity: usize,
+    },
+    TypeSpec {
+        line: u32,
+        opaque: bool,
+        name: EcoString,
+    },
+    FunctionTypeArguments,
+    FunctionType(Vec<Node>),
+    NamedType(EcoString),
+    RemoteNamedType {
+        module: EcoString,
+        name: EcoString,
+    },
+    TupleType,
+    UnionType,
+}
+
+impl OpenKind {
+    /// The number of items after which an item is automatically over. Items
+    /// that return `None` have to be closed explicitly by one of the `end_*`
+    /// functions.
+    fn operands(&self) -> Option<usize> {
+        match self {
+            OpenKind::UnaryOperator(_) => Some(1),
+            OpenKind::MapField
+            | OpenKind::Cons
+            | OpenKind::BinaryOperator(_)
+            | OpenKind::Match
+            | OpenKind::RecordField => Some(2),
+            OpenKind::BitArraySegment => Some(3),
+            OpenKind::Function { .. }
+            | OpenKind::Block
+            | OpenKind::Tuple
+            | OpenKind::Map
+            | OpenKind::BitArray
+            | OpenKind::RemoteCall { .. }
+            | OpenKind::CalledExpression
+            | OpenKind::Call
+            | OpenKind::CaseSubject
+            | OpenKind::Case
+            | OpenKind::ClausePattern
+            | OpenKind::ClauseGuards
+            | OpenKind::Guard
+            | OpenKind::ClauseBody { .. }
+            | OpenKind::RecordAttribute
+            | OpenKind::FunctionSpec { .. }
+            | OpenKind::TypeSpec { .. }
+            | OpenKind::FunctionTypeArguments
+            | OpenKind::FunctionType(_)
+            | OpenKind::NamedType(_)
+            | OpenKind::RemoteNamedType { .. }
+            | OpenKind::TupleType
+            | OpenKind::UnionType => None,
+        }
+    }
+}
+
+/// An expression, a pattern, or a type. Patterns share most of their variants
+/// with expressions, just like they do in the Erlang abstract format.
+#[derive(Debug, Clone)]
+enum Node {
+    Variable(EcoString),
+    Discard,
+    Atom(EcoString),
+    Int(BigInt),
+    Float(f64),
+    /// The unescaped content of a Gleam string.
+    String(String),
+    EmptyList,
+    Cons(Box<Node>, Box<Node>),
+    Tuple(Vec<Node>),
+    Map(Vec<(Node, Node)>),
+    BitArray(Vec<Segment>),
+    Block(Vec<Node>),
+    Function {
+        arguments: Vec<EcoString>,
+        body: Vec<Node>,
+    },
+    FunctionReference {
+        module: Option<EcoString>,
+        name: EcoString,
+        arity: usize,
+    },
+    RemoteCall {
+        module: EcoString,
+        function: EcoString,
+        arguments: Vec<Node>,
+    },
+    Call {
+        function: Box<Node>,
+        arguments: Vec<Node>,
+    },
+    Case {
+        subject: Box<Node>,
+        clauses: Vec<Clause>,
+    },
+    UnaryOperator {
+        operator: EcoString,
+        value: Box<Node>,
+    },
+    BinaryOperator {
+        operator: &'static str,
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+    /// A match expression `Pattern = Value`, or an alias pattern
+    /// `Pattern = Pattern`.
+    Match(Box<Node>, Box<Node>),
+
+    FunctionType {
+        arguments: Vec<Node>,
+        return_: Box<Node>,
+    },
+    NamedType {
+        name: EcoString,
+        arguments: Vec<Node>,
+    },
+    RemoteNamedType {
+        module: EcoString,
+        name: EcoString,
+        arguments: Vec<Node>,
+    },
+    TupleType(Vec<Node>),
+    UnionType(Vec<Node>),
+    TypeVariable(EcoString),
+    AtomType(EcoString),
+
+    /// These are only ever found inside the item they're generated for, and
+    /// are taken apart once it's over.
+    MapField(Box<Node>, Box<Node>),
+    Segment(Box<Segment>),
+    DefaultSegmentSize,
+    SegmentOptions(SegmentOptions),
+    Clause(Box<Clause>),
+    Guard(Vec<Node>),
+    RecordField,
+}
+
+#[derive(Debug, Clone)]
+struct Clause {
+    pattern: Node,
+    /// A clause matches if any of its guards is true, and a guard is true if
+    /// all of its expressions are true.
+    guards: Vec<Vec<Node>>,
+    body: Vec<Node>,
+}
+
+#[derive(Debug, Clone)]
+struct Segment {
+    value: Node,
+    size: Option<Node>,
+    options: SegmentOptions,
+}
+
+/// Core Erlang has no default for any of the options of a bit array segment,
+/// so the specifiers of each segment are turned into the full set of them.
+#[derive(Debug, Clone, Copy)]
+struct SegmentOptions {
+    type_: SegmentType,
+    signed: bool,
+    endianness: &'static str,
+    unit: Option<u8>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SegmentType {
+    Integer,
+    Float,
+    Binary,
+    Bitstring,
+    Utf8,
+    Utf16,
+    Utf32,
+}
+
+impl SegmentType {
+    fn name(self) -> &'static str {
+        match self {
+            SegmentType::Integer => "integer",
+            SegmentType::Float => "float",
+            SegmentType::Binary => "binary",
+            SegmentType::Bitstring => "binary",
+            SegmentType::Utf8 => "utf8",
+            SegmentType::Utf16 => "utf16",
+            SegmentType::Utf32 => "utf32",
+        }
+    }
+
+    fn is_utf(self) -> bool {
+        matches!(
+            self,
+            SegmentType::Utf8 | SegmentType::Utf16 | SegmentType::Utf32
+        )
+    }
+}
+
+impl ErlangBuilder<String> for ErlangCoreBuilder {
+    type BitArray = ();
+    type BitArrayPattern = ();
+    type Block = ();
+    type CalledExpression = ();
+    type Call = ();
+    type Case = ();
+    type Guard = ();
+    type CaseSubject = ();
+    type ClauseBody = ();
+    type ClauseGuards = ();
+    type ClausePattern = ();
+    type Function = ();
+    type FunctionSpec = ();
+    type FunctionType = ();
+    type FunctionTypeArguments = ();
+    type Map = ();
+    type NamedType = ();
+    type RemoteNamedType = ();
+    type RecordAttribute = ();
+    type Tuple = ();
+    type TuplePattern = ();
+    type TupleType = ();
+    type UnionType = ();
+    type TypeSpec = Vec<EcoString>;
+
+    fn new(module_name: Option<ErlangModuleName>) -> Self {
+        Self {
+            module_name: module_name.map(|name| name.0),
+            exports: vec![],
+            attributes: vec![],
+            functions: vec![],
+            open_items: vec![],
+            line: 1,
+            line_numbers: None,
+        }
+    }
+
+    fn into_output(self) -> String {
+        if !self.open_items.is_empty() {
+            panic!("module is over but some items are still open");
+        }
+        let name = self
+            .module_name
+            .expect("a Core Erlang module must have a name");
+
+        let mut printer = Printer {
+            code: String::new(),
+            indentation: 0,
+            in_guard: false,
+            generated_variables: 0,
+        };
+        printer.module(&name, &self.exports, &self.attributes, &self.functions);
+        printer.code
+    }
+
+    fn export_attribute<Name: AsRef<str>>(
+        &mut self,
+        exported: impl IntoIterator<Item = (Name, usize)>,
+    ) {
+        self.exports.extend(
+            exported
+                .into_iter()
+                .map(|(name, arity)| (name.as_ref().into(), arity)),
+        );
+    }
+
+    fn export_type_attribute<Name: AsRef<str>>(
+        &mut self,
+        exported: impl IntoIterator<Item = (Name, usize)>,
+    ) {
+        let exported = exported
+            .into_iter()
+            .map(|(name, arity)| (name.as_ref().into(), arity))
+            .collect_vec();
+        if !exported.is_empty() {
+            self.attributes.push(Attribute::ExportType(exported));
+        }
+    }
+
+    // Documentation is of no use to the tools reading Core Erlang, so the doc
+    // and compile attributes are left out.
+
+    fn doc_attribute(&mut self, _content: DocContent<'_>) {}
+
+    fn moduledoc_attribute(&mut self, _content: DocContent<'_>) {}
+
+    fn compile_attribute<'a>(&mut self, _arguments: impl IntoIterator<Item = &'a str>) {}
+
+    fn file_attribute(&mut self, file: &str, line: u32) {
+        self.line = line;
+        self.attributes.push(Attribute::File {
+            file: unescape_string_content(file),
+            line,
+        });
+    }
+
+    fn source_line_numbers(&mut self, line_numbers: LineNumbers) {
+        self.line_numbers = Some(line_numbers);
+    }
+
+    // Records only exist at compile time, so they're not part of the Core
+    // Erlang code. Their fields are still collected and then thrown away.
+
+    fn start_record_attribute(&mut self, _record_name: &str) -> Self::RecordAttribute {
+        self.open(OpenKind::RecordAttribute);
+    }
+
+    fn end_record_attribute(&mut self, _record: Self::RecordAttribute) {
+        let _ = self.close();
+    }
+
+    fn record_field(&mut self) {
+        self.open(OpenKind::RecordField);
+    }
+
+    fn start_function_spec(&mut self, name: &str, arity: usize) -> Self::FunctionSpec {
+        self.open(OpenKind::FunctionSpec {
+            line: self.line,
+            name: name.into(),
+            arity,
+        });
+    }
+
+    fn end_function_spec(&mut self, _function_spec: Self::FunctionSpec) {
+        let (kind, items) = self.close();
+        let OpenKind::FunctionSpec { line, name, arity } = kind else {
+            panic!("tried closing a function spec, but the innermost item is a different one");
+        };
+        self.attributes.push(Attribute::Spec {
+            line,
+            name,
+            arity,
+            type_: single(items),
+        });
+    }
+
+    fn start_type_spec<Name: AsRef<str>>(
+        &mut self,
+        opaque: bool,
+        name: &str,
+        type_parameters: impl IntoIterator<Item = Name>,
+    ) -> Self::TypeSpec {
+        self.open(OpenKind::TypeSpec {
+            line: self.line,
+            opaque,
+            name: name.into(),
+        });
+        type_parameters
+            .into_iter()
+            .map(|name| name.as_ref().into())
+            .collect()
+    }
+
+    fn end_type_spec(&mut self, type_parameters: Self::TypeSpec) {
+        let (kind, items) = self.close();
+        let OpenKind::TypeSpec { line, opaque, name } = kind else {
+            panic!("tried closing a type spec, but the innermost item is a different one");
+        };
+        self.attributes.push(Attribute::Type {
+            line,
+            opaque,
+            name,
+            parameters: type_parameters,
+            type_: single(items),
+        });
+    }
+
+    fn start_function_type(&mut self) -> Self::FunctionTypeArguments {
+        self.open(OpenKind::FunctionTypeArguments);
+    }
+
+    fn end_function_type_arguments(
+        &mut self,
+        _function_type: Self::FunctionTypeArguments,
+    ) -> Self::FunctionType {
+        let (_, arguments) = self.close();
+        self.open(OpenKind::FunctionType(arguments));
+    }
+
+    fn end_function_type(&mut self, _function_type: Self::FunctionType) {
+        let (kind, items) = self.close();
+        let OpenKind::FunctionType(arguments) = kind else {
+            panic!("tried closing a function type, but the innermost item is a different one");
+        };
+        self.item_generated(Node::FunctionType {
+            arguments,
+            return_: Box::new(single(items)),
+        });
+    }
+
+    fn start_named_type(&mut self, name: &str) -> Self::NamedType {
+        self.open(OpenKind::NamedType(name.into()));
+    }
+
+    fn end_named_type(&mut self, _named_type: Self::NamedType) {
+        let (kind, arguments) = self.close();
+        let OpenKind::NamedType(name) = kind else {
+            panic!("tried closing a named type, but the innermost item is a different one");
+        };
+        self.item_generated(Node::NamedType { name, arguments });
+    }
+
+    fn start_remote_named_type(
+        &mut self,
+        module: ErlangModuleName,
+        name: &str,
+    ) -> Self::RemoteNamedType {
+        self.open(OpenKind::RemoteNamedType {
+            module: module.0,
+            name: name.into(),
+        });
+    }
+
+    fn end_remote_named_type(&mut self, _named_type: Self::RemoteNamedType) {
+        let (kind, arguments) = self.close();
+        let OpenKind::RemoteNamedType { module, name } = kind else {
+            panic!("tried closing a remote type, but the innermost item is a different one");
+        };
+        self.item_generated(Node::RemoteNamedType {
+            module,
+            name,
+            arguments,
+        });
+    }
+
+    fn start_tuple_type(&mut self) -> Self::TupleType {
+        self.open(OpenKind::TupleType);
+    }
+
+    fn end_tuple_type(&mut self, _tuple: Self::TupleType) {
+        let (_, items) = self.close();
+        self.item_generated(Node::TupleType(items));
+    }
+
+    fn start_union_type(&mut self) -> Self::UnionType {
+        self.open(OpenKind::UnionType);
+    }
+
+    fn end_union_type(&mut self, _union_type: Self::UnionType) {
+        let (_, items) = self.close();
+        self.item_generated(Node::UnionType(items));
+    }
+
+    fn type_variable(&mut self, name: &str) {
+        self.item_generated(Node::TypeVariable(name.into()));
+    }
+
+    fn literal_atom_type(&mut self, name: &str) {
+        self.item_generated(Node::AtomType(name.into()));
+    }
+
+    fn start_function<Name: AsRef<str>>(
+        &mut self,
+        location: SrcSpan,
+        name: &str,
+        _arity: usize,
+        arguments_names: impl IntoIterator<Item = (SrcSpan, Name)>,
+    ) -> Self::Function {
+        self.locate(location);
+        self.open(OpenKind::Function {
+            line: self.line,
+            name: Some(name.into()),
+            arguments: arguments(arguments_names),
+        });
+    }
+
+    fn start_anonymous_function<Name: AsRef<str>>(
+        &mut self,
+        location: SrcSpan,
+        arguments_names: impl IntoIterator<Item = (SrcSpan, Name)>,
+    ) -> Self::Function {
+        self.locate(location);
+        self.open(OpenKind::Function {
+            line: self.line,
+            name: None,
+            arguments: arguments(arguments_names),
+        });
+    }
+
+    fn end_function(&mut self, _function: Self::Function) {
+        let (kind, body) = self.close();
+        let OpenKind::Function {
+            line,
+            name,
+            arguments,
+        } = kind
+        else {
+            panic!("tried closing a function, but the innermost item is a different one");
+        };
+        match name {
+            Some(name) => self.functions.push(Function {
+                line,
+                name,
+                arguments,
+                body,
+            }),
+            None => self.item_generated(Node::Function { arguments, body }),
+        }
+    }
+
+    fn start_block(&mut self, location: SrcSpan) -> Self::Block {
+        self.locate(location);
+        self.open(OpenKind::Block);
+    }
+
+    fn end_block(&mut self, _block: Self::Block) {
+        let (_, items) = self.close();
+        self.item_generated(Node::Block(items));
+    }
+
+    fn start_remote_call(
+        &mut self,
+        location: SrcSpan,
+        module: ErlangModuleName,
+        function: &str,
+    ) -> Self::Call {
+        self.locate(location);
+        self.open(OpenKind::RemoteCall {
+            module: module.0,
+            function: function.into(),
+        });
+    }
+
+    fn start_call(&mut self, location: SrcSpan) -> Self::CalledExpression {
+        self.locate(location);
+        self.open(OpenKind::CalledExpression);
+    }
+
+    fn end_called_expression(&mut self, _called: Self::CalledExpression) -> Self::Call {
+        let (_, function) = self.close();
+        // The called expression is kept as the first item of the call, and
+        // all the following ones are its arguments.
+        self.open_items.push(OpenItem {
+            kind: OpenKind::Call,
+            items: function,
+        });
+    }
+
+    fn end_call(&mut self, _call: Self::Call) {
+        let (kind, mut items) = self.close();
+        let node = match kind {
+            OpenKind::RemoteCall { module, function } => Node::RemoteCall {
+                module,
+                function,
+                arguments: items,
+            },
+            OpenKind::Call => {
+                let function = items.remove(0);
+                Node::Call {
+                    function: Box::new(function),
+                    arguments: items,
+                }
+            }
+            kind => panic!("tried closing a call, but the innermost item is {kind:?}"),
+        };
+        self.item_generated(node);
+    }
+
+    fn start_tuple(&mut self, location: SrcSpan) -> Self::Tuple {
+        self.locate(location);
+        self.open(OpenKind::Tuple);
+    }
+
+    fn end_tuple(&mut self, _tuple: Self::Tuple) {
+        let (_, items) = self.close();
+        self.item_generated(Node::Tuple(items));
+    }
+
+    fn start_map(&mut self, location: SrcSpan) -> Self::Map {
+        self.locate(location);
+        self.open(OpenKind::Map);
+    }
+
+    fn end_map(&mut self, _map: Self::Map) {
+        let (_, items) = self.close();
+        let fields = items
+            .into_iter()
+            .map(|field| match field {
+                Node::MapField(key, value) => (*key, *value),
+                _ => panic!("map items must be map fields"),
+            })
+            .collect();
+        self.item_generated(Node::Map(fields));
+    }
+
+    fn map_field(&mut self, location: SrcSpan) {
+        self.locate(location);
+        self.open(OpenKind::MapField);
+    }
+
+    fn start_bit_array(&mut self, location: SrcSpan) -> Self::BitArray {
+        self.locate(location);
+        self.open(OpenKind::BitArray);
+    }
+
+    fn end_bit_array(&mut self, _bit_array: Self::BitArray) {
+        let (_, items) = self.close();
+        let segments = items
+            .into_iter()
+            .map(|segment| match segment {
+                Node::Segment(segment) => *segment,
+                _ => panic!("bit array items must be segments"),
+            })
+            .collect();
+        self.item_generated(Node::BitArray(segments));
+    }
+
+    fn bit_array_segment(&mut self, location: SrcSpan) {
+        self.locate(location);
+        self.open(OpenKind::BitArraySegment);
+    }
+
+    fn bit_array_segment_default_size(&mut self) {
+        self.item_generated(Node::DefaultSegmentSize);
+    }
+
+    fn bit_array_segment_specifiers(
+        &mut self,
+        specifiers: impl IntoIterator<Item = BitArraySegmentSpecifier>,
+    ) {
+        let mut options = SegmentOptions {
+            type_: SegmentType::Integer,
+            signed: false,
+            endianness: "big",
+            unit: None,
+        };
+        for specifier in specifiers {
+            match specifier {
+                BitArraySegmentSpecifier::Utf8 => options.type_ = SegmentType::Utf8,
+                BitArraySegmentSpecifier::Utf16 => options.type_ = SegmentType::Utf16,
+                BitArraySegmentSpecifier::Utf32 => options.type_ = SegmentType::Utf32,
+                BitArraySegmentSpecifier::Integer => options.type_ = SegmentType::Integer,
+                BitArraySegmentSpecifier::Float => options.type_ = SegmentType::Float,
+                BitArraySegmentSpecifier::Binary => options.type_ = SegmentType::Binary,
+                BitArraySegmentSpecifier::Bitstring => options.type_ = SegmentType::Bitstring,
+                BitArraySegmentSpecifier::Signed => options.signed = true,
+                BitArraySegmentSpecifier::Unsigned => options.signed = false,
+                BitArraySegmentSpecifier::Little => options.endianness = "little",
+                BitArraySegmentSpecifier::Big => options.endianness = "big",
+                BitArraySegmentSpecifier::Native => options.endianness = "native",
+                BitArraySegmentSpecifier::Unit(unit) => options.unit = Some(unit),
+            }
+        }
+        self.item_generated(Node::SegmentOptions(options));
+    }
+
+    fn cons_list(&mut self, location: SrcSpan) {
+        self.locate(location);
+        self.open(OpenKind::Cons);
+    }
+
+    fn empty_list(&mut self, location: SrcSpan) {
+        self.locate(location);
+        self.item_generated(Node::EmptyList);
+    }
+
+    fn start_case(&mut self, location: SrcSpan) -> Self::CaseSubject {
+        self.locate(location);
+        self.open(OpenKind::CaseSubject);
+    }
+
+    fn end_case_subject(&mut self, _case: Self::CaseSubject) -> Self::Case {
+        let (_, subject) = self.close();
+        // The subject is kept as the first item of the case, and all the
+        // following ones are its clauses.
+        self.open_items.push(OpenItem {
+            kind: OpenKind::Case,
+            items: subject,
+        });
+    }
+
+    fn end_case(&mut self, _case: Self::Case) {
+        let (_, mut items) = self.close();
+        let subject = items.remove(0);
+        let clauses = items
+            .into_iter()
+            .map(|clause| match clause {
+                Node::Clause(clause) => *clause,
+                _ => panic!("case items must be clauses"),
+            })
+            .collect();
+        self.item_generated(Node::Case {
+            subject: Box::new(subject),
+            clauses,
+        });
+    }
+
+    fn start_case_clause(&mut self, location: SrcSpan) -> Self::ClausePattern {
+        self.locate(location);
+        self.open(OpenKind::ClausePattern);
+    }
+
+    fn end_clause_pattern(&mut self, _clause_pattern: Self::ClausePattern) -> Self::ClauseGuards {
+        let (_, pattern) = self.close();
+        self.open_items.push(OpenItem {
+            kind: OpenKind::ClauseGuards,
+            items: pattern,
+        });
+    }
+
+    fn start_clause_guard(&mut self) -> Self::Guard {
+        self.open(OpenKind::Guard);
+    }
+
+    fn end_clause_guard(&mut self, _clause_guard: Self::Guard) {
+        let (_, expressions) = self.close();
+        self.item_generated(Node::Guard(expressions));
+    }
+
+    fn end_clause_guards(&mut self, _clause_guards: Self::ClauseGuards) -> Self::ClauseBody {
+        let (_, mut items) = self.close();
+        let pattern = items.remove(0);
+        let guards = items
+            .into_iter()
+            .map(|guard| match guard {
+                Node::Guard(expressions) => expressions,
+                _ => panic!("clause guards must be guards"),
+            })
+            .collect();
+        self.open(OpenKind::ClauseBody { pattern, guards });
+    }
+
+    fn end_clause_body(&mut self, _clause_body: Self::ClauseBody) {
+        let (kind, body) = self.close();
+        let OpenKind::ClauseBody { pattern, guards } = kind else {
+            panic!("tried closing a clause body, but the innermost item is a different one");
+        };
+        self.item_generated(Node::Clause(Box::new(Clause {
+            pattern,
+            guards,
+            body,
+        })));
+    }
+
+    fn variable(&mut self, location: SrcSpan, name: &str) {
+        self.locate(location);
+        self.item_generated(Node::Variable(name.into()));
+    }
+
+    fn unary_operator(&mut self, location: SrcSpan, operator: &str) {
+        self.locate(location);
+        self.open(OpenKind::UnaryOperator(operator.into()));
+    }
+
+    fn binary_operator(&mut self, location: SrcSpan, operator: &'static str) {
+        self.locate(location);
+        self.open(OpenKind::BinaryOperator(operator));
+    }
+
+    fn function_reference(
+        &mut self,
+        location: SrcSpan,
+        module: Option<ErlangModuleName>,
+        name: &str,
+        arity: usize,
+    ) {
+        self.locate(location);
+        self.item_generated(Node::FunctionReference {
+            module: module.map(|module| module.0),
+            name: name.into(),
+            arity,
+        });
+    }
+
+    fn match_operator(&mut self, location: SrcSpan) {
+        self.locate(location);
+        self.open(OpenKind::Match);
+    }
+
+    fn match_pattern(&mut self, location: SrcSpan) {
+        self.locate(location);
+        self.open(OpenKind::Match);
+    }
+
+    fn variable_pattern(&mut self, location: SrcSpan, name: &str) {
+        self.variable(location, name);
+    }
+
+    fn discard_pattern(&mut self, location: SrcSpan) {
+        self.locate(location);
+        self.item_generated(Node::Discard);
+    }
+
+    fn int_pattern(&mut self, location: SrcSpan, number: BigInt) {
+        self.int(location, number);
+    }
+
+    fn float_pattern(&mut self, location: SrcSpan, number: f64) {
+        self.float(location, number);
+    }
+
+    fn string_pattern(&mut self, location: SrcSpan, content: &str) {
+        self.string(location, content);
+    }
+
+    fn atom_pattern(&mut self, location: SrcSpan, name: &str) {
+        self.atom(location, name);
+    }
+
+    fn start_tuple_pattern(&mut self, location: SrcSpan) -> Self::TuplePattern {
+        self.start_tuple(location)
+    }
+
+    fn end_tuple_pattern(&mut self, tuple: Self::TuplePattern) {
+        self.end_tuple(tuple)
+    }
+
+    fn start_bit_array_pattern(&mut self, location: SrcSpan) -> Self::BitArrayPattern {
+        self.start_bit_array(location)
+    }
+
+    fn end_bit_array_pattern(&mut self, bit_array: Self::BitArrayPattern) {
+        self.end_bit_array(bit_array)
+    }
+
+    fn cons_list_pattern(&mut self, location: SrcSpan) {
+        self.cons_list(location)
+    }
+
+    fn empty_list_pattern(&mut self, location: SrcSpan) {
+        self.empty_list(location)
+    }
+
+    fn string(&mut self, location: SrcSpan, content: &str) {
+        self.locate(location);
+        self.item_generated(Node::String(unescape_string_content(content)));
+    }
+
+    fn int(&mut self, location: SrcSpan, value: BigInt) {
+        self.locate(location);
+        self.item_generated(Node::Int(value));
+    }
+
+    fn float(&mut self, location: SrcSpan, value: f64) {
+        self.locate(location);
+        self.item_generated(Node::Float(value));
+    }
+
+    fn atom(&mut self, location: SrcSpan, name: &str) {
+        self.locate(location);
+        self.item_generated(Node::Atom(name.into()));
+    }
+}
+
+impl ErlangCoreBuilder {
+    fn open(&mut self, kind: OpenKind) {
+        self.open_items.push(OpenItem {
+            kind,
+            items: vec![],
+        });
+    }
+
+    /// Closes the innermost open item, returning the items generated inside
+    /// it.
+    fn close(&mut self) -> (OpenKind, Vec<Node>) {
+        let OpenItem { kind, items } = self
+            .open_items
+            .pop()
+            .expect("tried closing an item, but there's no open item");
+        (kind, items)
+    }
+
+    /// This has to be called every time a complete item has been generated.
+    /// It's added to the innermost open item, closing it if it now has all its
+    /// operands.
+    fn item_generated(&mut self, node: Node) {
+        let Some(open_item) = self.open_items.last_mut() else {
+            panic!("item generated outside of a function or attribute");
+        };
+        open_item.items.push(node);
+        if open_item.kind.operands() != Some(open_item.items.len()) {
+            return;
+        }
+
+        let (kind, items) = self.close();
+        let mut items = items.into_iter();
+        let mut operand = || {
+            Box::new(
+                items
+                    .next()
+                    .expect("item with operands closed before they were generated"),
+            )
+        };
+        let node = match kind {
+            OpenKind::UnaryOperator(operator) => Node::UnaryOperator {
+                operator,
+                value: operand(),
+            },
+            OpenKind::BinaryOperator(operator) => Node::BinaryOperator {
+                operator,
+                left: operand(),
+                right: operand(),
+            },
+            OpenKind::MapField => Node::MapField(operand(), operand()),
+            OpenKind::Cons => Node::Cons(operand(), operand()),
+            OpenKind::Match => Node::Match(operand(), operand()),
+            OpenKind::RecordField => Node::RecordField,
+            OpenKind::BitArraySegment => {
+                let value = *operand();
+                let size = match *operand() {
+                    Node::DefaultSegmentSize => None,
+                    size => Some(size),
+                };
+                let Node::SegmentOptions(options) = *operand() else {
+                    panic!("the last item of a bit array segment must be its specifiers");
+                };
+                Node::Segment(Box::new(Segment {
+                    value,
+                    size,
+                    options,
+                }))
+            }
+            kind => panic!("{kind:?} has no operands"),
+        };
+        self.item_generated(node);
+    }
+
+    /// Updates the line of the latest item with a location. Generated code
+    /// that doesn't come from the source file has a blank location, in that
+    /// case we keep the line of the enclosing item.
+    fn locate(&mut self, location: SrcSpan) {
+        if location == SrcSpan::default() {
+            return;
+        }
+        if let Some(line_numbers) = &self.line_numbers {
+            self.line = line_numbers.line_number(location.start);
+        }
+    }
+}
+
+fn arguments<Name: AsRef<str>>(
+    arguments_names: impl IntoIterator<Item = (SrcSpan, Name)>,
+) -> Vec<EcoString> {
+    arguments_names
+        .into_iter()
+        .map(|(_, name)| name.as_ref().into())
+        .collect()
+}
+
+fn single(items: Vec<Node>) -> Node {
+    items
+        .into_iter()
+        .exactly_one()
+        .unwrap_or_else(|_| panic!("expected a single item"))
+}
+
+const INDENT: usize = 4;
+
+/// Prints the tree collected by the builder as Core Erlang code.
+struct Printer {
+    code: String,
+    indentation: usize,
+    /// Guards can only hold a limited set of expressions, so some Erlang
+    /// constructs are translated differently when inside one.
+    in_guard: bool,
+    /// The number of variables generated so far, used to give each of them a
+    /// unique name.
+    generated_variables: usize,
+}
+
+impl Printer {
+    fn module(
+        &mut self,
+        name: &str,
+        exports: &[(EcoString, usize)],
+        attributes: &[Attribute],
+        functions: &[Function],
+    ) {
+        self.code.push_str("module ");
+        self.atom(name);
+        self.code.push_str(" [");
+        for (index, (name, arity)) in exports.iter().enumerate() {
+            if index > 0 {
+                self.code.push_str(", ");
+            }
+            self.function_name(name, *arity);
+        }
+        self.code.push(']');
+
+        self.indented(|this| {
+            this.newline();
+            this.code.push_str("attributes [");
+            this.indented(|this| {
+                for (index, attribute) in attributes.iter().enumerate() {
+                    if index > 0 {
+                        this.code.push(',');
+                    }
+                    this.newline();
+                    this.attribute(attribute);
+                }
+            });
+            if !attributes.is_empty() {
+                this.newline();
+            }
+            this.code.push(']');
+        });
+
+        for function in functions {
+            self.code.push('\n');
+            self.newline();
+            self.code.push_str(&format!("%% Line {}", function.line));
+            self.newline();
+            self.function_name(&function.name, function.arguments.len());
+            self.code.push_str(" =");
+            self.indented(|this| {
+                this.newline();
+                this.function(&function.arguments, &function.body);
+            });
+        }
+
+        self.code.push_str("\n\nend\n");
+    }
+
+    fn attribute(&mut self, attribute: &Attribute) {
+        match attribute {
+            Attribute::File { file, line } => {
+                self.code.push_str("'file' = [{");
+                self.charlist(file);
+                self.code.push_str(&format!(", {line}}}]"));
+            }
+
+            Attribute::ExportType(types) => {
+                self.code.push_str("'export_type' = [");
+                for (index, (name, arity)) in types.iter().enumerate() {
+                    if index > 0 {
+                        self.code.push_str(", ");
+                    }
+                    self.code.push('{');
+                    self.atom(name);
+                    self.code.push_str(&format!(", {arity}}}"));
+                }
+                self.code.push(']');
+            }
+
+            Attribute::Spec {
+                line,
+                name,
+                arity,
+                type_,
+            } => {
+                self.code.push_str("'spec' = [{{");
+                self.atom(name);
+                self.code.push_str(&format!(", {arity}}}, ["));
+                self.type_(*line, type_);
+                self.code.push_str("]}]");
+            }
+
+            Attribute::Type {
+                line,
+                opaque,
+                name,
+                parameters,
+                type_,
+            } => {
+                self.atom(if *opaque { "opaque" } else { "type" });
+                self.code.push_str(" = [{");
+                self.atom(name);
+                self.code.push_str(", ");
+                self.type_(*line, type_);
+                self.code.push_str(", [");
+                for (index, parameter) in parameters.iter().enumerate() {
+                    if index > 0 {
+                        self.code.push_str(", ");
+                    }
+                    self.type_(*line, &Node::TypeVariable(parameter.clone()));
+                }
+                self.code.push_str("]}]");
+            }
+        }
+    }
+
+    /// Types are part of the module attributes, where they're kept in the
+    /// same representation used by the Erlang abstract format.
+    fn type_(&mut self, line: u32, type_: &Node) {
+        match type_ {
+            Node::FunctionType { arguments, return_ } => {
+                self.code.push_str(&format!(
+                    "{{'type', {line}, 'fun', [{{'type', {line}, 'product', "
+                ));
+                self.types(line, arguments);
+                self.code.push_str("}, ");
+                self.type_(line, return_);
+                self.code.push_str("]}");
+            }
+            Node::NamedType { name, arguments } => {
+                let tag = if is_builtin_type(name) {
+                    "type"
+                } else {
+                    "user_type"
+                };
+                self.code.push_str(&format!("{{'{tag}', {line}, "));
+                self.atom(name);
+                self.code.push_str(", ");
+                self.types(line, arguments);
+                self.code.push('}');
+            }
+            Node::RemoteNamedType {
+                module,
+                name,
+                arguments,
+            } => {
+                self.code
+                    .push_str(&format!("{{'remote_type', {line}, [{{'atom', {line}, "));
+                self.atom(module);
+                self.code.push_str(&format!("}}, {{'atom', {line}, "));
+                self.atom(name);
+                self.code.push_str("}, ");
+                self.types(line, arguments);
+                self.code.push_str("]}");
+            }
+            Node::TupleType(items) => {
+                self.code.push_str(&format!("{{'type', {line}, 'tuple', "));
+                self.types(line, items);
+                self.code.push('}');
+            }
+            Node::UnionType(alternatives) => {
+                self.code.push_str(&format!("{{'type', {line}, 'union', "));
+                self.types(line, alternatives);
+                self.code.push('}');
+            }
+            Node::TypeVariable(name) => {
+                self.code.push_str(&format!("{{'var', {line}, "));
+                self.atom(name);
+                self.code.push('}');
+            }
+            Node::AtomType(name) => {
+                self.code.push_str(&format!("{{'atom', {line}, "));
+                self.atom(name);
+                self.code.push('}');
+            }
+            _ => panic!("expected a type, got {type_:?}"),
+        }
+    }
+
+    fn types(&mut self, line: u32, types: &[Node]) {
+        self.code.push('[');
+        for (index, type_) in types.iter().enumerate() {
+            if index > 0 {
+                self.code.push_str(", ");
+            }
+            self.type_(line, type_);
+        }
+        self.code.push(']');
+    }
+
+    fn function(&mut self, arguments: &[EcoString], body: &[Node]) {
+        let arguments = arguments
+            .iter()
+            .map(|argument| {
+                if argument == "_" {
+                    self.generated_variable()
+                } else {
+                    argument.clone()
+                }
+            })
+            .collect_vec();
+        self.code
+            .push_str(&format!("fun ({}) ->", arguments.iter().join(", ")));
+        self.indented(|this| {
+            this.newline();
+            this.sequence(body);
+        });
+    }
+
+    /// Prints a sequence of statements. Each match is turned into an
+    /// expression binding its variables in all the statements that follow it.
+    fn sequence(&mut self, statements: &[Node]) {
+        match statements {
+            [] => panic!("empty sequence of statements"),
+            [statement] => self.expression(statement),
+            [Node::Match(pattern, value), rest @ ..] => self.bind(pattern, value, rest),
+            [statement, rest @ ..] => {
+                self.code.push_str("do ");
+                self.indented(|this| this.expression(statement));
+                self.newline();
+                self.sequence(rest);
+            }
+        }
+    }
+
+    fn bind(&mut self, pattern: &Node, value: &Node, rest: &[Node]) {
+        match (pattern, value) {
+            // `X = Y = Value` binds both variables, so the inner match is
+            // bound first, and its variable is then matched with the outer
+            // pattern.
+            (_, Node::Match(inner_pattern, inner_value))
+                if matches!(inner_pattern.as_ref(), Node::Variable(_)) =>
+            {
+                let mut statements = vec![Node::Match(
+                    Box::new(pattern.clone()),
+                    inner_pattern.clone(),
+                )];
+                statements.extend(rest.iter().cloned());
+                self.bind(inner_pattern, inner_value, &statements);
+            }
+
+            (Node::Variable(name), _) => {
+                self.code.push_str(&format!("let <{name}> = "));
+                self.indented(|this| this.expression(value));
+                self.code.push_str(" in");
+                self.newline();
+                self.sequence(rest);
+            }
+
+            (Node::Discard, _) => {
+                self.code.push_str("do ");
+                self.indented(|this| this.expression(value));
+                self.newline();
+                self.sequence(rest);
+            }
+
+            (_, _) => {
+                self.code.push_str("case ");
+                self.indented(|this| this.expression(value));
+                self.code.push_str(" of");
+                self.indented(|this| {
+                    this.newline();
+                    this.code.push('<');
+                    this.pattern(pattern);
+                    this.code.push_str("> when 'true' ->");
+                    this.indented(|this| {
+                        this.newline();
+                        this.sequence(rest);
+                    });
+                    this.match_fail("badmatch");
+                });
+                self.newline();
+                self.code.push_str("end");
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Node) {
+        match expression {
+            Node::Variable(name) => self.code.push_str(name),
+            Node::Atom(name) => self.atom(name),
+            Node::Int(value) => self.code.push_str(&value.to_string()),
+            Node::Float(value) => self.code.push_str(&format_float(*value)),
+            Node::String(content) => self.binary_string(content),
+            Node::EmptyList | Node::Cons(..) => self.list(expression, Self::expression),
+            Node::Tuple(items) => {
+                self.code.push('{');
+                self.comma_separated(items, Self::expression);
+                self.code.push('}');
+            }
+            Node::Map(fields) => {
+                self.code.push_str("~{");
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        self.code.push_str(", ");
+                    }
+                    self.expression(key);
+                    self.code.push_str("=>");
+                    self.expression(value);
+                }
+                self.code.push_str("}~");
+            }
+            Node::BitArray(segments) => self.bit_array(segments, Self::expression),
+            Node::Block(statements) => self.sequence(statements),
+            Node::Function { arguments, body } => self.function(arguments, body),
+            Node::FunctionReference {
+                module: None,
+                name,
+                arity,
+            } => self.function_name(name, *arity),
+            Node::FunctionReference {
+                module: Some(module),
+                name,
+                arity,
+            } => {
+                self.code.push_str("call 'erlang':'make_fun'(");
+                self.atom(module);
+                self.code.push_str(", ");
+                self.atom(name);
+                self.code.push_str(&format!(", {arity})"));
+            }
+            Node::RemoteCall {
+                module,
+                function,
+                arguments,
+            } => self.remote_call(module, function, arguments),
+            Node::Call {
+                function,
+                arguments,
+            } => self.call(function, arguments),
+            Node::Case { subject, clauses } => self.case(subject, clauses),
+            Node::UnaryOperator { operator, value } => {
+                self.remote_call("erlang", operator, std::slice::from_ref(value.as_ref()))
+            }
+            Node::BinaryOperator {
+                operator,
+                left,
+                right,
+            } => self.binary_operator(operator, left, right),
+            Node::Match(pattern, value) => self.match_expression(pattern, value),
+
+            Node::Discard
+            | Node::FunctionType { .. }
+            | Node::NamedType { .. }
+            | Node::RemoteNamedType { .. }
+            | Node::TupleType(_)
+            | Node::UnionType(_)
+            | Node::TypeVariable(_)
+            | Node::AtomType(_)
+            | Node::MapField(..)
+            | Node::Segment(_)
+            | Node::DefaultSegmentSize
+            | Node::SegmentOptions(_)
+            | Node::Clause(_)
+            | Node::Guard(_)
+            | Node::RecordField => panic!("expected an expression, got {expression:?}"),
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Node) {
+        match pattern {
+            Node::Variable(name) if name != "_" => self.code.push_str(name),
+            Node::Variable(_) | Node::Discard => {
+                // Core Erlang has no discard pattern, every value that is not
+                // used is bound to a new variable.
+                let variable = self.generated_variable();
+                self.code.push_str(&variable);
+            }
+            Node::Atom(name) => self.atom(name),
+            Node::Int(value) => self.code.push_str(&value.to_string()),
+            Node::Float(value) => self.code.push_str(&format_float(*value)),
+            Node::String(content) => self.binary_string(content),
+            Node::EmptyList | Node::Cons(..) => self.list(pattern, Self::pattern),
+            Node::Tuple(items) => {
+                self.code.push('{');
+                self.comma_separated(items, Self::pattern);
+                self.code.push('}');
+            }
+            Node::BitArray(segments) => self.bit_array(segments, Self::pattern),
+            // An alias pattern must always have a variable on its left-hand
+            // side. Gleam's `as` patterns have it on the right-hand side.
+            Node::Match(left, right) => match (left.as_ref(), right.as_ref()) {
+                (Node::Variable(name), pattern) | (pattern, Node::Variable(name)) => {
+                    self.code.push_str(&format!("{name} = "));
+                    self.pattern(pattern);
+                }
+                (_, _) => panic!("an alias pattern must bind a variable"),
+            },
+            _ => panic!("expected a pattern, got {pattern:?}"),
+        }
+    }
+
+    /// A match used as an expression evaluates to the matched value.
+    fn match_expression(&mut self, pattern: &Node, value: &Node) {
+        match pattern {
+            Node::Variable(name) => {
+                self.code.push_str(&format!("let <{name}> = "));
+                self.indented(|this| this.expression(value));
+                self.code.push_str(&format!(" in {name}"));
+            }
+            Node::Discard => self.expression(value),
+            _ => {
+                let matched = self.generated_variable();
+                self.code.push_str("case ");
+                self.indented(|this| this.expression(value));
+                self.code.push_str(" of");
+                self.indented(|this| {
+                    this.newline();
+                    this.code.push_str(&format!("<{matched} = "));
+                    this.pattern(pattern);
+                    this.code.push_str("> when 'true' ->");
+                    this.indented(|this| {
+                        this.newline();
+                        this.code.push_str(&matched);
+                    });
+                    this.match_fail("badmatch");
+                });
+                self.newline();
+                self.code.push_str("end");
+            }
+        }
+    }
+
+    fn case(&mut self, subject: &Node, clauses: &[Clause]) {
+        self.code.push_str("case ");
+        self.indented(|this| this.expression(subject));
+        self.code.push_str(" of");
+        self.indented(|this| {
+            for clause in clauses {
+                this.newline();
+                this.code.push('<');
+                this.pattern(&clause.pattern);
+                this.code.push_str("> when ");
+                this.guards(&clause.guards);
+                this.code.push_str(" ->");
+                this.indented(|this| {
+                    this.newline();
+                    this.sequence(&clause.body);
+                });
+            }
+
+            // A case expression with no matching clause is not allowed in Core
+            // Erlang, so unless the last clause always matches we add one
+            // raising the same error Erlang would.
+            let always_matches = clauses.last().is_some_and(|clause| {
+                clause.guards.is_empty()
+                    && matches!(clause.pattern, Node::Variable(_) | Node::Discard)
+            });
+            if !always_matches {
+                this.match_fail("case_clause");
+            }
+        });
+        self.newline();
+        self.code.push_str("end");
+    }
+
+    /// A clause that matches any value, failing with the given error.
+    fn match_fail(&mut self, error: &str) {
+        let value = self.generated_variable();
+        self.newline();
+        self.code.push_str(&format!("<{value}> when 'true' ->"));
+        self.indented(|this| {
+            this.newline();
+            this.code
+                .push_str(&format!("primop 'match_fail'({{'{error}', {value}}})"));
+        });
+    }
+
+    fn guards(&mut self, guards: &[Vec<Node>]) {
+        if guards.is_empty() {
+            self.code.push_str("'true'");
+            return;
+        }
+
+        self.in_guard = true;
+        let alternatives = guards
+            .iter()
+            .map(|guard| {
+                guard
+                    .iter()
+                    .cloned()
+                    .reduce(|left, right| Node::BinaryOperator {
+                        operator: "and",
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    })
+                    .unwrap_or_else(|| Node::Atom("true".into()))
+            })
+            .reduce(|left, right| Node::BinaryOperator {
+                operator: "or",
+                left: Box::new(left),
+                right: Box::new(right),
+            });
+        if let Some(guard) = alternatives {
+            self.expression(&guard);
+        }
+        self.in_guard = false;
+    }
+
+    fn binary_operator(&mut self, operator: &str, left: &Node, right: &Node) {
+        let short_circuit = match operator {
+            "andalso" => "false",
+            "orelse" => "true",
+            _ => {
+                self.remote_call("erlang", operator, &[left.clone(), right.clone()]);
+                return;
+            }
+        };
+
+        // Guards can't hold case expressions, but they have no side effects
+        // so both sides can always be evaluated.
+        if self.in_guard {
+            let operator = if operator == "andalso" { "and" } else { "or" };
+            self.remote_call("erlang", operator, &[left.clone(), right.clone()]);
+            return;
+        }
+
+        let (on_true, on_false) = match short_circuit {
+            "false" => (right.clone(), Node::Atom("false".into())),
+            _ => (Node::Atom("true".into()), right.clone()),
+        };
+        let not_a_bool = self.generated_variable();
+        self.code.push_str("case ");
+        self.indented(|this| this.expression(left));
+        self.code.push_str(" of");
+        self.indented(|this| {
+            for (value, body) in [("true", on_true), ("false", on_false)] {
+                this.newline();
+                this.code.push_str(&format!("<'{value}'> when 'true' ->"));
+                this.indented(|this| {
+                    this.newline();
+                    this.expression(&body);
+                });
+            }
+            this.newline();
+            this.code
+                .push_str(&format!("<{not_a_bool}> when 'true' ->"));
+            this.indented(|this| {
+                this.newline();
+                this.code.push_str(&format!(
+                    "call 'erlang':'error'({{'badarg', {not_a_bool}}})"
+                ));
+            });
+        });
+        self.newline();
+        self.code.push_str("end");
+    }
+
+    fn remote_call(&mut self, module: &str, function: &str, arguments: &[Node]) {
+        self.code.push_str("call ");
+        self.atom(module);
+        self.code.push(':');
+        self.atom(function);
+        self.arguments(arguments);
+    }
+
+    fn call(&mut self, function: &Node, arguments: &[Node]) {
+        match function {
+            // Guards can only call built in functions, which are all defined
+            // in the `erlang` module.
+            Node::Atom(name) if self.in_guard => self.remote_call("erlang", name, arguments),
+            Node::Atom(name) => {
+                self.code.push_str("apply ");
+                self.function_name(name, arguments.len());
+                self.arguments(arguments);
+            }
+            Node::FunctionReference {
+                module: Some(module),
+                name,
+                ..
+            } => self.remote_call(module, name, arguments),
+            _ => {
+                self.code.push_str("apply ");
+                self.expression(function);
+                self.arguments(arguments);
+            }
+        }
+    }
+
+    fn arguments(&mut self, arguments: &[Node]) {
+        self.code.push('(');
+        self.comma_separated(arguments, Self::expression);
+        self.code.push(')');
+    }
+
+    /// Prints a list, using the `[A, B | Tail]` syntax to avoid nesting each
+    /// of its items.
+    fn list(&mut self, list: &Node, item: fn(&mut Self, &Node)) {
+        let mut elements = vec![];
+        let mut tail = list;
+        while let Node::Cons(head, rest) = tail {
+            elements.push(head.as_ref());
+            tail = rest;
+        }
+
+        self.code.push('[');
+        for (index, element) in elements.iter().enumerate() {
+            if index > 0 {
+                self.code.push_str(", ");
+            }
+            item(self, element);
+        }
+        if !matches!(tail, Node::EmptyList) {
+            self.code.push('|');
+            item(self, tail);
+        }
+        self.code.push(']');
+    }
+
+    fn bit_array(&mut self, segments: &[Segment], value: fn(&mut Self, &Node)) {
+        self.code.push_str("#{");
+        let mut first = true;
+        for segment in segments {
+            // A string segment is the same as a segment for each of its
+            // characters, and Core Erlang has no shorthand for it.
+            let values = match &segment.value {
+                Node::String(content) => content
+                    .chars()
+                    .map(|character| Node::Int(BigInt::from(character as u32)))
+                    .collect_vec(),
+                other => vec![other.clone()],
+            };
+            for segment_value in values {
+                if !first {
+                    self.code.push_str(", ");
+                }
+                first = false;
+                self.code.push_str("#<");
+                value(self, &segment_value);
+                self.code.push_str(">(");
+                self.segment_options(segment);
+                self.code.push(')');
+            }
+        }
+        self.code.push_str("}#");
+    }
+
+    fn segment_options(&mut self, segment: &Segment) {
+        let SegmentOptions {
+            type_,
+            signed,
+            endianness,
+            unit,
+        } = segment.options;
+
+        match &segment.size {
+            Some(size) => self.expression(size),
+            None => self.code.push_str(match type_ {
+                SegmentType::Integer => "8",
+                SegmentType::Float => "64",
+                SegmentType::Binary | SegmentType::Bitstring => "'all'",
+                SegmentType::Utf8 | SegmentType::Utf16 | SegmentType::Utf32 => "'undefined'",
+            }),
+        }
+
+        let unit = match (unit, type_) {
+            (_, _) if type_.is_utf() => "'undefined'".into(),
+            (Some(unit), _) => unit.to_string(),
+            (None, SegmentType::Binary) => "8".into(),
+            (None, _) => "1".into(),
+        };
+        let signedness = if signed { "signed" } else { "unsigned" };
+        self.code.push_str(&format!(
+            ", {unit}, '{}', ['{signedness}', '{endianness}']",
+            type_.name()
+        ));
+    }
+
+    /// Gleam strings are UTF-8 encoded binaries, written as a segment for each
+    /// of their bytes.
+    fn binary_string(&mut self, content: &str) {
+        self.code.push_str("#{");
+        for (index, byte) in content.bytes().enumerate() {
+            if index > 0 {
+                self.code.push_str(", ");
+            }
+            self.code
+                .push_str(&format!("#<{byte}>(8, 1, 'integer', ['unsigned', 'big'])"));
+        }
+        self.code.push_str("}#");
+    }
+
+    /// Prints a plain Erlang string, that is a list of characters.
+    fn charlist(&mut self, content: &str) {
+        if content.chars().all(|c| c.is_ascii_graphic() || c == ' ') {
+            self.code.push('"');
+            for character in content.chars() {
+                if character == '"' || character == '\\' {
+                    self.code.push('\\');
+                }
+                self.code.push(character);
+            }
+            self.code.push('"');
+        } else {
+            let characters = content.chars().map(|c| c as u32).join(", ");
+            self.code.push_str(&format!("[{characters}]"));
+        }
+    }
+
+    fn comma_separated(&mut self, items: &[Node], item: fn(&mut Self, &Node)) {
+        for (index, element) in items.iter().enumerate() {
+            if index > 0 {
+                self.code.push_str(", ");
+            }
+            item(self, element);
+        }
+    }
+
+    fn function_name(&mut self, name: &str, arity: usize) {
+        self.atom(name);
+        self.code.push_str(&format!("/{arity}"));
+    }
+
+    /// Atoms are always quoted in Core Erlang.
+    fn atom(&mut self, name: &str) {
+        self.code.push('\'');
+        for character in name.chars() {
+            if character == '\'' || character == '\\' {
+                self.code.push('\\');
+            }
+            self.code.push(character);
+        }
+        self.code.push('\'');
+    }
+
+    fn generated_variable(&mut self) -> EcoString {
+        let name = eco_format!("_@c{}", self.generated_variables);
+        self.generated_variables += 1;
+        name
+    }
+
+    fn newline(&mut self) {
+        self.code.push('\n');
+        self.code.push_str(&" ".repeat(self.indentation));
+    }
+
+    fn indented(&mut self, f: impl FnOnce(&mut Self)) {
+        self.indentation += INDENT;
+        f(self);
+        self.indentation -= INDENT;
+    }
+}
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+// The expected output in these tests can be compared to what the Erlang
+// compiler produces for the equivalent Erlang code, by compiling it with
+// `erlc +to_core wibble.erl`. The compiler's output will have a few more
+// annotations and its generated variables will be named differently, but the
+// structure of the code should be the same.
+//
+
+// The builder's tokens are all unit values, but we still want to pass them
+// around to show how the builder is meant to be used.
+#![allow(clippy::let_unit_value)]
+
+use super::*;
+
+#[test]
+fn empty_module() {
+    let mut builder = ErlangCoreBuilder::new(Some(ErlangModuleName::new("wibble/wobble")));
+    builder.compile_attribute(["no_auto_import"]);
+    builder.moduledoc_attribute(DocContent::String(" Some docs\n"));
+    builder.export_attribute::<&str>([]);
+
+    assert_eq!(
+        builder.into_output(),
+        "module 'wibble@wobble' []
+    attributes []
+
+end
+"
+    );
+}
+
+#[test]
+fn module_attributes() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangCoreBuilder::new(Some(ErlangModuleName::new("wibble")));
+    builder.export_attribute([("main", 0)]);
+    builder.export_type_attribute([("wobble", 1)]);
+    builder.file_attribute("src/wibble.gleam", 3);
+
+    // -type wobble(A) :: {A, nil}.
+    let type_spec = builder.start_type_spec(false, "wobble", ["A"]);
+    let tuple = builder.start_tuple_type();
+    builder.type_variable("A");
+    builder.literal_atom_type("nil");
+    builder.end_tuple_type(tuple);
+    builder.end_type_spec(type_spec);
+
+    // -spec main() -> integer().
+    let spec = builder.start_function_spec("main", 0);
+    let function_type = builder.start_function_type();
+    let function_type = builder.end_function_type_arguments(function_type);
+    let named_type = builder.start_named_type("integer");
+    builder.end_named_type(named_type);
+    builder.end_function_type(function_type);
+    builder.end_function_spec(spec);
+
+    let function = builder.start_function::<&str>(location, "main", 0, []);
+    builder.int(location, 1.into());
+    builder.end_function(function);
+
+    assert_eq!(
+        builder.into_output(),
+        r#"module 'wibble' ['main'/0]
+    attributes [
+        'export_type' = [{'wobble', 1}],
+        'file' = [{"src/wibble.gleam", 3}],
+        'type' = [{'wobble', {'type', 3, 'tuple', [{'var', 3, 'A'}, {'atom', 3, 'nil'}]}, [{'var', 3, 'A'}]}],
+        'spec' = [{{'main', 0}, [{'type', 3, 'fun', [{'type', 3, 'product', []}, {'type', 3, 'integer', []}]}]}]
+    ]
+
+%% Line 3
+'main'/0 =
+    fun () ->
+        1
+
+end
+"#
+    );
+}
+
+#[test]
+fn matches_bind_the_following_statements() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangCoreBuilder::new(Some(ErlangModuleName::new("wibble")));
+    let function = builder.start_function(location, "main", 2, [(location, "A"), (location, "_")]);
+
+    // X = A + 1,
+    builder.match_operator(location);
+    builder.variable_pattern(location, "X");
+    builder.binary_operator(location, "+");
+    builder.variable(location, "A");
+    builder.int(location, 1.into());
+
+    // {ok, Y} = X,
+    builder.match_operator(location);
+    let tuple = builder.start_tuple_pattern(location);
+    builder.atom_pattern(location, "ok");
+    builder.variable_pattern(location, "Y");
+    builder.end_tuple_pattern(tuple);
+    builder.variable(location, "X");
+
+    // wobble(Y).
+    let call = builder.start_call(location);
+    builder.atom(location, "wobble");
+    let call = builder.end_called_expression(call);
+    builder.variable(location, "Y");
+    builder.end_call(call);
+
+    builder.end_function(function);
+
+    assert_eq!(
+        builder.into_output(),
+        "module 'wibble' []
+    attributes []
+
+%% Line 1
+'main'/2 =
+    fun (A, _@c0) ->
+        let <X> = call 'erlang':'+'(A, 1) in
+        case X of
+            <{'ok', Y}> when 'true' ->
+                apply 'wobble'/1(Y)
+            <_@c1> when 'true' ->
+                primop 'match_fail'({'badmatch', _@c1})
+        end
+
+end
+"
+    );
+}
+
+#[test]
+fn case_with_guards() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangCoreBuilder::new(Some(ErlangModuleName::new("wibble")));
+    let function = builder.start_function(location, "main", 1, [(location, "X")]);
+
+    // case X of
+    //     [A | _] when A > 1 -> A;
+    //     [] -> 0
+    // end.
+    let case = builder.start_case(location);
+    builder.variable(location, "X");
+    let case = builder.end_case_subject(case);
+
+    let pattern = builder.start_case_clause(location);
+    builder.cons_list_pattern(location);
+    builder.variable_pattern(location, "A");
+    builder.discard_pattern(location);
+    let guards = builder.end_clause_pattern(pattern);
+    let guard = builder.start_clause_guard();
+    builder.binary_operator(location, ">");
+    builder.variable(location, "A");
+    builder.int(location, 1.into());
+    builder.end_clause_guard(guard);
+    let body = builder.end_clause_guards(guards);
+    builder.variable(location, "A");
+    builder.end_clause_body(body);
+
+    let pattern = builder.start_case_clause(location);
+    builder.empty_list_pattern(location);
+    let guards = builder.end_clause_pattern(pattern);
+    let body = builder.end_clause_guards(guards);
+    builder.int(location, 0.into());
+    builder.end_clause_body(body);
+
+    builder.end_case(case);
+    builder.end_function(function);
+
+    assert_eq!(
+        builder.into_output(),
+        "module 'wibble' []
+    attributes []
+
+%% Line 1
+'main'/1 =
+    fun (X) ->
+        case X of
+            <[A|_@c0]> when call 'erlang':'>'(A, 1) ->
+                A
+            <[]> when 'true' ->
+                0
+            <_@c1> when 'true' ->
+                primop 'match_fail'({'case_clause', _@c1})
+        end
+
+end
+"
+    );
+}
+
+#[test]
+fn bit_arrays() {
+    let location = SrcSpan::default();
+    let mut builder = ErlangCoreBuilder::new(Some(ErlangModuleName::new("wibble")));
+    let function = builder.start_function(location, "main", 1, [(location, "X")]);
+
+    // <<X:16/little, "hi"/utf8>>.
+    let bit_array = builder.start_bit_array(location);
+    builder.bit_array_segment(location);
+    builder.variable(location, "X");
+    builder.int(location, 16.into());
+    builder.bit_array_segment_specifiers([BitArraySegmentSpecifier::Little]);
+    builder.bit_array_segment(location);
+    builder.string(location, "hi");
+    builder.bit_array_segment_default_size();
+    builder.bit_array_segment_specifiers([BitArraySegmentSpecifier::Utf8]);
+    builder.end_bit_array(bit_array);
+
+    builder.end_function(function);
+
+    assert_eq!(
+        builder.into_output(),
+        "module 'wibble' []
+    attributes []
+
+%% Line 1
+'main'/1 =
+    fun (X) ->
+        #{#<X>(16, 1, 'integer', ['unsigned', 'little']), #<104>('undefined', 'undefined', 'utf8', ['unsigned', 'big']), #<105>('undefined', 'undefined', 'utf8', ['unsigned', 'big'])}#
+
+end
+"
+    );
+}
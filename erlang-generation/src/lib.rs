@@ -1,12 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-FileCopyrightText: 2026 The Gleam contributors
 
+mod abstract_format;
+mod core_erlang;
+
+pub use abstract_format::ErlangAbstractFormatBuilder;
+pub use core_erlang::ErlangCoreBuilder;
+
 use ecow::EcoString;
 use itertools::Itertools;
 use num_bigint::BigInt;
 use num_traits::Zero;
 use regex::Regex;
-use src_span::SrcSpan;
+use src_span::{LineNumbers, SrcSpan};
 use std::sync::OnceLock;
 
 /// This is to raise an `unreachable` pretty printed error when we try producing
@@ -331,6 +337,16 @@ pub trait ErlangBuilder<Output> {
     ///
     fn file_attribute(&mut self, file: &str, line: u32);
 
+    /// Provides the line numbers of the Gleam source file the module is
+    /// generated from.
+    /// Builders can use these to annotate each expression and pattern with the
+    /// line of the Gleam code it comes from, using the locations passed to
+    /// the other functions.
+    /// Builders that have no per-expression annotations can ignore them and
+    /// only rely on the `-file` attributes.
+    ///
+    fn source_line_numbers(&mut self, line_numbers: LineNumbers);
+
     /// Starts a `-record` attribute.
     /// After this you're supposed to generate a sequence of `record_field`, and
     /// once you're done you must end it with `end_record_attribute`.
@@ -1945,6 +1961,11 @@ impl ErlangBuilder<String> for ErlangSourceBuilder {
         self.code.push_str(").");
     }
 
+    fn source_line_numbers(&mut self, _line_numbers: LineNumbers) {
+        // Erlang source code can't annotate individual expressions, the
+        // `-file` attributes are all it can point back to the Gleam code with.
+    }
+
     fn start_record_attribute(&mut self, record_name: &str) -> Self::RecordAttribute {
         self.new_top_level_form();
         self.code.push_str("-record(");
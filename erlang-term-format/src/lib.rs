@@ -158,8 +158,10 @@ impl TermBuilder {
             self.small_integer(value);
         } else if let Some(value) = value.to_i32() {
             self.integer(value);
-        } else {
+        } else if value.magnitude().bits() <= 255 * 8 {
             self.small_big(value);
+        } else {
+            self.large_big(value);
         }
     }
 
@@ -173,6 +175,29 @@ impl TermBuilder {
         }
     }
 
+    /// Pushes the most compact representation of the given string as an Erlang
+    /// charlist: a list with the code point of each of its characters.
+    ///
+    /// https://www.erlang.org/doc/apps/erts/erl_ext_dist.html#string_ext
+    pub fn charlist(&mut self, string: &str) {
+        if string.is_empty() {
+            self.empty_list();
+        } else if string.len() <= u16::MAX as usize && string.is_ascii() {
+            // Strings made of bytes have a compact representation of their own.
+            self.push(107);
+            self.extend((string.len() as u16).to_be_bytes());
+            self.extend(string.bytes());
+        } else {
+            let list = self.start_list();
+            let mut length = 0;
+            for character in string.chars() {
+                self.usize(character as usize);
+                length += 1;
+            }
+            self.end_list(list, length);
+        }
+    }
+
     /// https://www.erlang.org/doc/apps/erts/erl_ext_dist.html#small_integer_ext
     fn small_integer(&mut self, value: u8) {
         self.push(97);
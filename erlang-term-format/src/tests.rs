@@ -376,3 +376,30 @@ fn negative_large_big() {
         ]
     );
 }
+
+#[test]
+fn empty_charlist() {
+    let mut etf = TermBuilder::new();
+    etf.charlist("");
+    assert_eq!(etf.into_vec(), [131, 106])
+}
+
+#[test]
+fn ascii_charlist() {
+    let mut etf = TermBuilder::new();
+    etf.charlist("abc");
+    assert_eq!(etf.into_vec(), [131, 107, 0, 3, 97, 98, 99])
+}
+
+#[test]
+fn unicode_charlist() {
+    let mut etf = TermBuilder::new();
+    etf.charlist("ksiąskę");
+    assert_eq!(
+        etf.into_vec(),
+        [
+            131, 108, 0, 0, 0, 7, 97, 107, 97, 115, 97, 105, 98, 0, 0, 1, 5, 97, 115, 97, 107, 98,
+            0, 0, 1, 25, 106
+        ]
+    )
+}
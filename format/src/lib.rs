@@ -28,10 +28,10 @@ const INDENT: isize = 2;
 
 pub fn pretty(writer: &mut impl Utf8Writer, src: &EcoString, path: &Utf8Path) -> Result<()> {
     let parsed = gleam_core::parse::parse_module(path.to_owned(), src, &WarningEmitter::null())
-        .map_err(|error| Error::Parse {
+        .map_err(|errors| Error::Parse {
             path: path.to_path_buf(),
             src: src.clone(),
-            error: Box::new(error),
+            errors,
         })?;
     let intermediate = Intermediate::from_extra(&parsed.extra, src);
     let arena = DocumentArena::new();
@@ -209,12 +209,26 @@ impl<'a, 'doc> Formatter<'a> {
         definition: &'a TargetedDefinition,
     ) -> Document<'a, 'doc> {
         let target = definition.target;
+        let allowed_warnings = &definition.allowed_warnings;
         let definition = &definition.definition;
         let start = definition.location().start;
 
         let comments = self.pop_comments_with_position(start);
         let comments = self.printed_documented_comments(arena, comments);
         let document = self.documented_definition(arena, definition);
+        let document = allowed_warnings
+            .iter()
+            .rev()
+            .fold(document, |document, warning| {
+                docvec![
+                    arena,
+                    "@allow(",
+                    warning.as_str(),
+                    ")",
+                    LINE_DOCUMENT,
+                    document
+                ]
+            });
         let document = match target {
             None => document,
             Some(Target::Erlang) => {
@@ -223,6 +237,9 @@ impl<'a, 'doc> Formatter<'a> {
             Some(Target::JavaScript) => {
                 docvec![arena, "@target(javascript)", LINE_DOCUMENT, document]
             }
+            Some(Target::Wasm) => {
+                docvec![arena, "@target(wasm)", LINE_DOCUMENT, document]
+            }
         };
         let document = document.group(arena);
         match comments {
@@ -1049,20 +1066,24 @@ impl<'a, 'doc> Formatter<'a> {
             body,
             publicity,
             deprecation,
+            inline,
             return_annotation,
             return_type: _,
             documentation: _,
             external_erlang,
             external_javascript,
+            external_wasm,
             implementations: _,
             purity: _,
         } = function;
 
         let attributes = AttributesPrinter::new()
             .set_deprecation(deprecation)
+            .set_inline(inline.is_some())
             .set_internal(*publicity)
             .set_external_erlang(external_erlang)
             .set_external_javascript(external_javascript)
+            .set_external_wasm(external_wasm)
             .to_doc(arena);
 
         // Fn name and args
@@ -1281,6 +1302,11 @@ impl<'a, 'doc> Formatter<'a> {
                 message.as_deref(),
             ),
 
+            UntypedExpr::Todo {
+                kind: TodoKind::Hole,
+                ..
+            } => UNDERSCORE_DOCUMENT,
+
             UntypedExpr::Todo { message, .. } => self.append_as_message_expression(
                 arena,
                 TODO_DOCUMENT,
@@ -2314,6 +2340,7 @@ impl<'a, 'doc> Formatter<'a> {
             typed_parameters: _,
             external_erlang,
             external_javascript,
+            external_wasm,
         } = type_;
 
         let _ = self.pop_empty_lines(location.end);
@@ -2323,6 +2350,7 @@ impl<'a, 'doc> Formatter<'a> {
             .set_internal(*publicity)
             .set_external_erlang(external_erlang)
             .set_external_javascript(external_javascript)
+            .set_external_wasm(external_wasm)
             .to_doc(arena);
 
         let doc = attributes
@@ -4307,7 +4335,9 @@ fn constant_call_arg_formatting(
 struct AttributesPrinter<'a> {
     external_erlang: &'a Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: &'a Option<(EcoString, EcoString, SrcSpan)>,
+    external_wasm: &'a Option<(EcoString, EcoString, SrcSpan)>,
     deprecation: &'a Deprecation,
+    inline: bool,
     internal: bool,
 }
 
@@ -4316,7 +4346,9 @@ impl<'a> AttributesPrinter<'a> {
         Self {
             external_erlang: &None,
             external_javascript: &None,
+            external_wasm: &None,
             deprecation: &Deprecation::NotDeprecated,
+            inline: false,
             internal: false,
         }
     }
@@ -4337,6 +4369,19 @@ impl<'a> AttributesPrinter<'a> {
         self
     }
 
+    pub fn set_external_wasm(
+        mut self,
+        external: &'a Option<(EcoString, EcoString, SrcSpan)>,
+    ) -> Self {
+        self.external_wasm = external;
+        self
+    }
+
+    pub fn set_inline(mut self, inline: bool) -> Self {
+        self.inline = inline;
+        self
+    }
+
     pub fn set_internal(mut self, publicity: Publicity) -> Self {
         self.internal = publicity.is_internal();
         self
@@ -4385,6 +4430,22 @@ impl<'a, 'doc> AttributesPrinter<'a> {
             ])
         };
 
+        if let Some((module, function, _)) = self.external_wasm {
+            attributes.push(docvec![
+                arena,
+                EXTERNAL_WASM_QUOTE_DOCUMENT,
+                module,
+                QUOTE_COMMA_SPACE_QUOTE_DOCUMENT,
+                function,
+                QUOTE_CLOSE_PAREN_DOCUMENT
+            ])
+        };
+
+        // @inline attribute
+        if self.inline {
+            attributes.push(INLINE_ATTRIBUTE_DOCUMENT);
+        };
+
         // @internal attribute
         if self.internal {
             attributes.push(INTERNAL_ATTRIBUTE_DOCUMENT);
@@ -3154,6 +3154,17 @@ fn expr_todo() {
     );
 }
 
+#[test]
+fn expr_typed_hole() {
+    assert_format!(
+        "fn main() {
+  let x: Int = _
+  [x, _]
+}
+"
+    );
+}
+
 #[test]
 fn expr_todo_with_label() {
     assert_format!(
@@ -5765,6 +5776,54 @@ pub fn main() {
     );
 }
 
+#[test]
+fn inline_attribute_on_function() {
+    assert_format!(
+        r#"@inline
+pub fn main() {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn inline_and_internal_attributes_on_function() {
+    assert_format!(
+        r#"@deprecated("Use something else")
+@inline
+@internal
+pub fn main() {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn allow_attribute_on_function() {
+    assert_format!(
+        r#"@allow(unused_variable)
+pub fn main(x) {
+  todo
+}
+"#
+    );
+}
+
+#[test]
+fn allow_and_target_attributes() {
+    assert_format!(
+        r#"@target(erlang)
+@allow(todo)
+@allow(unused)
+pub fn main() {
+  todo
+}
+"#
+    );
+}
+
 #[test]
 fn internal_attribute_on_type() {
     assert_format!(
@@ -356,6 +356,15 @@ It even has multiple lines!
     );
 }
 
+#[test]
+fn wasm_external_custom_type() {
+    assert_format!(
+        r#"@external(wasm, "gleam_stdlib", "dict")
+pub type Dict(key, value)
+"#
+    );
+}
+
 #[test]
 fn external_custom_type() {
     assert_format!(
@@ -31,6 +31,26 @@ fn one(x: Int) -> Int
     );
 }
 
+#[test]
+fn no_body_wasm() {
+    assert_format!(
+        r#"@external(wasm, "one", "one")
+fn one(x: Int) -> Int
+"#
+    );
+}
+
+#[test]
+fn no_body_all_targets() {
+    assert_format!(
+        r#"@external(erlang, "three", "three")
+@external(javascript, "two", "two")
+@external(wasm, "one", "one")
+fn one(x: Int) -> Int
+"#
+    );
+}
+
 #[test]
 fn erlang() {
     assert_format!(
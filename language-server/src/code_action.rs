@@ -559,80 +559,161 @@ pub fn code_action_inexhaustive_let_to_case(
             continue;
         };
 
-        let TypedAssignment {
-            value,
-            pattern,
-            kind: AssignmentKind::Let,
-            location,
-            compiled_case: _,
-            annotation: _,
-        } = assignment.as_ref()
-        else {
+        let AssignmentKind::Let = assignment.kind else {
             continue;
         };
 
-        // Get the source code for the tested expression
-        let value_location = value.location();
-        let expr = module
-            .code
-            .get(value_location.start as usize..value_location.end as usize)
-            .expect("Location must be valid");
+        let new_text = print_let_as_case(
+            module,
+            assignment,
+            range.start.character as usize,
+            missing,
+            "todo",
+        );
 
-        // Get the source code for the pattern
-        let pattern_location = pattern.location();
-        let pattern_code = module
-            .code
-            .get(pattern_location.start as usize..pattern_location.end as usize)
-            .expect("Location must be valid");
+        let uri = &params.text_document.uri;
 
-        let range = text_edits.src_span_to_lsp_range(*location);
+        text_edits.replace(assignment.location, new_text);
 
-        // Figure out which variables are assigned in the pattern
-        let variables = PatternVariableFinder::find_variables_in_pattern(pattern);
+        CodeActionBuilder::new("Convert to case")
+            .kind(CodeActionKind::QuickFix)
+            .changes(uri.clone(), text_edits.edits)
+            .preferred(true)
+            .push_to(actions);
+    }
+}
 
-        let assigned = match variables.len() {
-            0 => "_",
-            1 => variables.first().expect("Variables is length one"),
-            _ => &format!("#({})", variables.join(", ")),
+/// Code action to convert a `let assert` into a `case` expression with a
+/// `panic` branch for each of the values the assertion would crash on, as
+/// reported by the inexhaustive `let assert` warning.
+///
+pub fn code_action_inexhaustive_let_assert_to_case(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    params: &CodeActionParams,
+    actions: &mut Vec<CodeAction>,
+) {
+    let inexhaustive_asserts = module
+        .ast
+        .type_info
+        .warnings
+        .iter()
+        .filter_map(|warning| {
+            if let type_::Warning::InexhaustiveLetAssert { location, missing } = warning {
+                Some((*location, missing))
+            } else {
+                None
+            }
+        })
+        .collect_vec();
+
+    for (location, missing) in inexhaustive_asserts {
+        let mut text_edits = TextEdits::new(line_numbers);
+
+        let range = text_edits.src_span_to_lsp_range(location);
+        if !overlaps(params.range, range) {
+            continue;
+        }
+
+        let Some(Located::Statement(TypedStatement::Assignment(assignment))) =
+            module.find_node(location.start)
+        else {
+            continue;
         };
 
-        let mut new_text = format!("let {assigned} = ");
-        print_case_expression(
+        let AssignmentKind::Assert { message, .. } = &assignment.kind else {
+            continue;
+        };
+
+        let panic = match message {
+            Some(message) => {
+                let location = message.location();
+                let message = module
+                    .code
+                    .get(location.start as usize..location.end as usize)
+                    .expect("Location must be valid");
+                format!("panic as {message}")
+            }
+            None => "panic".into(),
+        };
+
+        let new_text = print_let_as_case(
+            module,
+            assignment,
             range.start.character as usize,
-            expr,
-            iter::once(CaseClause {
-                pattern: pattern_code,
-                expression: if assigned == "_" { "Nil" } else { assigned },
-            })
-            .chain(missing.iter().map(|pattern| CaseClause {
-                pattern,
-                expression: "todo",
-            }))
-            .collect(),
-            &mut new_text,
+            missing,
+            &panic,
         );
 
         let uri = &params.text_document.uri;
 
-        text_edits.replace(*location, new_text);
+        text_edits.replace(assignment.location, new_text);
 
-        CodeActionBuilder::new("Convert to case")
+        CodeActionBuilder::new("Convert to case with missing patterns")
             .kind(CodeActionKind::QuickFix)
             .changes(uri.clone(), text_edits.edits)
-            .preferred(true)
+            .preferred(false)
             .push_to(actions);
     }
 }
 
+/// Prints a `let` assignment as a `case` expression on its value, with a
+/// clause for its pattern that returns the variables it assigns, and a clause
+/// for each of the missing patterns.
+///
+fn print_let_as_case(
+    module: &Module,
+    assignment: &TypedAssignment,
+    indent_size: usize,
+    missing: &[EcoString],
+    missing_expression: &str,
+) -> String {
+    // Get the source code for the tested expression
+    let value_location = assignment.value.location();
+    let expr = module
+        .code
+        .get(value_location.start as usize..value_location.end as usize)
+        .expect("Location must be valid");
+
+    // Get the source code for the pattern
+    let pattern_location = assignment.pattern.location();
+    let pattern_code = module
+        .code
+        .get(pattern_location.start as usize..pattern_location.end as usize)
+        .expect("Location must be valid");
+
+    // Figure out which variables are assigned in the pattern
+    let variables = PatternVariableFinder::find_variables_in_pattern(&assignment.pattern);
+
+    let assigned = match variables.len() {
+        0 => "_",
+        1 => variables.first().expect("Variables is length one"),
+        _ => &format!("#({})", variables.join(", ")),
+    };
+
+    let mut new_text = format!("let {assigned} = ");
+    print_case_expression(
+        indent_size,
+        expr,
+        iter::once(CaseClause {
+            pattern: pattern_code,
+            expression: if assigned == "_" { "Nil" } else { assigned },
+        })
+        .chain(missing.iter().map(|pattern| CaseClause {
+            pattern,
+            expression: missing_expression,
+        }))
+        .collect(),
+        &mut new_text,
+    );
+    new_text
+}
+
 pub(crate) fn type_errors_for_module<'a>(
     error: &'a Option<Error>,
     module: &Module,
 ) -> Option<&'a Vec1<type_::Error>> {
-    let Some(Error::Type {
-        skipped_modules: _,
-        failed_modules,
-    }) = error
-    else {
+    let Some(Error::Type { failed_modules, .. }) = error else {
         return None;
     };
     Some(&failed_modules.get(&module.name)?.errors)
@@ -9410,6 +9491,7 @@ impl<'a> RemoveUnusedImports<'a> {
                     Some(UnusedImport::ModuleAlias(*location))
                 }
                 type_::Warning::Todo { .. }
+                | type_::Warning::TypedHole { .. }
                 | type_::Warning::ImplicitlyDiscardedResult { .. }
                 | type_::Warning::UnusedLiteral { .. }
                 | type_::Warning::UnusedValue { .. }
@@ -9417,6 +9499,7 @@ impl<'a> RemoveUnusedImports<'a> {
                 | type_::Warning::AllFieldsRecordUpdate { .. }
                 | type_::Warning::UnusedType { .. }
                 | type_::Warning::UnusedConstructor { .. }
+                | type_::Warning::UnusedRecordField { .. }
                 | type_::Warning::UnusedPrivateModuleConstant { .. }
                 | type_::Warning::UnusedPrivateFunction { .. }
                 | type_::Warning::UnusedVariable { .. }
@@ -9432,9 +9515,11 @@ impl<'a> RemoveUnusedImports<'a> {
                 | type_::Warning::OpaqueExternalType { .. }
                 | type_::Warning::RedundantAssertAssignment { .. }
                 | type_::Warning::AssertAssignmentOnImpossiblePattern { .. }
+                | type_::Warning::InexhaustiveLetAssert { .. }
                 | type_::Warning::TodoOrPanicUsedAsFunction { .. }
                 | type_::Warning::UnreachableCodeAfterPanic { .. }
                 | type_::Warning::RedundantPipeFunctionCapture { .. }
+                | type_::Warning::UninlinableFunction { .. }
                 | type_::Warning::FeatureRequiresHigherGleamVersion { .. }
                 | type_::Warning::JavaScriptIntUnsafe { .. }
                 | type_::Warning::AssertLiteralBool { .. }
@@ -12866,7 +12951,8 @@ impl<'a, IO> CreateUnknownModule<'a, IO> {
     }
 }
 
-/// Code action to discard unused variable.
+/// Code action to discard unused variable. Unused function arguments are
+/// discarded by prefixing them with `_`.
 pub struct DiscardUnusedVariable<'a> {
     module: &'a Module,
     params: &'a CodeActionParams,
@@ -12934,8 +13020,14 @@ impl<'a> DiscardUnusedVariable<'a> {
             type_::error::VariableSyntax::Generated => (),
         }
 
+        let title = if unused_variable.origin.is_function_parameter() {
+            "Prefix unused argument with `_`"
+        } else {
+            "Discard unused variable"
+        };
+
         let mut action = Vec::with_capacity(1);
-        CodeActionBuilder::new("Discard unused variable")
+        CodeActionBuilder::new(title)
             .kind(CodeActionKind::QuickFix)
             .changes(self.params.text_document.uri.clone(), self.edits.edits)
             .preferred(true)
@@ -12944,6 +13036,408 @@ impl<'a> DiscardUnusedVariable<'a> {
     }
 }
 
+/// Code action to remove a field of a private custom type that is never read,
+/// along with all the arguments passed to it when building or matching on the
+/// record.
+///
+pub struct RemoveUnusedField<'a> {
+    module: &'a Module,
+    params: &'a CodeActionParams,
+    edits: TextEdits<'a>,
+    field: Option<UnusedField<'a>>,
+    /// This is set to `false` if the field is used in a way that can't be
+    /// automatically removed, for example if the constructor is used as a
+    /// function or if the field is given by a `use` callback.
+    can_remove: bool,
+}
+
+#[derive(Debug)]
+struct UnusedField<'a> {
+    constructor: &'a TypedRecordConstructor,
+    index: usize,
+}
+
+impl<'a> RemoveUnusedField<'a> {
+    pub fn new(
+        module: &'a Module,
+        line_numbers: &'a LineNumbers,
+        params: &'a CodeActionParams,
+    ) -> Self {
+        Self {
+            module,
+            params,
+            edits: TextEdits::new(line_numbers),
+            field: None,
+            can_remove: true,
+        }
+    }
+
+    pub fn code_actions(mut self) -> Vec<CodeAction> {
+        let Some((location, constructor_name)) = self
+            .module
+            .ast
+            .type_info
+            .warnings
+            .iter()
+            .find_map(|warning| match warning {
+                type_::Warning::UnusedRecordField {
+                    location,
+                    constructor,
+                } if within(
+                    self.params.range,
+                    self.edits.src_span_to_lsp_range(*location),
+                ) =>
+                {
+                    Some((*location, constructor))
+                }
+                _ => None,
+            })
+        else {
+            return vec![];
+        };
+
+        let Some(constructor) = self
+            .module
+            .ast
+            .definitions
+            .custom_types
+            .iter()
+            .flat_map(|custom_type| &custom_type.constructors)
+            .find(|constructor| &constructor.name == constructor_name)
+        else {
+            return vec![];
+        };
+
+        let Some(index) = constructor
+            .arguments
+            .iter()
+            .position(|argument| argument.location == location)
+        else {
+            return vec![];
+        };
+
+        self.remove_item(
+            constructor
+                .arguments
+                .iter()
+                .map(|argument| argument.location),
+            location,
+            SrcSpan::new(constructor.name_location.end, constructor.location.end),
+        );
+
+        self.field = Some(UnusedField { constructor, index });
+        self.visit_typed_module(&self.module.ast);
+        if !self.can_remove {
+            return vec![];
+        }
+
+        let mut action = Vec::with_capacity(1);
+        CodeActionBuilder::new("Remove unused field")
+            .kind(CodeActionKind::QuickFix)
+            .changes(self.params.text_document.uri.clone(), self.edits.edits)
+            .preferred(true)
+            .push_to(&mut action);
+        action
+    }
+
+    /// Removes an item from a comma separated list of items, along with the
+    /// comma separating it from its neighbours. If it's the only item in the
+    /// list, `only_item` is removed instead: this way the parentheses around
+    /// the list can be removed as well.
+    ///
+    fn remove_item(
+        &mut self,
+        items: impl Iterator<Item = SrcSpan>,
+        item: SrcSpan,
+        only_item: SrcSpan,
+    ) {
+        // Labelled arguments can be written in any order, so we need to look
+        // at the items in the order they appear in the source code.
+        let items = items.sorted_by_key(|item| item.start).collect_vec();
+        let Some(position) = items.iter().position(|other| *other == item) else {
+            return;
+        };
+
+        if let Some(next) = items.get(position + 1) {
+            self.edits.delete(SrcSpan::new(item.start, next.start));
+        } else if let Some(previous) = position
+            .checked_sub(1)
+            .and_then(|position| items.get(position))
+        {
+            self.edits.delete(SrcSpan::new(previous.end, item.end));
+        } else {
+            self.edits.delete(only_item);
+        }
+    }
+
+    fn is_unused_field_constructor(&self, constructor: &TypedExpr) -> bool {
+        match constructor {
+            TypedExpr::Var { constructor, .. } => self.is_unused_field_value(constructor),
+            _ => false,
+        }
+    }
+
+    fn is_unused_field_value(&self, value: &ValueConstructor) -> bool {
+        match &value.variant {
+            type_::ValueConstructorVariant::Record { name, module, .. } => {
+                self.is_unused_field_record(module, name)
+            }
+            type_::ValueConstructorVariant::LocalVariable { .. }
+            | type_::ValueConstructorVariant::ModuleConstant { .. }
+            | type_::ValueConstructorVariant::ModuleFn { .. } => false,
+        }
+    }
+
+    fn is_unused_field_record(&self, module: &EcoString, name: &EcoString) -> bool {
+        self.field
+            .as_ref()
+            .is_some_and(|field| module == &self.module.name && name == &field.constructor.name)
+    }
+
+    fn unused_field_label(&self) -> Option<&'a EcoString> {
+        let field = self.field.as_ref()?;
+        let (_, label) = field
+            .constructor
+            .arguments
+            .get(field.index)?
+            .label
+            .as_ref()?;
+        Some(label)
+    }
+}
+
+impl<'ast> ast::visit::Visit<'ast> for RemoveUnusedField<'_> {
+    fn visit_typed_expr_var(
+        &mut self,
+        location: &'ast SrcSpan,
+        constructor: &'ast ValueConstructor,
+        name: &'ast EcoString,
+    ) {
+        // Constructors that are being called are not visited as variables, so
+        // if we get here the constructor is being used as a function and we
+        // can't remove one of its arguments.
+        if self.is_unused_field_value(constructor) {
+            self.can_remove = false;
+        }
+        ast::visit::visit_typed_expr_var(self, location, constructor, name);
+    }
+
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        fun: &'ast TypedExpr,
+        arguments: &'ast [TypedCallArg],
+        open_parenthesis: &'ast Option<u32>,
+    ) {
+        if !self.is_unused_field_constructor(fun) {
+            ast::visit::visit_typed_expr_call(
+                self,
+                location,
+                type_,
+                fun,
+                arguments,
+                open_parenthesis,
+            );
+            return;
+        }
+
+        let Some(index) = self.field.as_ref().map(|field| field.index) else {
+            return;
+        };
+        match arguments.get(index) {
+            Some(argument)
+                if !arguments.iter().any(|argument| argument.is_implicit())
+                    && !argument.is_capture_hole() =>
+            {
+                self.remove_item(
+                    arguments.iter().map(|argument| argument.location),
+                    argument.location,
+                    SrcSpan::new(fun.location().end, location.end),
+                );
+            }
+            Some(_) | None => self.can_remove = false,
+        }
+
+        for argument in arguments {
+            self.visit_typed_call_arg(argument);
+        }
+    }
+
+    fn visit_typed_expr_record_update(
+        &mut self,
+        location: &'ast SrcSpan,
+        spread_start: &'ast u32,
+        type_: &'ast Arc<Type>,
+        updated_record: &'ast TypedExpr,
+        updated_record_assigned_name: &'ast Option<EcoString>,
+        constructor: &'ast TypedExpr,
+        arguments: &'ast [TypedCallArg],
+    ) {
+        if !self.is_unused_field_constructor(constructor) {
+            ast::visit::visit_typed_expr_record_update(
+                self,
+                location,
+                spread_start,
+                type_,
+                updated_record,
+                updated_record_assigned_name,
+                constructor,
+                arguments,
+            );
+            return;
+        }
+
+        // Only labelled fields can be updated, and the fields that are not
+        // being updated are implicit arguments.
+        let explicit_arguments = arguments.iter().filter(|argument| !argument.is_implicit());
+        if let Some(label) = self.unused_field_label()
+            && let Some(argument) = explicit_arguments
+                .clone()
+                .find(|argument| argument.label.as_ref() == Some(label))
+        {
+            let spread = SrcSpan::new(*spread_start, updated_record.location().end);
+            self.remove_item(
+                explicit_arguments
+                    .map(|argument| argument.location)
+                    .chain(iter::once(spread)),
+                argument.location,
+                argument.location,
+            );
+        }
+
+        self.visit_typed_expr(updated_record);
+        for argument in arguments {
+            self.visit_typed_call_arg(argument);
+        }
+    }
+
+    fn visit_typed_pattern_constructor(
+        &mut self,
+        location: &'ast SrcSpan,
+        name_location: &'ast SrcSpan,
+        name: &'ast EcoString,
+        arguments: &'ast Vec<CallArg<TypedPattern>>,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        constructor: &'ast Inferred<type_::PatternConstructor>,
+        spread: &'ast Option<SrcSpan>,
+        type_: &'ast Arc<Type>,
+    ) {
+        if let Inferred::Known(constructor) = constructor
+            && self.is_unused_field_record(&constructor.module, &constructor.name)
+            && let Some(index) = self.field.as_ref().map(|field| field.index)
+            // If the field is ignored by the spread there's nothing to remove.
+            && let Some(argument) = arguments.get(index)
+            && !argument.is_implicit()
+        {
+            self.remove_item(
+                arguments
+                    .iter()
+                    .filter(|argument| !argument.is_implicit())
+                    .map(|argument| argument.location)
+                    .chain(*spread),
+                argument.location,
+                SrcSpan::new(name_location.end, location.end),
+            );
+        }
+
+        ast::visit::visit_typed_pattern_constructor(
+            self,
+            location,
+            name_location,
+            name,
+            arguments,
+            module,
+            constructor,
+            spread,
+            type_,
+        );
+    }
+
+    fn visit_typed_constant_record(
+        &mut self,
+        location: &'ast SrcSpan,
+        arguments_start_position: &'ast u32,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        name: &'ast EcoString,
+        arguments: &'ast Option<Vec<CallArg<ast::TypedConstant>>>,
+        type_: &'ast Arc<Type>,
+        field_map: &'ast Inferred<FieldMap>,
+        record_constructor: &'ast Option<Box<ValueConstructor>>,
+    ) {
+        if let Some(record_constructor) = record_constructor
+            && self.is_unused_field_value(record_constructor)
+        {
+            match arguments {
+                Some(arguments) => {
+                    if let Some(index) = self.field.as_ref().map(|field| field.index)
+                        && let Some(argument) = arguments.get(index)
+                    {
+                        self.remove_item(
+                            arguments.iter().map(|argument| argument.location),
+                            argument.location,
+                            SrcSpan::new(*arguments_start_position, location.end),
+                        );
+                    }
+                }
+                // The constructor is used as a function.
+                None => self.can_remove = false,
+            }
+        }
+
+        ast::visit::visit_typed_constant_record(
+            self,
+            location,
+            arguments_start_position,
+            module,
+            name,
+            arguments,
+            type_,
+            field_map,
+            record_constructor,
+        );
+    }
+
+    fn visit_typed_constant_record_update(
+        &mut self,
+        location: &'ast SrcSpan,
+        constructor_location: &'ast SrcSpan,
+        module: &'ast Option<(EcoString, SrcSpan)>,
+        name: &'ast EcoString,
+        record: &'ast ast::RecordBeingUpdated<ast::TypedConstant>,
+        arguments: &'ast [ast::RecordUpdateArg<ast::TypedConstant>],
+        type_: &'ast Arc<Type>,
+        field_map: &'ast Inferred<FieldMap>,
+    ) {
+        if module.is_none()
+            && self.is_unused_field_record(&self.module.name, name)
+            && let Some(label) = self.unused_field_label()
+            && let Some(argument) = arguments.iter().find(|argument| &argument.label == label)
+        {
+            self.remove_item(
+                arguments
+                    .iter()
+                    .map(|argument| argument.location)
+                    .chain(iter::once(record.location)),
+                argument.location,
+                argument.location,
+            );
+        }
+
+        ast::visit::visit_typed_constant_record_update(
+            self,
+            location,
+            constructor_location,
+            module,
+            name,
+            record,
+            arguments,
+            type_,
+            field_map,
+        );
+    }
+}
+
 pub fn code_action_fix_deprecated_pipe(
     module: &Module,
     line_numbers: &LineNumbers,
@@ -11,10 +11,11 @@ use gleam_core::{
     build::{self, Mode, Module, NullTelemetry, Outcome, ProjectCompiler},
     config::PackageConfig,
     io::{BeamCompilerIO, CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
+    lint::Linter,
     manifest::Manifest,
     paths::ProjectPaths,
     type_::ModuleInterface,
-    warning::VectorWarningEmitterIO,
+    warning::{VectorWarningEmitterIO, WarningEmitterIO, WarningKind, WarningLevel},
 };
 use src_span::LineNumbers;
 use std::{collections::HashMap, rc::Rc};
@@ -75,6 +76,8 @@ where
             compile: build::Compile::All,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            doctests: false,
+            inline: false,
         };
         let mut project_compiler = ProjectCompiler::new(
             config,
@@ -176,8 +179,30 @@ where
             .map(|m| m.input_path)
             .collect_vec();
 
+        // Lints are reported alongside the warnings of the compiled modules,
+        // at the level set for them in `gleam.toml`.
+        let lint_level = self
+            .project_compiler
+            .config
+            .warnings
+            .level(WarningKind::Lint);
+        let linter = match lint_level {
+            WarningLevel::Allow => Linter::new(),
+            WarningLevel::Warn | WarningLevel::Deny => Linter::with_builtin_rules(),
+        };
+
         // Store the compiled module information
         for module in modules {
+            for warning in linter.lint_module(&module) {
+                let warning = match lint_level {
+                    WarningLevel::Deny => Warning::Denied {
+                        warning: Box::new(warning),
+                    },
+                    WarningLevel::Allow | WarningLevel::Warn => warning,
+                };
+                self.warnings.emit_warning(warning);
+            }
+
             let path = module.input_path.as_os_str().to_string_lossy().to_string();
             let line_numbers = LineNumbers::new(&module.code);
             let source = ModuleSourceInformation { path, line_numbers };
@@ -16,6 +16,7 @@ use gleam_core::{
     },
     config::PackageConfig,
     io::{BeamCompilerIO, CommandExecutor, FileSystemReader, FileSystemWriter},
+    lint::Linter,
     paths::ProjectPaths,
     type_::{
         self, Deprecation, ModuleInterface, Type, TypeConstructor, ValueConstructor,
@@ -41,7 +42,8 @@ use std::{
 use crate::{
     code_action::{
         ConvertIntToDifferentBase, DiscardUnusedVariable, RemoveRedundantRecordUpdate,
-        ReplaceUnderscoreWithType, code_action_fix_deprecated_pipe, type_errors_for_module,
+        RemoveUnusedField, ReplaceUnderscoreWithType, code_action_fix_deprecated_pipe,
+        type_errors_for_module,
     },
     reference::find_module_references_in_module,
     rename::{rename_module_alias, rename_module_occurrences, rename_type_variable},
@@ -62,7 +64,8 @@ use super::{
         UnwrapAnonymousFunction, UseLabelShorthandSyntax, WrapInAnonymousFunction, WrapInBlock,
         code_action_add_missing_patterns, code_action_convert_qualified_constructor_to_unqualified,
         code_action_convert_unqualified_constructor_to_qualified, code_action_generate_type,
-        code_action_import_module, code_action_inexhaustive_let_to_case,
+        code_action_import_module, code_action_inexhaustive_let_assert_to_case,
+        code_action_inexhaustive_let_to_case,
     },
     compiler::LspProjectCompiler,
     completer::Completer,
@@ -476,6 +479,7 @@ where
             actions.extend(RemovePrivateOpaque::new(module, &lines, &params).code_actions());
             actions.extend(AddMissingTypeParameter::new(module, &lines, &params).code_actions());
             code_action_unused_values(module, &lines, &params, &mut actions);
+            code_action_lint_fixes(module, &lines, &params, &mut actions);
             code_action_convert_qualified_constructor_to_unqualified(
                 module,
                 &this.compiler,
@@ -496,6 +500,7 @@ where
                 &this.error,
                 &mut actions,
             );
+            code_action_inexhaustive_let_assert_to_case(module, &lines, &params, &mut actions);
             actions.extend(MergeCaseBranches::new(module, &lines, &params).code_actions());
             actions.extend(LetAssertToCase::new(module, &lines, &params).code_actions());
             actions
@@ -555,6 +560,7 @@ where
                 .code_actions(),
             );
             actions.extend(DiscardUnusedVariable::new(module, &lines, &params).code_actions());
+            actions.extend(RemoveUnusedField::new(module, &lines, &params).code_actions());
             code_action_fix_deprecated_pipe(module, &lines, &params, &mut actions);
             actions.extend(
                 ConvertBetweenDocAndRegularComment::new(module, &lines, &params).code_actions(),
@@ -2275,6 +2281,41 @@ fn code_action_unused_values(
     }
 }
 
+fn code_action_lint_fixes(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    params: &lsp::CodeActionParams,
+    actions: &mut Vec<CodeAction>,
+) {
+    let uri = &params.text_document.uri;
+    for lint in Linter::with_builtin_rules().lint(&module.ast, &module.code) {
+        let Some(fix) = lint.fix else {
+            continue;
+        };
+        if !overlaps(
+            params.range,
+            src_span_to_lsp_range(lint.location, line_numbers),
+        ) {
+            continue;
+        }
+
+        let edits = fix
+            .edits
+            .into_iter()
+            .map(|edit| TextEdit {
+                range: src_span_to_lsp_range(edit.location, line_numbers),
+                new_text: edit.replacement.to_string(),
+            })
+            .collect();
+
+        CodeActionBuilder::new(&fix.title)
+            .kind(lsp_types::CodeActionKind::QuickFix)
+            .changes(uri.clone(), edits)
+            .preferred(true)
+            .push_to(actions);
+    }
+}
+
 struct NameCorrection {
     pub location: SrcSpan,
     pub correction: EcoString,
@@ -218,6 +218,7 @@ mod tests {
         type_,
     };
     use src_span::SrcSpan;
+    use vec1::vec1;
 
     #[test]
     fn feedback() {
@@ -331,10 +332,10 @@ mod tests {
         let error = Error::Parse {
             path: file3.clone(),
             src: "blah".into(),
-            error: Box::new(ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            }),
+            }],
         };
 
         let feedback = book_keeper.build_with_error(
@@ -391,10 +392,10 @@ mod tests {
         let error = Error::Parse {
             path: file1.clone(),
             src: "blah".into(),
-            error: Box::new(ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            }),
+            }],
         };
 
         let feedback =
@@ -432,10 +433,10 @@ mod tests {
         let error = |file: &camino::Utf8Path| Error::Parse {
             path: file.to_path_buf(),
             src: "blah".into(),
-            error: Box::new(ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            }),
+            }],
         };
 
         let feedback =
@@ -475,10 +476,10 @@ mod tests {
         let error = Error::Parse {
             path: file1.clone(),
             src: "blah".into(),
-            error: Box::new(ParseError {
+            errors: vec1![ParseError {
                 error: ParseErrorType::ConcatPatternVariableLeftHandSide,
                 location: SrcSpan::new(1, 4),
-            }),
+            }],
         };
 
         let feedback =
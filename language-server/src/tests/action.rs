@@ -167,6 +167,7 @@ fn format_code_action_file_operations(actions: &[lsp_types::CodeAction]) -> Stri
 const REMOVE_UNUSED_IMPORTS: &str = "Remove unused imports";
 const REMOVE_REDUNDANT_TUPLES: &str = "Remove redundant tuples";
 const CONVERT_TO_CASE: &str = "Convert to case";
+const CONVERT_TO_CASE_WITH_MISSING_PATTERNS: &str = "Convert to case with missing patterns";
 const USE_LABEL_SHORTHAND_SYNTAX: &str = "Use label shorthand syntax";
 const FILL_LABELS: &str = "Fill labels";
 const ASSIGN_UNUSED_RESULT: &str = "Assign unused Result value to `_`";
@@ -206,6 +207,8 @@ const WRAP_IN_ANONYMOUS_FUNCTION: &str = "Wrap in anonymous function";
 const UNWRAP_ANONYMOUS_FUNCTION: &str = "Remove anonymous function wrapper";
 const REMOVE_REDUNDANT_RECORD_UPDATE: &str = "Remove redundant record update";
 const DISCARD_UNUSED_VARIABLE: &str = "Discard unused variable";
+const PREFIX_UNUSED_ARGUMENT: &str = "Prefix unused argument with `_`";
+const REMOVE_UNUSED_FIELD: &str = "Remove unused field";
 const ADD_EXTRA_PARENTHESES: &str = "Add extra parentheses";
 const CONVERT_TO_DOCUMENTATION_COMMENT: &str = "Convert to documentation comment";
 const CONVERT_TO_REGULAR_COMMENT: &str = "Convert to regular comment";
@@ -5976,6 +5979,42 @@ fn inexhaustive_let_string_prefix_pattern_alias_to_case() {
     );
 }
 
+#[test]
+fn inexhaustive_let_assert_to_case() {
+    assert_code_action!(
+        CONVERT_TO_CASE_WITH_MISSING_PATTERNS,
+        "pub fn main(result: Result(Int, Nil)) -> Int {
+  let assert Ok(value) = result
+  value
+}",
+        find_position_of("let").select_until(find_position_of("=")),
+    );
+}
+
+#[test]
+fn inexhaustive_let_assert_with_message_to_case() {
+    assert_code_action!(
+        CONVERT_TO_CASE_WITH_MISSING_PATTERNS,
+        r#"pub fn main(result: Result(Int, Nil)) -> Int {
+  let assert Ok(value) = result as "no value"
+  value
+}"#,
+        find_position_of("let").select_until(find_position_of("=")),
+    );
+}
+
+#[test]
+fn redundant_let_assert_has_no_convert_to_case_action() {
+    assert_no_code_actions!(
+        CONVERT_TO_CASE_WITH_MISSING_PATTERNS,
+        "pub fn main() -> Int {
+  let assert value = 1
+  value
+}",
+        find_position_of("let").select_until(find_position_of("=")),
+    );
+}
+
 #[test]
 fn inner_inexhaustive_let_to_case() {
     assert_code_action!(
@@ -15421,6 +15460,133 @@ fn discard_unused_variable_triggered_at_list_pattern_assignment() {
     );
 }
 
+#[test]
+fn prefix_unused_argument_of_public_function() {
+    assert_code_action!(
+        PREFIX_UNUSED_ARGUMENT,
+        "pub fn go(wibble: Int, wobble: Int) -> Int {
+  wobble
+}",
+        find_position_of("wibble").to_selection()
+    );
+}
+
+#[test]
+fn prefix_unused_labelled_argument() {
+    assert_code_action!(
+        PREFIX_UNUSED_ARGUMENT,
+        "pub fn go(label wibble: Int) -> Int {
+  1
+}",
+        find_position_of("wibble").to_selection()
+    );
+}
+
+#[test]
+fn prefix_unused_argument_of_anonymous_function() {
+    assert_code_action!(
+        PREFIX_UNUSED_ARGUMENT,
+        "pub fn go() {
+  fn(wibble) { 1 }
+}",
+        find_position_of("wibble").to_selection()
+    );
+}
+
+#[test]
+fn no_discard_unused_variable_for_unused_argument() {
+    assert_no_code_actions!(
+        DISCARD_UNUSED_VARIABLE,
+        "pub fn go(wibble: Int) -> Int {
+  1
+}",
+        find_position_of("wibble").to_selection()
+    );
+}
+
+#[test]
+fn remove_unused_field() {
+    assert_code_action!(
+        REMOVE_UNUSED_FIELD,
+        "type Wibble {
+  Wibble(a: Int, b: Int)
+}
+
+pub fn main() {
+  let wibble = Wibble(1, b: 2)
+  case wibble {
+    Wibble(a:, b: _) -> a
+  }
+}",
+        find_position_of("b: Int").to_selection()
+    );
+}
+
+#[test]
+fn remove_unused_field_in_first_position() {
+    assert_code_action!(
+        REMOVE_UNUSED_FIELD,
+        "type Wibble {
+  Wibble(a: Int, b: Int)
+}
+
+pub fn main() {
+  let Wibble(_, b) = Wibble(b: 2, a: 1)
+  b
+}",
+        find_position_of("a: Int").to_selection()
+    );
+}
+
+#[test]
+fn remove_unused_field_only_field() {
+    assert_code_action!(
+        REMOVE_UNUSED_FIELD,
+        "type Wibble {
+  Wibble(Int)
+}
+
+pub fn main() {
+  let Wibble(_) = Wibble(1)
+  Nil
+}",
+        find_position_of("Int").to_selection()
+    );
+}
+
+#[test]
+fn remove_unused_field_from_record_update() {
+    assert_code_action!(
+        REMOVE_UNUSED_FIELD,
+        "type Wibble {
+  Wibble(a: Int, b: Int)
+}
+
+pub fn main() {
+  let wibble = Wibble(a: 1, b: 2)
+  let wibble = Wibble(..wibble, b: 3)
+  wibble.a
+}",
+        find_position_of("b: Int").to_selection()
+    );
+}
+
+#[test]
+fn no_remove_unused_field_when_constructor_is_used_as_a_function() {
+    assert_no_code_actions!(
+        REMOVE_UNUSED_FIELD,
+        "type Wibble {
+  Wibble(a: Int, b: Int)
+}
+
+pub fn main() {
+  let make = Wibble
+  make(1, 2).a
+}",
+        find_position_of("b: Int").to_selection()
+    );
+}
+
 #[test]
 fn convert_to_function_call_on_single_step_pipeline() {
     assert_code_action!(
@@ -15964,3 +16130,17 @@ fn convert_to_int_has_nicely_separated_digits() {
         find_position_of("0b100101101011010000111").to_selection()
     );
 }
+
+#[test]
+fn redundant_let_lint_fix() {
+    assert_code_action!(
+        "Return the value directly",
+        r#"
+pub fn main() {
+  let x = 1 + 2
+  x
+}
+"#,
+        find_position_of("let").to_selection()
+    );
+}
@@ -45,7 +45,11 @@ fn compile_error_in_src() {
     let response = engine.compile_please();
     assert!(response.result.is_err());
     assert!(response.warnings.is_empty());
-    assert_eq!(response.compilation, Compilation::Yes(vec![]));
+    // The module is analysed despite the syntax error.
+    assert_eq!(
+        response.compilation,
+        Compilation::Yes(vec![Utf8PathBuf::from("/src/app/error.gleam")])
+    );
 
     drop(engine);
     let actions = io.into_actions();
@@ -77,7 +81,11 @@ fn compile_error_in_test() {
     let response = engine.compile_please();
     assert!(response.result.is_err());
     assert!(response.warnings.is_empty());
-    assert_eq!(response.compilation, Compilation::Yes(vec![]));
+    // The module is analysed despite the syntax error.
+    assert_eq!(
+        response.compilation,
+        Compilation::Yes(vec![Utf8PathBuf::from("/test/app/error.gleam")])
+    );
 
     drop(engine);
     let actions = io.into_actions();
@@ -109,7 +117,11 @@ fn compile_error_in_dev() {
     let response = engine.compile_please();
     assert!(response.result.is_err());
     assert!(response.warnings.is_empty());
-    assert_eq!(response.compilation, Compilation::Yes(vec![]));
+    // The module is analysed despite the syntax error.
+    assert_eq!(
+        response.compilation,
+        Compilation::Yes(vec![Utf8PathBuf::from("/dev/app/error.gleam")])
+    );
 
     drop(engine);
     let actions = io.into_actions();
@@ -2308,3 +2308,23 @@ const wibble = todo as [wobble]
         find_position_of("wobble").nth_occurrence(2)
     );
 }
+
+#[test]
+fn hover_in_module_with_syntax_error() {
+    assert_hover!(
+        "
+fn add_2(x) {
+  x + 2
+}
+
+pub fn main() {
+  let x =
+}
+
+pub fn wobble() {
+  add_2(1)
+}
+",
+        find_position_of("add_2(1)")
+    );
+}
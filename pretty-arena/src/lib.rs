@@ -712,8 +712,10 @@ const_str!(
     "@external(javascript, \"",
     23
 );
+const_str!(EXTERNAL_WASM_QUOTE_DOCUMENT, "@external(wasm, \"", 17);
 const_str!(QUOTE_COMMA_SPACE_QUOTE_DOCUMENT, "\", \"", 4);
 const_str!(INTERNAL_ATTRIBUTE_DOCUMENT, "@internal", 9);
+const_str!(INLINE_ATTRIBUTE_DOCUMENT, "@inline", 7);
 const_str!(INTERNAL_ATTRIBUTE_SPACE_DOCUMENT, "@internal ", 9);
 const_str!(TRUE_LOWERCASE_DOCUMENT, "true", 4);
 const_str!(FALSE_LOWERCASE_DOCUMENT, "false", 5);
@@ -768,6 +770,12 @@ const_str!(
     ".d.mts\" />",
     10
 );
+const_str!(DOT_CJS_DOT_MAP_DOCUMENT, ".cjs.map", 8);
+const_str!(
+    DOT_D_DOT_CTS_CLOSE_QUOTE_CLOSE_TAG_DOCUMENT,
+    ".d.cts\" />",
+    10
+);
 const_str!(
     SOURCE_MAPPING_URL_EQUAL_DOCUMENT,
     "//# sourceMappingURL=",
@@ -783,6 +791,60 @@ const_str!(
     "} from \"",
     8
 );
+const_str!(
+    SPACE_EQUAL_SPACE_REQUIRE_OPEN_PAREN_DOUBLE_QUOTE_DOCUMENT,
+    " = require(\"",
+    12
+);
+const_str!(
+    CLOSE_CURLY_SPACE_EQUAL_SPACE_REQUIRE_OPEN_PAREN_DOUBLE_QUOTE_DOCUMENT,
+    "} = require(\"",
+    13
+);
+const_str!(DOUBLE_QUOTE_CLOSE_PAREN_SEMICOLON_DOCUMENT, "\");", 3);
+const_str!(
+    MODULE_DOT_EXPORTS_SPACE_EQUAL_SPACE_OPEN_CURLY_DOCUMENT,
+    "module.exports = {",
+    18
+);
+const_str!(DOT_JS_DOT_MAP_DOCUMENT, ".js.map", 7);
+const_str!(DOT_D_DOT_TS_CLOSE_QUOTE_CLOSE_TAG_DOCUMENT, ".d.ts\" />", 9);
+const_str!(
+    SPACE_EQUAL_SPACE_GLOBAL_THIS_DOT_GLEAM_OPEN_SQUARE_DOUBLE_QUOTE_DOCUMENT,
+    " = globalThis.Gleam[\"",
+    21
+);
+const_str!(
+    CLOSE_CURLY_SPACE_EQUAL_SPACE_GLOBAL_THIS_DOT_GLEAM_OPEN_SQUARE_DOUBLE_QUOTE_DOCUMENT,
+    "} = globalThis.Gleam[\"",
+    22
+);
+const_str!(DOUBLE_QUOTE_CLOSE_SQUARE_SEMICOLON_DOCUMENT, "\"];", 3);
+const_str!(
+    OPEN_PAREN_GLOBAL_THIS_DOT_GLEAM_SPACE_QUESTION_QUESTION_EQUAL_SPACE_OPEN_CLOSE_CURLY_CLOSE_PAREN_OPEN_SQUARE_DOUBLE_QUOTE_DOCUMENT,
+    "(globalThis.Gleam ??= {})[\"",
+    27
+);
+const_str!(
+    DOUBLE_QUOTE_CLOSE_SQUARE_SPACE_EQUAL_SPACE_OPEN_CURLY_DOCUMENT,
+    "\"] = {",
+    6
+);
+const_str!(
+    OPEN_PAREN_OPEN_CLOSE_PAREN_SPACE_ARROW_SPACE_OPEN_CURLY_DOCUMENT,
+    "(() => {",
+    8
+);
+const_str!(
+    DOUBLE_QUOTE_USE_SPACE_STRICT_DOUBLE_QUOTE_SEMICOLON_DOCUMENT,
+    "\"use strict\";",
+    13
+);
+const_str!(
+    CLOSE_CURLY_CLOSE_PAREN_OPEN_CLOSE_PAREN_SEMICOLON_DOCUMENT,
+    "})();",
+    5
+);
 const_str!(EXPORT_TYPE_SPACE_DOCUMENT, "export type ", 12);
 const_str!(X_DOCUMENT, "x", 1);
 const_str!(ANY_DOCUMENT, "any", 3);
@@ -3,6 +3,7 @@
 
 use camino::Utf8PathBuf;
 use gleam_cli::{Command, ExportTarget, fs};
+use gleam_core::build::Target;
 use std::process;
 
 fn escript_compile(case: &str) -> Result<Utf8PathBuf, gleam_core::Error> {
@@ -71,3 +72,100 @@ fn escript_with_wrong_arity_main_function() {
         .pretty_string();
     insta::assert_snapshot!(error);
 }
+
+/// The value returned by the `main` function of the `erlang_compilation` case,
+/// with each element of the tuple printed on its own line.
+const ERLANG_COMPILATION_OUTPUT: &str = r#"6
+6.0
+2
+<<"Joe">>
+<<"negative">>
+<<"zero">>
+3
+0
+0.0
+1
+<<2,3>>
+"#;
+
+/// Loads the compiled `erlang_compilation` module from the given directory
+/// and prints each element of the tuple returned by its `main` function.
+fn run_erlang_compilation_main(ebin: &Utf8PathBuf) -> String {
+    let output = process::Command::new("erl")
+        .arg("-noshell")
+        .arg("-pa")
+        .arg(ebin)
+        .arg("-eval")
+        .arg(
+            "try \
+               Result = tuple_to_list(erlang_compilation:main()), \
+               lists:foreach(fun(Element) -> io:format(\"~p~n\", [Element]) end, Result) \
+             catch Class:Reason:Stacktrace -> \
+               io:format(standard_error, \"~p~n\", [{Class, Reason, Stacktrace}]), \
+               halt(1) \
+             end, \
+             halt(0).",
+        )
+        .output()
+        .expect("erl should be installed");
+    assert!(
+        output.status.success(),
+        "main should run OK:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("utf8 output")
+}
+
+#[test]
+fn core_erlang_export_is_compiled_by_erlc() {
+    let working_directory = Utf8PathBuf::from("./cases/erlang_compilation");
+    Command::Export(ExportTarget::CoreErlang)
+        .run(working_directory.clone())
+        .expect("should export Core Erlang");
+
+    let core =
+        working_directory.join("build/core-erlang/erlang_compilation/erlang_compilation.core");
+    let ebin = working_directory.join("build/core-erlang-ebin");
+    fs::mkdir(&ebin).expect("must be able to create the output directory");
+    let status = process::Command::new("erlc")
+        .arg("+from_core")
+        .arg("-o")
+        .arg(&ebin)
+        .arg(&core)
+        .status()
+        .expect("erlc should be installed");
+    assert!(
+        status.success(),
+        "erlc should compile the Core Erlang module"
+    );
+
+    assert_eq!(
+        run_erlang_compilation_main(&ebin),
+        ERLANG_COMPILATION_OUTPUT
+    );
+}
+
+#[test]
+fn abstract_forms_are_compiled_by_compile_forms() {
+    let working_directory = Utf8PathBuf::from("./cases/erlang_compilation");
+    let build = working_directory.join("build/dev/erlang/erlang_compilation");
+    fs::delete_directory(&build).expect("must be able to reset test directory");
+
+    Command::Build {
+        target: Some(Target::Erlang),
+        warnings_as_errors: true,
+        no_print_progress: true,
+        inline: false,
+    }
+    .run(working_directory.clone())
+    .expect("should build successfully");
+
+    // The module is compiled from its abstract forms rather than its source.
+    let forms = build.join("_gleam_artefacts/erlang_compilation.etf");
+    assert!(forms.is_file(), "{forms} should have been created");
+
+    assert_eq!(
+        run_erlang_compilation_main(&build.join("ebin")),
+        ERLANG_COMPILATION_OUTPUT
+    );
+}
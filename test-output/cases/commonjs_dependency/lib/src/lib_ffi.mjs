@@ -0,0 +1,17 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+import { Ok, Error } from "./gleam.mjs";
+
+export function parse(text) {
+  const number = parseInt(text, 10);
+  return Number.isNaN(number) ? new Error(undefined) : new Ok(number);
+}
+
+export function describe(shape) {
+  return `A square with sides of ${shape.side}`;
+}
+
+export function print(text) {
+  console.log(text);
+}
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+(() => {
+  const { Ok, Error } = globalThis.Gleam["prelude"];
+
+  function parse(text) {
+    const number = parseInt(text, 10);
+    return Number.isNaN(number) ? new Error(undefined) : new Ok(number);
+  }
+
+  function describe(shape) {
+    return `A square with sides of ${shape.side}`;
+  }
+
+  function print(text) {
+    console.log(text);
+  }
+
+  (globalThis.Gleam ??= {})["lib/lib_ffi"] = { parse, describe, print };
+})();
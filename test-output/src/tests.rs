@@ -3,3 +3,9 @@
 
 #[cfg(test)]
 mod echo;
+
+#[cfg(test)]
+mod module_formats;
+
+#[cfg(test)]
+mod wasm;
@@ -59,13 +59,16 @@ fn run_and_capture_output(
 ) -> String {
     fs::delete_directory(&paths.build_directory()).expect("delete build directory content");
 
-    let Command {
-        program,
-        args,
-        env,
-        cwd: _,
-        stdio: _,
-    } = run::setup(
+    let (
+        Command {
+            program,
+            args,
+            env,
+            cwd: _,
+            stdio: _,
+        },
+        _,
+    ) = run::setup(
         paths,
         vec![],
         target,
@@ -73,6 +76,7 @@ fn run_and_capture_output(
         Some(main_module.into()),
         Which::Src,
         true,
+        false,
     )
     .expect("run setup");
 
@@ -115,6 +119,7 @@ macro_rules! assert_echo {
             Target::Erlang => {
                 assert_echo!(&snapshot_name, Some($target), None, $project_name);
             }
+            Target::Wasm => panic!("echo is not supported on the WebAssembly target"),
         }
     };
 
@@ -130,6 +135,7 @@ fn snapshot_name(target: Option<Target>, runtime: Option<Runtime>, suffix: &str)
     let show_target = |target: Target| match target {
         Target::Erlang => "erlang",
         Target::JavaScript => "javascript",
+        Target::Wasm => "wasm",
     };
     let show_runtime = |runtime: Runtime| match runtime {
         Runtime::NodeJs => "nodejs",
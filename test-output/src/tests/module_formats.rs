@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use std::process::{Output, Stdio};
+
+use camino::Utf8Path;
+use gleam_core::{
+    build::{Runtime, Target},
+    io::Command,
+    paths::ProjectPaths,
+};
+
+use gleam_cli::{
+    fs,
+    run::{self, Which},
+};
+
+/// Runs the `main` module of a test project with NodeJS, the same way
+/// `gleam run` does, and returns what it printed. The projects set the module
+/// format of the generated JavaScript in their `gleam.toml`.
+///
+fn run_nodejs(project_name: &str) -> Output {
+    let path = fs::canonicalise(&Utf8Path::new("../test-output/cases").join(project_name))
+        .expect("canonicalise path");
+    let project_root = fs::get_project_root(path).expect("project root");
+    let paths = ProjectPaths::new(project_root);
+    fs::delete_directory(&paths.build_directory()).expect("delete build directory content");
+
+    let (
+        Command {
+            program,
+            args,
+            env,
+            cwd: _,
+            stdio: _,
+        },
+        _,
+    ) = run::setup(
+        &paths,
+        vec![],
+        Some(Target::JavaScript),
+        Some(Runtime::NodeJs),
+        Some("main".into()),
+        Which::Src,
+        true,
+        false,
+    )
+    .expect("run setup");
+
+    std::process::Command::new(&program)
+        .args(args)
+        .stdin(Stdio::null())
+        .envs(env.iter().map(|pair| (&pair.0, &pair.1)))
+        .current_dir(paths.root())
+        .output()
+        .unwrap_or_else(|error| panic!("Failed to spawn process '{}': {}", &program, &error))
+}
+
+#[test]
+fn commonjs_dependency_with_es_module_ffi() {
+    let output = run_nodejs("commonjs_dependency/app");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "A square with sides of 3\n"
+    );
+}
+
+#[test]
+fn global_dependency_with_ffi_script() {
+    let output = run_nodejs("global_dependency/app");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "A square with sides of 3\n"
+    );
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-FileCopyrightText: 2026 The Gleam contributors
+
+use std::process::{ExitStatus, Stdio};
+
+use camino::Utf8Path;
+use gleam_core::{build::Target, io::Command, paths::ProjectPaths};
+
+use gleam_cli::{
+    fs,
+    run::{self, Which},
+};
+
+/// Runs the `main` module of a test project with Wasmtime, the same way
+/// `gleam run --target wasm` does. The programs use `let assert` to check
+/// their results, so a failed assertion makes Wasmtime exit with an error.
+///
+fn run_wasm(project_name: &str) -> ExitStatus {
+    let path = fs::canonicalise(&Utf8Path::new("../test-output/cases").join(project_name))
+        .expect("canonicalise path");
+    let project_root = fs::get_project_root(path).expect("project root");
+    let paths = ProjectPaths::new(project_root);
+    fs::delete_directory(&paths.build_directory()).expect("delete build directory content");
+
+    let (
+        Command {
+            program,
+            args,
+            env,
+            cwd: _,
+            stdio: _,
+        },
+        _,
+    ) = run::setup(
+        &paths,
+        vec![],
+        Some(Target::Wasm),
+        None,
+        Some("main".into()),
+        Which::Src,
+        true,
+        false,
+    )
+    .expect("run setup");
+
+    std::process::Command::new(&program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .envs(env.iter().map(|pair| (&pair.0, &pair.1)))
+        .current_dir(paths.root())
+        .status()
+        .unwrap_or_else(|error| panic!("Failed to spawn process '{}': {}", &program, &error))
+}
+
+#[test]
+fn wasm_assertions() {
+    assert!(run_wasm("wasm_assertions").success());
+}
+
+#[test]
+fn wasm_failing_assertion() {
+    assert!(!run_wasm("wasm_failing_assertion").success());
+}
@@ -169,6 +169,17 @@ fn erlang_nested_qualified_constant() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn erlang_parallel_type_variables() {
+    let output = crate::prepare("./cases/erlang_parallel_type_variables");
+    insta::assert_snapshot!(
+        "erlang_parallel_type_variables",
+        output,
+        "./cases/erlang_parallel_type_variables",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn errors_from_related_modules() {
@@ -279,6 +290,17 @@ fn imported_record_constructors() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn javascript_commonjs() {
+    let output = crate::prepare("./cases/javascript_commonjs");
+    insta::assert_snapshot!(
+        "javascript_commonjs",
+        output,
+        "./cases/javascript_commonjs",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn javascript_d_ts() {
@@ -301,6 +323,28 @@ fn javascript_empty() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn javascript_global() {
+    let output = crate::prepare("./cases/javascript_global");
+    insta::assert_snapshot!(
+        "javascript_global",
+        output,
+        "./cases/javascript_global",
+    );
+}
+
+#[rustfmt::skip]
+#[test]
+fn javascript_global_es_module_ffi() {
+    let output = crate::prepare("./cases/javascript_global_es_module_ffi");
+    insta::assert_snapshot!(
+        "javascript_global_es_module_ffi",
+        output,
+        "./cases/javascript_global_es_module_ffi",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn javascript_import() {
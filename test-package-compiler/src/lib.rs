@@ -35,8 +35,13 @@ pub fn prepare(path: &str) -> String {
         Target::JavaScript => TargetCodegenConfiguration::JavaScript {
             emit_typescript_definitions: config.javascript.typescript_declarations,
             emit_source_maps: config.javascript.source_maps,
-            prelude_location: Utf8PathBuf::from("../prelude.mjs"),
+            module_format: config.javascript.module_format,
+            prelude_location: Utf8PathBuf::from(format!(
+                "../prelude.{}",
+                config.javascript.module_format.extension()
+            )),
         },
+        Target::Wasm => TargetCodegenConfiguration::Wasm,
     };
 
     let ids = gleam_core::uid::UniqueIdGenerator::new();
@@ -33,6 +33,8 @@ pub fn prepare(path: &str, mode: Mode) -> String {
         warnings_as_errors: false,
         root_target_support: TargetSupport::Enforced,
         no_print_progress: true,
+        doctests: false,
+        inline: false,
     };
 
     let compiler = ProjectCompiler::new(